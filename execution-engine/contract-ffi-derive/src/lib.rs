@@ -0,0 +1,371 @@
+//! Derive macros for the `ToBytes` and `FromBytes` traits of `contract_ffi::bytesrepr`.
+//!
+//! The derived implementations produce the same layout as the hand-written ones throughout the
+//! code base:
+//!
+//! * a struct is serialized as the concatenation of its fields, in declaration order;
+//! * an enum is serialized as a single `u8` tag followed by the fields of the variant.
+//!
+//! The tag of a variant is determined like its discriminant would be, unless the variant has a
+//! `#[bytesrepr(tag = ...)]` attribute.  The attribute takes either an integer
+//! literal or a string naming a `u8` constant which is in scope:
+//!
+//! ```ignore
+//! #[derive(ToBytes, FromBytes)]
+//! enum Key {
+//!     #[bytesrepr(tag = "KEY_ACCOUNT_ID")]
+//!     Account([u8; 32]),
+//!     #[bytesrepr(tag = 1)]
+//!     Hash([u8; 32]),
+//! }
+//! ```
+//!
+//! The generated code refers to the traits through the path `contract_ffi`, which is how every
+//! crate in this repository names the contract FFI dependency.  A crate which names it differently
+//! (or the contract FFI itself) can override this with `#[bytesrepr(crate = "path")]` on the type.
+//! The generated code only uses `core` and the contract FFI, so it is `no_std` compatible.
+
+extern crate proc_macro;
+
+use std::collections::BTreeSet;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Error, Expr, ExprLit, Fields,
+    Generics, Ident, Lit, Meta, NestedMeta, Path, Result,
+};
+
+const ATTRIBUTE_NAME: &str = "bytesrepr";
+const DEFAULT_CRATE_PATH: &str = "contract_ffi";
+
+#[proc_macro_derive(ToBytes, attributes(bytesrepr))]
+pub fn derive_to_bytes(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_to_bytes(input)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(FromBytes, attributes(bytesrepr))]
+pub fn derive_from_bytes(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_from_bytes(input)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// The tag written before the fields of an enum variant.
+enum Tag {
+    Literal(u8),
+    Constant(Path),
+}
+
+impl Tag {
+    fn to_tokens(&self) -> TokenStream2 {
+        match self {
+            Tag::Literal(value) => quote!(#value),
+            Tag::Constant(path) => quote!(#path),
+        }
+    }
+}
+
+fn expand_to_bytes(input: DeriveInput) -> Result<TokenStream2> {
+    let krate = crate_path(&input.attrs)?;
+    let bytesrepr = quote!(#krate::bytesrepr);
+    let name = &input.ident;
+    let generics = add_trait_bounds(input.generics.clone(), &parse_quote!(#bytesrepr::ToBytes));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let (length, write) = match &input.data {
+        Data::Struct(data) => {
+            let (pattern, bindings) = destructure(quote!(#name), &data.fields);
+            let length = if bindings.is_empty() {
+                quote!(0)
+            } else {
+                quote! {
+                    let #pattern = self;
+                    #(#bytesrepr::ToBytes::serialized_length(#bindings))+*
+                }
+            };
+            let write = quote! {
+                let #pattern = self;
+                #(#bytesrepr::ToBytes::write_bytes(#bindings, __writer)?;)*
+                ::core::result::Result::Ok(())
+            };
+            (length, write)
+        }
+        Data::Enum(data) => {
+            let tags = variant_tags(data.variants.iter())?;
+            let mut length_arms = Vec::new();
+            let mut write_arms = Vec::new();
+            for (variant, tag) in data.variants.iter().zip(tags.iter()) {
+                let variant_name = &variant.ident;
+                let (pattern, bindings) =
+                    destructure(quote!(#name::#variant_name), &variant.fields);
+                let tag = tag.to_tokens();
+                length_arms.push(quote! {
+                    #pattern => 1 #(+ #bytesrepr::ToBytes::serialized_length(#bindings))*,
+                });
+                write_arms.push(quote! {
+                    #pattern => {
+                        #bytesrepr::Write::write_all(__writer, &[#tag])?;
+                        #(#bytesrepr::ToBytes::write_bytes(#bindings, __writer)?;)*
+                        ::core::result::Result::Ok(())
+                    }
+                });
+            }
+            let length = quote! {
+                match self {
+                    #(#length_arms)*
+                }
+            };
+            let write = quote! {
+                match self {
+                    #(#write_arms)*
+                }
+            };
+            (length, write)
+        }
+        Data::Union(_) => {
+            return Err(Error::new(
+                Span::call_site(),
+                "ToBytes cannot be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics #bytesrepr::ToBytes for #name #ty_generics #where_clause {
+            fn to_bytes(
+                &self,
+            ) -> ::core::result::Result<#bytesrepr::__private::Vec<u8>, #bytesrepr::Error> {
+                #bytesrepr::to_vec(self)
+            }
+
+            fn serialized_length(&self) -> usize {
+                #length
+            }
+
+            fn write_bytes<__W: #bytesrepr::Write + ?Sized>(
+                &self,
+                __writer: &mut __W,
+            ) -> ::core::result::Result<(), #bytesrepr::Error> {
+                #write
+            }
+        }
+    })
+}
+
+fn expand_from_bytes(input: DeriveInput) -> Result<TokenStream2> {
+    let krate = crate_path(&input.attrs)?;
+    let bytesrepr = quote!(#krate::bytesrepr);
+    let name = &input.ident;
+    let generics = add_trait_bounds(input.generics.clone(), &parse_quote!(#bytesrepr::FromBytes));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => construct(quote!(#name), &data.fields, &bytesrepr),
+        Data::Enum(data) => {
+            let tags = variant_tags(data.variants.iter())?;
+            let arms = data.variants.iter().zip(tags.iter()).map(|(variant, tag)| {
+                let variant_name = &variant.ident;
+                let construct =
+                    construct(quote!(#name::#variant_name), &variant.fields, &bytesrepr);
+                match tag {
+                    Tag::Literal(value) => quote! {
+                        #value => { #construct }
+                    },
+                    // Compare against the constant in a guard: if it were used as a pattern and
+                    // not in scope, it would silently become a catch-all binding.
+                    Tag::Constant(path) => quote! {
+                        __tag if __tag == #path => { #construct }
+                    },
+                }
+            });
+            quote! {
+                let (__tag, __bytes): (u8, &[u8]) = #bytesrepr::FromBytes::from_bytes(__bytes)?;
+                match __tag {
+                    #(#arms)*
+                    _ => ::core::result::Result::Err(#bytesrepr::Error::FormattingError),
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new(
+                Span::call_site(),
+                "FromBytes cannot be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics #bytesrepr::FromBytes for #name #ty_generics #where_clause {
+            fn from_bytes(
+                __bytes: &[u8],
+            ) -> ::core::result::Result<(Self, &[u8]), #bytesrepr::Error> {
+                #body
+            }
+        }
+    })
+}
+
+/// Returns a pattern which binds every field of a struct or variant, along with the names of the
+/// bindings in declaration order.
+fn destructure(path: TokenStream2, fields: &Fields) -> (TokenStream2, Vec<Ident>) {
+    let bindings: Vec<Ident> = (0..fields.iter().count())
+        .map(|index| Ident::new(&format!("__field{}", index), Span::call_site()))
+        .collect();
+    let pattern = match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            quote!(#path { #(#names: #bindings),* })
+        }
+        Fields::Unnamed(_) => quote!(#path(#(#bindings),*)),
+        Fields::Unit => quote!(#path),
+    };
+    (pattern, bindings)
+}
+
+/// Returns statements which deserialize every field of a struct or variant from `__bytes` in
+/// declaration order, then return the constructed value along with the remaining bytes.
+fn construct(path: TokenStream2, fields: &Fields, bytesrepr: &TokenStream2) -> TokenStream2 {
+    let (pattern, bindings) = destructure(path, fields);
+    let types = fields.iter().map(|field| &field.ty);
+    quote! {
+        #(
+            let (#bindings, __bytes): (#types, &[u8]) =
+                #bytesrepr::FromBytes::from_bytes(__bytes)?;
+        )*
+        ::core::result::Result::Ok((#pattern, __bytes))
+    }
+}
+
+/// Returns the tag of every variant, checking that no two literal tags are equal.
+///
+/// Like enum discriminants, a variant without an explicit tag has the tag of the preceding
+/// variant plus one, or zero if it is the first variant.
+fn variant_tags<'a>(variants: impl Iterator<Item = &'a syn::Variant>) -> Result<Vec<Tag>> {
+    let mut tags = Vec::new();
+    let mut seen = BTreeSet::new();
+    // The implicit tag of the next variant, or `None` if it can't be determined.
+    let mut next: Option<u16> = Some(0);
+    for variant in variants {
+        let tag = match tag_attribute(&variant.attrs)? {
+            Some(tag) => tag,
+            None => match &variant.discriminant {
+                Some((_, expr)) => Tag::Literal(discriminant(expr)?),
+                None => match next {
+                    Some(value) if value <= u16::from(u8::max_value()) => Tag::Literal(value as u8),
+                    Some(_) => {
+                        return Err(Error::new(variant.span(), "too many variants for a u8 tag"))
+                    }
+                    None => {
+                        return Err(Error::new(
+                            variant.span(),
+                            "a variant following one tagged with a constant needs an explicit tag",
+                        ))
+                    }
+                },
+            },
+        };
+        next = match tag {
+            Tag::Literal(value) => Some(u16::from(value) + 1),
+            Tag::Constant(_) => None,
+        };
+        if let Tag::Literal(value) = tag {
+            if !seen.insert(value) {
+                return Err(Error::new(
+                    variant.span(),
+                    format!("duplicate bytesrepr tag {}", value),
+                ));
+            }
+        }
+        tags.push(tag);
+    }
+    Ok(tags)
+}
+
+fn discriminant(expr: &Expr) -> Result<u8> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse(),
+        _ => Err(Error::new(
+            expr.span(),
+            "only integer literal discriminants can be used as tags; \
+             use #[bytesrepr(tag = ...)] instead",
+        )),
+    }
+}
+
+/// Returns the contents of every `#[bytesrepr(...)]` attribute.
+fn bytesrepr_meta(attrs: &[Attribute]) -> Result<Vec<NestedMeta>> {
+    let mut ret = Vec::new();
+    for attr in attrs {
+        if !attr.path.is_ident(ATTRIBUTE_NAME) {
+            continue;
+        }
+        match attr.parse_meta()? {
+            Meta::List(list) => ret.extend(list.nested),
+            other => {
+                return Err(Error::new(
+                    other.span(),
+                    "expected #[bytesrepr(key = value)]",
+                ))
+            }
+        }
+    }
+    Ok(ret)
+}
+
+fn tag_attribute(attrs: &[Attribute]) -> Result<Option<Tag>> {
+    let mut tag = None;
+    for meta in bytesrepr_meta(attrs)? {
+        match meta {
+            NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("tag") => {
+                if tag.is_some() {
+                    return Err(Error::new(name_value.span(), "duplicate tag attribute"));
+                }
+                tag = Some(match &name_value.lit {
+                    Lit::Int(int) => Tag::Literal(int.base10_parse()?),
+                    Lit::Str(string) => Tag::Constant(string.parse()?),
+                    other => {
+                        return Err(Error::new(
+                            other.span(),
+                            "expected an integer literal or the name of a constant",
+                        ))
+                    }
+                });
+            }
+            other => return Err(Error::new(other.span(), "unknown bytesrepr attribute")),
+        }
+    }
+    Ok(tag)
+}
+
+fn crate_path(attrs: &[Attribute]) -> Result<Path> {
+    let mut path = None;
+    for meta in bytesrepr_meta(attrs)? {
+        match meta {
+            NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("crate") => {
+                match &name_value.lit {
+                    Lit::Str(string) => path = Some(string.parse()?),
+                    other => return Err(Error::new(other.span(), "expected a path string")),
+                }
+            }
+            other => return Err(Error::new(other.span(), "unknown bytesrepr attribute")),
+        }
+    }
+    Ok(path.unwrap_or_else(|| Ident::new(DEFAULT_CRATE_PATH, Span::call_site()).into()))
+}
+
+/// Requires every type parameter to implement `bound`.
+fn add_trait_bounds(mut generics: Generics, bound: &Path) -> Generics {
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(#bound));
+    }
+    generics
+}
@@ -1,5 +1,6 @@
-use super::alloc::collections::BTreeMap;
+use super::alloc::boxed::Box;
 use super::alloc::collections::TryReserveError;
+use super::alloc::collections::{BTreeMap, BTreeSet};
 use super::alloc::string::String;
 use super::alloc::vec::Vec;
 
@@ -9,7 +10,11 @@ use failure::Fail;
 use crate::value::ProtocolVersion;
 use crate::value::SemVer;
 
+pub use casperlabs_contract_ffi_derive::{FromBytes, ToBytes};
+
+pub const BOOL_SIZE: usize = size_of::<bool>();
 pub const I32_SIZE: usize = size_of::<i32>();
+pub const I64_SIZE: usize = size_of::<i64>();
 pub const U8_SIZE: usize = size_of::<u8>();
 pub const U16_SIZE: usize = size_of::<u16>();
 pub const U32_SIZE: usize = size_of::<u32>();
@@ -21,14 +26,103 @@ pub const OPTION_SIZE: usize = 1;
 pub const SEM_VER_SIZE: usize = 12;
 
 pub const N32: usize = 32;
-const N256: usize = 256;
+
+/// Items used by the code generated by the `ToBytes` and `FromBytes` derive macros.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::vec::Vec;
+}
 
 pub trait ToBytes {
     fn to_bytes(&self) -> Result<Vec<u8>, Error>;
+
+    /// Returns the length of the serialized form of `self`.
+    ///
+    /// The default implementation serializes `self` to find its length, so it should be
+    /// overridden wherever the length can be computed without allocating.  An implementation
+    /// whose `to_bytes` is [`to_vec`] must override it.
+    fn serialized_length(&self) -> usize {
+        self.to_bytes().map(|bytes| bytes.len()).unwrap_or_default()
+    }
+
+    /// Writes the serialized form of `self` to `writer`.
+    ///
+    /// The default implementation writes the result of `to_bytes`, so it should be overridden to
+    /// write the serialized form directly.  An implementation whose `to_bytes` is [`to_vec`] must
+    /// override it.
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_all(&self.to_bytes()?)
+    }
+
+    /// Writes the serialized forms of `items` to `writer`, one after another.
+    ///
+    /// This is used by the impls for slices, vectors and arrays, and only needs to be overridden
+    /// by types which can write a whole slice at once, like `u8`.
+    #[doc(hidden)]
+    fn write_slice<W: Write + ?Sized>(items: &[Self], writer: &mut W) -> Result<(), Error>
+    where
+        Self: Sized,
+    {
+        for item in items {
+            item.write_bytes(writer)?;
+        }
+        Ok(())
+    }
 }
 
 pub trait FromBytes: Sized {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error>;
+
+    /// Deserializes `count` values of `Self` which were serialized one after another.
+    ///
+    /// Like [`ToBytes::write_slice`], this only needs to be overridden by types which can read a
+    /// whole slice at once.
+    #[doc(hidden)]
+    fn from_bytes_vec(bytes: &[u8], count: usize) -> Result<(Vec<Self>, &[u8]), Error> {
+        let mut result = Vec::new();
+        result.try_reserve_exact(count)?;
+        let mut stream = bytes;
+        for _ in 0..count {
+            let (t, rem): (Self, &[u8]) = FromBytes::from_bytes(stream)?;
+            result.push(t);
+            stream = rem;
+        }
+        Ok((result, stream))
+    }
+}
+
+/// A type which can be deserialized by borrowing from the serialized bytes rather than copying
+/// them.
+pub trait FromBytesBorrowed<'a>: Sized {
+    fn from_bytes_borrowed(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Error>;
+}
+
+/// A buffer to which serialized bytes can be written.
+pub trait Write {
+    /// Appends all of `bytes` to the buffer, or fails if they don't fit.
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Error>;
+}
+
+impl Write for Vec<u8> {
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        self.try_reserve(bytes.len())?;
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// Writes to the start of the slice and advances it past the written bytes, so that a
+/// preallocated buffer can be filled without allocating.
+impl<'a> Write for &'a mut [u8] {
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        if bytes.len() > self.len() {
+            return Err(Error::OutOfMemoryError);
+        }
+        let (written, rest) = core::mem::replace(self, &mut []).split_at_mut(bytes.len());
+        written.copy_from_slice(bytes);
+        *self = rest;
+        Ok(())
+    }
 }
 
 #[derive(Debug, Fail, PartialEq, Eq, Clone)]
@@ -62,10 +156,40 @@ pub fn deserialize<T: FromBytes>(bytes: &[u8]) -> Result<T, Error> {
     }
 }
 
+pub fn deserialize_borrowed<'a, T: FromBytesBorrowed<'a>>(bytes: &'a [u8]) -> Result<T, Error> {
+    let (t, rem): (T, &[u8]) = FromBytesBorrowed::from_bytes_borrowed(bytes)?;
+    if rem.is_empty() {
+        Ok(t)
+    } else {
+        Err(Error::LeftOverBytes)
+    }
+}
+
 pub fn serialize(t: impl ToBytes) -> Result<Vec<u8>, Error> {
     t.to_bytes()
 }
 
+/// Serializes `t` into a buffer allocated once with its exact serialized length.
+///
+/// This is the `to_bytes` of every implementation of [`ToBytes`] which overrides both
+/// `serialized_length` and `write_bytes`.
+pub fn to_vec<T: ToBytes + ?Sized>(t: &T) -> Result<Vec<u8>, Error> {
+    let mut result = Vec::new();
+    result.try_reserve_exact(t.serialized_length())?;
+    t.write_bytes(&mut result)?;
+    Ok(result)
+}
+
+/// Returns an error if a sequence of `len` items can't be length-prefixed on a 32-bit
+/// architecture.
+fn check_length(len: usize) -> Result<(), Error> {
+    if len >= u32::max_value() as usize - U32_SIZE {
+        Err(Error::OutOfMemoryError)
+    } else {
+        Ok(())
+    }
+}
+
 pub fn safe_split_at(bytes: &[u8], n: usize) -> Result<(&[u8], &[u8]), Error> {
     if n > bytes.len() {
         Err(Error::EarlyEndOfStream)
@@ -80,6 +204,18 @@ impl ToBytes for u8 {
         result.push(*self);
         Ok(result)
     }
+
+    fn serialized_length(&self) -> usize {
+        U8_SIZE
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_all(&[*self])
+    }
+
+    fn write_slice<W: Write + ?Sized>(items: &[Self], writer: &mut W) -> Result<(), Error> {
+        writer.write_all(items)
+    }
 }
 
 impl FromBytes for u8 {
@@ -89,12 +225,52 @@ impl FromBytes for u8 {
             Some((byte, rem)) => Ok((*byte, rem)),
         }
     }
+
+    fn from_bytes_vec(bytes: &[u8], count: usize) -> Result<(Vec<Self>, &[u8]), Error> {
+        let (slice, rem) = safe_split_at(bytes, count)?;
+        let mut result = Vec::new();
+        result.try_reserve_exact(count)?;
+        result.extend_from_slice(slice);
+        Ok((result, rem))
+    }
+}
+
+impl ToBytes for bool {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        u8::from(*self).to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        BOOL_SIZE
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        u8::from(*self).write_bytes(writer)
+    }
+}
+
+impl FromBytes for bool {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        match FromBytes::from_bytes(bytes)? {
+            (0u8, rem) => Ok((false, rem)),
+            (1u8, rem) => Ok((true, rem)),
+            _ => Err(Error::FormattingError),
+        }
+    }
 }
 
 impl ToBytes for i32 {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         Ok(self.to_le_bytes().to_vec())
     }
+
+    fn serialized_length(&self) -> usize {
+        I32_SIZE
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_all(&self.to_le_bytes())
+    }
 }
 
 impl FromBytes for i32 {
@@ -106,10 +282,41 @@ impl FromBytes for i32 {
     }
 }
 
+impl ToBytes for i64 {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        Ok(self.to_le_bytes().to_vec())
+    }
+
+    fn serialized_length(&self) -> usize {
+        I64_SIZE
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_all(&self.to_le_bytes())
+    }
+}
+
+impl FromBytes for i64 {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let mut result: [u8; I64_SIZE] = [0u8; I64_SIZE];
+        let (bytes, rem) = safe_split_at(bytes, I64_SIZE)?;
+        result.copy_from_slice(bytes);
+        Ok((i64::from_le_bytes(result), rem))
+    }
+}
+
 impl ToBytes for u32 {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         Ok(self.to_le_bytes().to_vec())
     }
+
+    fn serialized_length(&self) -> usize {
+        U32_SIZE
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_all(&self.to_le_bytes())
+    }
 }
 
 impl FromBytes for u32 {
@@ -125,6 +332,14 @@ impl ToBytes for u64 {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         Ok(self.to_le_bytes().to_vec())
     }
+
+    fn serialized_length(&self) -> usize {
+        U64_SIZE
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_all(&self.to_le_bytes())
+    }
 }
 
 impl FromBytes for u64 {
@@ -136,65 +351,55 @@ impl FromBytes for u64 {
     }
 }
 
-impl FromBytes for Vec<u8> {
-    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
-        let (size, mut stream): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
-        let mut result: Vec<u8> = Vec::new();
-        result.try_reserve_exact(size as usize)?;
-        for _ in 0..size {
-            let (t, rem): (u8, &[u8]) = FromBytes::from_bytes(stream)?;
-            result.push(t);
-            stream = rem;
-        }
-        Ok((result, stream))
+impl<T: ToBytes> ToBytes for [T] {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        to_vec(self)
     }
-}
 
-impl ToBytes for Vec<u8> {
-    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        // Return error if size of serialized vector would exceed limit for
+    fn serialized_length(&self) -> usize {
+        U32_SIZE + self.iter().map(ToBytes::serialized_length).sum::<usize>()
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        // Return error if the length of the vector would exceed limit for
         // 32-bit architecture.
-        if self.len() >= u32::max_value() as usize - U32_SIZE {
-            return Err(Error::OutOfMemoryError);
-        }
-        let size = self.len() as u32;
-        let mut result: Vec<u8> = Vec::with_capacity(U32_SIZE + size as usize);
-        result.extend(size.to_bytes()?);
-        result.extend(self);
-        Ok(result)
+        check_length(self.len())?;
+        (self.len() as u32).write_bytes(writer)?;
+        T::write_slice(self, writer)
     }
 }
 
-impl FromBytes for Vec<i32> {
-    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
-        let (size, mut stream): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
-        let mut result: Vec<i32> = Vec::new();
-        result.try_reserve_exact(size as usize)?;
-        for _ in 0..size {
-            let (t, rem): (i32, &[u8]) = FromBytes::from_bytes(stream)?;
-            result.push(t);
-            stream = rem;
-        }
-        Ok((result, stream))
+impl<'a> FromBytesBorrowed<'a> for &'a [u8] {
+    fn from_bytes_borrowed(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        let (size, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        safe_split_at(rem, size as usize)
     }
 }
 
 impl<T: ToBytes> ToBytes for Option<T> {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        to_vec(self)
+    }
+
+    fn serialized_length(&self) -> usize {
+        match self {
+            Some(v) => U8_SIZE + v.serialized_length(),
+            None => U8_SIZE,
+        }
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
         match self {
             Some(v) => {
-                let mut value = v.to_bytes()?;
-                if value.len() >= u32::max_value() as usize - U8_SIZE {
+                if v.serialized_length() >= u32::max_value() as usize - U8_SIZE {
                     return Err(Error::OutOfMemoryError);
                 }
-                let mut result: Vec<u8> = Vec::with_capacity(U8_SIZE + value.len());
-                result.append(&mut 1u8.to_bytes()?);
-                result.append(&mut value);
-                Ok(result)
+                1u8.write_bytes(writer)?;
+                v.write_bytes(writer)
             }
             // In the case of None there is no value to serialize, but we still
             // need to write out a tag to indicate which variant we are using
-            None => Ok(0u8.to_bytes()?),
+            None => 0u8.write_bytes(writer),
         }
     }
 }
@@ -213,157 +418,193 @@ impl<T: FromBytes> FromBytes for Option<T> {
     }
 }
 
-impl ToBytes for Vec<i32> {
+impl<T: ToBytes> ToBytes for Vec<T> {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        // Return error if size of vector would exceed length of serialized data
-        if self.len() * I32_SIZE >= u32::max_value() as usize - U32_SIZE {
-            return Err(Error::OutOfMemoryError);
-        }
-        let size = self.len() as u32;
-        let mut result: Vec<u8> = Vec::with_capacity(U32_SIZE + (I32_SIZE * size as usize));
-        result.extend(size.to_bytes()?);
-        result.extend(
-            self.iter()
-                .map(ToBytes::to_bytes)
-                .collect::<Result<Vec<_>, _>>()?
-                .into_iter()
-                .flatten(),
-        );
-        Ok(result)
+        self.as_slice().to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.as_slice().serialized_length()
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.as_slice().write_bytes(writer)
     }
 }
 
-impl FromBytes for Vec<Vec<u8>> {
+impl<T: FromBytes> FromBytes for Vec<T> {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
-        let (size, mut stream): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
-        let mut result = Vec::new();
-        result.try_reserve_exact(size as usize)?;
-        for _ in 0..size {
-            let (v, rem): (Vec<u8>, &[u8]) = FromBytes::from_bytes(stream)?;
-            result.push(v);
-            stream = rem;
-        }
-        Ok((result, stream))
+        let (size, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        T::from_bytes_vec(rem, size as usize)
     }
 }
 
-impl ToBytes for Vec<Vec<u8>> {
+impl<T: ToBytes> ToBytes for BTreeSet<T> {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        if self.len() >= u32::max_value() as usize - U32_SIZE {
-            // Fail fast for large enough vectors
-            return Err(Error::OutOfMemoryError);
-        }
-        // Calculate total length of all vectors
-        let total_length: usize = self.iter().map(Vec::len).sum();
-        // It could be either length of vector which serialized would exceed
-        // the maximum size of vector (i.e. vector of vectors of size 1),
-        // or the total length of all vectors (i.e. vector of size 1 which holds
-        // vector of size 2^32-1)
-        if total_length >= u32::max_value() as usize - U32_SIZE {
-            return Err(Error::OutOfMemoryError);
-        }
-        let size = self.len() as u32;
-        let mut result: Vec<u8> = Vec::with_capacity(U32_SIZE + size as usize + total_length);
-        result.extend_from_slice(&size.to_bytes()?);
-        for n in 0..size {
-            result.extend_from_slice(&self[n as usize].to_bytes()?);
+        to_vec(self)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U32_SIZE + self.iter().map(ToBytes::serialized_length).sum::<usize>()
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        check_length(self.len())?;
+        (self.len() as u32).write_bytes(writer)?;
+        for item in self.iter() {
+            item.write_bytes(writer)?;
         }
-        Ok(result)
+        Ok(())
     }
 }
 
-impl FromBytes for Vec<String> {
+impl<T: FromBytes + Ord> FromBytes for BTreeSet<T> {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
-        let (size, mut stream): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
-        let mut result = Vec::new();
-        result.try_reserve_exact(size as usize)?;
-        for _ in 0..size {
-            let (s, rem): (String, &[u8]) = FromBytes::from_bytes(stream)?;
-            result.push(s);
+        let (num_items, mut stream): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let mut result = BTreeSet::new();
+        for _ in 0..num_items {
+            let (t, rem): (T, &[u8]) = FromBytes::from_bytes(stream)?;
+            // Only the canonical encoding, with items in strictly ascending order, is accepted
+            if let Some(last) = result.iter().next_back() {
+                if t <= *last {
+                    return Err(Error::FormattingError);
+                }
+            }
+            result.insert(t);
             stream = rem;
         }
         Ok((result, stream))
     }
 }
 
-impl ToBytes for Vec<String> {
+impl<T: ToBytes + ?Sized> ToBytes for Box<T> {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let size = self.len() as u32;
-        let mut result: Vec<u8> = Vec::with_capacity(U32_SIZE);
-        result.extend(size.to_bytes()?);
-        result.extend(
-            self.iter()
-                .map(ToBytes::to_bytes)
-                .collect::<Result<Vec<_>, _>>()?
-                .into_iter()
-                .flatten(),
-        );
-        Ok(result)
+        self.as_ref().to_bytes()
     }
-}
 
-impl ToBytes for [u8; N32] {
-    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let mut result: Vec<u8> = Vec::with_capacity(U32_SIZE + N32);
-        result.extend((N32 as u32).to_bytes()?);
-        result.extend(self);
-        Ok(result)
+    fn serialized_length(&self) -> usize {
+        self.as_ref().serialized_length()
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.as_ref().write_bytes(writer)
     }
 }
 
-impl FromBytes for [u8; N32] {
+impl<T: FromBytes> FromBytes for Box<T> {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
-        let (bytes, rem): (Vec<u8>, &[u8]) = FromBytes::from_bytes(bytes)?;
-        if bytes.len() != N32 {
-            return Err(Error::FormattingError);
-        };
-        let mut result = [0u8; N32];
-        result.copy_from_slice(&bytes);
-        Ok((result, rem))
+        let (t, rem): (T, &[u8]) = FromBytes::from_bytes(bytes)?;
+        Ok((Box::new(t), rem))
     }
 }
 
-impl<T: ToBytes> ToBytes for [T; N256] {
-    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        if self.len() * size_of::<T>() >= u32::max_value() as usize - U32_SIZE {
-            return Err(Error::OutOfMemoryError);
-        }
-        let mut result: Vec<u8> = Vec::with_capacity(U32_SIZE + (self.len() * size_of::<T>()));
-        result.extend((N256 as u32).to_bytes()?);
-        result.extend(
-            self.iter()
-                .map(ToBytes::to_bytes)
-                .collect::<Result<Vec<_>, _>>()?
-                .into_iter()
-                .flatten(),
-        );
-        Ok(result)
+/// Arrays are serialized like slices, i.e. prefixed with their length, so that an array and a
+/// `Vec` of the same elements have the same serialized form.
+macro_rules! impl_byte_repr_for_arrays {
+    ($($n:expr)+) => {
+        $(
+            impl<T: ToBytes> ToBytes for [T; $n] {
+                fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+                    self[..].to_bytes()
+                }
+
+                fn serialized_length(&self) -> usize {
+                    self[..].serialized_length()
+                }
+
+                fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+                    self[..].write_bytes(writer)
+                }
+            }
+
+            impl<T: FromBytes> FromBytes for [T; $n] {
+                fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+                    let (size, mut stream): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+                    if size as usize != $n {
+                        return Err(Error::FormattingError);
+                    }
+                    let mut result: MaybeUninit<[T; $n]> = MaybeUninit::uninit();
+                    let result_ptr = result.as_mut_ptr() as *mut T;
+                    unsafe {
+                        for i in 0..$n {
+                            let (t, rem): (T, &[u8]) = FromBytes::from_bytes(stream)?;
+                            result_ptr.add(i).write(t);
+                            stream = rem;
+                        }
+                        Ok((result.assume_init(), stream))
+                    }
+                }
+            }
+        )+
     }
 }
 
-impl<T: FromBytes> FromBytes for [T; N256] {
-    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
-        let (size, mut stream): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
-        if size != N256 as u32 {
-            return Err(Error::FormattingError);
-        }
-        let mut result: MaybeUninit<[T; N256]> = MaybeUninit::uninit();
-        let result_ptr = result.as_mut_ptr() as *mut T;
-        unsafe {
-            for i in 0..N256 {
-                let (t, rem): (T, &[u8]) = FromBytes::from_bytes(stream)?;
-                result_ptr.add(i).write(t);
-                stream = rem;
+impl_byte_repr_for_arrays! {
+     0  1  2  3  4  5  6  7  8  9
+    10 11 12 13 14 15 16 17 18 19
+    20 21 22 23 24 25 26 27 28 29
+    30 31 32
+    64 128 256 512 1024
+}
+
+macro_rules! impl_byte_repr_for_tuples {
+    ($(($($ty:ident $var:ident),+))+) => {
+        $(
+            impl<$($ty: ToBytes),+> ToBytes for ($($ty,)+) {
+                fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+                    to_vec(self)
+                }
+
+                fn serialized_length(&self) -> usize {
+                    let ($($var,)+) = self;
+                    let mut length = 0;
+                    $(length += $var.serialized_length();)+
+                    length
+                }
+
+                fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+                    let ($($var,)+) = self;
+                    $($var.write_bytes(writer)?;)+
+                    Ok(())
+                }
             }
-            Ok((result.assume_init(), stream))
-        }
+
+            impl<$($ty: FromBytes),+> FromBytes for ($($ty,)+) {
+                fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+                    let stream = bytes;
+                    $(let ($var, stream): ($ty, &[u8]) = FromBytes::from_bytes(stream)?;)+
+                    Ok((($($var,)+), stream))
+                }
+            }
+        )+
     }
 }
 
+impl_byte_repr_for_tuples! {
+    (T1 t1)
+    (T1 t1, T2 t2)
+    (T1 t1, T2 t2, T3 t3)
+    (T1 t1, T2 t2, T3 t3, T4 t4)
+    (T1 t1, T2 t2, T3 t3, T4 t4, T5 t5)
+    (T1 t1, T2 t2, T3 t3, T4 t4, T5 t5, T6 t6)
+    (T1 t1, T2 t2, T3 t3, T4 t4, T5 t5, T6 t6, T7 t7)
+    (T1 t1, T2 t2, T3 t3, T4 t4, T5 t5, T6 t6, T7 t7, T8 t8)
+    (T1 t1, T2 t2, T3 t3, T4 t4, T5 t5, T6 t6, T7 t7, T8 t8, T9 t9)
+    (T1 t1, T2 t2, T3 t3, T4 t4, T5 t5, T6 t6, T7 t7, T8 t8, T9 t9, T10 t10)
+}
+
 impl ToBytes for String {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         self.as_str().to_bytes()
     }
+
+    fn serialized_length(&self) -> usize {
+        self.as_str().serialized_length()
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.as_str().write_bytes(writer)
+    }
 }
 
 impl FromBytes for String {
@@ -374,10 +615,26 @@ impl FromBytes for String {
     }
 }
 
+impl<'a> FromBytesBorrowed<'a> for &'a str {
+    fn from_bytes_borrowed(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        let (str_bytes, rem): (&[u8], &[u8]) = FromBytesBorrowed::from_bytes_borrowed(bytes)?;
+        let result = core::str::from_utf8(str_bytes).map_err(|_| Error::FormattingError)?;
+        Ok((result, rem))
+    }
+}
+
 impl ToBytes for () {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         Ok(Vec::new())
     }
+
+    fn serialized_length(&self) -> usize {
+        0
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, _writer: &mut W) -> Result<(), Error> {
+        Ok(())
+    }
 }
 
 impl FromBytes for () {
@@ -392,34 +649,25 @@ where
     V: ToBytes,
 {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let num_keys = self.len() as u32;
-        let bytes = self
-            .iter()
-            .map(move |(k, v)| {
-                let k_bytes = k.to_bytes().map_err(Error::from);
-                let v_bytes = v.to_bytes().map_err(Error::from);
-                // For each key and value pair create a vector of
-                // serialization results
-                let mut vs = Vec::with_capacity(2);
-                vs.push(k_bytes);
-                vs.push(v_bytes);
-                vs
-            })
-            // Flatten iterable of key and value pairs
-            .flatten()
-            // Collect into a single Result of bytes (if successful)
-            .collect::<Result<Vec<_>, _>>()?
-            .into_iter()
-            .flatten();
-
-        let (lower_bound, _upper_bound) = bytes.size_hint();
-        if lower_bound >= u32::max_value() as usize - U32_SIZE {
-            return Err(Error::OutOfMemoryError);
+        to_vec(self)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U32_SIZE
+            + self
+                .iter()
+                .map(|(k, v)| k.serialized_length() + v.serialized_length())
+                .sum::<usize>()
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        check_length(self.len())?;
+        (self.len() as u32).write_bytes(writer)?;
+        for (k, v) in self.iter() {
+            k.write_bytes(writer)?;
+            v.write_bytes(writer)?;
         }
-        let mut result: Vec<u8> = Vec::with_capacity(U32_SIZE + lower_bound);
-        result.append(&mut num_keys.to_bytes()?);
-        result.extend(bytes);
-        Ok(result)
+        Ok(())
     }
 }
 
@@ -441,30 +689,58 @@ where
     }
 }
 
+impl ToBytes for str {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.as_bytes().to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.as_bytes().serialized_length()
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.as_bytes().write_bytes(writer)
+    }
+}
+
 impl ToBytes for &str {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        if self.len() >= u32::max_value() as usize - U32_SIZE {
-            return Err(Error::OutOfMemoryError);
-        }
-        let bytes = self.as_bytes();
-        let size = self.len();
-        let mut result: Vec<u8> = Vec::with_capacity(U32_SIZE + size);
-        result.extend((size as u32).to_bytes()?);
-        result.extend(bytes);
-        Ok(result)
+        (*self).to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        (*self).serialized_length()
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        (*self).write_bytes(writer)
     }
 }
 
 impl<T: ToBytes, E: ToBytes> ToBytes for Result<T, E> {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let (mut variant, mut value) = match self {
-            Ok(result) => (1u8.to_bytes()?, result.to_bytes()?),
-            Err(error) => (0u8.to_bytes()?, error.to_bytes()?),
-        };
-        let mut result: Vec<u8> = Vec::with_capacity(U8_SIZE + value.len());
-        result.append(&mut variant);
-        result.append(&mut value);
-        Ok(result)
+        to_vec(self)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SIZE
+            + match self {
+                Ok(result) => result.serialized_length(),
+                Err(error) => error.serialized_length(),
+            }
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        match self {
+            Ok(result) => {
+                1u8.write_bytes(writer)?;
+                result.write_bytes(writer)
+            }
+            Err(error) => {
+                0u8.write_bytes(writer)?;
+                error.write_bytes(writer)
+            }
+        }
     }
 }
 
@@ -487,11 +763,17 @@ impl<T: FromBytes, E: FromBytes> FromBytes for Result<T, E> {
 
 impl ToBytes for SemVer {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let mut ret: Vec<u8> = Vec::with_capacity(SEM_VER_SIZE);
-        ret.append(&mut self.major.to_bytes()?);
-        ret.append(&mut self.minor.to_bytes()?);
-        ret.append(&mut self.patch.to_bytes()?);
-        Ok(ret)
+        to_vec(self)
+    }
+
+    fn serialized_length(&self) -> usize {
+        SEM_VER_SIZE
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.major.write_bytes(writer)?;
+        self.minor.write_bytes(writer)?;
+        self.patch.write_bytes(writer)
     }
 }
 
@@ -506,8 +788,15 @@ impl FromBytes for SemVer {
 
 impl ToBytes for ProtocolVersion {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let value_bytes = self.value().to_bytes()?;
-        Ok(value_bytes.to_vec())
+        self.value().to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.value().serialized_length()
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.value().write_bytes(writer)
     }
 }
 
@@ -526,17 +815,102 @@ where
     T: ToBytes + FromBytes + PartialEq,
 {
     let serialized = ToBytes::to_bytes(t).expect("Unable to serialize data");
+    assert_eq!(serialized.len(), t.serialized_length());
     let deserialized = deserialize::<T>(&serialized).expect("Unable to deserialize data");
     assert!(*t == deserialized)
 }
 
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeSet;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use crate::bytesrepr::{self, Error, ToBytes};
+
+    #[test]
+    fn should_write_into_preallocated_slice() {
+        let value = (String::from("hello"), 42u64);
+        let mut buffer = [0u8; 17];
+        {
+            let mut writer: &mut [u8] = &mut buffer;
+            value
+                .0
+                .write_bytes(&mut writer)
+                .expect("should write string");
+            value.1.write_bytes(&mut writer).expect("should write u64");
+            assert!(writer.is_empty());
+        }
+        let mut expected = value.0.to_bytes().unwrap();
+        expected.append(&mut value.1.to_bytes().unwrap());
+        assert_eq!(&buffer[..], expected.as_slice());
+    }
+
+    #[test]
+    fn should_fail_to_write_past_end_of_slice() {
+        let mut buffer = [0u8; 3];
+        let mut writer: &mut [u8] = &mut buffer;
+        assert_eq!(42u32.write_bytes(&mut writer), Err(Error::OutOfMemoryError));
+    }
+
+    #[test]
+    fn should_deserialize_borrowed_bytes_and_str() {
+        let bytes = vec![1u8, 2, 3].to_bytes().unwrap();
+        let borrowed: &[u8] = bytesrepr::deserialize_borrowed(&bytes).unwrap();
+        assert_eq!(borrowed, &[1, 2, 3]);
+
+        let serialized = "casper".to_bytes().unwrap();
+        let borrowed: &str = bytesrepr::deserialize_borrowed(&serialized).unwrap();
+        assert_eq!(borrowed, "casper");
+
+        let invalid_utf8: Vec<u8> = vec![0xff, 0xfe].to_bytes().unwrap();
+        assert_eq!(
+            bytesrepr::deserialize_borrowed::<&str>(&invalid_utf8),
+            Err(Error::FormattingError)
+        );
+    }
+
+    #[test]
+    fn to_vec_should_match_to_bytes() {
+        let value: Vec<String> = vec![String::from("a"), String::from("bc")];
+        assert_eq!(
+            bytesrepr::to_vec(&value).unwrap(),
+            value.to_bytes().unwrap()
+        );
+        assert_eq!(value.serialized_length(), value.to_bytes().unwrap().len());
+    }
+
+    #[test]
+    fn should_reject_non_canonical_btree_set() {
+        let set: BTreeSet<u32> = vec![1, 2, 3].into_iter().collect();
+        assert_eq!(
+            bytesrepr::deserialize::<BTreeSet<u32>>(&set.to_bytes().unwrap()),
+            Ok(set)
+        );
+
+        let unsorted = vec![2u32, 1, 3].to_bytes().unwrap();
+        assert_eq!(
+            bytesrepr::deserialize::<BTreeSet<u32>>(&unsorted),
+            Err(Error::FormattingError)
+        );
+
+        let duplicated = vec![1u32, 1, 3].to_bytes().unwrap();
+        assert_eq!(
+            bytesrepr::deserialize::<BTreeSet<u32>>(&duplicated),
+            Err(Error::FormattingError)
+        );
+    }
+}
+
 #[allow(clippy::unnecessary_operation)]
 #[cfg(test)]
 mod proptests {
+    use alloc::boxed::Box;
+
     use proptest::collection::vec;
     use proptest::prelude::*;
 
-    use crate::bytesrepr;
+    use crate::bytesrepr::{self, ToBytes};
     use crate::gens::*;
 
     proptest! {
@@ -550,11 +924,21 @@ mod proptests {
             bytesrepr::test_serialization_roundtrip(&u)
         }
 
+        #[test]
+        fn test_bool(u in any::<bool>()) {
+            bytesrepr::test_serialization_roundtrip(&u)
+        }
+
         #[test]
         fn test_i32(u in any::<i32>()) {
             bytesrepr::test_serialization_roundtrip(&u)
         }
 
+        #[test]
+        fn test_i64(u in any::<i64>()) {
+            bytesrepr::test_serialization_roundtrip(&u)
+        }
+
         #[test]
         fn test_u64(u in any::<u64>()) {
             bytesrepr::test_serialization_roundtrip(&u)
@@ -669,5 +1053,190 @@ mod proptests {
         fn test_sem_ver(sem_ver in sem_ver_arb()) {
             bytesrepr::test_serialization_roundtrip(&sem_ver)
         }
+
+        #[test]
+        fn test_cl_value(cl_value in cl_value_arb()) {
+            bytesrepr::test_serialization_roundtrip(&cl_value)
+        }
+
+        #[test]
+        fn test_vec_key(keys in vec(key_arb(), 0..50)) {
+            bytesrepr::test_serialization_roundtrip(&keys)
+        }
+
+        #[test]
+        fn test_vec_option_u512(v in vec(proptest::option::of(u512_arb()), 0..50)) {
+            bytesrepr::test_serialization_roundtrip(&v)
+        }
+
+        #[test]
+        fn test_btree_set(set in u512_btree_set_arb(20)) {
+            bytesrepr::test_serialization_roundtrip(&set)
+        }
+
+        #[test]
+        fn test_u256_keyed_btree_map(map in u256_keyed_btree_map_arb(20)) {
+            bytesrepr::test_serialization_roundtrip(&map)
+        }
+
+        #[test]
+        fn test_box(key in key_arb()) {
+            bytesrepr::test_serialization_roundtrip(&Box::new(key))
+        }
+
+        #[test]
+        fn test_arrays(
+            empty in Just([0u64; 0]),
+            keys in proptest::array::uniform8(key_arb()),
+            bytes in any::<[u8; 17]>(),
+        ) {
+            bytesrepr::test_serialization_roundtrip(&empty);
+            bytesrepr::test_serialization_roundtrip(&keys);
+            bytesrepr::test_serialization_roundtrip(&bytes);
+        }
+
+        #[test]
+        fn test_array_matches_vec(arr in any::<[u32; 16]>()) {
+            prop_assert_eq!(arr.to_bytes().unwrap(), arr.to_vec().to_bytes().unwrap());
+        }
+
+        #[test]
+        fn test_tuples(
+            t1 in (any::<u8>(),),
+            t3 in (key_arb(), "\\PC*", u512_arb()),
+            t10 in (
+                any::<u8>(),
+                any::<i32>(),
+                any::<u32>(),
+                any::<u64>(),
+                u128_arb(),
+                u256_arb(),
+                uref_arb(),
+                public_key_arb(),
+                vec(any::<u8>(), 0..10),
+                proptest::option::of("\\PC*"),
+            ),
+        ) {
+            bytesrepr::test_serialization_roundtrip(&t1);
+            bytesrepr::test_serialization_roundtrip(&t3);
+            bytesrepr::test_serialization_roundtrip(&t10);
+        }
+    }
+}
+
+#[cfg(test)]
+mod derive_tests {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use crate::bytesrepr::{self, FromBytes, ToBytes};
+    use crate::execution::Phase;
+    use crate::value::SemVer;
+
+    const TAG_NAMED: u8 = 7;
+
+    #[derive(Debug, PartialEq, ToBytes, FromBytes)]
+    #[bytesrepr(crate = "crate")]
+    struct DerivedSemVer {
+        major: u32,
+        minor: u32,
+        patch: u32,
+    }
+
+    #[derive(Debug, PartialEq, ToBytes, FromBytes)]
+    #[bytesrepr(crate = "crate")]
+    struct Wrapper<T>(T, Option<T>);
+
+    #[derive(Debug, PartialEq, ToBytes, FromBytes)]
+    #[bytesrepr(crate = "crate")]
+    struct Unit;
+
+    #[derive(Debug, PartialEq, ToBytes, FromBytes)]
+    #[bytesrepr(crate = "crate")]
+    enum Tagged {
+        First,
+        Second(u64),
+        #[bytesrepr(tag = "TAG_NAMED")]
+        Named {
+            name: String,
+            values: Vec<u8>,
+        },
+        #[bytesrepr(tag = 42)]
+        Answer,
+        AfterAnswer(Unit),
+    }
+
+    #[test]
+    fn derived_struct_should_match_hand_written_layout() {
+        let derived = DerivedSemVer {
+            major: 1,
+            minor: 2,
+            patch: 3,
+        };
+        let hand_written = SemVer::new(1, 2, 3);
+        assert_eq!(
+            derived.to_bytes().expect("should serialize"),
+            hand_written.to_bytes().expect("should serialize")
+        );
+        bytesrepr::test_serialization_roundtrip(&derived);
+    }
+
+    #[test]
+    fn derived_generic_and_unit_structs_should_roundtrip() {
+        let wrapper = Wrapper(5u32, Some(6u32));
+        let mut expected = 5u32.to_bytes().unwrap();
+        expected.append(&mut Some(6u32).to_bytes().unwrap());
+        assert_eq!(wrapper.to_bytes().unwrap(), expected);
+        bytesrepr::test_serialization_roundtrip(&wrapper);
+
+        assert!(Unit.to_bytes().unwrap().is_empty());
+        bytesrepr::test_serialization_roundtrip(&Unit);
+    }
+
+    #[test]
+    fn derived_enum_should_prefix_fields_with_tag() {
+        assert_eq!(Tagged::First.to_bytes().unwrap(), vec![0]);
+
+        let mut expected = vec![1];
+        expected.append(&mut 9u64.to_bytes().unwrap());
+        assert_eq!(Tagged::Second(9).to_bytes().unwrap(), expected);
+
+        let named = Tagged::Named {
+            name: String::from("name"),
+            values: vec![1, 2, 3],
+        };
+        let mut expected = vec![TAG_NAMED];
+        expected.append(&mut String::from("name").to_bytes().unwrap());
+        expected.append(&mut vec![1u8, 2, 3].to_bytes().unwrap());
+        assert_eq!(named.to_bytes().unwrap(), expected);
+
+        assert_eq!(Tagged::Answer.to_bytes().unwrap(), vec![42]);
+        assert_eq!(Tagged::AfterAnswer(Unit).to_bytes().unwrap(), vec![43]);
+
+        for value in &[
+            Tagged::First,
+            Tagged::Second(9),
+            named,
+            Tagged::Answer,
+            Tagged::AfterAnswer(Unit),
+        ] {
+            bytesrepr::test_serialization_roundtrip(value);
+        }
+    }
+
+    #[test]
+    fn derived_enum_should_reject_unknown_tag() {
+        assert_eq!(
+            bytesrepr::deserialize::<Tagged>(&[2]),
+            Err(bytesrepr::Error::FormattingError)
+        );
+    }
+
+    #[test]
+    fn derived_phase_should_use_discriminants_as_tags() {
+        assert_eq!(Phase::System.to_bytes().unwrap(), vec![0]);
+        assert_eq!(Phase::FinalizePayment.to_bytes().unwrap(), vec![3]);
+        assert_eq!(bytesrepr::deserialize::<Phase>(&[2]), Ok(Phase::Session));
+        assert!(bytesrepr::deserialize::<Phase>(&[4]).is_err());
     }
 }
@@ -2,13 +2,13 @@ use core::convert::{TryFrom, TryInto};
 
 use super::runtime::get_caller;
 use super::storage::read_untyped;
-use super::to_ptr;
+use super::{result_from, to_ptr, Error};
 use crate::ext_ffi;
 use crate::key::Key;
 pub use crate::value::account::PublicKey;
 use crate::value::account::{
-    ActionType, AddKeyFailure, PurseId, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure,
-    Weight,
+    ActionType, AddKeyFailure, BlockTime, PurseId, RemoveKeyFailure, SetThresholdFailure,
+    UpdateKeyFailure, Weight,
 };
 use crate::value::Account;
 
@@ -76,3 +76,15 @@ pub fn update_associated_key(
         Err(UpdateKeyFailure::try_from(result).expect("invalid result"))
     }
 }
+
+/// Sets the period of inactivity after which the [`ActionType::KeyRecovery`] threshold, rather
+/// than the [`ActionType::KeyManagement`] one, is required to manage the keys of an account.
+///
+/// Fails with [`Error::PermissionDenied`] if the authorization keys of the deploy can't manage
+/// the keys of the account, or with [`Error::InvalidArgument`] if `inactivity_period` is shorter
+/// than `MIN_INACTIVITY_PERIOD_TIME`.
+pub fn set_inactivity_period(inactivity_period: BlockTime) -> Result<(), Error> {
+    let (inactivity_period_ptr, _inactivity_period_size, _bytes) = to_ptr(&inactivity_period);
+    let result = unsafe { ext_ffi::set_inactivity_period(inactivity_period_ptr) };
+    result_from(result)
+}
@@ -7,6 +7,7 @@ use crate::system_contracts::{mint, pos};
 use crate::value::account::{
     AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure,
 };
+use crate::value::contract_package::ContractPackageError;
 
 /// All `Error` variants defined in this library other than `Error::User` will convert to a `u32`
 /// value less than or equal to `RESERVED_ERROR_MAX`.
@@ -111,6 +112,16 @@ pub enum Error {
     InsufficientTotalWeight,
     /// Returns when contract tries to obtain URef to a system contract that does not exist.
     InvalidSystemContract,
+    /// The contract package has no version with the given number.
+    InvalidContractVersion,
+    /// The given version of the contract package is disabled.
+    DisabledContractVersion,
+    /// The host doesn't support the requested hash or signature algorithm.
+    UnsupportedAlgorithm,
+    /// The signature is malformed or doesn't match the public key and message.
+    InvalidSignature,
+    /// The latest version of a contract package can't be disabled.
+    LatestContractVersion,
     /// Error specific to Mint contract.
     Mint(u8),
     /// Error specific to Proof of Stake contract.
@@ -180,6 +191,16 @@ impl From<AccessRightsError> for Error {
     }
 }
 
+impl From<ContractPackageError> for Error {
+    fn from(error: ContractPackageError) -> Self {
+        match error {
+            ContractPackageError::InvalidVersion => Error::InvalidContractVersion,
+            ContractPackageError::DisabledVersion => Error::DisabledContractVersion,
+            ContractPackageError::LatestVersion => Error::LatestContractVersion,
+        }
+    }
+}
+
 impl From<mint::Error> for Error {
     fn from(error: mint::Error) -> Self {
         Error::Mint(error as u8)
@@ -226,6 +247,11 @@ impl From<Error> for u32 {
             Error::PermissionDeniedError => 29,
             Error::InsufficientTotalWeight => 30,
             Error::InvalidSystemContract => 31,
+            Error::InvalidContractVersion => 32,
+            Error::DisabledContractVersion => 33,
+            Error::UnsupportedAlgorithm => 34,
+            Error::InvalidSignature => 35,
+            Error::LatestContractVersion => 36,
             Error::Mint(value) => MINT_ERROR_OFFSET + u32::from(value),
             Error::ProofOfStake(value) => POS_ERROR_OFFSET + u32::from(value),
             Error::User(value) => RESERVED_ERROR_MAX + 1 + u32::from(value),
@@ -269,6 +295,11 @@ impl Debug for Error {
             Error::PermissionDeniedError => write!(f, "Error::PermissionDeniedError")?,
             Error::InsufficientTotalWeight => write!(f, "Error::InsufficientTotalWeight")?,
             Error::InvalidSystemContract => write!(f, "Error::InvalidSystemContract")?,
+            Error::InvalidContractVersion => write!(f, "Error::InvalidContractVersion")?,
+            Error::DisabledContractVersion => write!(f, "Error::DisabledContractVersion")?,
+            Error::UnsupportedAlgorithm => write!(f, "Error::UnsupportedAlgorithm")?,
+            Error::InvalidSignature => write!(f, "Error::InvalidSignature")?,
+            Error::LatestContractVersion => write!(f, "Error::LatestContractVersion")?,
             Error::Mint(value) => write!(f, "Error::Mint({})", value)?,
             Error::ProofOfStake(value) => write!(f, "Error::ProofOfStake({})", value)?,
             Error::User(value) => write!(f, "Error::User({})", value)?,
@@ -318,6 +349,11 @@ pub fn result_from(value: i32) -> Result<(), Error> {
         29 => Err(Error::PermissionDeniedError),
         30 => Err(Error::InsufficientTotalWeight),
         31 => Err(Error::InvalidSystemContract),
+        32 => Err(Error::InvalidContractVersion),
+        33 => Err(Error::DisabledContractVersion),
+        34 => Err(Error::UnsupportedAlgorithm),
+        35 => Err(Error::InvalidSignature),
+        36 => Err(Error::LatestContractVersion),
         _ => {
             if value > RESERVED_ERROR_MAX as i32 && value <= (2 * RESERVED_ERROR_MAX + 1) as i32 {
                 Err(Error::User(value as u16))
@@ -404,6 +440,11 @@ mod tests {
         round_trip(Err(Error::PermissionDeniedError));
         round_trip(Err(Error::InsufficientTotalWeight));
         round_trip(Err(Error::InvalidSystemContract));
+        round_trip(Err(Error::InvalidContractVersion));
+        round_trip(Err(Error::DisabledContractVersion));
+        round_trip(Err(Error::UnsupportedAlgorithm));
+        round_trip(Err(Error::InvalidSignature));
+        round_trip(Err(Error::LatestContractVersion));
         round_trip(Err(Error::Mint(0)));
         round_trip(Err(Error::Mint(u8::MAX)));
         round_trip(Err(Error::ProofOfStake(0)));
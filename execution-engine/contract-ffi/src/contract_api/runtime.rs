@@ -6,12 +6,17 @@ use super::error::{result_from, Error};
 use super::{alloc_bytes, str_ref_to_ptr, to_ptr, ContractRef, TURef};
 use crate::args_parser::ArgsParser;
 use crate::bytesrepr::{self, deserialize, FromBytes, ToBytes};
+use crate::crypto::{HashAlgorithm, SignatureAlgorithm, HASH_LENGTH};
 use crate::execution::{Phase, PHASE_SIZE};
 use crate::ext_ffi;
 use crate::key::Key;
+use crate::named_args::NamedArgs;
+use crate::unwrap_or_revert::UnwrapOrRevert;
 use crate::uref::URef;
 use crate::value::account::{BlockTime, PublicKey, BLOCKTIME_SER_SIZE};
-use crate::value::{Contract, Value};
+use crate::value::{
+    CLTyped, CLValue, CLValueError, Contract, ContractPackage, ContractVersion, Value,
+};
 
 /// Return `t` to the host, terminating the currently running module.
 /// Note this function is only relevant to contracts stored on chain which
@@ -52,6 +57,99 @@ pub fn call_contract<A: ArgsParser, T: FromBytes>(
             key_ptr, key_size, args_ptr, args_size, urefs_ptr, urefs_size,
         )
     };
+    get_call_result(res_size)
+}
+
+/// Call the given contract, passing the given named arguments to the host.  The called contract
+/// reads them with `get_named_arg`.  The value returned from the contract call is returned from
+/// this function.
+#[allow(clippy::ptr_arg)]
+pub fn call_contract_with_named_args<T: FromBytes>(
+    c_ptr: ContractRef,
+    named_args: &NamedArgs,
+    extra_urefs: &Vec<Key>,
+) -> T {
+    let contract_key: Key = c_ptr.into();
+    let (key_ptr, key_size, _bytes1) = to_ptr(&contract_key);
+    let (named_args_ptr, named_args_size, _bytes2) = to_ptr(named_args);
+    let (urefs_ptr, urefs_size, _bytes3) = to_ptr(extra_urefs);
+    let res_size = unsafe {
+        ext_ffi::call_contract_with_named_args(
+            key_ptr,
+            key_size,
+            named_args_ptr,
+            named_args_size,
+            urefs_ptr,
+            urefs_size,
+        )
+    };
+    get_call_result(res_size)
+}
+
+/// Call the entry point named `entry_point` of the given contract, passing the given named
+/// arguments to the host.  Each parameter declared by the entry point must be present in
+/// `named_args` with the declared type.  The value returned from the contract call is returned
+/// from this function.
+#[allow(clippy::ptr_arg)]
+pub fn call_entry_point<T: FromBytes>(
+    c_ptr: ContractRef,
+    entry_point: &str,
+    named_args: &NamedArgs,
+    extra_urefs: &Vec<Key>,
+) -> T {
+    let contract_key: Key = c_ptr.into();
+    let (key_ptr, key_size, _bytes1) = to_ptr(&contract_key);
+    let (entry_point_ptr, entry_point_size, _bytes2) = str_ref_to_ptr(entry_point);
+    let (named_args_ptr, named_args_size, _bytes3) = to_ptr(named_args);
+    let (urefs_ptr, urefs_size, _bytes4) = to_ptr(extra_urefs);
+    let res_size = unsafe {
+        ext_ffi::call_entry_point(
+            key_ptr,
+            key_size,
+            entry_point_ptr,
+            entry_point_size,
+            named_args_ptr,
+            named_args_size,
+            urefs_ptr,
+            urefs_size,
+        )
+    };
+    get_call_result(res_size)
+}
+
+/// Call the entry point named `entry_point` of the given version of the contract package under
+/// `package`.  Execution stops if the version doesn't exist or is disabled.  The value returned
+/// from the contract call is returned from this function.
+#[allow(clippy::ptr_arg)]
+pub fn call_versioned_contract<T: FromBytes>(
+    package: TURef<ContractPackage>,
+    version: ContractVersion,
+    entry_point: &str,
+    named_args: &NamedArgs,
+    extra_urefs: &Vec<Key>,
+) -> T {
+    let package_key: Key = package.into();
+    let (key_ptr, key_size, _bytes1) = to_ptr(&package_key);
+    let (entry_point_ptr, entry_point_size, _bytes2) = str_ref_to_ptr(entry_point);
+    let (named_args_ptr, named_args_size, _bytes3) = to_ptr(named_args);
+    let (urefs_ptr, urefs_size, _bytes4) = to_ptr(extra_urefs);
+    let res_size = unsafe {
+        ext_ffi::call_versioned_contract(
+            key_ptr,
+            key_size,
+            version,
+            entry_point_ptr,
+            entry_point_size,
+            named_args_ptr,
+            named_args_size,
+            urefs_ptr,
+            urefs_size,
+        )
+    };
+    get_call_result(res_size)
+}
+
+fn get_call_result<T: FromBytes>(res_size: usize) -> T {
     let res_ptr = alloc_bytes(res_size);
     let res_bytes = unsafe {
         ext_ffi::get_call_result(res_ptr);
@@ -99,6 +197,32 @@ pub fn get_arg<T: FromBytes>(i: u32) -> Option<Result<T, bytesrepr::Error>> {
     Some(deserialize(&arg_bytes))
 }
 
+/// Return the argument passed under `name` to the current module invocation.
+///
+/// Reverts with `Error::MissingArgument` if there is no such argument and with
+/// `Error::InvalidArgument` if it was passed with a type other than `T`.
+pub fn get_named_arg<T: CLTyped + FromBytes>(name: &str) -> T {
+    let (name_ptr, name_size, _bytes) = str_ref_to_ptr(name);
+    let arg_size = unsafe { ext_ffi::load_named_arg(name_ptr, name_size) };
+    if arg_size < 0 {
+        revert(Error::MissingArgument)
+    }
+    let arg_size = arg_size as usize;
+    let arg_bytes = {
+        let dest_ptr = alloc_bytes(arg_size);
+        unsafe {
+            ext_ffi::get_arg(dest_ptr);
+            Vec::from_raw_parts(dest_ptr, arg_size, arg_size)
+        }
+    };
+    let cl_value: CLValue = deserialize(&arg_bytes).unwrap_or_revert();
+    match cl_value.to_t() {
+        Ok(value) => value,
+        Err(CLValueError::Type(_)) => revert(Error::InvalidArgument),
+        Err(CLValueError::Serialization(error)) => revert(error),
+    }
+}
+
 /// Returns caller of current context.
 /// When in root context (not in the sub call) - returns None.
 /// When in the sub call - returns public key of the account that made the
@@ -185,3 +309,70 @@ pub fn is_valid<T: Into<Value>>(t: T) -> bool {
     let result = unsafe { ext_ffi::is_valid(value_ptr, value_size) };
     result != 0
 }
+
+/// Emits an event under `topic` carrying `payload`.  Events are returned with the deploy's
+/// result in the order they were emitted; they are discarded if the deploy fails.
+pub fn emit<T: CLTyped + ToBytes>(topic: &str, payload: T) {
+    let payload = CLValue::from_t(&payload).unwrap_or_revert_with(Error::ValueConversion);
+    let (topic_ptr, topic_size, _bytes1) = str_ref_to_ptr(topic);
+    let (payload_ptr, payload_size, _bytes2) = to_ptr(&payload);
+    unsafe { ext_ffi::emit_event(topic_ptr, topic_size, payload_ptr, payload_size) }
+}
+
+/// Hashes `data` with the given algorithm.  Hashing on the host is much cheaper than hashing in
+/// Wasm, as it is charged per byte rather than per instruction.
+pub fn hash(algorithm: HashAlgorithm, data: &[u8]) -> [u8; HASH_LENGTH] {
+    let mut digest = [0u8; HASH_LENGTH];
+    let result = unsafe {
+        ext_ffi::hash(
+            algorithm.into(),
+            data.as_ptr(),
+            data.len(),
+            digest.as_mut_ptr(),
+            digest.len(),
+        )
+    };
+    result_from(result).unwrap_or_revert();
+    digest
+}
+
+/// Returns `true` if `signature` is a valid signature of `message` by `public_key` under the
+/// given algorithm.  Malformed public keys and signatures are reported as invalid.
+pub fn verify_signature(
+    algorithm: SignatureAlgorithm,
+    public_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> bool {
+    let result = unsafe {
+        ext_ffi::verify_signature(
+            algorithm.into(),
+            public_key.as_ptr(),
+            public_key.len(),
+            message.as_ptr(),
+            message.len(),
+            signature.as_ptr(),
+            signature.len(),
+        )
+    };
+    match result_from(result) {
+        Ok(()) => true,
+        Err(Error::InvalidSignature) => false,
+        Err(error) => revert(error),
+    }
+}
+
+/// Returns `n` random bytes.  The bytes are derived deterministically from the deploy hash, the
+/// current phase and the call depth, so every node executing the deploy sees the same values; they
+/// are unpredictable only to parties that can't foresee the deploy hash.  Drawing random bytes
+/// doesn't affect the addresses of URefs created by the contract.
+pub fn random_bytes(n: usize) -> Vec<u8> {
+    if n == 0 {
+        return Vec::new();
+    }
+    let dest_ptr = alloc_bytes(n);
+    unsafe {
+        ext_ffi::random_bytes(dest_ptr, n);
+        Vec::from_raw_parts(dest_ptr, n, n)
+    }
+}
@@ -5,13 +5,13 @@ use core::convert::{From, TryFrom, TryInto};
 use core::u8;
 
 use super::{alloc_bytes, str_ref_to_ptr, to_ptr, ContractRef, TURef};
-use crate::bytesrepr::{self, deserialize, ToBytes};
-use crate::contract_api::Error;
+use crate::bytesrepr::{self, deserialize, ToBytes, U32_SIZE};
+use crate::contract_api::{result_from, Error};
 use crate::ext_ffi;
 use crate::key::{Key, UREF_SIZE};
 use crate::unwrap_or_revert::UnwrapOrRevert;
 use crate::uref::AccessRights;
-use crate::value::{Contract, Value};
+use crate::value::{Contract, ContractPackage, ContractVersion, EntryPoints, Value};
 
 pub(crate) fn read_untyped(key: &Key) -> Result<Option<Value>, bytesrepr::Error> {
     // Note: _bytes is necessary to keep the Vec<u8> in scope. If _bytes is
@@ -153,6 +153,132 @@ pub fn store_function_at_hash(name: &str, named_keys: BTreeMap<String, Key>) ->
     ContractRef::Hash(addr)
 }
 
+/// Stores the current module as a contract with the given entry points at an immutable address
+/// generated by the host.  Each entry point must be the name of an exported function; all other
+/// code not reachable from the entry points is removed.
+pub fn store_contract_at_hash(
+    entry_points: EntryPoints,
+    named_keys: BTreeMap<String, Key>,
+) -> ContractRef {
+    let (entry_points_ptr, entry_points_size, _bytes1) = to_ptr(&entry_points);
+    let (keys_ptr, keys_size, _bytes2) = to_ptr(&named_keys);
+    let mut addr = [0u8; 32];
+    unsafe {
+        ext_ffi::store_contract_at_hash(
+            entry_points_ptr,
+            entry_points_size,
+            keys_ptr,
+            keys_size,
+            addr.as_mut_ptr(),
+        );
+    }
+    ContractRef::Hash(addr)
+}
+
+/// Returns a new unforgable pointer to an empty contract package.  Versions are added to it with
+/// `add_contract_version` or `add_function_version`.
+pub fn new_contract_package() -> TURef<ContractPackage> {
+    new_turef(ContractPackage::new())
+}
+
+/// Stores the current module as a contract with the given entry points, as with
+/// `store_contract_at_hash`, and adds it as a new version of the contract package under `package`.
+/// The new version becomes the latest version of the package, and `named_keys` are added to the
+/// named keys of the package.  If `package` holds a plain contract, that contract first becomes
+/// version 1 of a new package.  Returns the number of the new version.
+pub fn add_contract_version(
+    package: TURef<ContractPackage>,
+    entry_points: EntryPoints,
+    named_keys: BTreeMap<String, Key>,
+) -> ContractVersion {
+    let package_key: Key = package.into();
+    let (key_ptr, key_size, _bytes1) = to_ptr(&package_key);
+    let (entry_points_ptr, entry_points_size, _bytes2) = to_ptr(&entry_points);
+    let (keys_ptr, keys_size, _bytes3) = to_ptr(&named_keys);
+    let mut version_bytes = [0u8; U32_SIZE];
+    unsafe {
+        ext_ffi::add_contract_version(
+            key_ptr,
+            key_size,
+            entry_points_ptr,
+            entry_points_size,
+            keys_ptr,
+            keys_size,
+            version_bytes.as_mut_ptr(),
+        );
+    }
+    deserialize(&version_bytes).unwrap_or_revert()
+}
+
+/// Stores the function `name` as a contract, as with `store_function`, and adds it as a new
+/// version of the contract package under `package`, as with `add_contract_version`.  Returns the
+/// number of the new version.
+pub fn add_function_version(
+    package: TURef<ContractPackage>,
+    name: &str,
+    named_keys: BTreeMap<String, Key>,
+) -> ContractVersion {
+    let package_key: Key = package.into();
+    let (key_ptr, key_size, _bytes1) = to_ptr(&package_key);
+    let (name_ptr, name_size, _bytes2) = str_ref_to_ptr(name);
+    let (keys_ptr, keys_size, _bytes3) = to_ptr(&named_keys);
+    let mut version_bytes = [0u8; U32_SIZE];
+    unsafe {
+        ext_ffi::add_function_version(
+            key_ptr,
+            key_size,
+            name_ptr,
+            name_size,
+            keys_ptr,
+            keys_size,
+            version_bytes.as_mut_ptr(),
+        );
+    }
+    deserialize(&version_bytes).unwrap_or_revert()
+}
+
+/// Designates `version` of the contract package under `package` as its latest version, which is
+/// the version run when the package itself is called.  The version must be enabled.
+pub fn set_latest_contract_version(
+    package: TURef<ContractPackage>,
+    version: ContractVersion,
+) -> Result<(), Error> {
+    let package_key: Key = package.into();
+    let (key_ptr, key_size, _bytes) = to_ptr(&package_key);
+    let result = unsafe { ext_ffi::set_latest_contract_version(key_ptr, key_size, version) };
+    result_from(result)
+}
+
+/// Disables `version` of the contract package under `package`.  A disabled version can't be
+/// called until it is enabled again.  The latest version of the package can't be disabled.
+pub fn disable_contract_version(
+    package: TURef<ContractPackage>,
+    version: ContractVersion,
+) -> Result<(), Error> {
+    set_contract_version_enabled(package, version, false)
+}
+
+/// Re-enables `version` of the contract package under `package`.
+pub fn enable_contract_version(
+    package: TURef<ContractPackage>,
+    version: ContractVersion,
+) -> Result<(), Error> {
+    set_contract_version_enabled(package, version, true)
+}
+
+fn set_contract_version_enabled(
+    package: TURef<ContractPackage>,
+    version: ContractVersion,
+    is_enabled: bool,
+) -> Result<(), Error> {
+    let package_key: Key = package.into();
+    let (key_ptr, key_size, _bytes) = to_ptr(&package_key);
+    let result = unsafe {
+        ext_ffi::set_contract_version_enabled(key_ptr, key_size, version, is_enabled as u32)
+    };
+    result_from(result)
+}
+
 /// Returns a new unforgable pointer, where value is initialized to `init`
 pub fn new_turef<T: Into<Value>>(init: T) -> TURef<T> {
     let key_ptr = alloc_bytes(UREF_SIZE);
@@ -3,14 +3,16 @@ use core::fmt::Debug;
 use core::u8;
 
 use super::error::Error;
+use super::runtime::call_contract;
 use super::runtime::revert;
 use super::{alloc_bytes, to_ptr, ContractRef, TURef};
 use crate::bytesrepr::deserialize;
 use crate::contract_api::error::result_from;
 use crate::ext_ffi;
-use crate::system_contracts::SystemContract;
-use crate::uref::UREF_SIZE_SERIALIZED;
-use crate::value::account::{PublicKey, PurseId, PURSE_ID_SIZE_SERIALIZED};
+use crate::key::Key;
+use crate::system_contracts::{mint, SystemContract};
+use crate::uref::{URef, UREF_SIZE_SERIALIZED};
+use crate::value::account::{BlockTime, PublicKey, PurseId, PURSE_ID_SIZE_SERIALIZED};
 use crate::value::U512;
 
 pub type TransferResult = Result<TransferredTo, Error>;
@@ -89,6 +91,74 @@ pub fn get_balance(purse_id: PurseId) -> Option<U512> {
     Some(balance)
 }
 
+/// Creates a new purse holding `amount` of motes taken from `source`, which the mint only lets
+/// be transferred out as they unlock.  Nothing is unlocked until `cliff` has passed since the
+/// current block time, after which the motes unlock linearly until all of them are unlocked once
+/// `duration` has passed.
+pub fn create_vesting_purse(
+    source: PurseId,
+    amount: U512,
+    cliff: BlockTime,
+    duration: BlockTime,
+) -> Result<PurseId, Error> {
+    let result: Result<URef, mint::Error> = call_contract(
+        get_mint(),
+        &("create_vesting", source, amount, cliff.0, duration.0),
+        &vec![Key::URef(source.value())],
+    );
+    result.map(PurseId::new).map_err(Error::from)
+}
+
+/// Gets the part of the balance of a given purse which is still locked by its vesting schedule.
+/// Purses created without a vesting schedule never have a locked balance.
+///
+/// Returns `None` if the purse doesn't exist or `purse_id` doesn't grant write access to it.
+pub fn get_locked_balance(purse_id: PurseId) -> Option<U512> {
+    call_contract(
+        get_mint(),
+        &("locked_balance", purse_id),
+        &vec![Key::URef(purse_id.value())],
+    )
+}
+
+/// Gets the part of the balance of a given purse which can be transferred.
+pub fn get_unlocked_balance(purse_id: PurseId) -> Option<U512> {
+    let balance = get_balance(purse_id)?;
+    let locked_balance = get_locked_balance(purse_id)?;
+    Some(balance.saturating_sub(locked_balance))
+}
+
+/// Delegates `amount` motes taken from `source` to the given bonded validator.  The delegated motes
+/// are tracked separately from the validator's own bond and earn the caller a pro rata share of
+/// the validators' rewards.  Any failure will trigger `revert()` with a `pos::Error`.
+pub fn delegate(validator: PublicKey, amount: U512, source: PurseId) {
+    call_contract::<_, ()>(
+        get_proof_of_stake(),
+        &("delegate", validator, amount, source),
+        &vec![Key::URef(source.value())],
+    );
+}
+
+/// Withdraws `maybe_amount` motes the caller has delegated to the given validator, or all of them
+/// if `None`.  The motes are paid out to the caller's main purse once the unbonding delay has
+/// passed.  Any failure will trigger `revert()` with a `pos::Error`.
+pub fn undelegate(validator: PublicKey, maybe_amount: Option<U512>) {
+    call_contract::<_, ()>(
+        get_proof_of_stake(),
+        &("undelegate", validator, maybe_amount),
+        &Vec::new(),
+    );
+}
+
+/// Gets the amount of motes `delegator` has delegated to the given validator.
+pub fn get_delegation(validator: PublicKey, delegator: PublicKey) -> U512 {
+    call_contract(
+        get_proof_of_stake(),
+        &("get_delegation", validator, delegator),
+        &Vec::new(),
+    )
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(i32)]
 pub enum TransferredTo {
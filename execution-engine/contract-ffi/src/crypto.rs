@@ -0,0 +1,106 @@
+use core::convert::TryFrom;
+
+use crate::contract_api::Error;
+
+/// Length in bytes of the digests produced by every [`HashAlgorithm`].
+pub const HASH_LENGTH: usize = 32;
+
+/// Hash functions provided by the host via
+/// [`runtime::hash`](crate::contract_api::runtime::hash).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    /// BLAKE2b with a 256-bit digest.
+    Blake2b256,
+    /// SHA-256.
+    Sha256,
+    /// Keccak-256, as used by Ethereum (not the standardized SHA3-256).
+    Keccak256,
+}
+
+impl Into<u32> for HashAlgorithm {
+    fn into(self) -> u32 {
+        match self {
+            HashAlgorithm::Blake2b256 => 0,
+            HashAlgorithm::Sha256 => 1,
+            HashAlgorithm::Keccak256 => 2,
+        }
+    }
+}
+
+impl TryFrom<u32> for HashAlgorithm {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<HashAlgorithm, Self::Error> {
+        match value {
+            0 => Ok(HashAlgorithm::Blake2b256),
+            1 => Ok(HashAlgorithm::Sha256),
+            2 => Ok(HashAlgorithm::Keccak256),
+            _ => Err(Error::UnsupportedAlgorithm),
+        }
+    }
+}
+
+/// Signature schemes the host can verify via
+/// [`runtime::verify_signature`](crate::contract_api::runtime::verify_signature).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SignatureAlgorithm {
+    /// Ed25519 with a 32-byte public key and a 64-byte signature.
+    Ed25519,
+    /// ECDSA over secp256k1 with a 33- or 65-byte SEC1 public key and a 64-byte compact
+    /// signature of the SHA-256 digest of the message.
+    Secp256k1,
+}
+
+impl Into<u32> for SignatureAlgorithm {
+    fn into(self) -> u32 {
+        match self {
+            SignatureAlgorithm::Ed25519 => 0,
+            SignatureAlgorithm::Secp256k1 => 1,
+        }
+    }
+}
+
+impl TryFrom<u32> for SignatureAlgorithm {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<SignatureAlgorithm, Self::Error> {
+        match value {
+            0 => Ok(SignatureAlgorithm::Ed25519),
+            1 => Ok(SignatureAlgorithm::Secp256k1),
+            _ => Err(Error::UnsupportedAlgorithm),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::TryFrom;
+
+    use super::{HashAlgorithm, SignatureAlgorithm};
+    use crate::contract_api::Error;
+
+    #[test]
+    fn should_round_trip_algorithm_indices() {
+        for algorithm in &[
+            HashAlgorithm::Blake2b256,
+            HashAlgorithm::Sha256,
+            HashAlgorithm::Keccak256,
+        ] {
+            let index: u32 = (*algorithm).into();
+            assert_eq!(HashAlgorithm::try_from(index), Ok(*algorithm));
+        }
+        for algorithm in &[SignatureAlgorithm::Ed25519, SignatureAlgorithm::Secp256k1] {
+            let index: u32 = (*algorithm).into();
+            assert_eq!(SignatureAlgorithm::try_from(index), Ok(*algorithm));
+        }
+    }
+
+    #[test]
+    fn should_reject_unknown_algorithm_indices() {
+        assert_eq!(HashAlgorithm::try_from(3), Err(Error::UnsupportedAlgorithm));
+        assert_eq!(
+            SignatureAlgorithm::try_from(2),
+            Err(Error::UnsupportedAlgorithm)
+        );
+    }
+}
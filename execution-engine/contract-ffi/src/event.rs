@@ -0,0 +1,55 @@
+use alloc::string::String;
+
+use crate::bytesrepr::ToBytes;
+use crate::value::{CLTyped, CLValue, CLValueError};
+
+/// A structured event emitted by a contract with
+/// [`runtime::emit`](crate::contract_api::runtime::emit).
+///
+/// Events are collected per deploy in the order they were emitted and returned alongside the
+/// deploy's effects.  They are not written to global state.
+#[derive(PartialEq, Eq, Clone, Debug, ToBytes, FromBytes)]
+#[bytesrepr(crate = "crate")]
+pub struct Event {
+    topic: String,
+    payload: CLValue,
+}
+
+impl Event {
+    pub fn new(topic: String, payload: CLValue) -> Self {
+        Event { topic, payload }
+    }
+
+    /// Serializes `payload` and creates an event under `topic`.
+    pub fn from_t<T: CLTyped + ToBytes>(topic: &str, payload: T) -> Result<Self, CLValueError> {
+        let payload = CLValue::from_t(&payload)?;
+        Ok(Event::new(String::from(topic), payload))
+    }
+
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    pub fn payload(&self) -> &CLValue {
+        &self.payload
+    }
+
+    pub fn destructure(self) -> (String, CLValue) {
+        (self.topic, self.payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytesrepr;
+    use crate::event::Event;
+    use crate::value::U512;
+
+    #[test]
+    fn should_create_event_and_round_trip() {
+        let event = Event::from_t("transfer", U512::from(42)).expect("should create event");
+        assert_eq!(event.topic(), "transfer");
+        assert_eq!(event.payload().to_t::<U512>(), Ok(U512::from(42)));
+        bytesrepr::test_serialization_roundtrip(&event);
+    }
+}
@@ -25,11 +25,48 @@ extern "C" {
         named_keys_size: usize,
         hash_ptr: *const u8,
     );
+    pub fn store_contract_at_hash(
+        entry_points_ptr: *const u8,
+        entry_points_size: usize,
+        named_keys_ptr: *const u8,
+        named_keys_size: usize,
+        hash_ptr: *const u8,
+    );
+    pub fn add_contract_version(
+        package_key_ptr: *const u8,
+        package_key_size: usize,
+        entry_points_ptr: *const u8,
+        entry_points_size: usize,
+        named_keys_ptr: *const u8,
+        named_keys_size: usize,
+        version_ptr: *mut u8,
+    );
+    pub fn add_function_version(
+        package_key_ptr: *const u8,
+        package_key_size: usize,
+        function_name_ptr: *const u8,
+        function_name_size: usize,
+        named_keys_ptr: *const u8,
+        named_keys_size: usize,
+        version_ptr: *mut u8,
+    );
+    pub fn set_contract_version_enabled(
+        package_key_ptr: *const u8,
+        package_key_size: usize,
+        version: u32,
+        is_enabled: u32,
+    ) -> i32;
+    pub fn set_latest_contract_version(
+        package_key_ptr: *const u8,
+        package_key_size: usize,
+        version: u32,
+    ) -> i32;
     pub fn serialize_named_keys() -> usize;
     // Can only be called after `serialize_named_keys`.
     pub fn list_named_keys(dest_ptr: *mut u8);
     pub fn load_arg(i: u32) -> isize;
-    pub fn get_arg(dest: *mut u8); //can only be called after `load_arg`
+    pub fn get_arg(dest: *mut u8); //can only be called after `load_arg` or `load_named_arg`
+    pub fn load_named_arg(name_ptr: *const u8, name_size: usize) -> isize;
     pub fn ret(
         value_ptr: *const u8,
         value_size: usize,
@@ -46,6 +83,38 @@ extern "C" {
         extra_urefs_ptr: *const u8,
         extra_urefs_size: usize,
     ) -> usize;
+    pub fn call_contract_with_named_args(
+        key_ptr: *const u8,
+        key_size: usize,
+        named_args_ptr: *const u8,
+        named_args_size: usize,
+        // extra urefs known by the caller to make available to the callee
+        extra_urefs_ptr: *const u8,
+        extra_urefs_size: usize,
+    ) -> usize;
+    pub fn call_entry_point(
+        key_ptr: *const u8,
+        key_size: usize,
+        entry_point_ptr: *const u8,
+        entry_point_size: usize,
+        named_args_ptr: *const u8,
+        named_args_size: usize,
+        // extra urefs known by the caller to make available to the callee
+        extra_urefs_ptr: *const u8,
+        extra_urefs_size: usize,
+    ) -> usize;
+    pub fn call_versioned_contract(
+        package_key_ptr: *const u8,
+        package_key_size: usize,
+        version: u32,
+        entry_point_ptr: *const u8,
+        entry_point_size: usize,
+        named_args_ptr: *const u8,
+        named_args_size: usize,
+        // extra urefs known by the caller to make available to the callee
+        extra_urefs_ptr: *const u8,
+        extra_urefs_size: usize,
+    ) -> usize;
     pub fn get_call_result(res_ptr: *mut u8); //can only be called after `call_contract`
     pub fn get_key(name_ptr: *const u8, name_size: usize) -> usize;
     pub fn has_key(name_ptr: *const u8, name_size: usize) -> i32;
@@ -95,4 +164,28 @@ extern "C" {
         dest_ptr: *mut u8,
         dest_size: usize,
     ) -> i32;
+    pub fn emit_event(
+        topic_ptr: *const u8,
+        topic_size: usize,
+        payload_ptr: *const u8,
+        payload_size: usize,
+    );
+    pub fn hash(
+        algorithm: u32,
+        input_ptr: *const u8,
+        input_size: usize,
+        dest_ptr: *mut u8,
+        dest_size: usize,
+    ) -> i32;
+    pub fn verify_signature(
+        algorithm: u32,
+        public_key_ptr: *const u8,
+        public_key_size: usize,
+        message_ptr: *const u8,
+        message_size: usize,
+        signature_ptr: *const u8,
+        signature_size: usize,
+    ) -> i32;
+    pub fn random_bytes(dest_ptr: *mut u8, dest_size: usize);
+    pub fn set_inactivity_period(inactivity_period_ptr: *const u8) -> i32;
 }
@@ -6,9 +6,10 @@ use crate::value::account::{
     MAX_KEYS,
 };
 use crate::value::*;
-use alloc::collections::BTreeMap;
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::string::String;
-use proptest::collection::{btree_map, vec};
+use proptest::collection::{btree_map, btree_set, vec};
 use proptest::prelude::*;
 use proptest::{array, bits, option, result};
 
@@ -80,7 +81,15 @@ pub fn associated_keys_arb(size: usize) -> impl Strategy<Value = AssociatedKeys>
 }
 
 pub fn action_threshold_arb() -> impl Strategy<Value = ActionThresholds> {
-    Just(Default::default())
+    option::of(1u8..).prop_map(|key_recovery| {
+        let mut action_thresholds = ActionThresholds::default();
+        if let Some(key_recovery) = key_recovery {
+            action_thresholds
+                .set_key_recovery_threshold(Weight::new(key_recovery))
+                .unwrap();
+        }
+        action_thresholds
+    })
 }
 
 pub fn account_activity_arb() -> impl Strategy<Value = AccountActivity> {
@@ -109,15 +118,78 @@ prop_compose! {
     }
 }
 
+pub fn cl_type_arb() -> impl Strategy<Value = CLType> {
+    let leaf = prop_oneof![
+        Just(CLType::Bool),
+        Just(CLType::I64),
+        Just(CLType::U512),
+        Just(CLType::String),
+        Just(CLType::Key),
+        Just(CLType::URef),
+    ];
+    leaf.prop_recursive(3, 8, 2, |inner| {
+        prop_oneof![
+            inner.clone().prop_map(|t| CLType::Option(Box::new(t))),
+            inner.clone().prop_map(|t| CLType::List(Box::new(t))),
+            (inner.clone(), inner).prop_map(|(key, value)| CLType::Map {
+                key: Box::new(key),
+                value: Box::new(value)
+            }),
+        ]
+    })
+}
+
+pub fn entry_point_arb() -> impl Strategy<Value = EntryPoint> {
+    (
+        vec(
+            ("\\PC*", cl_type_arb()).prop_map(|(name, cl_type)| Parameter::new(&name, cl_type)),
+            0..5,
+        ),
+        prop_oneof![
+            Just(EntryPointAccess::Public),
+            Just(EntryPointAccess::System)
+        ],
+    )
+        .prop_map(|(parameters, access)| EntryPoint::new(parameters, access))
+}
+
 pub fn contract_arb() -> impl Strategy<Value = Contract> {
     protocol_version_arb().prop_flat_map(move |protocol_version_arb| {
         named_keys_arb(20).prop_flat_map(move |urefs| {
-            vec(any::<u8>(), 1..1000)
-                .prop_map(move |body| Contract::new(body, urefs.clone(), protocol_version_arb))
+            (
+                vec(any::<u8>(), 1..1000),
+                btree_map("\\PC*", entry_point_arb(), 0..5),
+            )
+                .prop_map(move |(body, entry_points)| {
+                    Contract::new(body, urefs.clone(), protocol_version_arb)
+                        .with_entry_points(entry_points)
+                })
         })
     })
 }
 
+pub fn contract_package_arb() -> impl Strategy<Value = ContractPackage> {
+    (
+        vec(key_arb(), 0..10),
+        any::<u32>(),
+        vec(any::<u32>(), 0..10),
+        named_keys_arb(10),
+    )
+        .prop_map(|(contract_keys, latest, disabled, mut named_keys)| {
+            let mut package = ContractPackage::new();
+            for contract_key in contract_keys {
+                package.add_version(contract_key);
+            }
+            // Invalid versions are rejected; the package is unchanged.
+            let _ = package.set_latest_version(latest % 10 + 1);
+            for version in disabled {
+                let _ = package.disable_version(version % 10 + 1);
+            }
+            package.named_keys_append(&mut named_keys);
+            package
+        })
+}
+
 pub fn sem_ver_arb() -> impl Strategy<Value = SemVer> {
     (any::<u32>(), any::<u32>(), any::<u32>())
         .prop_map(|(major, minor, patch)| SemVer::new(major, minor, patch))
@@ -139,6 +211,28 @@ pub fn u512_arb() -> impl Strategy<Value = U512> {
     vec(any::<u8>(), 0..64).prop_map(|b| U512::from_little_endian(b.as_slice()))
 }
 
+pub fn u512_btree_set_arb(size: usize) -> impl Strategy<Value = BTreeSet<U512>> {
+    btree_set(u512_arb(), size)
+}
+
+pub fn u256_keyed_btree_map_arb(size: usize) -> impl Strategy<Value = BTreeMap<U256, Key>> {
+    btree_map(u256_arb(), key_arb(), size)
+}
+
+pub fn cl_value_arb() -> impl Strategy<Value = CLValue> {
+    prop_oneof![
+        any::<bool>().prop_map(|b| CLValue::from_t(&b)),
+        any::<i64>().prop_map(|i| CLValue::from_t(&i)),
+        u512_arb().prop_map(|u| CLValue::from_t(&u)),
+        vec(key_arb(), 0..10).prop_map(|keys| CLValue::from_t(&keys)),
+        option::of(uref_arb()).prop_map(|uref| CLValue::from_t(&uref)),
+        named_keys_arb(10).prop_map(|named_keys| CLValue::from_t(&named_keys)),
+        result_arb().prop_map(|result| CLValue::from_t(&result)),
+        ("\\PC*", u256_arb(), any::<()>()).prop_map(|tuple| CLValue::from_t(&tuple)),
+    ]
+    .prop_map(|cl_value| cl_value.expect("should create CLValue"))
+}
+
 pub fn value_arb() -> impl Strategy<Value = Value> {
     // If compiler brings you here it most probably means you've added a variant to
     // `Value` enum but forgot to add generator for it.
@@ -158,7 +252,9 @@ pub fn value_arb() -> impl Strategy<Value = Value> {
             | Value::Contract(_)
             | Value::Key(_)
             | Value::NamedKey(_, _)
-            | Value::Unit => (),
+            | Value::Unit
+            | Value::CLValue(_)
+            | Value::ContractPackage(_) => (),
         }
     };
     prop_oneof![
@@ -176,6 +272,8 @@ pub fn value_arb() -> impl Strategy<Value = Value> {
         u512_arb().prop_map(Value::UInt512),
         Just(Value::Unit),
         (any::<u64>().prop_map(Value::UInt64)),
+        cl_value_arb().prop_map(Value::CLValue),
+        contract_package_arb().prop_map(Value::ContractPackage),
     ]
 }
 
@@ -5,7 +5,7 @@ use blake2::VarBlake2b;
 
 use crate::alloc::vec::Vec;
 use crate::base16;
-use crate::bytesrepr::{Error, FromBytes, ToBytes, N32, U32_SIZE};
+use crate::bytesrepr::{self, Error, FromBytes, ToBytes};
 use crate::contract_api::{ContractRef, TURef};
 use crate::uref::{AccessRights, URef, UREF_SIZE_SERIALIZED};
 
@@ -19,10 +19,7 @@ pub const LOCAL_KEY_SIZE: usize = 32;
 pub const LOCAL_SEED_SIZE: usize = 32;
 
 const KEY_ID_SIZE: usize = 1; // u8 used to determine the ID
-const ACCOUNT_KEY_SIZE: usize = KEY_ID_SIZE + U32_SIZE + N32;
-const HASH_KEY_SIZE: usize = KEY_ID_SIZE + U32_SIZE + N32;
 pub const UREF_SIZE: usize = KEY_ID_SIZE + UREF_SIZE_SERIALIZED;
-const LOCAL_SIZE: usize = KEY_ID_SIZE + U32_SIZE + LOCAL_KEY_SIZE;
 
 /// Creates a 32-byte BLAKE2b hash digest from a given a piece of data
 fn hash(bytes: &[u8]) -> [u8; LOCAL_KEY_SIZE] {
@@ -34,6 +31,16 @@ fn hash(bytes: &[u8]) -> [u8; LOCAL_KEY_SIZE] {
     ret
 }
 
+/// The variant of a [`Key`], as encoded in the first byte of its serialized form.
+#[repr(u8)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum KeyTag {
+    Account = ACCOUNT_ID,
+    Hash = HASH_ID,
+    URef = UREF_ID,
+    Local = LOCAL_ID,
+}
+
 #[repr(C)]
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 pub enum Key {
@@ -50,6 +57,15 @@ impl Key {
         Key::Local(hash)
     }
 
+    pub fn tag(&self) -> KeyTag {
+        match self {
+            Key::Account(_) => KeyTag::Account,
+            Key::Hash(_) => KeyTag::Hash,
+            Key::URef(_) => KeyTag::URef,
+            Key::Local(_) => KeyTag::Local,
+        }
+    }
+
     pub fn type_string(&self) -> String {
         match self {
             Key::Account(_) => String::from("Key::Account"),
@@ -198,30 +214,36 @@ impl<T> From<TURef<T>> for Key {
 
 impl ToBytes for Key {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        bytesrepr::to_vec(self)
+    }
+
+    fn serialized_length(&self) -> usize {
+        KEY_ID_SIZE
+            + match self {
+                Key::Account(addr) => addr.serialized_length(),
+                Key::Hash(hash) => hash.serialized_length(),
+                Key::URef(uref) => uref.serialized_length(),
+                Key::Local(hash) => hash.serialized_length(),
+            }
+    }
+
+    fn write_bytes<W: bytesrepr::Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
         match self {
             Key::Account(addr) => {
-                let mut result = Vec::with_capacity(ACCOUNT_KEY_SIZE);
-                result.push(ACCOUNT_ID);
-                result.append(&mut addr.to_bytes()?);
-                Ok(result)
+                ACCOUNT_ID.write_bytes(writer)?;
+                addr.write_bytes(writer)
             }
             Key::Hash(hash) => {
-                let mut result = Vec::with_capacity(HASH_KEY_SIZE);
-                result.push(HASH_ID);
-                result.append(&mut hash.to_bytes()?);
-                Ok(result)
+                HASH_ID.write_bytes(writer)?;
+                hash.write_bytes(writer)
             }
             Key::URef(uref) => {
-                let mut result = Vec::with_capacity(UREF_SIZE);
-                result.push(UREF_ID);
-                result.append(&mut uref.to_bytes()?);
-                Ok(result)
+                UREF_ID.write_bytes(writer)?;
+                uref.write_bytes(writer)
             }
             Key::Local(hash) => {
-                let mut result = Vec::with_capacity(LOCAL_SIZE);
-                result.push(LOCAL_ID);
-                result.append(&mut hash.to_bytes()?);
-                Ok(result)
+                LOCAL_ID.write_bytes(writer)?;
+                hash.write_bytes(writer)
             }
         }
     }
@@ -252,46 +274,32 @@ impl FromBytes for Key {
     }
 }
 
-impl FromBytes for Vec<Key> {
-    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
-        let (size, rest): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
-        let mut result = Vec::new();
-        result.try_reserve_exact(size as usize)?;
-        let mut stream = rest;
-        for _ in 0..size {
-            let (t, rem): (Key, &[u8]) = FromBytes::from_bytes(stream)?;
-            result.push(t);
-            stream = rem;
-        }
-        Ok((result, stream))
-    }
-}
-
-impl ToBytes for Vec<Key> {
-    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let size = self.len() as u32;
-        let mut result: Vec<u8> = Vec::with_capacity(4 + (size as usize) * UREF_SIZE);
-        result.extend(size.to_bytes()?);
-        result.extend(
-            self.iter()
-                .map(ToBytes::to_bytes)
-                .collect::<Result<Vec<_>, _>>()?
-                .into_iter()
-                .flatten(),
-        );
-        Ok(result)
-    }
-}
-
 #[allow(clippy::unnecessary_operation)]
 #[cfg(test)]
 mod tests {
-    use crate::bytesrepr::{Error, FromBytes};
-    use crate::key::Key;
+    use crate::bytesrepr::{Error, FromBytes, ToBytes};
+    use crate::key::{Key, KeyTag};
     use crate::uref::{AccessRights, URef};
     use alloc::string::String;
     use alloc::vec::Vec;
 
+    #[test]
+    fn key_tag_should_match_first_serialized_byte() {
+        let keys = [
+            (Key::Account([1; 32]), KeyTag::Account),
+            (Key::Hash([2; 32]), KeyTag::Hash),
+            (
+                Key::URef(URef::new([3; 32], AccessRights::READ)),
+                KeyTag::URef,
+            ),
+            (Key::Local([4; 32]), KeyTag::Local),
+        ];
+        for (key, tag) in keys.iter() {
+            assert_eq!(key.tag(), *tag);
+            assert_eq!(key.to_bytes().unwrap()[0], *tag as u8);
+        }
+    }
+
     fn test_readable(right: AccessRights, is_true: bool) {
         assert_eq!(right.is_readable(), is_true)
     }
@@ -33,6 +33,8 @@ pub mod args_parser;
 pub mod base16;
 pub mod bytesrepr;
 pub mod contract_api;
+pub mod crypto;
+pub mod event;
 pub mod execution;
 pub mod ext_ffi;
 #[cfg(any(test, feature = "gens"))]
@@ -40,6 +42,7 @@ pub mod gens;
 #[cfg(not(feature = "std"))]
 pub mod handlers;
 pub mod key;
+pub mod named_args;
 pub mod system_contracts;
 pub mod unwrap_or_revert;
 pub mod uref;
@@ -0,0 +1,88 @@
+use alloc::collections::btree_map::{BTreeMap, Iter};
+use alloc::string::String;
+
+use crate::bytesrepr::ToBytes;
+use crate::value::{CLTyped, CLValue, CLValueError};
+
+/// Arguments passed to a session or a stored contract by name, each carrying its own type.
+///
+/// Unlike the positional arguments produced by [`ArgsParser`](crate::args_parser::ArgsParser),
+/// named arguments are read with
+/// [`runtime::get_named_arg`](crate::contract_api::runtime::get_named_arg), which checks the
+/// requested type against the type the argument was passed with.
+#[derive(PartialEq, Eq, Clone, Debug, Default, ToBytes, FromBytes)]
+#[bytesrepr(crate = "crate")]
+pub struct NamedArgs(BTreeMap<String, CLValue>);
+
+impl NamedArgs {
+    pub fn new() -> Self {
+        NamedArgs(BTreeMap::new())
+    }
+
+    /// Serializes `value` and inserts it under `name`, replacing any argument of the same name.
+    pub fn insert<T: CLTyped + ToBytes>(
+        &mut self,
+        name: &str,
+        value: T,
+    ) -> Result<(), CLValueError> {
+        let cl_value = CLValue::from_t(&value)?;
+        self.insert_cl_value(name, cl_value);
+        Ok(())
+    }
+
+    pub fn insert_cl_value(&mut self, name: &str, cl_value: CLValue) {
+        self.0.insert(String::from(name), cl_value);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&CLValue> {
+        self.0.get(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> Iter<String, CLValue> {
+        self.0.iter()
+    }
+}
+
+impl From<BTreeMap<String, CLValue>> for NamedArgs {
+    fn from(map: BTreeMap<String, CLValue>) -> Self {
+        NamedArgs(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use crate::bytesrepr;
+    use crate::named_args::NamedArgs;
+    use crate::value::{CLValue, U512};
+
+    #[test]
+    fn should_insert_and_get_typed_arguments() {
+        let mut named_args = NamedArgs::new();
+        named_args
+            .insert("amount", U512::from(100))
+            .expect("should insert amount");
+        named_args
+            .insert("name", String::from("purse"))
+            .expect("should insert name");
+
+        assert_eq!(named_args.len(), 2);
+        assert_eq!(
+            named_args.get("amount").map(CLValue::to_t::<U512>),
+            Some(Ok(U512::from(100)))
+        );
+        assert!(named_args.get("amount").unwrap().to_t::<u64>().is_err());
+        assert_eq!(named_args.get("missing"), None);
+
+        bytesrepr::test_serialization_roundtrip(&named_args);
+    }
+}
@@ -25,6 +25,10 @@ pub enum Error {
     InvalidAccessRights = 4,
     #[fail(display = "Invalid non-empty purse creation")]
     InvalidNonEmptyPurseCreation = 5,
+    /// The source purse holds enough funds, but too many of them are still locked by its
+    /// [`VestingSchedule`](crate::system_contracts::mint::VestingSchedule).
+    #[fail(display = "Insufficient unlocked funds")]
+    InsufficientUnlockedFunds = 6,
     #[fail(display = "Missing argument")]
     MissingArgument = 102,
     #[fail(display = "Passed argument is invalid")]
@@ -64,6 +68,9 @@ impl TryFrom<u8> for Error {
             d if d == Error::InvalidNonEmptyPurseCreation as u8 => {
                 Ok(Error::InvalidNonEmptyPurseCreation)
             }
+            d if d == Error::InsufficientUnlockedFunds as u8 => {
+                Ok(Error::InsufficientUnlockedFunds)
+            }
             _ => Err(TryFromU8ForError(())),
         }
     }
@@ -74,6 +81,17 @@ impl ToBytes for Error {
         let value = *self as u8;
         value.to_bytes()
     }
+
+    fn serialized_length(&self) -> usize {
+        bytesrepr::U8_SIZE
+    }
+
+    fn write_bytes<W: bytesrepr::Write + ?Sized>(
+        &self,
+        writer: &mut W,
+    ) -> Result<(), bytesrepr::Error> {
+        (*self as u8).write_bytes(writer)
+    }
 }
 
 impl FromBytes for Error {
@@ -1,5 +1,7 @@
 mod error;
 mod purse_id;
+mod vesting;
 
 pub use error::Error;
 pub use purse_id::PurseIdError;
+pub use vesting::VestingSchedule;
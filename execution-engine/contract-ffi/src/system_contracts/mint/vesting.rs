@@ -0,0 +1,154 @@
+use alloc::vec;
+
+use crate::value::account::BlockTime;
+use crate::value::{CLType, CLTyped, U512};
+
+/// A cliff and linear vesting schedule attached to a purse by the mint.
+///
+/// Nothing of `amount` is unlocked until `cliff` has passed since `start`.  From then on, the
+/// unlocked part grows linearly with the time passed since `start`, until all of `amount` is
+/// unlocked once `duration` has passed.  Anything deposited into the purse on top of the schedule
+/// is unlocked straight away.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, ToBytes, FromBytes)]
+#[bytesrepr(crate = "crate")]
+pub struct VestingSchedule {
+    start: BlockTime,
+    cliff: BlockTime,
+    duration: BlockTime,
+    amount: U512,
+}
+
+impl VestingSchedule {
+    /// Creates a schedule vesting `amount` over `duration` starting at `start`, or `None` if
+    /// `cliff` is longer than `duration`.
+    pub fn new(
+        start: BlockTime,
+        cliff: BlockTime,
+        duration: BlockTime,
+        amount: U512,
+    ) -> Option<VestingSchedule> {
+        if cliff > duration {
+            return None;
+        }
+        Some(VestingSchedule {
+            start,
+            cliff,
+            duration,
+            amount,
+        })
+    }
+
+    pub fn start(&self) -> BlockTime {
+        self.start
+    }
+
+    pub fn cliff(&self) -> BlockTime {
+        self.cliff
+    }
+
+    pub fn duration(&self) -> BlockTime {
+        self.duration
+    }
+
+    pub fn amount(&self) -> U512 {
+        self.amount
+    }
+
+    /// Returns the part of `amount` which is unlocked at `blocktime`.
+    pub fn unlocked_amount(&self, blocktime: BlockTime) -> U512 {
+        let elapsed = blocktime.0.saturating_sub(self.start.0);
+        if elapsed < self.cliff.0 {
+            return U512::zero();
+        }
+        if elapsed >= self.duration.0 {
+            return self.amount;
+        }
+        // Split the multiplication so that it can't overflow for any `amount`
+        let elapsed = U512::from(elapsed);
+        let duration = U512::from(self.duration.0);
+        self.amount / duration * elapsed + self.amount % duration * elapsed / duration
+    }
+
+    /// Returns the part of `amount` which is still locked at `blocktime`.
+    pub fn locked_amount(&self, blocktime: BlockTime) -> U512 {
+        self.amount - self.unlocked_amount(blocktime)
+    }
+}
+
+/// A [`VestingSchedule`] is stored as a tuple of its start, cliff and duration block times
+/// followed by its amount.
+impl CLTyped for VestingSchedule {
+    fn cl_type() -> CLType {
+        CLType::Tuple(vec![CLType::U64, CLType::U64, CLType::U64, CLType::U512])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VestingSchedule;
+    use crate::bytesrepr;
+    use crate::value::account::BlockTime;
+    use crate::value::{CLValue, U512};
+
+    fn schedule() -> VestingSchedule {
+        VestingSchedule::new(
+            BlockTime(100),
+            BlockTime(25),
+            BlockTime(100),
+            U512::from(1000),
+        )
+        .expect("should create schedule")
+    }
+
+    #[test]
+    fn should_reject_cliff_longer_than_duration() {
+        assert!(
+            VestingSchedule::new(BlockTime(0), BlockTime(2), BlockTime(1), U512::one()).is_none()
+        );
+    }
+
+    #[test]
+    fn should_lock_everything_before_cliff() {
+        let schedule = schedule();
+        assert_eq!(schedule.locked_amount(BlockTime(0)), U512::from(1000));
+        assert_eq!(schedule.locked_amount(BlockTime(124)), U512::from(1000));
+    }
+
+    #[test]
+    fn should_unlock_linearly_after_cliff() {
+        let schedule = schedule();
+        assert_eq!(schedule.unlocked_amount(BlockTime(125)), U512::from(250));
+        assert_eq!(schedule.unlocked_amount(BlockTime(150)), U512::from(500));
+        assert_eq!(schedule.locked_amount(BlockTime(199)), U512::from(10));
+        assert_eq!(schedule.locked_amount(BlockTime(200)), U512::zero());
+        assert_eq!(
+            schedule.locked_amount(BlockTime(u64::max_value())),
+            U512::zero()
+        );
+    }
+
+    #[test]
+    fn should_not_overflow_for_max_amount() {
+        let schedule =
+            VestingSchedule::new(BlockTime(0), BlockTime(0), BlockTime(3), U512::max_value())
+                .expect("should create schedule");
+        assert_eq!(
+            schedule.unlocked_amount(BlockTime(1)),
+            U512::max_value() / 3
+        );
+    }
+
+    #[test]
+    fn should_round_trip_as_cl_value() {
+        let schedule = schedule();
+        bytesrepr::test_serialization_roundtrip(&schedule);
+
+        let cl_value = CLValue::from_t(&schedule).expect("should create CLValue");
+        let (start, cliff, duration, amount): (u64, u64, u64, U512) =
+            cl_value.to_t().expect("should read back as tuple");
+        assert_eq!(BlockTime(start), schedule.start());
+        assert_eq!(BlockTime(cliff), schedule.cliff());
+        assert_eq!(BlockTime(duration), schedule.duration());
+        assert_eq!(amount, schedule.amount());
+    }
+}
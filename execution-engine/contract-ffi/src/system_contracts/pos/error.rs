@@ -41,6 +41,33 @@ pub enum Error {
     FailedTransferToRewardsPurse,
     FailedTransferToAccountPurse,
     SetRefundPurseCalledOutsidePayment,
+    /// Returned when a delegator has nothing delegated to the given validator.
+    NotDelegated,
+    UndelegateTooLarge,
+    DelegationsNotStoredAsByteArray,
+    DelegationsDeserializationFailed,
+    DelegationsDeserializationExtraBytes,
+    /// Returned when a delegation request is for less than the minimum amount.
+    DelegationTooSmall,
+    /// Returned when a validator already has the maximum number of delegators.
+    TooManyDelegators,
+    RewardsPurseBalanceNotFound,
+    BurnPurseNotFound,
+    BurnPurseKeyUnexpectedType,
+    TreasuryPurseNotFound,
+    TreasuryPurseKeyUnexpectedType,
+    FailedTransferToBurnPurse,
+    FailedTransferToTreasuryPurse,
+    /// Returned when `slash` is called outside of the system phase.
+    SlashCalledOutsideSystemPhase,
+    /// Returned when the slashed fraction is more than a million millionths.
+    InvalidSlashFraction,
+    /// Returned when slashing would leave no bonded validators.
+    CannotSlashAllValidators,
+    /// Returned when `distribute_rewards` is called outside of the system phase.
+    DistributeRewardsCalledOutsideSystemPhase,
+    /// Returned when the time of the last rewards distribution can't be read.
+    LastDistributionDeserializationFailed,
 }
 
 pub type Result<T> = result::Result<T, Error>;
@@ -71,4 +98,18 @@ impl PurseLookupError {
             PurseLookupError::KeyUnexpectedType => Error::RewardsPurseKeyUnexpectedType,
         }
     }
+
+    pub fn burn(err: PurseLookupError) -> Error {
+        match err {
+            PurseLookupError::KeyNotFound => Error::BurnPurseNotFound,
+            PurseLookupError::KeyUnexpectedType => Error::BurnPurseKeyUnexpectedType,
+        }
+    }
+
+    pub fn treasury(err: PurseLookupError) -> Error {
+        match err {
+            PurseLookupError::KeyNotFound => Error::TreasuryPurseNotFound,
+            PurseLookupError::KeyUnexpectedType => Error::TreasuryPurseKeyUnexpectedType,
+        }
+    }
 }
@@ -1,5 +1,12 @@
 mod error;
+mod rewards_config;
+mod slash;
 
 pub use error::Error;
 pub use error::PurseLookupError;
 pub use error::Result;
+pub use rewards_config::{
+    share_of, RewardsConfig, DEFAULT_DISTRIBUTION_INTERVAL, REWARDS_CONFIG_SIZE_SERIALIZED,
+    SHARE_DENOMINATOR,
+};
+pub use slash::SlashDestination;
@@ -0,0 +1,152 @@
+use crate::value::account::BlockTime;
+use crate::value::U512;
+
+/// The denominator of the shares in a [`RewardsConfig`], i.e. shares are given in millionths.
+pub const SHARE_DENOMINATOR: u32 = 1_000_000;
+
+/// The serialized size of a [`RewardsConfig`].
+pub const REWARDS_CONFIG_SIZE_SERIALIZED: usize = 8 + 3 * 4;
+
+/// The default time between two distributions of the rewards purse.
+pub const DEFAULT_DISTRIBUTION_INTERVAL: BlockTime = BlockTime(3_600_000);
+
+/// Returns `share` millionths of `amount`, rounded down.
+pub fn share_of(amount: U512, share: u32) -> U512 {
+    // Split the multiplication so that it can't overflow for any `amount`
+    let denominator = U512::from(SHARE_DENOMINATOR);
+    let share = U512::from(share);
+    amount / denominator * share + amount % denominator * share / denominator
+}
+
+/// How the Proof of Stake contract splits the payment collected for computation, and how often
+/// it distributes the validators' part of it.
+///
+/// Out of each payment, `validator_commission` millionths are collected in the rewards purse and
+/// paid out to the bonded validators and their delegators pro rata to their stakes once
+/// `distribution_interval` has passed since the last distribution.  `burn` millionths are moved
+/// to the burn purse, which is never paid out, and `treasury` millionths to the treasury purse.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ToBytes, FromBytes)]
+#[bytesrepr(crate = "crate")]
+pub struct RewardsConfig {
+    distribution_interval: BlockTime,
+    validator_commission: u32,
+    burn: u32,
+    treasury: u32,
+}
+
+impl RewardsConfig {
+    /// Creates a new [`RewardsConfig`], or `None` if the shares don't add up to
+    /// [`SHARE_DENOMINATOR`].
+    pub fn new(
+        distribution_interval: BlockTime,
+        validator_commission: u32,
+        burn: u32,
+        treasury: u32,
+    ) -> Option<RewardsConfig> {
+        let total = u64::from(validator_commission) + u64::from(burn) + u64::from(treasury);
+        if total != u64::from(SHARE_DENOMINATOR) {
+            return None;
+        }
+        Some(RewardsConfig {
+            distribution_interval,
+            validator_commission,
+            burn,
+            treasury,
+        })
+    }
+
+    pub fn distribution_interval(&self) -> BlockTime {
+        self.distribution_interval
+    }
+
+    pub fn validator_commission(&self) -> u32 {
+        self.validator_commission
+    }
+
+    pub fn burn(&self) -> u32 {
+        self.burn
+    }
+
+    pub fn treasury(&self) -> u32 {
+        self.treasury
+    }
+
+    /// Splits `amount` into the parts for the validators, the burn purse and the treasury purse.
+    /// The rounding remainder is added to the validators' part, so the parts always add up to
+    /// `amount`.
+    pub fn split(&self, amount: U512) -> (U512, U512, U512) {
+        let burn = share_of(amount, self.burn);
+        let treasury = share_of(amount, self.treasury);
+        (amount - burn - treasury, burn, treasury)
+    }
+}
+
+/// By default, all of the payment goes to the validators and is distributed every
+/// [`DEFAULT_DISTRIBUTION_INTERVAL`].
+impl Default for RewardsConfig {
+    fn default() -> Self {
+        RewardsConfig {
+            distribution_interval: DEFAULT_DISTRIBUTION_INTERVAL,
+            validator_commission: SHARE_DENOMINATOR,
+            burn: 0,
+            treasury: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{share_of, RewardsConfig, REWARDS_CONFIG_SIZE_SERIALIZED, SHARE_DENOMINATOR};
+    use crate::bytesrepr::{self, ToBytes};
+    use crate::value::account::BlockTime;
+    use crate::value::U512;
+
+    #[test]
+    fn should_reject_shares_not_adding_up() {
+        assert!(RewardsConfig::new(BlockTime(1), 500_000, 250_000, 250_001).is_none());
+        assert!(RewardsConfig::new(BlockTime(1), 500_000, 250_000, 249_999).is_none());
+        assert!(RewardsConfig::new(BlockTime(1), u32::max_value(), 1, 0).is_none());
+        assert!(RewardsConfig::new(BlockTime(1), 0, 0, SHARE_DENOMINATOR).is_some());
+    }
+
+    #[test]
+    fn should_split_amount() {
+        let config = RewardsConfig::new(BlockTime(1), 500_000, 250_000, 250_000)
+            .expect("should create config");
+        assert_eq!(
+            config.split(U512::from(1_003)),
+            (U512::from(503), U512::from(250), U512::from(250))
+        );
+        let (commission, burn, treasury) = config.split(U512::max_value());
+        assert_eq!(commission + burn + treasury, U512::max_value());
+        assert_eq!(
+            RewardsConfig::default().split(U512::from(7)),
+            (U512::from(7), U512::zero(), U512::zero())
+        );
+    }
+
+    #[test]
+    fn should_take_share_of_amount() {
+        assert_eq!(share_of(U512::from(999), 500_000), U512::from(499));
+        assert_eq!(
+            share_of(U512::from(999), SHARE_DENOMINATOR),
+            U512::from(999)
+        );
+        assert_eq!(share_of(U512::max_value(), 0), U512::zero());
+        assert_eq!(
+            share_of(U512::max_value(), SHARE_DENOMINATOR),
+            U512::max_value()
+        );
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize() {
+        let config = RewardsConfig::new(BlockTime(42), 1, 2, SHARE_DENOMINATOR - 3)
+            .expect("should create config");
+        assert_eq!(
+            config.to_bytes().expect("should serialize").len(),
+            REWARDS_CONFIG_SIZE_SERIALIZED
+        );
+        bytesrepr::test_serialization_roundtrip(&config);
+    }
+}
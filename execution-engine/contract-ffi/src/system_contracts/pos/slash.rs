@@ -0,0 +1,27 @@
+/// Where the Proof of Stake contract moves the motes taken from slashed validators.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ToBytes, FromBytes)]
+#[bytesrepr(crate = "crate")]
+pub enum SlashDestination {
+    /// The slashed motes are moved to the burn purse and never paid out.
+    Burn,
+    /// The slashed motes are moved to the treasury purse.
+    Treasury,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SlashDestination;
+    use crate::bytesrepr::{self, ToBytes};
+
+    #[test]
+    fn should_serialize_and_deserialize() {
+        assert_eq!(
+            SlashDestination::Treasury
+                .to_bytes()
+                .expect("should serialize"),
+            vec![1]
+        );
+        bytesrepr::test_serialization_roundtrip(&SlashDestination::Burn);
+        bytesrepr::test_serialization_roundtrip(&SlashDestination::Treasury);
+    }
+}
@@ -4,7 +4,7 @@ use crate::alloc::string::String;
 use crate::alloc::vec::Vec;
 use crate::base16;
 use crate::bytesrepr;
-use crate::bytesrepr::{OPTION_SIZE, U32_SIZE};
+use crate::bytesrepr::{ToBytes, Write, OPTION_SIZE, U32_SIZE};
 use crate::contract_api::TURef;
 
 pub const UREF_ADDR_SIZE: usize = 32;
@@ -58,6 +58,14 @@ impl bytesrepr::ToBytes for AccessRights {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         self.bits.to_bytes()
     }
+
+    fn serialized_length(&self) -> usize {
+        ACCESS_RIGHTS_SIZE
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), bytesrepr::Error> {
+        self.bits.write_bytes(writer)
+    }
 }
 
 impl bytesrepr::FromBytes for AccessRights {
@@ -180,10 +188,16 @@ impl URef {
 
 impl bytesrepr::ToBytes for URef {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
-        let mut result = Vec::with_capacity(UREF_SIZE_SERIALIZED);
-        result.append(&mut self.0.to_bytes()?);
-        result.append(&mut self.1.to_bytes()?);
-        Ok(result)
+        bytesrepr::to_vec(self)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length() + self.1.serialized_length()
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), bytesrepr::Error> {
+        self.0.write_bytes(writer)?;
+        self.1.write_bytes(writer)
     }
 }
 
@@ -196,36 +210,6 @@ impl bytesrepr::FromBytes for URef {
     }
 }
 
-impl bytesrepr::FromBytes for Vec<URef> {
-    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
-        let (size, mut stream): (u32, &[u8]) = bytesrepr::FromBytes::from_bytes(bytes)?;
-        let mut result = Vec::new();
-        result.try_reserve_exact(size as usize)?;
-        for _ in 0..size {
-            let (uref, rem): (URef, &[u8]) = bytesrepr::FromBytes::from_bytes(stream)?;
-            result.push(uref);
-            stream = rem;
-        }
-        Ok((result, stream))
-    }
-}
-
-impl bytesrepr::ToBytes for Vec<URef> {
-    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
-        let size = self.len() as u32;
-        let mut result: Vec<u8> = Vec::with_capacity(U32_SIZE);
-        result.extend(size.to_bytes()?);
-        result.extend(
-            self.iter()
-                .map(bytesrepr::ToBytes::to_bytes)
-                .collect::<Result<Vec<_>, _>>()?
-                .into_iter()
-                .flatten(),
-        );
-        Ok(result)
-    }
-}
-
 impl<T> From<TURef<T>> for URef {
     fn from(input: TURef<T>) -> Self {
         URef(input.addr(), Some(input.access_rights()))
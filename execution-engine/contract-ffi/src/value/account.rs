@@ -1,7 +1,7 @@
-use crate::bytesrepr::{Error, FromBytes, ToBytes, U32_SIZE, U64_SIZE, U8_SIZE};
+use crate::bytesrepr::{self, Error, FromBytes, ToBytes, Write, U64_SIZE, U8_SIZE};
 use crate::contract_api::runtime;
 use crate::contract_api::Error as ApiError;
-use crate::key::{addr_to_hex, Key, UREF_SIZE};
+use crate::key::{addr_to_hex, Key};
 use crate::unwrap_or_revert::UnwrapOrRevert;
 use crate::uref::{AccessRights, URef, UREF_SIZE_SERIALIZED};
 use alloc::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
@@ -12,7 +12,10 @@ use core::fmt::{Debug, Display, Formatter};
 use failure::Fail;
 
 const DEFAULT_CURRENT_BLOCK_TIME: BlockTime = BlockTime(0);
-const DEFAULT_INACTIVITY_PERIOD_TIME: BlockTime = BlockTime(100);
+/// Thirty days, in milliseconds.
+const DEFAULT_INACTIVITY_PERIOD_TIME: BlockTime = BlockTime(30 * 24 * 60 * 60 * 1000);
+/// The shortest inactivity period limit an account can set: one day, in milliseconds.
+pub const MIN_INACTIVITY_PERIOD_TIME: BlockTime = BlockTime(24 * 60 * 60 * 1000);
 
 pub const PURSE_ID_SIZE_SERIALIZED: usize = UREF_SIZE_SERIALIZED;
 
@@ -49,6 +52,14 @@ impl ToBytes for PurseId {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         ToBytes::to_bytes(&self.0)
     }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.0.write_bytes(writer)
+    }
 }
 
 impl FromBytes for PurseId {
@@ -64,6 +75,9 @@ pub enum ActionType {
     /// Required when adding/removing associated keys, changing threshold
     /// levels.
     KeyManagement = 1,
+    /// Required when managing associated keys of an account that has been
+    /// inactive for longer than its inactivity period limit.
+    KeyRecovery = 2,
 }
 
 /// convert from u32 representation of `[ActionType]`
@@ -77,16 +91,21 @@ impl TryFrom<u32> for ActionType {
         match value {
             d if d == ActionType::Deployment as u32 => Ok(ActionType::Deployment),
             d if d == ActionType::KeyManagement as u32 => Ok(ActionType::KeyManagement),
+            d if d == ActionType::KeyRecovery as u32 => Ok(ActionType::KeyRecovery),
             _ => Err(TryFromIntError(())),
         }
     }
 }
 
 /// Thresholds that has to be met when executing an action of certain type.
+///
+/// Key recovery is opt-in: until a key recovery threshold is set explicitly, there is none and
+/// an inactive account still requires the key management threshold.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ActionThresholds {
     deployment: Weight,
     key_management: Weight,
+    key_recovery: Option<Weight>,
 }
 
 /// Represents an error that occurs during the change of a thresholds on an
@@ -115,6 +134,8 @@ pub enum SetThresholdFailure {
         display = "New threshold should be lower or equal than total weight of associated keys"
     )]
     InsufficientTotalWeight = 4,
+    #[fail(display = "Key recovery threshold should be greater than zero")]
+    KeyRecoveryThresholdError = 5,
 }
 
 /// convert from i32 representation of `[SetThresholdFailure]`
@@ -135,6 +156,9 @@ impl TryFrom<i32> for SetThresholdFailure {
             d if d == SetThresholdFailure::InsufficientTotalWeight as i32 => {
                 Ok(SetThresholdFailure::InsufficientTotalWeight)
             }
+            d if d == SetThresholdFailure::KeyRecoveryThresholdError as i32 => {
+                Ok(SetThresholdFailure::KeyRecoveryThresholdError)
+            }
             _ => Err(TryFromIntError(())),
         }
     }
@@ -144,7 +168,7 @@ impl ActionThresholds {
     /// Creates new ActionThresholds object with provided weights
     ///
     /// Requires deployment threshold to be lower than or equal to
-    /// key management threshold.
+    /// key management threshold. There is no key recovery threshold.
     pub fn new(
         deployment: Weight,
         key_management: Weight,
@@ -155,6 +179,7 @@ impl ActionThresholds {
         Ok(ActionThresholds {
             deployment,
             key_management,
+            key_recovery: None,
         })
     }
     /// Sets new threshold for [ActionType::Deployment].
@@ -188,6 +213,24 @@ impl ActionThresholds {
         }
     }
 
+    /// Sets new threshold for [ActionType::KeyRecovery], which opts the
+    /// account into key recovery.
+    ///
+    /// Recovery only makes key management easier if the new threshold is
+    /// lower than the key management threshold, so no invariant is enforced
+    /// against the other thresholds, but it can't be zero.
+    pub fn set_key_recovery_threshold(
+        &mut self,
+        new_threshold: Weight,
+    ) -> Result<(), SetThresholdFailure> {
+        if new_threshold.value() == 0 {
+            Err(SetThresholdFailure::KeyRecoveryThresholdError)
+        } else {
+            self.key_recovery = Some(new_threshold);
+            Ok(())
+        }
+    }
+
     pub fn deployment(&self) -> &Weight {
         &self.deployment
     }
@@ -196,6 +239,12 @@ impl ActionThresholds {
         &self.key_management
     }
 
+    /// Returns the key recovery threshold, or `None` if the account hasn't
+    /// opted into key recovery.
+    pub fn key_recovery(&self) -> Option<&Weight> {
+        self.key_recovery.as_ref()
+    }
+
     /// Unified function that takes an action type, and changes appropriate
     /// threshold defined by the [ActionType] variants.
     pub fn set_threshold(
@@ -206,6 +255,7 @@ impl ActionThresholds {
         match action_type {
             ActionType::Deployment => self.set_deployment_threshold(new_threshold),
             ActionType::KeyManagement => self.set_key_management_threshold(new_threshold),
+            ActionType::KeyRecovery => self.set_key_recovery_threshold(new_threshold),
         }
     }
 }
@@ -215,6 +265,7 @@ impl Default for ActionThresholds {
         ActionThresholds {
             deployment: Weight::new(1),
             key_management: Weight::new(1),
+            key_recovery: None,
         }
     }
 }
@@ -234,7 +285,6 @@ pub struct AccountActivity {
 }
 
 impl AccountActivity {
-    // TODO: We need default for inactivity_period_limit.
     // `current_block_time` value is passed in from the node and is coming from the
     // parent block. [inactivity_period_limit] block time period after which
     // account is eligible for recovery.
@@ -272,6 +322,14 @@ impl AccountActivity {
     pub fn inactivity_period_limit(&self) -> BlockTime {
         self.inactivity_period_limit
     }
+
+    /// Checks whether neither action was used during the inactivity period
+    /// limit preceding `current_block_time`.
+    pub fn is_inactive(&self, current_block_time: BlockTime) -> bool {
+        let last_used =
+            core::cmp::max(self.key_management_last_used.0, self.deployment_last_used.0);
+        current_block_time.0.saturating_sub(last_used) >= self.inactivity_period_limit.0
+    }
 }
 
 pub const KEY_SIZE: usize = 32;
@@ -355,6 +413,14 @@ impl ToBytes for PublicKey {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         ToBytes::to_bytes(&self.0)
     }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.0.write_bytes(writer)
+    }
 }
 
 impl FromBytes for PublicKey {
@@ -660,6 +726,10 @@ impl Account {
         &self.account_activity
     }
 
+    pub fn account_activity_mut(&mut self) -> &mut AccountActivity {
+        &mut self.account_activity
+    }
+
     pub fn add_associated_key(
         &mut self,
         public_key: PublicKey,
@@ -760,12 +830,52 @@ impl Account {
 
         total_weight >= *self.action_thresholds().key_management()
     }
+
+    /// Checks whether the account opted into key recovery with a threshold
+    /// lower than the key management threshold, i.e. whether activity on this
+    /// account needs to be tracked.
+    pub fn has_key_recovery(&self) -> bool {
+        match self.action_thresholds().key_recovery() {
+            Some(key_recovery) => key_recovery < self.action_thresholds().key_management(),
+            None => false,
+        }
+    }
+
+    /// Checks whether the account has been inactive at `current_block_time`
+    /// and the sum of the weights of all authorization keys is greater or
+    /// equal to key recovery threshold.
+    pub fn can_recover_keys_with(
+        &self,
+        authorization_keys: &BTreeSet<PublicKey>,
+        current_block_time: BlockTime,
+    ) -> bool {
+        if !self.has_key_recovery() || !self.account_activity.is_inactive(current_block_time) {
+            return false;
+        }
+
+        let total_weight = self
+            .associated_keys
+            .calculate_keys_weight(authorization_keys);
+
+        match self.action_thresholds().key_recovery() {
+            Some(key_recovery) => total_weight >= *key_recovery,
+            None => false,
+        }
+    }
 }
 
 impl ToBytes for Weight {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         ToBytes::to_bytes(&self.0)
     }
+
+    fn serialized_length(&self) -> usize {
+        WEIGHT_SIZE
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.0.write_bytes(writer)
+    }
 }
 
 impl FromBytes for Weight {
@@ -779,6 +889,14 @@ impl ToBytes for AssociatedKeys {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         ToBytes::to_bytes(&self.0)
     }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.0.write_bytes(writer)
+    }
 }
 
 impl FromBytes for AssociatedKeys {
@@ -801,6 +919,14 @@ impl ToBytes for BlockTime {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         self.0.to_bytes()
     }
+
+    fn serialized_length(&self) -> usize {
+        BLOCKTIME_SER_SIZE
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.0.write_bytes(writer)
+    }
 }
 
 impl FromBytes for BlockTime {
@@ -810,12 +936,35 @@ impl FromBytes for BlockTime {
     }
 }
 
+/// Written after the deployment and key management thresholds when there is no key recovery
+/// threshold.
+const NO_KEY_RECOVERY_THRESHOLD_TAG: u8 = 3;
+/// Written after the deployment and key management thresholds, followed by the key recovery
+/// threshold, when there is one.
+const KEY_RECOVERY_THRESHOLD_TAG: u8 = 4;
+
 impl ToBytes for ActionThresholds {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let mut result = Vec::with_capacity(2 * WEIGHT_SIZE);
-        result.extend(&self.deployment.to_bytes()?);
-        result.extend(&self.key_management.to_bytes()?);
-        Ok(result)
+        bytesrepr::to_vec(self)
+    }
+
+    fn serialized_length(&self) -> usize {
+        match self.key_recovery {
+            Some(_) => 2 * WEIGHT_SIZE + U8_SIZE + WEIGHT_SIZE,
+            None => 2 * WEIGHT_SIZE + U8_SIZE,
+        }
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.deployment.write_bytes(writer)?;
+        self.key_management.write_bytes(writer)?;
+        match self.key_recovery {
+            Some(key_recovery) => {
+                KEY_RECOVERY_THRESHOLD_TAG.write_bytes(writer)?;
+                key_recovery.write_bytes(writer)
+            }
+            None => NO_KEY_RECOVERY_THRESHOLD_TAG.write_bytes(writer),
+        }
     }
 }
 
@@ -823,9 +972,20 @@ impl FromBytes for ActionThresholds {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
         let (deployment, rem): (Weight, &[u8]) = FromBytes::from_bytes(&bytes)?;
         let (key_management, rem): (Weight, &[u8]) = FromBytes::from_bytes(&rem)?;
+        let (key_recovery, rem) = match rem.split_first() {
+            Some((&NO_KEY_RECOVERY_THRESHOLD_TAG, rem)) => (None, rem),
+            Some((&KEY_RECOVERY_THRESHOLD_TAG, rem)) => {
+                let (key_recovery, rem): (Weight, &[u8]) = FromBytes::from_bytes(rem)?;
+                (Some(key_recovery), rem)
+            }
+            // The layout before key recovery existed, which has no tag.  In an account, it is
+            // followed by account activity, whose field ids are all different from the tags.
+            _ => (None, rem),
+        };
         let ret = ActionThresholds {
             deployment,
             key_management,
+            key_recovery,
         };
         Ok((ret, rem))
     }
@@ -837,14 +997,20 @@ const INACTIVITY_PERIOD_LIMIT_ID: u8 = 2;
 
 impl ToBytes for AccountActivity {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let mut result = Vec::with_capacity(3 * (BLOCKTIME_SER_SIZE + U8_SIZE));
-        result.push(KEY_MANAGEMENT_LAST_USED_ID);
-        result.extend(&self.key_management_last_used.to_bytes()?);
-        result.push(DEPLOYMENT_LAST_USED_ID);
-        result.extend(&self.deployment_last_used.to_bytes()?);
-        result.push(INACTIVITY_PERIOD_LIMIT_ID);
-        result.extend(&self.inactivity_period_limit.to_bytes()?);
-        Ok(result)
+        bytesrepr::to_vec(self)
+    }
+
+    fn serialized_length(&self) -> usize {
+        3 * (BLOCKTIME_SER_SIZE + U8_SIZE)
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        KEY_MANAGEMENT_LAST_USED_ID.write_bytes(writer)?;
+        self.key_management_last_used.write_bytes(writer)?;
+        DEPLOYMENT_LAST_USED_ID.write_bytes(writer)?;
+        self.deployment_last_used.write_bytes(writer)?;
+        INACTIVITY_PERIOD_LIMIT_ID.write_bytes(writer)?;
+        self.inactivity_period_limit.write_bytes(writer)
     }
 }
 
@@ -875,29 +1041,28 @@ impl FromBytes for AccountActivity {
 
 impl ToBytes for Account {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        let action_thresholds_size = 2 * (WEIGHT_SIZE + U8_SIZE);
-        let account_activity_size: usize = 3 * (BLOCKTIME_SER_SIZE + U8_SIZE);
-        let associated_keys_size =
-            self.associated_keys.0.len() * (PUBLIC_KEY_SIZE + WEIGHT_SIZE) + U32_SIZE;
-        let named_keys_size = UREF_SIZE * self.named_keys.len() + U32_SIZE;
-        let purse_id_size = UREF_SIZE;
-        let serialized_account_size = KEY_SIZE // pub key
-            + named_keys_size
-            + purse_id_size
-            + associated_keys_size
-            + action_thresholds_size
-            + account_activity_size;
-        if serialized_account_size >= u32::max_value() as usize {
+        bytesrepr::to_vec(self)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.public_key.serialized_length()
+            + self.named_keys.serialized_length()
+            + self.purse_id.serialized_length()
+            + self.associated_keys.serialized_length()
+            + self.action_thresholds.serialized_length()
+            + self.account_activity.serialized_length()
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        if self.serialized_length() >= u32::max_value() as usize {
             return Err(Error::OutOfMemoryError);
         }
-        let mut result: Vec<u8> = Vec::with_capacity(serialized_account_size);
-        result.extend(&self.public_key.to_bytes()?);
-        result.append(&mut self.named_keys.to_bytes()?);
-        result.append(&mut self.purse_id.value().to_bytes()?);
-        result.append(&mut self.associated_keys.to_bytes()?);
-        result.append(&mut self.action_thresholds.to_bytes()?);
-        result.append(&mut self.account_activity.to_bytes()?);
-        Ok(result)
+        self.public_key.write_bytes(writer)?;
+        self.named_keys.write_bytes(writer)?;
+        self.purse_id.write_bytes(writer)?;
+        self.associated_keys.write_bytes(writer)?;
+        self.action_thresholds.write_bytes(writer)?;
+        self.account_activity.write_bytes(writer)
     }
 }
 
@@ -926,6 +1091,7 @@ impl FromBytes for Account {
 
 #[cfg(test)]
 mod tests {
+    use crate::bytesrepr::{self, FromBytes, ToBytes};
     use crate::uref::{AccessRights, URef};
     use crate::value::account::{
         Account, AccountActivity, ActionThresholds, ActionType, AddKeyFailure, AssociatedKeys,
@@ -1160,6 +1326,106 @@ mod tests {
         ])));
     }
 
+    #[test]
+    fn account_can_recover_keys_with_after_inactivity_period() {
+        let owner_key = PublicKey::new([1u8; 32]);
+        let recovery_key = PublicKey::new([2u8; 32]);
+        let associated_keys = {
+            let mut res = AssociatedKeys::new(owner_key, Weight::new(3));
+            res.add_key(recovery_key, Weight::new(1))
+                .expect("should add recovery key");
+            res
+        };
+        let mut account = Account::new(
+            [0u8; 32],
+            BTreeMap::new(),
+            PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
+            associated_keys,
+            ActionThresholds::new(Weight::new(1), Weight::new(3))
+                .expect("should create thresholds"),
+            AccountActivity::new(BlockTime(10), BlockTime(100)),
+        );
+        let recovery_keys = BTreeSet::from_iter(vec![recovery_key]);
+
+        // key recovery is opt-in
+        assert_eq!(account.action_thresholds().key_recovery(), None);
+        assert!(!account.has_key_recovery());
+        assert!(!account.can_recover_keys_with(&recovery_keys, BlockTime(1000)));
+
+        account
+            .set_action_threshold(ActionType::KeyRecovery, Weight::new(0))
+            .expect_err("should not set zero recovery threshold");
+        account
+            .set_action_threshold(ActionType::KeyRecovery, Weight::new(1))
+            .expect("should set recovery threshold");
+        assert!(account.has_key_recovery());
+
+        // inactivity period hasn't elapsed yet
+        assert!(!account.can_recover_keys_with(&recovery_keys, BlockTime(109)));
+        assert!(account.can_recover_keys_with(&recovery_keys, BlockTime(110)));
+
+        // any later activity restarts the inactivity period
+        account
+            .account_activity_mut()
+            .update_deployment_last_used(BlockTime(500));
+        assert!(!account.can_recover_keys_with(&recovery_keys, BlockTime(599)));
+        assert!(account.can_recover_keys_with(&recovery_keys, BlockTime(600)));
+        assert!(!account.can_manage_keys_with(&recovery_keys));
+    }
+
+    #[test]
+    fn action_thresholds_serialization_roundtrip() {
+        let mut thresholds = ActionThresholds::new(Weight::new(1), Weight::new(2))
+            .expect("should create thresholds");
+        bytesrepr::test_serialization_roundtrip(&thresholds);
+
+        // The thresholds end where they say they do, whatever follows them
+        let mut bytes = thresholds.to_bytes().expect("should serialize");
+        let trailing = [super::KEY_RECOVERY_THRESHOLD_TAG, 1];
+        bytes.extend_from_slice(&trailing);
+        let (read_back, rem): (ActionThresholds, &[u8]) =
+            FromBytes::from_bytes(&bytes).expect("should deserialize");
+        assert_eq!(read_back, thresholds);
+        assert_eq!(rem, &trailing);
+
+        thresholds
+            .set_key_recovery_threshold(Weight::new(3))
+            .expect("should set recovery threshold");
+        bytesrepr::test_serialization_roundtrip(&thresholds);
+        assert_eq!(
+            thresholds.to_bytes().expect("should serialize").len(),
+            thresholds.serialized_length()
+        );
+    }
+
+    #[test]
+    fn should_read_account_stored_without_key_recovery_threshold() {
+        let mut account = Account::create(
+            [0u8; 32],
+            BTreeMap::new(),
+            PurseId::new(URef::new([0u8; 32], AccessRights::READ_ADD_WRITE)),
+        );
+        bytesrepr::test_serialization_roundtrip(&account);
+
+        // The layout before key recovery existed lacks the tag which ends the action thresholds
+        let mut legacy_bytes = account.to_bytes().expect("should serialize");
+        let tag_index = legacy_bytes.len() - account.account_activity().serialized_length() - 1;
+        assert_eq!(
+            legacy_bytes.remove(tag_index),
+            super::NO_KEY_RECOVERY_THRESHOLD_TAG
+        );
+        let (legacy_account, rem): (Account, &[u8]) =
+            FromBytes::from_bytes(&legacy_bytes).expect("should deserialize");
+        assert!(rem.is_empty());
+        assert_eq!(legacy_account, account);
+
+        account
+            .set_action_threshold(ActionType::KeyRecovery, Weight::new(1))
+            .expect("should set recovery threshold");
+        assert_eq!(account.to_bytes().unwrap().len(), legacy_bytes.len() + 2);
+        bytesrepr::test_serialization_roundtrip(&account);
+    }
+
     #[test]
     fn public_key_from_slice() {
         let bytes: Vec<u8> = (0..32).collect();
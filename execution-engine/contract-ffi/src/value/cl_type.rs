@@ -0,0 +1,314 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::bytesrepr::{Error, FromBytes, ToBytes};
+use crate::key::Key;
+use crate::uref::URef;
+use crate::value::{U128, U256, U512};
+
+// NOTE: These tags are part of the serialized form of values in global state. DO NOT CHANGE.
+const CL_TYPE_TAG_BOOL: u8 = 0;
+const CL_TYPE_TAG_I32: u8 = 1;
+const CL_TYPE_TAG_I64: u8 = 2;
+const CL_TYPE_TAG_U8: u8 = 3;
+const CL_TYPE_TAG_U32: u8 = 4;
+const CL_TYPE_TAG_U64: u8 = 5;
+const CL_TYPE_TAG_U128: u8 = 6;
+const CL_TYPE_TAG_U256: u8 = 7;
+const CL_TYPE_TAG_U512: u8 = 8;
+const CL_TYPE_TAG_UNIT: u8 = 9;
+const CL_TYPE_TAG_STRING: u8 = 10;
+const CL_TYPE_TAG_KEY: u8 = 11;
+const CL_TYPE_TAG_UREF: u8 = 12;
+const CL_TYPE_TAG_OPTION: u8 = 13;
+const CL_TYPE_TAG_LIST: u8 = 14;
+const CL_TYPE_TAG_RESULT: u8 = 15;
+const CL_TYPE_TAG_MAP: u8 = 16;
+const CL_TYPE_TAG_TUPLE: u8 = 17;
+
+/// The maximum nesting depth of a deserialized `CLType`.  Types are read from untrusted input, so
+/// this bounds the recursion when deserializing them and the values they describe.
+pub const CL_TYPE_MAX_DEPTH: usize = 32;
+
+/// The type of a value stored in a [`CLValue`](crate::value::CLValue).
+///
+/// A `CLType` describes the serialized form of a value, so the value can be inspected without
+/// knowing which contract stored it.
+#[derive(PartialEq, Eq, Clone, Debug, ToBytes)]
+#[bytesrepr(crate = "crate")]
+pub enum CLType {
+    #[bytesrepr(tag = "CL_TYPE_TAG_BOOL")]
+    Bool,
+    #[bytesrepr(tag = "CL_TYPE_TAG_I32")]
+    I32,
+    #[bytesrepr(tag = "CL_TYPE_TAG_I64")]
+    I64,
+    #[bytesrepr(tag = "CL_TYPE_TAG_U8")]
+    U8,
+    #[bytesrepr(tag = "CL_TYPE_TAG_U32")]
+    U32,
+    #[bytesrepr(tag = "CL_TYPE_TAG_U64")]
+    U64,
+    #[bytesrepr(tag = "CL_TYPE_TAG_U128")]
+    U128,
+    #[bytesrepr(tag = "CL_TYPE_TAG_U256")]
+    U256,
+    #[bytesrepr(tag = "CL_TYPE_TAG_U512")]
+    U512,
+    #[bytesrepr(tag = "CL_TYPE_TAG_UNIT")]
+    Unit,
+    #[bytesrepr(tag = "CL_TYPE_TAG_STRING")]
+    String,
+    #[bytesrepr(tag = "CL_TYPE_TAG_KEY")]
+    Key,
+    #[bytesrepr(tag = "CL_TYPE_TAG_UREF")]
+    URef,
+    #[bytesrepr(tag = "CL_TYPE_TAG_OPTION")]
+    Option(Box<CLType>),
+    #[bytesrepr(tag = "CL_TYPE_TAG_LIST")]
+    List(Box<CLType>),
+    #[bytesrepr(tag = "CL_TYPE_TAG_RESULT")]
+    Result { ok: Box<CLType>, err: Box<CLType> },
+    #[bytesrepr(tag = "CL_TYPE_TAG_MAP")]
+    Map {
+        key: Box<CLType>,
+        value: Box<CLType>,
+    },
+    #[bytesrepr(tag = "CL_TYPE_TAG_TUPLE")]
+    Tuple(Vec<CLType>),
+}
+
+impl CLType {
+    fn boxed_from_bytes_with_depth(
+        bytes: &[u8],
+        depth: usize,
+    ) -> Result<(Box<Self>, &[u8]), Error> {
+        let (cl_type, rem) = CLType::from_bytes_with_depth(bytes, depth)?;
+        Ok((Box::new(cl_type), rem))
+    }
+
+    fn from_bytes_with_depth(bytes: &[u8], depth: usize) -> Result<(Self, &[u8]), Error> {
+        if depth >= CL_TYPE_MAX_DEPTH {
+            return Err(Error::FormattingError);
+        }
+        let (tag, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        match tag {
+            CL_TYPE_TAG_BOOL => Ok((CLType::Bool, rem)),
+            CL_TYPE_TAG_I32 => Ok((CLType::I32, rem)),
+            CL_TYPE_TAG_I64 => Ok((CLType::I64, rem)),
+            CL_TYPE_TAG_U8 => Ok((CLType::U8, rem)),
+            CL_TYPE_TAG_U32 => Ok((CLType::U32, rem)),
+            CL_TYPE_TAG_U64 => Ok((CLType::U64, rem)),
+            CL_TYPE_TAG_U128 => Ok((CLType::U128, rem)),
+            CL_TYPE_TAG_U256 => Ok((CLType::U256, rem)),
+            CL_TYPE_TAG_U512 => Ok((CLType::U512, rem)),
+            CL_TYPE_TAG_UNIT => Ok((CLType::Unit, rem)),
+            CL_TYPE_TAG_STRING => Ok((CLType::String, rem)),
+            CL_TYPE_TAG_KEY => Ok((CLType::Key, rem)),
+            CL_TYPE_TAG_UREF => Ok((CLType::URef, rem)),
+            CL_TYPE_TAG_OPTION => {
+                let (inner, rem) = CLType::boxed_from_bytes_with_depth(rem, depth + 1)?;
+                Ok((CLType::Option(inner), rem))
+            }
+            CL_TYPE_TAG_LIST => {
+                let (inner, rem) = CLType::boxed_from_bytes_with_depth(rem, depth + 1)?;
+                Ok((CLType::List(inner), rem))
+            }
+            CL_TYPE_TAG_RESULT => {
+                let (ok, rem) = CLType::boxed_from_bytes_with_depth(rem, depth + 1)?;
+                let (err, rem) = CLType::boxed_from_bytes_with_depth(rem, depth + 1)?;
+                Ok((CLType::Result { ok, err }, rem))
+            }
+            CL_TYPE_TAG_MAP => {
+                let (key, rem) = CLType::boxed_from_bytes_with_depth(rem, depth + 1)?;
+                let (value, rem) = CLType::boxed_from_bytes_with_depth(rem, depth + 1)?;
+                Ok((CLType::Map { key, value }, rem))
+            }
+            CL_TYPE_TAG_TUPLE => {
+                let (len, mut stream): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+                let mut types = Vec::new();
+                for _ in 0..len {
+                    let (cl_type, rem) = CLType::from_bytes_with_depth(stream, depth + 1)?;
+                    types.push(cl_type);
+                    stream = rem;
+                }
+                Ok((CLType::Tuple(types), stream))
+            }
+            _ => Err(Error::FormattingError),
+        }
+    }
+}
+
+impl FromBytes for CLType {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        CLType::from_bytes_with_depth(bytes, 0)
+    }
+}
+
+/// A type which has a `CLType`, i.e. whose serialized form is described by [`CLTyped::cl_type`].
+pub trait CLTyped {
+    fn cl_type() -> CLType;
+}
+
+macro_rules! impl_cl_typed {
+    ($($type:ty => $cl_type:ident),+ $(,)?) => {
+        $(
+            impl CLTyped for $type {
+                fn cl_type() -> CLType {
+                    CLType::$cl_type
+                }
+            }
+        )+
+    };
+}
+
+impl_cl_typed! {
+    bool => Bool,
+    i32 => I32,
+    i64 => I64,
+    u8 => U8,
+    u32 => U32,
+    u64 => U64,
+    U128 => U128,
+    U256 => U256,
+    U512 => U512,
+    () => Unit,
+    String => String,
+    str => String,
+    Key => Key,
+    URef => URef,
+}
+
+impl<T: CLTyped + ?Sized> CLTyped for &T {
+    fn cl_type() -> CLType {
+        T::cl_type()
+    }
+}
+
+impl<T: CLTyped + ?Sized> CLTyped for Box<T> {
+    fn cl_type() -> CLType {
+        T::cl_type()
+    }
+}
+
+impl<T: CLTyped> CLTyped for Option<T> {
+    fn cl_type() -> CLType {
+        CLType::Option(Box::new(T::cl_type()))
+    }
+}
+
+impl<T: CLTyped> CLTyped for Vec<T> {
+    fn cl_type() -> CLType {
+        CLType::List(Box::new(T::cl_type()))
+    }
+}
+
+impl<T: CLTyped> CLTyped for [T] {
+    fn cl_type() -> CLType {
+        CLType::List(Box::new(T::cl_type()))
+    }
+}
+
+impl<T: CLTyped, E: CLTyped> CLTyped for Result<T, E> {
+    fn cl_type() -> CLType {
+        CLType::Result {
+            ok: Box::new(T::cl_type()),
+            err: Box::new(E::cl_type()),
+        }
+    }
+}
+
+impl<K: CLTyped, V: CLTyped> CLTyped for BTreeMap<K, V> {
+    fn cl_type() -> CLType {
+        CLType::Map {
+            key: Box::new(K::cl_type()),
+            value: Box::new(V::cl_type()),
+        }
+    }
+}
+
+macro_rules! impl_cl_typed_for_tuples {
+    ($(($($name:ident),+))+) => {
+        $(
+            impl<$($name: CLTyped),+> CLTyped for ($($name,)+) {
+                fn cl_type() -> CLType {
+                    CLType::Tuple(vec![$($name::cl_type()),+])
+                }
+            }
+        )+
+    };
+}
+
+impl_cl_typed_for_tuples! {
+    (T1)
+    (T1, T2)
+    (T1, T2, T3)
+    (T1, T2, T3, T4)
+    (T1, T2, T3, T4, T5)
+    (T1, T2, T3, T4, T5, T6)
+    (T1, T2, T3, T4, T5, T6, T7)
+    (T1, T2, T3, T4, T5, T6, T7, T8)
+    (T1, T2, T3, T4, T5, T6, T7, T8, T9)
+    (T1, T2, T3, T4, T5, T6, T7, T8, T9, T10)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::boxed::Box;
+    use alloc::collections::BTreeMap;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use crate::bytesrepr::{self, Error, ToBytes};
+    use crate::key::Key;
+    use crate::value::cl_type::{CLType, CLTyped, CL_TYPE_MAX_DEPTH, CL_TYPE_TAG_OPTION};
+    use crate::value::U512;
+
+    #[test]
+    fn should_describe_nested_types() {
+        assert_eq!(
+            <BTreeMap<String, Vec<Option<U512>>>>::cl_type(),
+            CLType::Map {
+                key: Box::new(CLType::String),
+                value: Box::new(CLType::List(Box::new(CLType::Option(Box::new(
+                    CLType::U512
+                ))))),
+            }
+        );
+        assert_eq!(
+            <(Key, Result<(), u32>)>::cl_type(),
+            CLType::Tuple(vec![
+                CLType::Key,
+                CLType::Result {
+                    ok: Box::new(CLType::Unit),
+                    err: Box::new(CLType::U32),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize() {
+        bytesrepr::test_serialization_roundtrip(&<BTreeMap<String, Vec<Option<U512>>>>::cl_type());
+        bytesrepr::test_serialization_roundtrip(&<(bool, i64, (), Result<u8, String>)>::cl_type());
+    }
+
+    #[test]
+    fn should_reject_deeply_nested_types() {
+        let mut cl_type = CLType::Unit;
+        for _ in 0..CL_TYPE_MAX_DEPTH - 1 {
+            cl_type = CLType::Option(Box::new(cl_type));
+        }
+        let bytes = cl_type.to_bytes().unwrap();
+        assert_eq!(bytesrepr::deserialize::<CLType>(&bytes), Ok(cl_type));
+
+        let mut bytes: Vec<u8> = vec![CL_TYPE_TAG_OPTION; CL_TYPE_MAX_DEPTH];
+        bytes.append(&mut CLType::Unit.to_bytes().unwrap());
+        assert_eq!(
+            bytesrepr::deserialize::<CLType>(&bytes),
+            Err(Error::FormattingError)
+        );
+    }
+}
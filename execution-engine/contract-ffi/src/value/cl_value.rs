@@ -0,0 +1,290 @@
+use alloc::vec::Vec;
+
+use crate::bytesrepr::{self, FromBytes, FromBytesBorrowed, ToBytes};
+use crate::key::Key;
+use crate::uref::URef;
+use crate::value::cl_type::{CLType, CLTyped};
+use crate::value::{U128, U256, U512};
+
+/// Error while converting a [`CLValue`] to or from a concrete type.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum CLValueError {
+    /// The value couldn't be serialized or deserialized.
+    Serialization(bytesrepr::Error),
+    /// The value doesn't have the requested type.
+    Type(CLTypeMismatch),
+}
+
+/// The type a [`CLValue`] was expected to have and the type it actually has.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct CLTypeMismatch {
+    pub expected: CLType,
+    pub found: CLType,
+}
+
+impl From<bytesrepr::Error> for CLValueError {
+    fn from(error: bytesrepr::Error) -> Self {
+        CLValueError::Serialization(error)
+    }
+}
+
+/// A serialized value together with its [`CLType`].
+#[derive(PartialEq, Eq, Clone, Debug, ToBytes, FromBytes)]
+#[bytesrepr(crate = "crate")]
+pub struct CLValue {
+    cl_type: CLType,
+    bytes: Vec<u8>,
+}
+
+impl CLValue {
+    /// Serializes `t` along with its type.
+    pub fn from_t<T: CLTyped + ToBytes + ?Sized>(t: &T) -> Result<CLValue, CLValueError> {
+        Ok(CLValue {
+            cl_type: T::cl_type(),
+            bytes: t.to_bytes()?,
+        })
+    }
+
+    /// Deserializes the value as a `T`, which must have the same type the value was stored with.
+    pub fn to_t<T: CLTyped + FromBytes>(&self) -> Result<T, CLValueError> {
+        let expected = T::cl_type();
+        if self.cl_type != expected {
+            return Err(CLValueError::Type(CLTypeMismatch {
+                expected,
+                found: self.cl_type.clone(),
+            }));
+        }
+        Ok(bytesrepr::deserialize(&self.bytes)?)
+    }
+
+    /// Creates a `CLValue` from an already serialized value.  The bytes are not checked against
+    /// the type; [`CLValue::keys`] fails if they don't match.
+    pub fn from_components(cl_type: CLType, bytes: Vec<u8>) -> Self {
+        CLValue { cl_type, bytes }
+    }
+
+    pub fn destructure(self) -> (CLType, Vec<u8>) {
+        (self.cl_type, self.bytes)
+    }
+
+    pub fn cl_type(&self) -> &CLType {
+        &self.cl_type
+    }
+
+    pub fn inner_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns all the keys and urefs held by the value, with urefs as `Key::URef`.
+    ///
+    /// Fails if the serialized value doesn't match its type.
+    pub fn keys(&self) -> Result<Vec<Key>, bytesrepr::Error> {
+        let mut keys = Vec::new();
+        let mut budget = element_budget(&self.bytes);
+        let rem = collect_keys(&self.cl_type, &self.bytes, &mut budget, &mut keys)?;
+        if !rem.is_empty() {
+            return Err(bytesrepr::Error::LeftOverBytes);
+        }
+        Ok(keys)
+    }
+}
+
+/// The number of zero-sized elements, such as units, which a serialized value may hold in its lists
+/// and maps on top of one element per byte.
+pub const ZERO_SIZED_ELEMENT_ALLOWANCE: usize = 1 << 16;
+
+/// Returns the number of list and map elements which walking the serialized value `bytes` may
+/// decode, counted across every nested collection.
+///
+/// Elements which take up space can never outnumber the bytes holding them; the allowance bounds
+/// the rest, so that walking untrusted bytes takes time proportional to their length.
+pub fn element_budget(bytes: &[u8]) -> usize {
+    bytes.len().saturating_add(ZERO_SIZED_ELEMENT_ALLOWANCE)
+}
+
+/// Reads the `u32` length prefix of a serialized list or map and charges its elements to `budget`,
+/// which is shared by every collection in the value being walked.
+pub fn read_collection_length<'a>(
+    bytes: &'a [u8],
+    budget: &mut usize,
+) -> Result<(usize, &'a [u8]), bytesrepr::Error> {
+    let (len, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+    let len = len as usize;
+    if len > *budget {
+        return Err(bytesrepr::Error::FormattingError);
+    }
+    *budget -= len;
+    Ok((len, rem))
+}
+
+fn skip<T: FromBytes>(bytes: &[u8]) -> Result<&[u8], bytesrepr::Error> {
+    let (_, rem): (T, &[u8]) = FromBytes::from_bytes(bytes)?;
+    Ok(rem)
+}
+
+/// Walks the serialized value described by `cl_type`, pushing any keys and urefs to `keys`, and
+/// returns the bytes following the value.
+fn collect_keys<'a>(
+    cl_type: &CLType,
+    bytes: &'a [u8],
+    budget: &mut usize,
+    keys: &mut Vec<Key>,
+) -> Result<&'a [u8], bytesrepr::Error> {
+    match cl_type {
+        CLType::Bool => skip::<bool>(bytes),
+        CLType::I32 => skip::<i32>(bytes),
+        CLType::I64 => skip::<i64>(bytes),
+        CLType::U8 => skip::<u8>(bytes),
+        CLType::U32 => skip::<u32>(bytes),
+        CLType::U64 => skip::<u64>(bytes),
+        CLType::U128 => skip::<U128>(bytes),
+        CLType::U256 => skip::<U256>(bytes),
+        CLType::U512 => skip::<U512>(bytes),
+        CLType::Unit => Ok(bytes),
+        CLType::String => {
+            let (_, rem): (&str, &[u8]) = FromBytesBorrowed::from_bytes_borrowed(bytes)?;
+            Ok(rem)
+        }
+        CLType::Key => {
+            let (key, rem): (Key, &[u8]) = FromBytes::from_bytes(bytes)?;
+            keys.push(key);
+            Ok(rem)
+        }
+        CLType::URef => {
+            let (uref, rem): (URef, &[u8]) = FromBytes::from_bytes(bytes)?;
+            keys.push(Key::URef(uref));
+            Ok(rem)
+        }
+        CLType::Option(inner) => match FromBytes::from_bytes(bytes)? {
+            (0u8, rem) => Ok(rem),
+            (1u8, rem) => collect_keys(inner, rem, budget, keys),
+            _ => Err(bytesrepr::Error::FormattingError),
+        },
+        CLType::List(inner) => {
+            let (len, mut stream) = read_collection_length(bytes, budget)?;
+            for _ in 0..len {
+                stream = collect_keys(inner, stream, budget, keys)?;
+            }
+            Ok(stream)
+        }
+        CLType::Result { ok, err } => match FromBytes::from_bytes(bytes)? {
+            (0u8, rem) => collect_keys(err, rem, budget, keys),
+            (1u8, rem) => collect_keys(ok, rem, budget, keys),
+            _ => Err(bytesrepr::Error::FormattingError),
+        },
+        CLType::Map { key, value } => {
+            let (len, mut stream) = read_collection_length(bytes, budget)?;
+            for _ in 0..len {
+                stream = collect_keys(key, stream, budget, keys)?;
+                stream = collect_keys(value, stream, budget, keys)?;
+            }
+            Ok(stream)
+        }
+        CLType::Tuple(types) => types.iter().try_fold(bytes, |stream, cl_type| {
+            collect_keys(cl_type, stream, budget, keys)
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::boxed::Box;
+    use alloc::collections::BTreeMap;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use crate::bytesrepr;
+    use crate::key::Key;
+    use crate::uref::{AccessRights, URef};
+    use crate::value::cl_type::{CLType, CLTyped};
+    use crate::value::cl_value::{
+        CLTypeMismatch, CLValue, CLValueError, ZERO_SIZED_ELEMENT_ALLOWANCE,
+    };
+    use crate::value::U512;
+
+    #[test]
+    fn should_convert_to_and_from_t() {
+        let mut map = BTreeMap::new();
+        map.insert(String::from("a"), vec![Some(U512::from(1)), None]);
+        let cl_value = CLValue::from_t(&map).expect("should create CLValue");
+        assert_eq!(
+            cl_value.cl_type(),
+            &<BTreeMap<String, Vec<Option<U512>>>>::cl_type()
+        );
+        assert_eq!(
+            cl_value.to_t::<BTreeMap<String, Vec<Option<U512>>>>(),
+            Ok(map)
+        );
+        bytesrepr::test_serialization_roundtrip(&cl_value);
+    }
+
+    #[test]
+    fn should_fail_to_convert_to_other_type() {
+        let cl_value = CLValue::from_t(&1u64).expect("should create CLValue");
+        assert_eq!(
+            cl_value.to_t::<i64>(),
+            Err(CLValueError::Type(CLTypeMismatch {
+                expected: CLType::I64,
+                found: CLType::U64,
+            }))
+        );
+    }
+
+    #[test]
+    fn should_collect_keys() {
+        let uref = URef::new([1; 32], AccessRights::READ);
+        let key = Key::Hash([2; 32]);
+        let value: (String, Vec<Key>, Result<URef, u8>, Option<Key>) =
+            (String::from("name"), vec![key], Ok(uref), None);
+        let cl_value = CLValue::from_t(&value).expect("should create CLValue");
+        assert_eq!(cl_value.keys(), Ok(vec![key, Key::URef(uref)]));
+    }
+
+    #[test]
+    fn should_fail_to_collect_keys_if_bytes_do_not_match_type() {
+        let cl_value = CLValue::from_components(CLType::Key, vec![1, 2, 3]);
+        assert!(cl_value.keys().is_err());
+
+        let cl_value = CLValue::from_components(CLType::U8, vec![1, 2]);
+        assert_eq!(cl_value.keys(), Err(bytesrepr::Error::LeftOverBytes));
+    }
+
+    #[test]
+    fn should_fail_to_collect_keys_if_length_exceeds_budget() {
+        let huge_length = u32::max_value().to_le_bytes().to_vec();
+        let cl_value =
+            CLValue::from_components(CLType::List(Box::new(CLType::Unit)), huge_length.clone());
+        assert_eq!(cl_value.keys(), Err(bytesrepr::Error::FormattingError));
+
+        let cl_value = CLValue::from_components(
+            CLType::Map {
+                key: Box::new(CLType::Unit),
+                value: Box::new(CLType::Unit),
+            },
+            huge_length,
+        );
+        assert_eq!(cl_value.keys(), Err(bytesrepr::Error::FormattingError));
+    }
+
+    #[test]
+    fn should_fail_to_collect_keys_if_nested_lengths_exceed_budget() {
+        // Each inner list is within the budget on its own, but not all of them together.
+        let inner_length = ZERO_SIZED_ELEMENT_ALLOWANCE as u32;
+        let mut bytes = 8u32.to_le_bytes().to_vec();
+        for _ in 0..8 {
+            bytes.extend_from_slice(&inner_length.to_le_bytes());
+        }
+        let cl_type = CLType::List(Box::new(CLType::List(Box::new(CLType::Unit))));
+        let cl_value = CLValue::from_components(cl_type, bytes);
+        assert_eq!(cl_value.keys(), Err(bytesrepr::Error::FormattingError));
+    }
+
+    #[test]
+    fn should_collect_keys_from_list_of_units() {
+        let units = vec![(); 10];
+        let cl_value = CLValue::from_t(&units).expect("should create CLValue");
+        assert_eq!(cl_value.keys(), Ok(Vec::new()));
+        assert_eq!(cl_value.to_t::<Vec<()>>(), Ok(units));
+        bytesrepr::test_serialization_roundtrip(&cl_value);
+    }
+}
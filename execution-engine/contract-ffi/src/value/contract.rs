@@ -1,14 +1,89 @@
-use crate::bytesrepr::{Error, FromBytes, ToBytes, U32_SIZE, U64_SIZE};
-use crate::key::{Key, UREF_SIZE};
-use crate::value::ProtocolVersion;
+use crate::bytesrepr::{self, Error, FromBytes, ToBytes, Write, U32_SIZE, U8_SIZE};
+use crate::key::Key;
+use crate::value::{CLType, ProtocolVersion};
 use alloc::collections::btree_map::BTreeMap;
 use alloc::string::String;
 use alloc::vec::Vec;
 
+/// The name of the export invoked when a contract is called without naming an entry point.
+pub const DEFAULT_ENTRY_POINT_NAME: &str = "call";
+
+/// Starts a serialized contract in place of the length of its module bytes to show that a layout
+/// version follows.  Serialized module bytes are never this long.
+const VERSIONED_LAYOUT_MARKER: u32 = u32::max_value();
+
+/// The layout of a contract with entry points: the module bytes, named keys, entry points and
+/// protocol version.
+const ENTRY_POINTS_LAYOUT_VERSION: u8 = 1;
+
+/// A named, typed parameter of an [`EntryPoint`].
+#[derive(PartialEq, Eq, Clone, Debug, ToBytes, FromBytes)]
+#[bytesrepr(crate = "crate")]
+pub struct Parameter {
+    name: String,
+    cl_type: CLType,
+}
+
+impl Parameter {
+    pub fn new(name: &str, cl_type: CLType) -> Self {
+        Parameter {
+            name: String::from(name),
+            cl_type,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn cl_type(&self) -> &CLType {
+        &self.cl_type
+    }
+}
+
+/// Who may call an [`EntryPoint`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug, ToBytes, FromBytes)]
+#[bytesrepr(crate = "crate")]
+pub enum EntryPointAccess {
+    /// Any account or contract may call the entry point.
+    Public,
+    /// Only the system account may call the entry point.
+    System,
+}
+
+/// A function exported by a stored contract which can be called by name.
+///
+/// The arguments of the call are passed as named arguments, and each parameter must be present
+/// with the declared type for the call to go ahead.
+#[derive(PartialEq, Eq, Clone, Debug, ToBytes, FromBytes)]
+#[bytesrepr(crate = "crate")]
+pub struct EntryPoint {
+    parameters: Vec<Parameter>,
+    access: EntryPointAccess,
+}
+
+impl EntryPoint {
+    pub fn new(parameters: Vec<Parameter>, access: EntryPointAccess) -> Self {
+        EntryPoint { parameters, access }
+    }
+
+    pub fn parameters(&self) -> &[Parameter] {
+        &self.parameters
+    }
+
+    pub fn access(&self) -> EntryPointAccess {
+        self.access
+    }
+}
+
+/// The entry points of a contract, keyed by the name of the export implementing each of them.
+pub type EntryPoints = BTreeMap<String, EntryPoint>;
+
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct Contract {
     bytes: Vec<u8>,
     named_keys: BTreeMap<String, Key>,
+    entry_points: EntryPoints,
     protocol_version: ProtocolVersion,
 }
 
@@ -21,10 +96,18 @@ impl Contract {
         Contract {
             bytes,
             named_keys,
+            entry_points: EntryPoints::new(),
             protocol_version,
         }
     }
 
+    /// Returns the contract with the given entry points.  A contract without entry points can
+    /// only be called through its `call` export.
+    pub fn with_entry_points(mut self, entry_points: EntryPoints) -> Self {
+        self.entry_points = entry_points;
+        self
+    }
+
     pub fn named_keys_append(&mut self, keys: &mut BTreeMap<String, Key>) {
         self.named_keys.append(keys);
     }
@@ -37,14 +120,23 @@ impl Contract {
         &mut self.named_keys
     }
 
-    pub fn destructure(self) -> (Vec<u8>, BTreeMap<String, Key>, ProtocolVersion) {
-        (self.bytes, self.named_keys, self.protocol_version)
+    pub fn destructure(self) -> (Vec<u8>, BTreeMap<String, Key>, EntryPoints, ProtocolVersion) {
+        (
+            self.bytes,
+            self.named_keys,
+            self.entry_points,
+            self.protocol_version,
+        )
     }
 
     pub fn bytes(&self) -> &[u8] {
         &self.bytes
     }
 
+    pub fn entry_points(&self) -> &EntryPoints {
+        &self.entry_points
+    }
+
     pub fn protocol_version(&self) -> ProtocolVersion {
         self.protocol_version
     }
@@ -52,37 +144,106 @@ impl Contract {
 
 impl ToBytes for Contract {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        if self.bytes.len() + UREF_SIZE * self.named_keys.len() + U64_SIZE
-            >= u32::max_value() as usize - U32_SIZE * 2
-        {
+        bytesrepr::to_vec(self)
+    }
+
+    fn serialized_length(&self) -> usize {
+        let legacy_length = self.bytes.serialized_length()
+            + self.named_keys.serialized_length()
+            + self.protocol_version.serialized_length();
+        if self.entry_points.is_empty() {
+            legacy_length
+        } else {
+            U32_SIZE + U8_SIZE + legacy_length + self.entry_points.serialized_length()
+        }
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        if self.serialized_length() >= u32::max_value() as usize {
             return Err(Error::OutOfMemoryError);
         }
-        let size: usize = U32_SIZE +                    //size for length of bytes
-                    self.bytes.len() +                  //size for elements of bytes
-                    U32_SIZE +                          //size for length of named_keys
-                    UREF_SIZE * self.named_keys.len() + //size for named_keys elements
-                    U64_SIZE; //size for protocol_version
-
-        let mut result = Vec::with_capacity(size);
-        result.append(&mut self.bytes.to_bytes()?);
-        result.append(&mut self.named_keys.to_bytes()?);
-        result.append(&mut self.protocol_version.to_bytes()?);
-        Ok(result)
+        // Contracts without entry points keep the layout they had before entry points existed,
+        // so they serialize exactly as they did then.
+        if self.entry_points.is_empty() {
+            self.bytes.write_bytes(writer)?;
+            self.named_keys.write_bytes(writer)?;
+            return self.protocol_version.write_bytes(writer);
+        }
+        VERSIONED_LAYOUT_MARKER.write_bytes(writer)?;
+        ENTRY_POINTS_LAYOUT_VERSION.write_bytes(writer)?;
+        self.bytes.write_bytes(writer)?;
+        self.named_keys.write_bytes(writer)?;
+        self.entry_points.write_bytes(writer)?;
+        self.protocol_version.write_bytes(writer)
     }
 }
 
 impl FromBytes for Contract {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
-        let (bytes, rem1): (Vec<u8>, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (marker, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        if marker != VERSIONED_LAYOUT_MARKER {
+            // The legacy layout, which starts with the length of the module bytes
+            let (bytes, rem1): (Vec<u8>, &[u8]) = FromBytes::from_bytes(bytes)?;
+            let (named_keys, rem2): (BTreeMap<String, Key>, &[u8]) = FromBytes::from_bytes(rem1)?;
+            let (protocol_version, rem3): (ProtocolVersion, &[u8]) = FromBytes::from_bytes(rem2)?;
+            return Ok((Contract::new(bytes, named_keys, protocol_version), rem3));
+        }
+        let (version, rem): (u8, &[u8]) = FromBytes::from_bytes(rem)?;
+        if version != ENTRY_POINTS_LAYOUT_VERSION {
+            return Err(Error::FormattingError);
+        }
+        let (bytes, rem1): (Vec<u8>, &[u8]) = FromBytes::from_bytes(rem)?;
         let (named_keys, rem2): (BTreeMap<String, Key>, &[u8]) = FromBytes::from_bytes(rem1)?;
-        let (protocol_version, rem3): (ProtocolVersion, &[u8]) = FromBytes::from_bytes(rem2)?;
+        let (entry_points, rem3): (EntryPoints, &[u8]) = FromBytes::from_bytes(rem2)?;
+        let (protocol_version, rem4): (ProtocolVersion, &[u8]) = FromBytes::from_bytes(rem3)?;
         Ok((
             Contract {
                 bytes,
                 named_keys,
+                entry_points,
                 protocol_version,
             },
-            rem3,
+            rem4,
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+    use alloc::string::String;
+
+    use super::{Contract, EntryPoint, EntryPointAccess, EntryPoints};
+    use crate::bytesrepr::{self, ToBytes};
+    use crate::key::Key;
+    use crate::value::ProtocolVersion;
+
+    #[test]
+    fn should_read_contract_in_legacy_layout() {
+        let mut named_keys = BTreeMap::new();
+        named_keys.insert(String::from("a"), Key::Hash([1; 32]));
+        let contract = Contract::new(vec![1, 2, 3], named_keys, ProtocolVersion::V1_0_0);
+
+        // The layout before entry points were added
+        let mut legacy_bytes = contract.bytes().to_vec().to_bytes().unwrap();
+        legacy_bytes.append(&mut contract.named_keys().to_bytes().unwrap());
+        legacy_bytes.append(&mut contract.protocol_version().to_bytes().unwrap());
+
+        assert_eq!(contract.to_bytes().unwrap(), legacy_bytes);
+        assert_eq!(bytesrepr::deserialize(&legacy_bytes), Ok(contract));
+    }
+
+    #[test]
+    fn should_round_trip_contract_with_entry_points() {
+        let mut entry_points = EntryPoints::new();
+        entry_points.insert(
+            String::from("transfer"),
+            EntryPoint::new(vec![], EntryPointAccess::Public),
+        );
+        let contract = Contract::new(vec![1, 2, 3], BTreeMap::new(), ProtocolVersion::V1_0_0)
+            .with_entry_points(entry_points);
+        let bytes = contract.to_bytes().unwrap();
+        assert_eq!(bytes.len(), contract.serialized_length());
+        assert_eq!(bytesrepr::deserialize(&bytes), Ok(contract));
+    }
+}
@@ -0,0 +1,226 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+
+use failure::Fail;
+
+use crate::key::Key;
+use crate::value::Contract;
+
+/// The number of a version within a [`ContractPackage`].  Versions are numbered from 1.
+pub type ContractVersion = u32;
+
+/// The first version number given to a contract added to a [`ContractPackage`].
+pub const FIRST_CONTRACT_VERSION: ContractVersion = 1;
+
+/// Errors that can occur when selecting or changing a version of a [`ContractPackage`].
+#[derive(Fail, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ContractPackageError {
+    #[fail(display = "The contract package has no such version")]
+    InvalidVersion,
+    #[fail(display = "The contract version is disabled")]
+    DisabledVersion,
+    #[fail(display = "The latest version of the contract package can't be disabled")]
+    LatestVersion,
+}
+
+/// A collection of versions of a contract.
+///
+/// Each version is a [`Contract`] stored under its own hash.  Versions are never removed, so
+/// callers can pin a version, but they can be disabled and re-enabled.  One enabled version is
+/// designated as the latest; it is the version run when the package itself is called.  Adding a
+/// version designates it as the latest, and rolling back means designating an older version.
+///
+/// The named keys of a package are shared by all of its versions, so they, like the local state
+/// of the package, outlive upgrades.
+#[derive(PartialEq, Eq, Clone, Debug, Default, ToBytes, FromBytes)]
+#[bytesrepr(crate = "crate")]
+pub struct ContractPackage {
+    versions: BTreeMap<ContractVersion, Key>,
+    disabled_versions: BTreeSet<ContractVersion>,
+    latest_version: Option<ContractVersion>,
+    named_keys: BTreeMap<String, Key>,
+}
+
+impl ContractPackage {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn versions(&self) -> &BTreeMap<ContractVersion, Key> {
+        &self.versions
+    }
+
+    pub fn disabled_versions(&self) -> &BTreeSet<ContractVersion> {
+        &self.disabled_versions
+    }
+
+    pub fn named_keys(&self) -> &BTreeMap<String, Key> {
+        &self.named_keys
+    }
+
+    pub fn named_keys_mut(&mut self) -> &mut BTreeMap<String, Key> {
+        &mut self.named_keys
+    }
+
+    pub fn named_keys_append(&mut self, keys: &mut BTreeMap<String, Key>) {
+        self.named_keys.append(keys);
+    }
+
+    /// Adds the contract stored under `contract_key` as a new, enabled version, designates it as
+    /// the latest version and returns its number.
+    pub fn add_version(&mut self, contract_key: Key) -> ContractVersion {
+        let version = self
+            .versions
+            .keys()
+            .next_back()
+            .map_or(FIRST_CONTRACT_VERSION, |last| last + 1);
+        self.versions.insert(version, contract_key);
+        self.latest_version = Some(version);
+        version
+    }
+
+    pub fn is_version_enabled(&self, version: ContractVersion) -> bool {
+        self.versions.contains_key(&version) && !self.disabled_versions.contains(&version)
+    }
+
+    /// Returns the key of the contract stored as `version`, if that version is enabled.
+    pub fn enabled_version(&self, version: ContractVersion) -> Result<Key, ContractPackageError> {
+        let contract_key = self
+            .versions
+            .get(&version)
+            .ok_or(ContractPackageError::InvalidVersion)?;
+        if self.disabled_versions.contains(&version) {
+            return Err(ContractPackageError::DisabledVersion);
+        }
+        Ok(*contract_key)
+    }
+
+    /// Disables `version`.  The latest version can't be disabled; designate another version as
+    /// the latest first.
+    pub fn disable_version(
+        &mut self,
+        version: ContractVersion,
+    ) -> Result<(), ContractPackageError> {
+        if !self.versions.contains_key(&version) {
+            return Err(ContractPackageError::InvalidVersion);
+        }
+        if self.latest_version == Some(version) {
+            return Err(ContractPackageError::LatestVersion);
+        }
+        self.disabled_versions.insert(version);
+        Ok(())
+    }
+
+    pub fn enable_version(&mut self, version: ContractVersion) -> Result<(), ContractPackageError> {
+        if !self.versions.contains_key(&version) {
+            return Err(ContractPackageError::InvalidVersion);
+        }
+        self.disabled_versions.remove(&version);
+        Ok(())
+    }
+
+    /// Returns the version designated as the latest, or `None` if the package has no versions.
+    pub fn latest_version(&self) -> Option<ContractVersion> {
+        self.latest_version
+    }
+
+    /// Designates `version`, which must be enabled, as the latest version.
+    pub fn set_latest_version(
+        &mut self,
+        version: ContractVersion,
+    ) -> Result<(), ContractPackageError> {
+        self.enabled_version(version)?;
+        self.latest_version = Some(version);
+        Ok(())
+    }
+
+    /// Returns the key of the contract stored as the latest version.
+    pub fn latest_version_key(&self) -> Result<Key, ContractPackageError> {
+        let version = self
+            .latest_version
+            .ok_or(ContractPackageError::InvalidVersion)?;
+        self.enabled_version(version)
+    }
+
+    /// Returns `contract`, one of the versions of this package, as it is run: with the named keys
+    /// of the package in place of its own.
+    pub fn version_contract(&self, contract: Contract) -> Contract {
+        let (bytes, _, entry_points, protocol_version) = contract.destructure();
+        Contract::new(bytes, self.named_keys.clone(), protocol_version)
+            .with_entry_points(entry_points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+    use alloc::string::String;
+
+    use super::{ContractPackage, ContractPackageError};
+    use crate::bytesrepr;
+    use crate::key::Key;
+    use crate::uref::{AccessRights, URef};
+
+    #[test]
+    fn should_track_designated_latest_version() {
+        let mut package = ContractPackage::new();
+        assert_eq!(package.latest_version(), None);
+        assert_eq!(
+            package.latest_version_key(),
+            Err(ContractPackageError::InvalidVersion)
+        );
+
+        assert_eq!(package.add_version(Key::Hash([1; 32])), 1);
+        assert_eq!(package.add_version(Key::Hash([2; 32])), 2);
+        assert_eq!(package.latest_version(), Some(2));
+
+        // The latest version can only be disabled once another version is designated.
+        assert_eq!(
+            package.disable_version(2),
+            Err(ContractPackageError::LatestVersion)
+        );
+        package
+            .set_latest_version(1)
+            .expect("should designate version 1");
+        package
+            .disable_version(2)
+            .expect("should disable version 2");
+        assert_eq!(package.latest_version(), Some(1));
+        assert_eq!(package.latest_version_key(), Ok(Key::Hash([1; 32])));
+        assert_eq!(
+            package.enabled_version(2),
+            Err(ContractPackageError::DisabledVersion)
+        );
+        assert_eq!(
+            package.set_latest_version(2),
+            Err(ContractPackageError::DisabledVersion)
+        );
+
+        // Re-enabling a version doesn't designate it.
+        package.enable_version(2).expect("should enable version 2");
+        assert!(package.is_version_enabled(2));
+        assert_eq!(package.latest_version(), Some(1));
+
+        // A disabled version keeps its number.
+        assert_eq!(package.add_version(Key::Hash([3; 32])), 3);
+        assert_eq!(package.latest_version(), Some(3));
+
+        assert_eq!(
+            package.disable_version(4),
+            Err(ContractPackageError::InvalidVersion)
+        );
+        assert_eq!(
+            package.set_latest_version(4),
+            Err(ContractPackageError::InvalidVersion)
+        );
+
+        let mut named_keys = BTreeMap::new();
+        named_keys.insert(
+            String::from("purse"),
+            Key::URef(URef::new([4; 32], AccessRights::READ_ADD_WRITE)),
+        );
+        package.named_keys_append(&mut named_keys);
+
+        bytesrepr::test_serialization_roundtrip(&package);
+    }
+}
@@ -1,5 +1,8 @@
 pub mod account;
+pub mod cl_type;
+pub mod cl_value;
 pub mod contract;
+pub mod contract_package;
 pub mod protocol_version;
 mod semver;
 pub mod uint;
@@ -7,18 +10,17 @@ pub mod uint;
 use alloc::string::String;
 use alloc::vec::Vec;
 use core::convert::TryFrom;
-use core::iter;
-use core::mem::size_of;
 
 pub use self::account::Account;
-pub use self::contract::Contract;
+pub use self::cl_type::{CLType, CLTyped};
+pub use self::cl_value::{CLTypeMismatch, CLValue, CLValueError};
+pub use self::contract::{Contract, EntryPoint, EntryPointAccess, EntryPoints, Parameter};
+pub use self::contract_package::{ContractPackage, ContractPackageError, ContractVersion};
 pub use self::protocol_version::ProtocolVersion;
 pub use self::semver::SemVer;
 pub use self::uint::{U128, U256, U512};
-use crate::bytesrepr::{
-    Error, FromBytes, ToBytes, U128_SIZE, U256_SIZE, U32_SIZE, U512_SIZE, U64_SIZE, U8_SIZE,
-};
-use crate::key::{self, Key, UREF_SIZE};
+use crate::bytesrepr::{self, Error, FromBytes, ToBytes, Write, U8_SIZE};
+use crate::key::{self, Key};
 use crate::uref::URef;
 
 const INT32_ID: u8 = 0;
@@ -35,6 +37,8 @@ const U512_ID: u8 = 10;
 const KEY_ID: u8 = 11;
 const UNIT_ID: u8 = 12;
 const U64_ID: u8 = 13;
+const CL_VALUE_ID: u8 = 14;
+const CONTRACT_PACKAGE_ID: u8 = 15;
 
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub enum Value {
@@ -52,116 +56,89 @@ pub enum Value {
     Account(account::Account),
     Contract(contract::Contract),
     Unit,
+    CLValue(CLValue),
+    ContractPackage(ContractPackage),
+}
+
+impl Value {
+    fn tag(&self) -> u8 {
+        match self {
+            Value::Int32(_) => INT32_ID,
+            Value::UInt64(_) => U64_ID,
+            Value::UInt128(_) => U128_ID,
+            Value::UInt256(_) => U256_ID,
+            Value::UInt512(_) => U512_ID,
+            Value::ByteArray(_) => BYTEARRAY_ID,
+            Value::ListInt32(_) => LISTINT32_ID,
+            Value::String(_) => STRING_ID,
+            Value::ListString(_) => LISTSTRING_ID,
+            Value::NamedKey(_, _) => NAMEDKEY_ID,
+            Value::Key(_) => KEY_ID,
+            Value::Account(_) => ACCT_ID,
+            Value::Contract(_) => CONTRACT_ID,
+            Value::Unit => UNIT_ID,
+            Value::CLValue(_) => CL_VALUE_ID,
+            Value::ContractPackage(_) => CONTRACT_PACKAGE_ID,
+        }
+    }
 }
 
 impl ToBytes for Value {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        match self {
-            Value::Int32(i) => {
-                let mut result = Vec::with_capacity(U8_SIZE + U32_SIZE);
-                result.push(INT32_ID);
-                result.append(&mut i.to_bytes()?);
-                Ok(result)
-            }
-            Value::UInt128(u) => {
-                let mut result = Vec::with_capacity(U8_SIZE + U128_SIZE);
-                result.push(U128_ID);
-                result.append(&mut u.to_bytes()?);
-                Ok(result)
-            }
-            Value::UInt256(u) => {
-                let mut result = Vec::with_capacity(U8_SIZE + U256_SIZE);
-                result.push(U256_ID);
-                result.append(&mut u.to_bytes()?);
-                Ok(result)
-            }
-            Value::UInt512(u) => {
-                let mut result = Vec::with_capacity(U8_SIZE + U512_SIZE);
-                result.push(U512_ID);
-                result.append(&mut u.to_bytes()?);
-                Ok(result)
-            }
-            Value::ByteArray(arr) => {
-                if arr.len() >= u32::max_value() as usize - U8_SIZE - U32_SIZE {
-                    return Err(Error::OutOfMemoryError);
-                }
-                let mut result = Vec::with_capacity(U8_SIZE + U32_SIZE + arr.len());
-                result.push(BYTEARRAY_ID);
-                result.append(&mut arr.to_bytes()?);
-                Ok(result)
-            }
-            Value::ListInt32(arr) => {
-                if arr.len() * size_of::<i32>() >= u32::max_value() as usize - U8_SIZE - U32_SIZE {
-                    return Err(Error::OutOfMemoryError);
-                }
-                let mut result = Vec::with_capacity(U8_SIZE + U32_SIZE + U32_SIZE * arr.len());
-                result.push(LISTINT32_ID);
-                result.append(&mut arr.to_bytes()?);
-                Ok(result)
-            }
-            Value::String(s) => {
-                if s.len() >= u32::max_value() as usize - U8_SIZE - U32_SIZE {
-                    return Err(Error::OutOfMemoryError);
-                }
-                let size = U8_SIZE + U32_SIZE + s.len();
-                let mut result = Vec::with_capacity(size);
-                result.push(STRING_ID);
-                result.append(&mut s.to_bytes()?);
-                Ok(result)
-            }
-            Value::Account(a) => {
-                let mut result = Vec::new();
-                result.push(ACCT_ID);
-                let mut bytes = a.to_bytes()?;
-                if bytes.len() >= u32::max_value() as usize - result.len() {
-                    return Err(Error::OutOfMemoryError);
-                }
-                result.append(&mut bytes);
-                Ok(result)
+        bytesrepr::to_vec(self)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SIZE
+            + match self {
+                Value::Int32(i) => i.serialized_length(),
+                Value::UInt64(num) => num.serialized_length(),
+                Value::UInt128(u) => u.serialized_length(),
+                Value::UInt256(u) => u.serialized_length(),
+                Value::UInt512(u) => u.serialized_length(),
+                Value::ByteArray(arr) => arr.serialized_length(),
+                Value::ListInt32(arr) => arr.serialized_length(),
+                Value::String(s) => s.serialized_length(),
+                Value::ListString(arr) => arr.serialized_length(),
+                Value::NamedKey(n, k) => n.serialized_length() + k.serialized_length(),
+                Value::Key(k) => k.serialized_length(),
+                Value::Account(a) => a.serialized_length(),
+                Value::Contract(c) => c.serialized_length(),
+                Value::Unit => 0,
+                Value::CLValue(cl_value) => cl_value.serialized_length(),
+                Value::ContractPackage(package) => package.serialized_length(),
             }
-            Value::Contract(c) => Ok(iter::once(CONTRACT_ID).chain(c.to_bytes()?).collect()),
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        if self.serialized_length() >= u32::max_value() as usize {
+            return Err(Error::OutOfMemoryError);
+        }
+        self.tag().write_bytes(writer)?;
+        match self {
+            Value::Int32(i) => i.write_bytes(writer),
+            Value::UInt64(num) => num.write_bytes(writer),
+            Value::UInt128(u) => u.write_bytes(writer),
+            Value::UInt256(u) => u.write_bytes(writer),
+            Value::UInt512(u) => u.write_bytes(writer),
+            Value::ByteArray(arr) => arr.write_bytes(writer),
+            Value::ListInt32(arr) => arr.write_bytes(writer),
+            Value::String(s) => s.write_bytes(writer),
+            Value::ListString(arr) => arr.write_bytes(writer),
             Value::NamedKey(n, k) => {
-                if n.len() + UREF_SIZE >= u32::max_value() as usize - U32_SIZE - U8_SIZE {
-                    return Err(Error::OutOfMemoryError);
-                }
-                let size: usize = U8_SIZE + //size for ID
-                  U32_SIZE +                 //size for length of String
-                  n.len() +           //size of String
-                  UREF_SIZE; //size of urefs
-                let mut result = Vec::with_capacity(size);
-                result.push(NAMEDKEY_ID);
-                result.append(&mut n.to_bytes()?);
-                result.append(&mut k.to_bytes()?);
-                Ok(result)
-            }
-            Value::Key(k) => {
-                let size: usize = U8_SIZE + UREF_SIZE;
-                let mut result = Vec::with_capacity(size);
-                result.push(KEY_ID);
-                result.append(&mut k.to_bytes()?);
-                Ok(result)
-            }
-            Value::ListString(arr) => {
-                let size: usize = U8_SIZE + U32_SIZE + arr.len();
-                let mut result = Vec::with_capacity(size);
-                result.push(LISTSTRING_ID);
-                let bytes = arr.to_bytes()?;
-                if bytes.len() >= u32::max_value() as usize - result.len() {
-                    return Err(Error::OutOfMemoryError);
-                }
-                result.append(&mut arr.to_bytes()?);
-                Ok(result)
-            }
-            Value::Unit => Ok(vec![UNIT_ID]),
-            Value::UInt64(num) => {
-                let mut result = Vec::with_capacity(U8_SIZE + U64_SIZE);
-                result.push(U64_ID);
-                result.append(&mut num.to_bytes()?);
-                Ok(result)
+                n.write_bytes(writer)?;
+                k.write_bytes(writer)
             }
+            Value::Key(k) => k.write_bytes(writer),
+            Value::Account(a) => a.write_bytes(writer),
+            Value::Contract(c) => c.write_bytes(writer),
+            Value::Unit => Ok(()),
+            Value::CLValue(cl_value) => cl_value.write_bytes(writer),
+            Value::ContractPackage(package) => package.write_bytes(writer),
         }
     }
 }
+
 impl FromBytes for Value {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
         let (id, rest): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
@@ -220,6 +197,14 @@ impl FromBytes for Value {
                 let (num, rem): (u64, &[u8]) = FromBytes::from_bytes(rest)?;
                 Ok((Value::UInt64(num), rem))
             }
+            CL_VALUE_ID => {
+                let (cl_value, rem): (CLValue, &[u8]) = FromBytes::from_bytes(rest)?;
+                Ok((Value::CLValue(cl_value), rem))
+            }
+            CONTRACT_PACKAGE_ID => {
+                let (package, rem): (ContractPackage, &[u8]) = FromBytes::from_bytes(rest)?;
+                Ok((Value::ContractPackage(package), rem))
+            }
             _ => Err(Error::FormattingError),
         }
     }
@@ -242,6 +227,8 @@ impl Value {
             Value::ListString(_) => String::from("Value::List[String]"),
             Value::Unit => String::from("Value::Unit"),
             Value::UInt64(_) => String::from("Value::UInt64"),
+            Value::CLValue(_) => String::from("Value::CLValue"),
+            Value::ContractPackage(_) => String::from("Value::ContractPackage"),
         }
     }
 }
@@ -280,6 +267,8 @@ from_try_from_impl!(String, String);
 from_try_from_impl!(Key, Key);
 from_try_from_impl!(Account, Account);
 from_try_from_impl!(Contract, Contract);
+from_try_from_impl!(CLValue, CLValue);
+from_try_from_impl!(ContractPackage, ContractPackage);
 
 impl From<()> for Value {
     fn from(_: ()) -> Self {
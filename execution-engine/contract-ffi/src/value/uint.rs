@@ -1,4 +1,4 @@
-use crate::bytesrepr::{self, Error, FromBytes, ToBytes};
+use crate::bytesrepr::{self, Error, FromBytes, ToBytes, Write};
 use alloc::vec::Vec;
 use num_traits::{Bounded, Num, One, Unsigned, WrappingAdd, WrappingSub, Zero};
 
@@ -37,14 +37,19 @@ macro_rules! ser_and_num_impls {
     ($type:ident, $total_bytes:expr) => {
         impl ToBytes for $type {
             fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+                bytesrepr::to_vec(self)
+            }
+
+            fn serialized_length(&self) -> usize {
+                1 + (self.bits() + 7) / 8
+            }
+
+            fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
                 let mut buf = [0u8; $total_bytes];
                 self.to_little_endian(&mut buf);
-                let mut non_zero_bytes: Vec<u8> =
-                    buf.iter().rev().skip_while(|b| **b == 0).cloned().collect();
-                let num_bytes = non_zero_bytes.len() as u8;
-                non_zero_bytes.push(num_bytes);
-                non_zero_bytes.reverse();
-                Ok(non_zero_bytes)
+                let num_bytes = (self.bits() + 7) / 8;
+                writer.write_all(&[num_bytes as u8])?;
+                writer.write_all(&buf[..num_bytes])
             }
         }
 
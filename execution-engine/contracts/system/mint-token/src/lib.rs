@@ -19,11 +19,11 @@ use core::convert::TryInto;
 
 use contract_ffi::contract_api::{runtime, storage, Error as ApiError};
 use contract_ffi::key::Key;
-use contract_ffi::system_contracts::mint::Error;
+use contract_ffi::system_contracts::mint::{Error, VestingSchedule};
 use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
 use contract_ffi::uref::{AccessRights, URef};
-use contract_ffi::value::account::KEY_SIZE;
-use contract_ffi::value::U512;
+use contract_ffi::value::account::{BlockTime, KEY_SIZE};
+use contract_ffi::value::{CLValue, U512};
 
 use capabilities::{ARef, RAWRef};
 use internal_purse_id::{DepositId, WithdrawId};
@@ -31,6 +31,10 @@ use mint::Mint;
 
 const SYSTEM_ACCOUNT: [u8; KEY_SIZE] = [0u8; KEY_SIZE];
 
+/// Tags the local key under which the vesting schedule of a purse is stored, so that it can't
+/// collide with the purse's balance entry.
+const VESTING_SCHEDULE_TAG: u8 = 1;
+
 pub struct CLMint;
 
 impl Mint<ARef<U512>, RAWRef<U512>> for CLMint {
@@ -67,17 +71,32 @@ impl Mint<ARef<U512>, RAWRef<U512>> for CLMint {
         Ok(purse_id)
     }
 
-    fn lookup(&self, p: Self::PurseId) -> Option<RAWRef<U512>> {
+    fn lookup(&self, p: &Self::PurseId) -> Option<RAWRef<U512>> {
         storage::read_local(p.raw_id())
             .ok()?
             .and_then(|key: Key| key.try_into().ok())
     }
 
-    fn dep_lookup(&self, p: Self::DepOnlyId) -> Option<ARef<U512>> {
+    fn dep_lookup(&self, p: &Self::DepOnlyId) -> Option<ARef<U512>> {
         storage::read_local(p.raw_id())
             .ok()?
             .and_then(|key: Key| key.try_into().ok())
     }
+
+    fn vesting_schedule(&self, p: &Self::PurseId) -> Option<VestingSchedule> {
+        storage::read_local((VESTING_SCHEDULE_TAG, p.raw_id()))
+            .ok()?
+            .and_then(|cl_value: CLValue| cl_value.to_t().ok())
+    }
+
+    fn set_vesting_schedule(&self, p: &Self::PurseId, schedule: VestingSchedule) {
+        let cl_value = CLValue::from_t(&schedule).unwrap_or_revert();
+        storage::write_local((VESTING_SCHEDULE_TAG, p.raw_id()), cl_value);
+    }
+
+    fn blocktime(&self) -> BlockTime {
+        runtime::get_blocktime()
+    }
 }
 
 pub fn delegate() {
@@ -116,12 +135,62 @@ pub fn delegate() {
                 .unwrap_or_revert_with(ApiError::MissingArgument)
                 .unwrap_or_revert_with(ApiError::InvalidArgument);
             let purse_id: WithdrawId = WithdrawId::from_uref(key).unwrap();
-            let balance_uref = mint.lookup(purse_id);
+            let balance_uref = mint.lookup(&purse_id);
             let balance: Option<U512> =
                 balance_uref.and_then(|uref| storage::read(uref.into()).unwrap_or_default());
             runtime::ret(balance, vec![])
         }
 
+        // argument: URef
+        // return: Option<U512>
+        "locked_balance" => {
+            let key: URef = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            // A URef without write access has no locked balance to report, rather than trapping
+            let locked_balance: Option<U512> =
+                WithdrawId::from_uref(key).ok().and_then(|purse_id| {
+                    mint.lookup(&purse_id)
+                        .map(|_| mint.locked_balance(&purse_id))
+                });
+            runtime::ret(locked_balance, vec![])
+        }
+
+        // arguments: URef, U512, u64, u64
+        // return: Result<URef, mint::error::Error>
+        "create_vesting" => {
+            let source: URef = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let amount: U512 = runtime::get_arg(2)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let cliff: u64 = runtime::get_arg(3)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let duration: u64 = runtime::get_arg(4)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+            let source: WithdrawId = match WithdrawId::from_uref(source) {
+                Ok(withdraw_id) => withdraw_id,
+                Err(error) => {
+                    let create_result: Result<URef, Error> = Err(error.into());
+                    runtime::ret(create_result, vec![])
+                }
+            };
+
+            let maybe_purse_key = mint
+                .create_vesting(source, amount, BlockTime(cliff), BlockTime(duration))
+                .map(|purse_id| URef::new(purse_id.raw_id(), AccessRights::READ_ADD_WRITE));
+
+            if let Ok(purse_key) = maybe_purse_key {
+                runtime::ret(maybe_purse_key, vec![purse_key])
+            } else {
+                runtime::ret(maybe_purse_key, vec![])
+            }
+        }
+
         "transfer" => {
             let source: URef = runtime::get_arg(1)
                 .unwrap_or_revert_with(ApiError::MissingArgument)
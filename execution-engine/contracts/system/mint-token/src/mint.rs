@@ -1,4 +1,5 @@
-use contract_ffi::system_contracts::mint::Error;
+use contract_ffi::system_contracts::mint::{Error, VestingSchedule};
+use contract_ffi::value::account::BlockTime;
 use contract_ffi::value::U512;
 
 use crate::capabilities::{Addable, Readable, Writable};
@@ -12,31 +13,73 @@ where
     type DepOnlyId;
 
     fn mint(&self, initial_balance: U512) -> Result<Self::PurseId, Error>;
-    fn lookup(&self, p: Self::PurseId) -> Option<RW>;
-    fn dep_lookup(&self, p: Self::DepOnlyId) -> Option<A>;
+    fn lookup(&self, p: &Self::PurseId) -> Option<RW>;
+    fn dep_lookup(&self, p: &Self::DepOnlyId) -> Option<A>;
+    fn vesting_schedule(&self, p: &Self::PurseId) -> Option<VestingSchedule>;
+    fn set_vesting_schedule(&self, p: &Self::PurseId, schedule: VestingSchedule);
+    fn blocktime(&self) -> BlockTime;
 
     fn create(&self) -> Self::PurseId {
         self.mint(U512::zero())
             .expect("Creating a zero balance purse should always be allowed.")
     }
 
+    /// Returns the part of the purse's balance which can't be transferred yet.
+    fn locked_balance(&self, p: &Self::PurseId) -> U512 {
+        self.vesting_schedule(p)
+            .map(|schedule| schedule.locked_amount(self.blocktime()))
+            .unwrap_or_default()
+    }
+
+    /// Looks up the source purse and checks that `amount` of its balance is unlocked, returning
+    /// the purse's balance and its current value.
+    fn withdrawable(&self, source: &Self::PurseId, amount: U512) -> Result<(RW, U512), Error> {
+        let source_bal = self.lookup(source).ok_or(Error::SourceNotFound)?;
+        let source_value = source_bal.read();
+        if amount > source_value {
+            return Err(Error::InsufficientFunds);
+        }
+        if amount > source_value.saturating_sub(self.locked_balance(source)) {
+            return Err(Error::InsufficientUnlockedFunds);
+        }
+        Ok((source_bal, source_value))
+    }
+
     fn transfer(
         &self,
         source: Self::PurseId,
         dest: Self::DepOnlyId,
         amount: U512,
     ) -> Result<(), Error> {
-        let source_bal = self.lookup(source).ok_or(Error::SourceNotFound)?;
-        let source_value = source_bal.read();
-        if amount > source_value {
-            return Err(Error::InsufficientFunds);
-        }
+        let (source_bal, source_value) = self.withdrawable(&source, amount)?;
 
-        let dest_bal = self.dep_lookup(dest).ok_or(Error::DestNotFound)?;
+        let dest_bal = self.dep_lookup(&dest).ok_or(Error::DestNotFound)?;
         source_bal.write(source_value - amount);
         dest_bal.add(amount);
         Ok(())
     }
+
+    /// Creates a new purse holding `amount` taken from `source`, which unlocks over `duration`
+    /// starting from the current block time, with nothing unlocked before `cliff` has passed.
+    fn create_vesting(
+        &self,
+        source: Self::PurseId,
+        amount: U512,
+        cliff: BlockTime,
+        duration: BlockTime,
+    ) -> Result<Self::PurseId, Error> {
+        let schedule = VestingSchedule::new(self.blocktime(), cliff, duration, amount)
+            .ok_or(Error::InvalidArgument)?;
+        let (source_bal, source_value) = self.withdrawable(&source, amount)?;
+
+        let purse = self.create();
+        // Safe to unwrap, as the purse was just created
+        let purse_bal = self.lookup(&purse).unwrap();
+        self.set_vesting_schedule(&purse, schedule);
+        source_bal.write(source_value - amount);
+        purse_bal.write(amount);
+        Ok(purse)
+    }
 }
 
 #[cfg(test)]
@@ -46,6 +89,8 @@ mod tests {
     use core::cell::{Cell, RefCell};
     use core::ops::Add;
 
+    use contract_ffi::system_contracts::mint::VestingSchedule;
+    use contract_ffi::value::account::BlockTime;
     use contract_ffi::value::U512;
 
     use crate::capabilities::{Addable, Readable, Writable};
@@ -104,7 +149,12 @@ mod tests {
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     struct DepId(u32);
 
-    struct SimpleMint(RefCell<BTreeMap<u32, Balance>>, Cell<u32>);
+    struct SimpleMint(
+        RefCell<BTreeMap<u32, Balance>>,
+        Cell<u32>,
+        RefCell<BTreeMap<u32, VestingSchedule>>,
+        Cell<BlockTime>,
+    );
 
     impl SimpleMint {
         pub fn new() -> Self {
@@ -116,7 +166,16 @@ mod tests {
                 );
                 ret
             };
-            SimpleMint(RefCell::new(initial), Cell::new(1))
+            SimpleMint(
+                RefCell::new(initial),
+                Cell::new(1),
+                RefCell::new(BTreeMap::new()),
+                Cell::new(BlockTime(0)),
+            )
+        }
+
+        pub fn set_blocktime(&self, blocktime: BlockTime) {
+            self.3.set(blocktime);
         }
 
         pub fn balance(&self, id: u32) -> Option<U512> {
@@ -137,13 +196,25 @@ mod tests {
             Ok(FullId(id))
         }
 
-        fn lookup(&self, p: Self::PurseId) -> Option<Balance> {
+        fn lookup(&self, p: &Self::PurseId) -> Option<Balance> {
             self.0.borrow().get(&p.0).map(Rc::clone)
         }
 
-        fn dep_lookup(&self, p: Self::DepOnlyId) -> Option<Balance> {
+        fn dep_lookup(&self, p: &Self::DepOnlyId) -> Option<Balance> {
             self.0.borrow().get(&p.0).map(Rc::clone)
         }
+
+        fn vesting_schedule(&self, p: &Self::PurseId) -> Option<VestingSchedule> {
+            self.2.borrow().get(&p.0).cloned()
+        }
+
+        fn set_vesting_schedule(&self, p: &Self::PurseId, schedule: VestingSchedule) {
+            self.2.borrow_mut().insert(p.0, schedule);
+        }
+
+        fn blocktime(&self) -> BlockTime {
+            self.3.get()
+        }
     }
 
     #[test]
@@ -226,4 +297,75 @@ mod tests {
         // balance remains unchanged
         assert_eq!(balance1, b1);
     }
+
+    #[test]
+    fn transfer_from_vesting_purse() {
+        let mint = SimpleMint::new();
+        mint.set_blocktime(BlockTime(100));
+
+        let vesting_purse = mint
+            .create_vesting(GENESIS_PURSE, U512::from(100), BlockTime(10), BlockTime(50))
+            .expect("should create vesting purse");
+        let purse2 = mint.create().to_dep();
+
+        assert_eq!(
+            mint.balance(GENESIS_PURSE.0).unwrap(),
+            U512::from(GENESIS_PURSE_AMOUNT - 100)
+        );
+        assert_eq!(mint.balance(vesting_purse.0).unwrap(), U512::from(100));
+
+        // nothing is unlocked before the cliff
+        mint.set_blocktime(BlockTime(109));
+        assert_eq!(mint.locked_balance(&vesting_purse), U512::from(100));
+        assert_eq!(
+            Err(Error::InsufficientUnlockedFunds),
+            mint.transfer(vesting_purse, purse2, U512::one())
+        );
+
+        // deposits on top of the schedule are unlocked straight away
+        mint.transfer(GENESIS_PURSE, vesting_purse.to_dep(), U512::from(5))
+            .expect("should deposit into vesting purse");
+        mint.transfer(vesting_purse, purse2, U512::from(5))
+            .expect("should transfer deposited funds");
+
+        // a fifth of the schedule is unlocked at the cliff
+        mint.set_blocktime(BlockTime(110));
+        assert_eq!(mint.locked_balance(&vesting_purse), U512::from(80));
+        assert_eq!(
+            Err(Error::InsufficientUnlockedFunds),
+            mint.transfer(vesting_purse, purse2, U512::from(21))
+        );
+        mint.transfer(vesting_purse, purse2, U512::from(20))
+            .expect("should transfer unlocked funds");
+
+        // everything is unlocked after the vesting duration
+        mint.set_blocktime(BlockTime(150));
+        assert_eq!(mint.locked_balance(&vesting_purse), U512::zero());
+        mint.transfer(vesting_purse, purse2, U512::from(80))
+            .expect("should transfer all remaining funds");
+        assert_eq!(mint.balance(purse2.0).unwrap(), U512::from(105));
+    }
+
+    #[test]
+    fn create_vesting_overdraft() {
+        let mint = SimpleMint::new();
+
+        assert_eq!(
+            Err(Error::InsufficientFunds),
+            mint.create_vesting(
+                GENESIS_PURSE,
+                U512::from(GENESIS_PURSE_AMOUNT + 1),
+                BlockTime(0),
+                BlockTime(1)
+            )
+        );
+        assert_eq!(
+            Err(Error::InvalidArgument),
+            mint.create_vesting(GENESIS_PURSE, U512::one(), BlockTime(2), BlockTime(1))
+        );
+        assert_eq!(
+            mint.balance(GENESIS_PURSE.0).unwrap(),
+            U512::from(GENESIS_PURSE_AMOUNT)
+        );
+    }
 }
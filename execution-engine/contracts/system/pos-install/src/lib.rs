@@ -22,6 +22,8 @@ const PLACEHOLDER_KEY: Key = Key::Hash([0u8; 32]);
 const POS_BONDING_PURSE: &str = "pos_bonding_purse";
 const POS_PAYMENT_PURSE: &str = "pos_payment_purse";
 const POS_REWARDS_PURSE: &str = "pos_rewards_purse";
+const POS_BURN_PURSE: &str = "pos_burn_purse";
+const POS_TREASURY_PURSE: &str = "pos_treasury_purse";
 const POS_FUNCTION_NAME: &str = "pos_ext";
 
 #[repr(u32)]
@@ -72,12 +74,16 @@ pub extern "C" fn call() {
     let bonding_purse = mint_purse(&mint, total_bonds);
     let payment_purse = mint_purse(&mint, U512::zero());
     let rewards_purse = mint_purse(&mint, U512::zero());
+    let burn_purse = mint_purse(&mint, U512::zero());
+    let treasury_purse = mint_purse(&mint, U512::zero());
 
     // Include PoS purses in its named_keys
     [
         (POS_BONDING_PURSE, bonding_purse.value()),
         (POS_PAYMENT_PURSE, payment_purse.value()),
         (POS_REWARDS_PURSE, rewards_purse.value()),
+        (POS_BURN_PURSE, burn_purse.value()),
+        (POS_TREASURY_PURSE, treasury_purse.value()),
     ]
     .iter()
     .for_each(|(name, uref)| {
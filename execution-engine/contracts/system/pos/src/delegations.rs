@@ -0,0 +1,276 @@
+use alloc::collections::BTreeMap;
+use core::convert::TryFrom;
+
+use contract_ffi::bytesrepr::{FromBytes, ToBytes};
+use contract_ffi::contract_api::storage;
+use contract_ffi::system_contracts::pos::{self, Error, Result};
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::{Value, U512};
+
+use crate::stakes::Stakes;
+
+const DELEGATIONS_KEY: u8 = 3;
+
+pub trait DelegationsProvider {
+    /// Reads the delegations.
+    fn read() -> Result<Delegations>;
+
+    /// Writes the delegations.
+    fn write(delegations: &Delegations);
+}
+
+/// A `DelegationsProvider` that reads and writes the delegations to/from the
+/// contract's local state.
+pub struct DelegationsLocal;
+
+impl DelegationsProvider for DelegationsLocal {
+    /// Reads the delegations from the local state of the contract.
+    fn read() -> Result<Delegations> {
+        storage::read_local(DELEGATIONS_KEY)
+            .map(Option::unwrap_or_default)
+            .map_err(|_| Error::DelegationsDeserializationFailed)
+    }
+
+    /// Writes the delegations to the local state of the contract.
+    fn write(delegations: &Delegations) {
+        storage::write_local(DELEGATIONS_KEY, delegations);
+    }
+}
+
+/// The motes delegated to each validator, by delegator. These are tracked
+/// separately from the validators' own bonds in the `Stakes`.
+#[derive(Clone, Debug, Default, PartialEq, ToBytes, FromBytes)]
+pub struct Delegations(pub BTreeMap<PublicKey, BTreeMap<PublicKey, U512>>);
+
+impl Delegations {
+    /// Adds `amount` to the motes `delegator` has delegated to `validator`.
+    pub fn delegate(&mut self, validator: &PublicKey, delegator: &PublicKey, amount: U512) {
+        self.0
+            .entry(*validator)
+            .or_default()
+            .entry(*delegator)
+            .and_modify(|x| *x += amount)
+            .or_insert(amount);
+    }
+
+    /// If `maybe_amount` is `None`, removes all the motes `delegator` has
+    /// delegated to `validator`, otherwise subtracts the given amount.
+    ///
+    /// Returns the amount that was actually subtracted, or an error if the
+    /// delegator has nothing delegated to the validator or less than the
+    /// specified amount.
+    pub fn undelegate(
+        &mut self,
+        validator: &PublicKey,
+        delegator: &PublicKey,
+        maybe_amount: Option<U512>,
+    ) -> Result<U512> {
+        let delegators = self.0.get_mut(validator).ok_or(Error::NotDelegated)?;
+        let delegation = delegators.get_mut(delegator).ok_or(Error::NotDelegated)?;
+        let amount = maybe_amount.unwrap_or(*delegation);
+        if amount > *delegation {
+            return Err(Error::UndelegateTooLarge);
+        }
+        *delegation -= amount;
+        if delegation.is_zero() {
+            delegators.remove(delegator);
+            if delegators.is_empty() {
+                self.0.remove(validator);
+            }
+        }
+        Ok(amount)
+    }
+
+    /// Returns the number of accounts with motes delegated to `validator`.
+    pub fn delegator_count(&self, validator: &PublicKey) -> usize {
+        self.0.get(validator).map_or(0, BTreeMap::len)
+    }
+
+    /// Returns the motes `delegator` has delegated to `validator`.
+    pub fn delegation(&self, validator: &PublicKey, delegator: &PublicKey) -> U512 {
+        self.0
+            .get(validator)
+            .and_then(|delegators| delegators.get(delegator))
+            .cloned()
+            .unwrap_or_else(U512::zero)
+    }
+
+    /// Removes `fraction` millionths of the motes each delegator has delegated
+    /// to `validator`. Delegations with nothing left are removed.
+    ///
+    /// Returns the total amount that was removed.
+    pub fn slash(&mut self, validator: &PublicKey, fraction: u32) -> U512 {
+        let delegators = match self.0.get_mut(validator) {
+            Some(delegators) => delegators,
+            None => return U512::zero(),
+        };
+        let mut total = U512::zero();
+        for delegation in delegators.values_mut() {
+            let amount = pos::share_of(*delegation, fraction);
+            *delegation -= amount;
+            total += amount;
+        }
+        delegators.retain(|_, delegation| !delegation.is_zero());
+        if delegators.is_empty() {
+            self.0.remove(validator);
+        }
+        total
+    }
+
+    /// Splits `total` between the bonded validators and their delegators pro
+    /// rata to their own bonds and delegated motes respectively. Delegations to
+    /// validators which are no longer bonded don't earn a share.
+    ///
+    /// Returns the share of each account. The shares are rounded down, so
+    /// their sum may be less than `total`.
+    pub fn reward_shares(&self, stakes: &Stakes, total: U512) -> BTreeMap<PublicKey, U512> {
+        let mut weights: BTreeMap<PublicKey, U512> = BTreeMap::new();
+        for (validator, stake) in &stakes.0 {
+            *weights.entry(*validator).or_default() += *stake;
+            for (delegator, amount) in self.0.get(validator).into_iter().flatten() {
+                *weights.entry(*delegator).or_default() += *amount;
+            }
+        }
+        let total_weight = weights.values().fold(U512::zero(), |sum, w| sum + *w);
+        if total_weight.is_zero() {
+            return BTreeMap::new();
+        }
+        weights
+            .into_iter()
+            .map(|(account, weight)| (account, total * weight / total_weight))
+            .filter(|(_, share)| !share.is_zero())
+            .collect()
+    }
+}
+
+impl TryFrom<Value> for Delegations {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        let bytes = match value {
+            Value::ByteArray(bytes) => bytes,
+            _ => return Err(Error::DelegationsNotStoredAsByteArray),
+        };
+        let (delegations, rest) =
+            Delegations::from_bytes(&bytes).map_err(|_| Error::DelegationsDeserializationFailed)?;
+        if !rest.is_empty() {
+            return Err(Error::DelegationsDeserializationExtraBytes);
+        }
+        Ok(delegations)
+    }
+}
+
+impl Into<Value> for &Delegations {
+    fn into(self) -> Value {
+        Value::ByteArray(self.to_bytes().expect("Serialization cannot fail"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use contract_ffi::system_contracts::pos::Error;
+    use contract_ffi::value::{account::PublicKey, U512};
+
+    use crate::delegations::Delegations;
+    use crate::stakes::Stakes;
+
+    const KEY1: [u8; 32] = [1; 32];
+    const KEY2: [u8; 32] = [2; 32];
+    const KEY3: [u8; 32] = [3; 32];
+    const KEY4: [u8; 32] = [4; 32];
+
+    fn new_stakes(stakes: &[([u8; 32], u64)]) -> Stakes {
+        Stakes(
+            stakes
+                .iter()
+                .map(|&(key, amount)| (PublicKey::new(key), U512::from(amount)))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_delegate_undelegate() {
+        let validator = PublicKey::new(KEY1);
+        let delegator = PublicKey::new(KEY2);
+        let mut delegations: Delegations = Default::default();
+        delegations.delegate(&validator, &delegator, U512::from(5));
+        delegations.delegate(&validator, &delegator, U512::from(6));
+        assert_eq!(
+            U512::from(11),
+            delegations.delegation(&validator, &delegator)
+        );
+
+        assert_eq!(
+            Err(Error::UndelegateTooLarge),
+            delegations.undelegate(&validator, &delegator, Some(U512::from(12)))
+        );
+        assert_eq!(
+            Ok(U512::from(4)),
+            delegations.undelegate(&validator, &delegator, Some(U512::from(4)))
+        );
+        assert_eq!(
+            Ok(U512::from(7)),
+            delegations.undelegate(&validator, &delegator, None)
+        );
+        assert_eq!(Delegations::default(), delegations);
+        assert_eq!(
+            Err(Error::NotDelegated),
+            delegations.undelegate(&validator, &delegator, None)
+        );
+    }
+
+    #[test]
+    fn test_slash() {
+        let validator = PublicKey::new(KEY1);
+        let mut delegations: Delegations = Default::default();
+        delegations.delegate(&validator, &PublicKey::new(KEY2), U512::from(10));
+        delegations.delegate(&validator, &PublicKey::new(KEY3), U512::from(1));
+        delegations.delegate(&PublicKey::new(KEY4), &PublicKey::new(KEY3), U512::from(6));
+
+        // The slashed part of each delegation is rounded down.
+        assert_eq!(U512::from(5), delegations.slash(&validator, 500_000));
+        assert_eq!(
+            U512::from(5),
+            delegations.delegation(&validator, &PublicKey::new(KEY2))
+        );
+        assert_eq!(
+            U512::from(1),
+            delegations.delegation(&validator, &PublicKey::new(KEY3))
+        );
+        assert_eq!(U512::from(6), delegations.slash(&validator, 1_000_000));
+        assert_eq!(None, delegations.0.get(&validator));
+        assert_eq!(
+            U512::from(6),
+            delegations.delegation(&PublicKey::new(KEY4), &PublicKey::new(KEY3))
+        );
+    }
+
+    #[test]
+    fn test_reward_shares() {
+        let stakes = new_stakes(&[(KEY1, 300), (KEY2, 100)]);
+        let mut delegations: Delegations = Default::default();
+        delegations.delegate(
+            &PublicKey::new(KEY1),
+            &PublicKey::new(KEY3),
+            U512::from(100),
+        );
+        // A validator can delegate to another validator, too.
+        delegations.delegate(
+            &PublicKey::new(KEY1),
+            &PublicKey::new(KEY2),
+            U512::from(300),
+        );
+        // Delegations to unbonded validators don't earn rewards.
+        delegations.delegate(
+            &PublicKey::new(KEY4),
+            &PublicKey::new(KEY3),
+            U512::from(1_000),
+        );
+
+        let shares = delegations.reward_shares(&stakes, U512::from(1_001));
+        assert_eq!(Some(&U512::from(375)), shares.get(&PublicKey::new(KEY1)));
+        assert_eq!(Some(&U512::from(500)), shares.get(&PublicKey::new(KEY2)));
+        assert_eq!(Some(&U512::from(125)), shares.get(&PublicKey::new(KEY3)));
+        assert_eq!(None, shares.get(&PublicKey::new(KEY4)));
+    }
+}
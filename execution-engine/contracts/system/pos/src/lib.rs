@@ -4,22 +4,27 @@
 extern crate alloc;
 extern crate contract_ffi;
 
+mod delegations;
 mod queue;
 mod stakes;
 
+use alloc::collections::BTreeSet;
 use alloc::string::String;
 use alloc::vec::Vec;
 
-use contract_ffi::contract_api::{runtime, system};
+use contract_ffi::contract_api::{runtime, storage, system};
 use contract_ffi::execution::Phase;
 use contract_ffi::key::Key;
-use contract_ffi::system_contracts::pos::{Error, PurseLookupError, Result};
+use contract_ffi::system_contracts::pos::{
+    Error, PurseLookupError, Result, RewardsConfig, SlashDestination, SHARE_DENOMINATOR,
+};
 use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
 use contract_ffi::uref::{AccessRights, URef};
 use contract_ffi::value::account::{BlockTime, PublicKey, PurseId};
 use contract_ffi::value::U512;
 
-use crate::queue::{QueueEntry, QueueLocal, QueueProvider};
+use crate::delegations::{DelegationsLocal, DelegationsProvider};
+use crate::queue::{QueueLocal, QueueProvider};
 use crate::stakes::{ContractStakes, StakesProvider};
 
 /// Account used to run system functions (in particular `finalize_payment`).
@@ -37,10 +42,20 @@ const PAYMENT_PURSE_KEY: &str = "pos_payment_purse";
 /// them.
 const REWARDS_PURSE_KEY: &str = "pos_rewards_purse";
 
+/// The uref name where the PoS moves the burnt part of the payment. Motes in this
+/// purse are never paid out.
+const BURN_PURSE_KEY: &str = "pos_burn_purse";
+
+/// The uref name where the PoS moves the treasury's part of the payment.
+const TREASURY_PURSE_KEY: &str = "pos_treasury_purse";
+
 /// The uref name where the PoS will refund unused payment back to the user. The
 /// uref this name corresponds to is set by the user.
 const REFUND_PURSE_KEY: &str = "pos_refund_purse";
 
+/// The local key under which the time of the last rewards distribution is stored.
+const LAST_DISTRIBUTION_KEY: u8 = 4;
+
 /// The time from a bonding request until the bond becomes effective and part of
 /// the stake.
 const BOND_DELAY: u64 = 0;
@@ -50,6 +65,13 @@ const UNBOND_DELAY: u64 = 0;
 const MAX_BOND_LEN: usize = 100;
 /// The maximum number of pending unbonding requests.
 const MAX_UNBOND_LEN: usize = 1000;
+/// The maximum number of pending delegator withdrawal requests.
+const MAX_WITHDRAWAL_LEN: usize = 1000;
+/// The minimum number of motes in a single delegation request.
+// TODO: Pick a reasonable value.
+const MIN_DELEGATION_AMOUNT: u64 = 100;
+/// The maximum number of accounts delegating to a single validator.
+const MAX_DELEGATORS_PER_VALIDATOR: usize = 1000;
 /// The maximum difference between the largest and the smallest stakes.
 // TODO: Should this be a percentage instead?
 // TODO: Pick a reasonable value.
@@ -114,18 +136,116 @@ fn unbond<Q: QueueProvider, S: StakesProvider>(
     Ok(())
 }
 
+/// Delegates `amount` from the deploy's creator to `validator`. The delegated
+/// motes are tracked separately from the validator's own bond and take effect
+/// immediately.
+///
+/// Each request must be for at least `MIN_DELEGATION_AMOUNT`, and a validator
+/// accepts at most `MAX_DELEGATORS_PER_VALIDATOR` distinct delegators.
+fn delegate_to<S: StakesProvider, D: DelegationsProvider>(
+    amount: U512,
+    validator: PublicKey,
+    delegator: PublicKey,
+) -> Result<()> {
+    if amount < U512::from(MIN_DELEGATION_AMOUNT) {
+        return Err(Error::DelegationTooSmall);
+    }
+    if !S::read()?.0.contains_key(&validator) {
+        return Err(Error::NotBonded);
+    }
+    let mut delegations = D::read()?;
+    if delegations.delegation(&validator, &delegator).is_zero()
+        && delegations.delegator_count(&validator) >= MAX_DELEGATORS_PER_VALIDATOR
+    {
+        return Err(Error::TooManyDelegators);
+    }
+    delegations.delegate(&validator, &delegator, amount);
+    D::write(&delegations);
+    Ok(())
+}
+
+/// Enqueues the deploy's creator for withdrawing motes delegated to
+/// `validator`. The delegation is decreased immediately, but the funds will
+/// only be released after the unbonding delay. If `maybe_amount` is `None`, all
+/// motes delegated to the validator are enqueued for withdrawal.
+fn undelegate_from<Q: QueueProvider, D: DelegationsProvider>(
+    maybe_amount: Option<U512>,
+    validator: PublicKey,
+    delegator: PublicKey,
+    timestamp: BlockTime,
+) -> Result<()> {
+    let mut queue = Q::read_withdrawal();
+    if queue.0.len() >= MAX_WITHDRAWAL_LEN {
+        return Err(Error::TooManyEventsInQueue);
+    }
+
+    let mut delegations = D::read()?;
+    let payout = delegations.undelegate(&validator, &delegator, maybe_amount)?;
+    D::write(&delegations);
+    queue.push(validator, delegator, payout, timestamp)?;
+    Q::write_withdrawal(&queue);
+    Ok(())
+}
+
+/// Removes `fraction` millionths of the stakes of each of the `validators`, as
+/// well as of their pending unbonding requests, the motes delegated to them and
+/// the pending withdrawals of those motes.
+///
+/// Returns the total amount that was removed.
+fn slash_stakes<Q: QueueProvider, S: StakesProvider, D: DelegationsProvider>(
+    validators: &BTreeSet<PublicKey>,
+    fraction: u32,
+) -> Result<U512> {
+    if fraction > SHARE_DENOMINATOR {
+        return Err(Error::InvalidSlashFraction);
+    }
+
+    let mut stakes = S::read()?;
+    let mut unbonding_queue = Q::read_unbonding();
+    let mut delegations = D::read()?;
+    let mut withdrawal_queue = Q::read_withdrawal();
+    let mut total = U512::zero();
+    for validator in validators {
+        total += stakes.slash(validator, fraction);
+        total += unbonding_queue.slash(validator, fraction);
+        total += delegations.slash(validator, fraction);
+        total += withdrawal_queue.slash(validator, fraction);
+    }
+    if stakes.0.is_empty() {
+        return Err(Error::CannotSlashAllValidators);
+    }
+
+    S::write(&stakes);
+    Q::write_unbonding(&unbonding_queue);
+    D::write(&delegations);
+    Q::write_withdrawal(&withdrawal_queue);
+    Ok(total)
+}
+
 /// Removes all due requests from the queues and applies them.
-fn step<Q: QueueProvider, S: StakesProvider>(timestamp: BlockTime) -> Result<Vec<QueueEntry>> {
+///
+/// Returns the account and amount of each due unbonding and delegator
+/// withdrawal request, which are yet to be paid out.
+fn step<Q: QueueProvider, S: StakesProvider>(
+    timestamp: BlockTime,
+) -> Result<Vec<(PublicKey, U512)>> {
     let mut bonding_queue = Q::read_bonding();
     let mut unbonding_queue = Q::read_unbonding();
+    let mut withdrawal_queue = Q::read_withdrawal();
 
     let bonds = bonding_queue.pop_due(BlockTime(timestamp.0.saturating_sub(BOND_DELAY)));
-    let unbonds = unbonding_queue.pop_due(BlockTime(timestamp.0.saturating_sub(UNBOND_DELAY)));
+    let unbond_due = BlockTime(timestamp.0.saturating_sub(UNBOND_DELAY));
+    let unbonds = unbonding_queue.pop_due(unbond_due);
+    let withdrawals = withdrawal_queue.pop_due(unbond_due);
 
     if !unbonds.is_empty() {
         Q::write_unbonding(&unbonding_queue);
     }
 
+    if !withdrawals.is_empty() {
+        Q::write_withdrawal(&withdrawal_queue);
+    }
+
     if !bonds.is_empty() {
         Q::write_bonding(&bonding_queue);
         let mut stakes = S::read()?;
@@ -135,7 +255,16 @@ fn step<Q: QueueProvider, S: StakesProvider>(timestamp: BlockTime) -> Result<Vec
         S::write(&stakes);
     }
 
-    Ok(unbonds)
+    let payouts = unbonds
+        .into_iter()
+        .map(|entry| (entry.validator, entry.amount))
+        .chain(
+            withdrawals
+                .into_iter()
+                .map(|entry| (entry.delegator, entry.amount)),
+        )
+        .collect();
+    Ok(payouts)
 }
 
 /// Attempts to look up a purse from the named_keys
@@ -163,6 +292,16 @@ fn get_rewards_purse() -> Result<PurseId> {
     get_purse_id(REWARDS_PURSE_KEY).map_err(PurseLookupError::rewards)
 }
 
+/// Returns the purse for burnt payment
+fn get_burn_purse() -> Result<PurseId> {
+    get_purse_id(BURN_PURSE_KEY).map_err(PurseLookupError::burn)
+}
+
+/// Returns the purse for the treasury's part of the payment
+fn get_treasury_purse() -> Result<PurseId> {
+    get_purse_id(TREASURY_PURSE_KEY).map_err(PurseLookupError::treasury)
+}
+
 /// Sets the purse where refunds (excess funds not spent to pay for computation)
 /// will be sent. Note that if this function is never called, the default
 /// location is the main purse of the deployer's account.
@@ -185,13 +324,14 @@ fn get_refund_purse() -> Option<PurseId> {
     }
 }
 
-/// Transfers funds from the payment purse to the validator rewards
-/// purse, as well as to the refund purse, depending on how much
-/// was spent on the computation. This function maintains the invariant
-/// that the balance of the payment purse is zero at the beginning and
-/// end of each deploy and that the refund purse is unset at the beginning
-/// and end of each deploy.
-fn finalize_payment(amount_spent: U512, account: PublicKey) {
+/// Transfers funds from the payment purse to the validator rewards, burn and
+/// treasury purses according to `rewards_config`, as well as to the refund
+/// purse, depending on how much was spent on the computation. This function
+/// maintains the invariant that the balance of the payment purse is zero at the
+/// beginning and end of each deploy and that the refund purse is unset at the
+/// beginning and end of each deploy. The rewards purse is paid out separately,
+/// by `distribute_rewards`.
+fn finalize_payment(amount_spent: U512, account: PublicKey, rewards_config: RewardsConfig) {
     let caller = runtime::get_caller();
     if caller.value() != SYSTEM_ACCOUNT {
         runtime::revert(Error::SystemFunctionCalledByUserAccount);
@@ -209,9 +349,20 @@ fn finalize_payment(amount_spent: U512, account: PublicKey) {
     let refund_purse = get_refund_purse();
     runtime::remove_key(REFUND_PURSE_KEY); //unset refund purse after reading it
 
-    // pay validators
-    system::transfer_from_purse_to_purse(payment_purse, rewards_purse, amount_spent)
+    // pay validators, burn and treasury
+    let (commission, burn, treasury) = rewards_config.split(amount_spent);
+    system::transfer_from_purse_to_purse(payment_purse, rewards_purse, commission)
         .unwrap_or_revert_with(Error::FailedTransferToRewardsPurse);
+    if !burn.is_zero() {
+        let burn_purse = get_burn_purse().unwrap_or_revert();
+        system::transfer_from_purse_to_purse(payment_purse, burn_purse, burn)
+            .unwrap_or_revert_with(Error::FailedTransferToBurnPurse);
+    }
+    if !treasury.is_zero() {
+        let treasury_purse = get_treasury_purse().unwrap_or_revert();
+        system::transfer_from_purse_to_purse(payment_purse, treasury_purse, treasury)
+            .unwrap_or_revert_with(Error::FailedTransferToTreasuryPurse);
+    }
 
     // give refund
     if !refund_amount.is_zero() {
@@ -227,11 +378,93 @@ fn finalize_payment(amount_spent: U512, account: PublicKey) {
     }
 }
 
+/// Penalizes the `validators`, e.g. for equivocating, by slashing `fraction`
+/// millionths of their stakes, the motes delegated to them and the pending
+/// requests to withdraw either. The slashed motes are moved from the bonding
+/// purse to the burn or treasury purse. This can only be called by the system,
+/// in the system phase.
+fn slash(validators: BTreeSet<PublicKey>, fraction: u32, destination: SlashDestination) {
+    let caller = runtime::get_caller();
+    if caller.value() != SYSTEM_ACCOUNT {
+        runtime::revert(Error::SystemFunctionCalledByUserAccount);
+    }
+    if runtime::get_phase() != Phase::System {
+        runtime::revert(Error::SlashCalledOutsideSystemPhase);
+    }
+
+    let bonding_purse = get_bonding_purse().unwrap_or_revert();
+    let amount =
+        slash_stakes::<QueueLocal, ContractStakes, DelegationsLocal>(&validators, fraction)
+            .unwrap_or_revert();
+    if amount.is_zero() {
+        return;
+    }
+    match destination {
+        SlashDestination::Burn => {
+            let burn_purse = get_burn_purse().unwrap_or_revert();
+            system::transfer_from_purse_to_purse(bonding_purse, burn_purse, amount)
+                .unwrap_or_revert_with(Error::FailedTransferToBurnPurse);
+        }
+        SlashDestination::Treasury => {
+            let treasury_purse = get_treasury_purse().unwrap_or_revert();
+            system::transfer_from_purse_to_purse(bonding_purse, treasury_purse, amount)
+                .unwrap_or_revert_with(Error::FailedTransferToTreasuryPurse);
+        }
+    }
+}
+
 fn refund_to_account(payment_purse: PurseId, account: PublicKey, amount: U512) {
     system::transfer_from_purse_to_account(payment_purse, account, amount)
         .unwrap_or_revert_with(Error::FailedTransferToAccountPurse);
 }
 
+/// Pays out the balance of the rewards purse to the bonded validators and their
+/// delegators once the distribution interval of `rewards_config` has passed
+/// since the last distribution. This can only be called by the system, in the
+/// system phase.
+fn distribute_rewards(rewards_config: RewardsConfig) {
+    let caller = runtime::get_caller();
+    if caller.value() != SYSTEM_ACCOUNT {
+        runtime::revert(Error::SystemFunctionCalledByUserAccount);
+    }
+    if runtime::get_phase() != Phase::System {
+        runtime::revert(Error::DistributeRewardsCalledOutsideSystemPhase);
+    }
+
+    let timestamp = runtime::get_blocktime();
+    let last_distribution: u64 = storage::read_local(LAST_DISTRIBUTION_KEY)
+        .unwrap_or_revert_with(Error::LastDistributionDeserializationFailed)
+        .unwrap_or_default();
+    if timestamp.0.saturating_sub(last_distribution) < rewards_config.distribution_interval().0 {
+        return;
+    }
+
+    let rewards_purse = get_rewards_purse().unwrap_or_revert();
+    pay_out_rewards(rewards_purse, timestamp);
+}
+
+/// Pays out the balance of the rewards purse to the bonded validators and their
+/// delegators, pro rata to their bonds and delegated motes, and records
+/// `timestamp` as the time of the last distribution.
+fn pay_out_rewards(rewards_purse: PurseId, timestamp: BlockTime) {
+    let total = system::get_balance(rewards_purse)
+        .unwrap_or_revert_with(Error::RewardsPurseBalanceNotFound);
+    let stakes = match ContractStakes::read() {
+        Ok(stakes) => stakes,
+        // Without any bonded validators, the rewards stay in the purse.
+        Err(Error::StakesNotFound) => return,
+        Err(error) => runtime::revert(error),
+    };
+    let delegations = DelegationsLocal::read().unwrap_or_revert();
+
+    for (account, share) in delegations.reward_shares(&stakes, total) {
+        // A share which can't be transferred, as well as the remainder of the
+        // rounding, stays in the rewards purse for the next distribution.
+        let _ = system::transfer_from_purse_to_account(rewards_purse, account, share);
+    }
+    storage::write_local(LAST_DISTRIBUTION_KEY, timestamp.0);
+}
+
 pub fn delegate() {
     let method_name: String = runtime::get_arg(0)
         .unwrap_or_revert_with(Error::MissingArgument)
@@ -262,13 +495,9 @@ pub fn delegate() {
 
             // TODO: Remove this and set nonzero delays once the system calls `step` in each
             // block.
-            let unbonds = step::<QueueLocal, ContractStakes>(timestamp).unwrap_or_revert();
-            for entry in unbonds {
-                let _ = system::transfer_from_purse_to_account(
-                    pos_purse,
-                    entry.validator,
-                    entry.amount,
-                );
+            let payouts = step::<QueueLocal, ContractStakes>(timestamp).unwrap_or_revert();
+            for (account, amount) in payouts {
+                let _ = system::transfer_from_purse_to_account(pos_purse, account, amount);
             }
         }
         // Type of this method: `fn unbond(amount: Option<U512>)`
@@ -282,29 +511,101 @@ pub fn delegate() {
 
             // TODO: Remove this and set nonzero delays once the system calls `step` in each
             // block.
-            let unbonds = step::<QueueLocal, ContractStakes>(timestamp).unwrap_or_revert();
-            for entry in unbonds {
-                system::transfer_from_purse_to_account(pos_purse, entry.validator, entry.amount)
+            let payouts = step::<QueueLocal, ContractStakes>(timestamp).unwrap_or_revert();
+            for (account, amount) in payouts {
+                system::transfer_from_purse_to_account(pos_purse, account, amount)
+                    .unwrap_or_revert_with(Error::UnbondTransferFailed);
+            }
+        }
+        // Type of this method: `fn delegate(validator: PublicKey, amount: U512, purse: URef)`
+        "delegate" => {
+            let delegator = runtime::get_caller();
+            let validator: PublicKey = runtime::get_arg(1)
+                .unwrap_or_revert_with(Error::MissingArgument)
+                .unwrap_or_revert_with(Error::InvalidArgument);
+            let amount: U512 = runtime::get_arg(2)
+                .unwrap_or_revert_with(Error::MissingArgument)
+                .unwrap_or_revert_with(Error::InvalidArgument);
+            let source_uref: URef = runtime::get_arg(3)
+                .unwrap_or_revert_with(Error::MissingArgument)
+                .unwrap_or_revert_with(Error::InvalidArgument);
+            let source = PurseId::new(source_uref);
+            system::transfer_from_purse_to_purse(source, pos_purse, amount)
+                .unwrap_or_revert_with(Error::BondTransferFailed);
+            delegate_to::<ContractStakes, DelegationsLocal>(amount, validator, delegator)
+                .unwrap_or_revert();
+        }
+        // Type of this method: `fn undelegate(validator: PublicKey, amount: Option<U512>)`
+        "undelegate" => {
+            let delegator = runtime::get_caller();
+            let validator: PublicKey = runtime::get_arg(1)
+                .unwrap_or_revert_with(Error::MissingArgument)
+                .unwrap_or_revert_with(Error::InvalidArgument);
+            let maybe_amount = runtime::get_arg(2)
+                .unwrap_or_revert_with(Error::MissingArgument)
+                .unwrap_or_revert_with(Error::InvalidArgument);
+            undelegate_from::<QueueLocal, DelegationsLocal>(
+                maybe_amount,
+                validator,
+                delegator,
+                timestamp,
+            )
+            .unwrap_or_revert();
+
+            // TODO: Remove this and set nonzero delays once the system calls `step` in each
+            // block.
+            let payouts = step::<QueueLocal, ContractStakes>(timestamp).unwrap_or_revert();
+            for (account, amount) in payouts {
+                system::transfer_from_purse_to_account(pos_purse, account, amount)
                     .unwrap_or_revert_with(Error::UnbondTransferFailed);
             }
         }
+        // Type of this method: `fn get_delegation(validator: PublicKey, delegator: PublicKey) ->
+        // U512`
+        "get_delegation" => {
+            let validator: PublicKey = runtime::get_arg(1)
+                .unwrap_or_revert_with(Error::MissingArgument)
+                .unwrap_or_revert_with(Error::InvalidArgument);
+            let delegator: PublicKey = runtime::get_arg(2)
+                .unwrap_or_revert_with(Error::MissingArgument)
+                .unwrap_or_revert_with(Error::InvalidArgument);
+            let delegations = DelegationsLocal::read().unwrap_or_revert();
+            runtime::ret(delegations.delegation(&validator, &delegator), Vec::new());
+        }
+        // Type of this method: `fn distribute_rewards(rewards_config: RewardsConfig)`
+        "distribute_rewards" => {
+            let rewards_config: RewardsConfig = runtime::get_arg(1)
+                .unwrap_or_revert_with(Error::MissingArgument)
+                .unwrap_or_revert_with(Error::InvalidArgument);
+            distribute_rewards(rewards_config);
+        }
+        // Type of this method: `fn slash(validators: Vec<PublicKey>, fraction: u32,
+        // destination: SlashDestination)`
+        "slash" => {
+            let validators: Vec<PublicKey> = runtime::get_arg(1)
+                .unwrap_or_revert_with(Error::MissingArgument)
+                .unwrap_or_revert_with(Error::InvalidArgument);
+            let fraction: u32 = runtime::get_arg(2)
+                .unwrap_or_revert_with(Error::MissingArgument)
+                .unwrap_or_revert_with(Error::InvalidArgument);
+            let destination: SlashDestination = runtime::get_arg(3)
+                .unwrap_or_revert_with(Error::MissingArgument)
+                .unwrap_or_revert_with(Error::InvalidArgument);
+            slash(validators.into_iter().collect(), fraction, destination);
+        }
         // Type of this method: `fn step()`
         "step" => {
             // This is called by the system in every block.
-            let unbonds = step::<QueueLocal, ContractStakes>(timestamp).unwrap_or_revert();
+            let payouts = step::<QueueLocal, ContractStakes>(timestamp).unwrap_or_revert();
 
             // Mateusz: Moved outside of `step` function so that it [step] can be unit
             // tested.
-            for entry in unbonds {
+            for (account, amount) in payouts {
                 // TODO: We currently ignore `TransferResult::TransferError`s here, since we
                 // can't recover from them and we shouldn't retry indefinitely.
                 // That would mean the contract just keeps the money forever,
                 // though.
-                let _ = system::transfer_from_purse_to_account(
-                    pos_purse,
-                    entry.validator,
-                    entry.amount,
-                );
+                let _ = system::transfer_from_purse_to_account(pos_purse, account, amount);
             }
         }
         "get_payment_purse" => {
@@ -341,7 +642,10 @@ pub fn delegate() {
             let account: PublicKey = runtime::get_arg(2)
                 .unwrap_or_revert_with(Error::MissingArgument)
                 .unwrap_or_revert_with(Error::InvalidArgument);
-            finalize_payment(amount_spent, account);
+            let rewards_config: RewardsConfig = runtime::get_arg(3)
+                .unwrap_or_revert_with(Error::MissingArgument)
+                .unwrap_or_revert_with(Error::InvalidArgument);
+            finalize_payment(amount_spent, account, rewards_config);
         }
         _ => {}
     }
@@ -356,27 +660,35 @@ pub extern "C" fn call() {
 #[cfg(test)]
 mod tests {
     use std::cell::RefCell;
+    use std::collections::BTreeSet;
     use std::iter;
 
-    use contract_ffi::system_contracts::pos::Result;
+    use contract_ffi::system_contracts::pos::{Error, Result};
     use contract_ffi::value::{
         account::{BlockTime, PublicKey},
         U512,
     };
 
-    use crate::queue::{Queue, QueueProvider};
+    use crate::delegations::{Delegations, DelegationsProvider};
+    use crate::queue::{Queue, QueueEntry, QueueProvider, WithdrawalEntry, WithdrawalQueue};
     use crate::stakes::{Stakes, StakesProvider};
-    use crate::{bond, step, unbond, BOND_DELAY, UNBOND_DELAY};
+    use crate::{
+        bond, delegate_to, slash_stakes, step, unbond, undelegate_from, BOND_DELAY,
+        MAX_DELEGATORS_PER_VALIDATOR, MIN_DELEGATION_AMOUNT, UNBOND_DELAY,
+    };
 
     const KEY1: [u8; 32] = [1; 32];
     const KEY2: [u8; 32] = [2; 32];
+    const KEY3: [u8; 32] = [3; 32];
 
     thread_local! {
         static BONDING: RefCell<Queue> = RefCell::new(Queue(Default::default()));
         static UNBONDING: RefCell<Queue> = RefCell::new(Queue(Default::default()));
+        static WITHDRAWAL: RefCell<WithdrawalQueue> = RefCell::new(Default::default());
         static STAKES: RefCell<Stakes> = RefCell::new(
             Stakes(iter::once((PublicKey::new(KEY1), U512::from(1_000))).collect())
         );
+        static DELEGATIONS: RefCell<Delegations> = RefCell::new(Default::default());
     }
 
     struct TestQueues;
@@ -390,6 +702,10 @@ mod tests {
             UNBONDING.with(|ub| ub.borrow().clone())
         }
 
+        fn read_withdrawal() -> WithdrawalQueue {
+            WITHDRAWAL.with(|w| w.borrow().clone())
+        }
+
         fn write_bonding(queue: &Queue) {
             BONDING.with(|b| b.replace(queue.clone()));
         }
@@ -397,6 +713,10 @@ mod tests {
         fn write_unbonding(queue: &Queue) {
             UNBONDING.with(|ub| ub.replace(queue.clone()));
         }
+
+        fn write_withdrawal(queue: &WithdrawalQueue) {
+            WITHDRAWAL.with(|w| w.replace(queue.clone()));
+        }
     }
 
     struct TestStakes;
@@ -411,6 +731,18 @@ mod tests {
         }
     }
 
+    struct TestDelegations;
+
+    impl DelegationsProvider for TestDelegations {
+        fn read() -> Result<Delegations> {
+            DELEGATIONS.with(|d| Ok(d.borrow().clone()))
+        }
+
+        fn write(delegations: &Delegations) {
+            DELEGATIONS.with(|d| d.replace(delegations.clone()));
+        }
+    }
+
     fn assert_stakes(stakes: &[([u8; 32], usize)]) {
         let expected = Stakes(
             stakes
@@ -441,4 +773,175 @@ mod tests {
         step::<TestQueues, TestStakes>(BlockTime(2 + UNBOND_DELAY)).expect("step 3");
         assert_stakes(&[(KEY1, 500), (KEY2, 500)]);
     }
+
+    #[test]
+    fn test_delegate_step_undelegate() {
+        let validator = PublicKey::new(KEY1);
+        let delegator = PublicKey::new(KEY3);
+
+        assert_eq!(
+            Err(Error::NotBonded),
+            delegate_to::<TestStakes, TestDelegations>(
+                U512::from(100),
+                PublicKey::new(KEY2),
+                delegator
+            )
+        );
+        delegate_to::<TestStakes, TestDelegations>(U512::from(100), validator, delegator)
+            .expect("delegate to validator 1");
+
+        // The delegation doesn't change the validator's own bond.
+        assert_stakes(&[(KEY1, 1_000)]);
+        let delegations = TestDelegations::read().expect("read delegations");
+        assert_eq!(
+            U512::from(100),
+            delegations.delegation(&validator, &delegator)
+        );
+
+        undelegate_from::<TestQueues, TestDelegations>(
+            Some(U512::from(40)),
+            validator,
+            delegator,
+            BlockTime(3),
+        )
+        .expect("partly undelegate from validator 1");
+
+        // The delegation decreases immediately, the payout happens after the delay.
+        let delegations = TestDelegations::read().expect("read delegations");
+        assert_eq!(
+            U512::from(60),
+            delegations.delegation(&validator, &delegator)
+        );
+        // The withdrawal doesn't conflict with the validator's own unbonding request.
+        unbond::<TestQueues, TestStakes>(Some(U512::from(100)), validator, BlockTime(3))
+            .expect("partly unbond validator 1");
+        assert!(TestQueues::read_unbonding()
+            .0
+            .iter()
+            .all(|entry| entry.validator == validator));
+        assert_eq!(
+            Ok(vec![
+                (validator, U512::from(100)),
+                (delegator, U512::from(40))
+            ]),
+            step::<TestQueues, TestStakes>(BlockTime(3 + UNBOND_DELAY))
+        );
+        assert!(TestQueues::read_withdrawal().0.is_empty());
+        assert_stakes(&[(KEY1, 900)]);
+    }
+
+    #[test]
+    fn test_delegation_limits() {
+        let validator = PublicKey::new(KEY1);
+        let min_amount = U512::from(MIN_DELEGATION_AMOUNT);
+        let delegator = |i: usize| {
+            let mut key = [0xff; 32];
+            key[..8].copy_from_slice(&(i as u64).to_le_bytes());
+            PublicKey::new(key)
+        };
+
+        assert_eq!(
+            Err(Error::DelegationTooSmall),
+            delegate_to::<TestStakes, TestDelegations>(
+                min_amount - U512::one(),
+                validator,
+                PublicKey::new(KEY3)
+            )
+        );
+        for i in 0..MAX_DELEGATORS_PER_VALIDATOR {
+            delegate_to::<TestStakes, TestDelegations>(min_amount, validator, delegator(i))
+                .expect("delegate to validator 1");
+        }
+
+        // Further delegators are rejected, but existing ones can still add to their delegations.
+        assert_eq!(
+            Err(Error::TooManyDelegators),
+            delegate_to::<TestStakes, TestDelegations>(min_amount, validator, PublicKey::new(KEY3))
+        );
+        delegate_to::<TestStakes, TestDelegations>(min_amount, validator, delegator(0))
+            .expect("add to delegation");
+        let delegations = TestDelegations::read().expect("read delegations");
+        assert_eq!(
+            MAX_DELEGATORS_PER_VALIDATOR,
+            delegations.delegator_count(&validator)
+        );
+        assert_eq!(
+            min_amount * 2,
+            delegations.delegation(&validator, &delegator(0))
+        );
+    }
+
+    #[test]
+    fn test_bond_unbond_slash() {
+        let validator_1 = PublicKey::new(KEY1);
+        let validator_2 = PublicKey::new(KEY2);
+        bond::<TestQueues, TestStakes>(U512::from(500), validator_2, BlockTime(1))
+            .expect("bond validator 2");
+        step::<TestQueues, TestStakes>(BlockTime(1 + BOND_DELAY)).expect("step 1");
+        unbond::<TestQueues, TestStakes>(Some(U512::from(100)), validator_2, BlockTime(2))
+            .expect("partly unbond validator 2");
+        assert_stakes(&[(KEY1, 1_000), (KEY2, 400)]);
+        let delegator = PublicKey::new(KEY3);
+        delegate_to::<TestStakes, TestDelegations>(U512::from(200), validator_2, delegator)
+            .expect("delegate to validator 2");
+        undelegate_from::<TestQueues, TestDelegations>(
+            Some(U512::from(80)),
+            validator_2,
+            delegator,
+            BlockTime(2),
+        )
+        .expect("partly undelegate from validator 2");
+
+        let validators: BTreeSet<PublicKey> = iter::once(validator_2).collect();
+        assert_eq!(
+            Err(Error::InvalidSlashFraction),
+            slash_stakes::<TestQueues, TestStakes, TestDelegations>(&validators, 1_000_001)
+        );
+
+        // The stake, the delegation and the pending requests are all slashed.
+        assert_eq!(
+            Ok(U512::from(175)),
+            slash_stakes::<TestQueues, TestStakes, TestDelegations>(&validators, 250_000)
+        );
+        assert_stakes(&[(KEY1, 1_000), (KEY2, 300)]);
+        assert_eq!(
+            vec![QueueEntry {
+                validator: validator_2,
+                amount: U512::from(75),
+                timestamp: BlockTime(2),
+            }],
+            TestQueues::read_unbonding().0
+        );
+        let delegations = TestDelegations::read().expect("read delegations");
+        assert_eq!(
+            U512::from(90),
+            delegations.delegation(&validator_2, &delegator)
+        );
+        assert_eq!(
+            vec![WithdrawalEntry {
+                validator: validator_2,
+                delegator,
+                amount: U512::from(60),
+                timestamp: BlockTime(2),
+            }],
+            TestQueues::read_withdrawal().0
+        );
+
+        // Slashing the whole stake removes the validator, unless none would be left.
+        let validators: BTreeSet<PublicKey> = vec![validator_1, validator_2].into_iter().collect();
+        assert_eq!(
+            Err(Error::CannotSlashAllValidators),
+            slash_stakes::<TestQueues, TestStakes, TestDelegations>(&validators, 1_000_000)
+        );
+        assert_stakes(&[(KEY1, 1_000), (KEY2, 300)]);
+        let validators: BTreeSet<PublicKey> = iter::once(validator_2).collect();
+        assert_eq!(
+            Ok(U512::from(525)),
+            slash_stakes::<TestQueues, TestStakes, TestDelegations>(&validators, 1_000_000)
+        );
+        assert_stakes(&[(KEY1, 1_000)]);
+        assert!(TestQueues::read_unbonding().0.is_empty());
+        assert_eq!(Ok(Delegations::default()), TestDelegations::read());
+        assert!(TestQueues::read_withdrawal().0.is_empty());
+    }
 }
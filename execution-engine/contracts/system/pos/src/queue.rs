@@ -2,17 +2,18 @@ use alloc::vec::Vec;
 use core::convert::TryFrom;
 use core::result;
 
-use contract_ffi::bytesrepr::{self, FromBytes, ToBytes};
+use contract_ffi::bytesrepr::{self, FromBytes, ToBytes, Write, U64_SIZE};
 use contract_ffi::contract_api::storage;
-use contract_ffi::system_contracts::pos::{Error, Result};
+use contract_ffi::system_contracts::pos::{self, Error, Result};
 use contract_ffi::value::account::{BlockTime, PublicKey};
 use contract_ffi::value::{Value, U512};
 
 const BONDING_KEY: u8 = 1;
 const UNBONDING_KEY: u8 = 2;
+const WITHDRAWAL_KEY: u8 = 5;
 
 /// A pending entry in the bonding or unbonding queue.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, ToBytes, FromBytes)]
 pub struct QueueEntry {
     /// The validator who is bonding or unbonding.
     pub validator: PublicKey,
@@ -33,27 +34,17 @@ impl QueueEntry {
     }
 }
 
-impl FromBytes for QueueEntry {
-    fn from_bytes(bytes: &[u8]) -> result::Result<(Self, &[u8]), bytesrepr::Error> {
-        let (validator, bytes) = PublicKey::from_bytes(bytes)?;
-        let (amount, bytes) = U512::from_bytes(bytes)?;
-        let (timestamp, bytes) = BlockTime::from_bytes(bytes)?;
-        let entry = QueueEntry {
-            validator,
-            amount,
-            timestamp,
-        };
-        Ok((entry, bytes))
-    }
-}
-
-impl ToBytes for QueueEntry {
-    fn to_bytes(&self) -> result::Result<Vec<u8>, bytesrepr::Error> {
-        Ok((self.validator.to_bytes()?.into_iter())
-            .chain(self.amount.to_bytes()?)
-            .chain(self.timestamp.to_bytes()?)
-            .collect())
-    }
+/// A pending withdrawal of motes a delegator delegated to a validator.
+#[derive(Clone, Copy, Debug, PartialEq, ToBytes, FromBytes)]
+pub struct WithdrawalEntry {
+    /// The validator the motes were delegated to.
+    pub validator: PublicKey,
+    /// The delegator who is withdrawing the motes.
+    pub delegator: PublicKey,
+    /// The amount to pay out to the delegator.
+    pub amount: U512,
+    /// The timestamp when the request was made.
+    pub timestamp: BlockTime,
 }
 
 pub trait QueueProvider {
@@ -63,11 +54,17 @@ pub trait QueueProvider {
     /// Reads unbonding queue.
     fn read_unbonding() -> Queue;
 
+    /// Reads delegator withdrawal queue.
+    fn read_withdrawal() -> WithdrawalQueue;
+
     /// Writes bonding queue.
     fn write_bonding(queue: &Queue);
 
     /// Writes unbonding queue.
     fn write_unbonding(queue: &Queue);
+
+    /// Writes delegator withdrawal queue.
+    fn write_withdrawal(queue: &WithdrawalQueue);
 }
 
 /// A `QueueProvider` that reads and writes the queue to/from the contract's
@@ -89,6 +86,13 @@ impl QueueProvider for QueueLocal {
             .unwrap_or_default()
     }
 
+    /// Reads delegator withdrawal queue from the local state of the contract.
+    fn read_withdrawal() -> WithdrawalQueue {
+        storage::read_local(WITHDRAWAL_KEY)
+            .unwrap_or_default()
+            .unwrap_or_default()
+    }
+
     /// Writes bonding queue to the local state of the contract.
     fn write_bonding(queue: &Queue) {
         storage::write_local(BONDING_KEY, queue);
@@ -98,6 +102,11 @@ impl QueueProvider for QueueLocal {
     fn write_unbonding(queue: &Queue) {
         storage::write_local(UNBONDING_KEY, queue);
     }
+
+    /// Writes delegator withdrawal queue to the local state of the contract.
+    fn write_withdrawal(queue: &WithdrawalQueue) {
+        storage::write_local(WITHDRAWAL_KEY, queue);
+    }
 }
 
 /// A queue of bonding or unbonding requests, sorted by timestamp in ascending
@@ -131,6 +140,94 @@ impl Queue {
         self.0 = rest;
         older_than
     }
+
+    /// Removes `fraction` millionths of the amount of the validator's entries.
+    /// Entries with nothing left are removed.
+    ///
+    /// Returns the total amount that was removed.
+    pub fn slash(&mut self, validator: &PublicKey, fraction: u32) -> U512 {
+        let mut total = U512::zero();
+        for entry in self
+            .0
+            .iter_mut()
+            .filter(|entry| entry.validator == *validator)
+        {
+            let amount = pos::share_of(entry.amount, fraction);
+            entry.amount -= amount;
+            total += amount;
+        }
+        self.0.retain(|entry| !entry.amount.is_zero());
+        total
+    }
+}
+
+/// A queue of delegator withdrawal requests, sorted by timestamp in ascending
+/// order. Unlike validators' unbonding requests, they are identified by both
+/// the validator and the delegator.
+#[derive(Clone, Default, ToBytes, FromBytes)]
+pub struct WithdrawalQueue(pub Vec<WithdrawalEntry>);
+
+impl WithdrawalQueue {
+    /// Pushes a new entry to the end of the queue.
+    ///
+    /// Returns an error if the delegator already has a request to withdraw
+    /// motes delegated to the validator in the queue.
+    pub fn push(
+        &mut self,
+        validator: PublicKey,
+        delegator: PublicKey,
+        amount: U512,
+        timestamp: BlockTime,
+    ) -> Result<()> {
+        if self
+            .0
+            .iter()
+            .any(|entry| entry.validator == validator && entry.delegator == delegator)
+        {
+            return Err(Error::MultipleRequests);
+        }
+        if let Some(entry) = self.0.last() {
+            if entry.timestamp > timestamp {
+                return Err(Error::TimeWentBackwards);
+            }
+        }
+        self.0.push(WithdrawalEntry {
+            validator,
+            delegator,
+            amount,
+            timestamp,
+        });
+        Ok(())
+    }
+
+    /// Returns all queue entries at least as old as the specified timestamp.
+    pub fn pop_due(&mut self, timestamp: BlockTime) -> Vec<WithdrawalEntry> {
+        let (older_than, rest) = self
+            .0
+            .iter()
+            .partition(|entry| entry.timestamp <= timestamp);
+        self.0 = rest;
+        older_than
+    }
+
+    /// Removes `fraction` millionths of the amount of the entries withdrawing
+    /// motes delegated to the validator. Entries with nothing left are removed.
+    ///
+    /// Returns the total amount that was removed.
+    pub fn slash(&mut self, validator: &PublicKey, fraction: u32) -> U512 {
+        let mut total = U512::zero();
+        for entry in self
+            .0
+            .iter_mut()
+            .filter(|entry| entry.validator == *validator)
+        {
+            let amount = pos::share_of(entry.amount, fraction);
+            entry.amount -= amount;
+            total += amount;
+        }
+        self.0.retain(|entry| !entry.amount.is_zero());
+        total
+    }
 }
 
 impl TryFrom<Value> for Queue {
@@ -156,6 +253,31 @@ impl Into<Value> for &Queue {
     }
 }
 
+impl TryFrom<Value> for WithdrawalQueue {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        let bytes = match value {
+            Value::ByteArray(bytes) => bytes,
+            _ => return Err(Error::QueueNotStoredAsByteArray),
+        };
+        let (queue, rest) =
+            WithdrawalQueue::from_bytes(&bytes).map_err(|_| Error::QueueDeserializationFailed)?;
+        if !rest.is_empty() {
+            return Err(Error::QueueDeserializationExtraBytes);
+        }
+        Ok(queue)
+    }
+}
+
+impl Into<Value> for &WithdrawalQueue {
+    fn into(self) -> Value {
+        Value::ByteArray(self.to_bytes().expect("Serialization cannot fail"))
+    }
+}
+
+// The queue's length prefix is a `u64`, unlike the `u32` of `Vec`, so that queues already in
+// global state keep deserializing.
 impl FromBytes for Queue {
     fn from_bytes(bytes: &[u8]) -> result::Result<(Self, &[u8]), bytesrepr::Error> {
         let (len, mut bytes) = u64::from_bytes(bytes)?;
@@ -171,21 +293,33 @@ impl FromBytes for Queue {
 
 impl ToBytes for Queue {
     fn to_bytes(&self) -> result::Result<Vec<u8>, bytesrepr::Error> {
-        let mut bytes = (self.0.len() as u64).to_bytes()?; // TODO: Allocate correct capacity.
+        bytesrepr::to_vec(self)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U64_SIZE + self.0.iter().map(ToBytes::serialized_length).sum::<usize>()
+    }
+
+    fn write_bytes<W: Write + ?Sized>(
+        &self,
+        writer: &mut W,
+    ) -> result::Result<(), bytesrepr::Error> {
+        (self.0.len() as u64).write_bytes(writer)?;
         for entry in &self.0 {
-            bytes.extend(entry.to_bytes()?);
+            entry.write_bytes(writer)?;
         }
-        Ok(bytes)
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use contract_ffi::bytesrepr::{self, ToBytes};
     use contract_ffi::system_contracts::pos::Error;
     use contract_ffi::value::account::{BlockTime, PublicKey};
     use contract_ffi::value::U512;
 
-    use crate::queue::{Queue, QueueEntry};
+    use crate::queue::{Queue, QueueEntry, WithdrawalEntry, WithdrawalQueue};
 
     const KEY1: [u8; 32] = [1; 32];
     const KEY2: [u8; 32] = [2; 32];
@@ -230,4 +364,73 @@ mod tests {
             queue.pop_due(BlockTime(105))
         );
     }
+
+    #[test]
+    fn test_push_withdrawal() {
+        let val1 = PublicKey::new(KEY1);
+        let val2 = PublicKey::new(KEY2);
+        let del = PublicKey::new(KEY3);
+        let mut queue: WithdrawalQueue = Default::default();
+        assert_eq!(Ok(()), queue.push(val1, del, U512::from(5), BlockTime(100)));
+        // The same delegator can withdraw from another validator, and a validator
+        // can withdraw motes they delegated.
+        assert_eq!(Ok(()), queue.push(val2, del, U512::from(6), BlockTime(101)));
+        assert_eq!(
+            Ok(()),
+            queue.push(val2, val1, U512::from(7), BlockTime(101))
+        );
+        assert_eq!(
+            Err(Error::MultipleRequests),
+            queue.push(val1, del, U512::from(5), BlockTime(102))
+        );
+        assert_eq!(
+            Err(Error::TimeWentBackwards),
+            queue.push(val1, val2, U512::from(5), BlockTime(100))
+        );
+        assert_eq!(
+            vec![WithdrawalEntry {
+                validator: val1,
+                delegator: del,
+                amount: U512::from(5),
+                timestamp: BlockTime(100),
+            }],
+            queue.pop_due(BlockTime(100))
+        );
+    }
+
+    #[test]
+    fn test_serialization() {
+        let mut queue: Queue = Default::default();
+        assert_eq!(
+            Ok(()),
+            queue.push(PublicKey::new(KEY1), U512::from(5), BlockTime(100))
+        );
+        assert_eq!(
+            Ok(()),
+            queue.push(PublicKey::new(KEY2), U512::from(6), BlockTime(101))
+        );
+        let bytes = queue.to_bytes().expect("should serialize");
+        assert_eq!(bytes.len(), queue.serialized_length());
+        // The length prefix is a little-endian u64
+        assert_eq!(&bytes[..8], &2u64.to_le_bytes());
+        let deserialized: Queue = bytesrepr::deserialize(&bytes).expect("should deserialize");
+        assert_eq!(deserialized.0, queue.0);
+    }
+
+    #[test]
+    fn test_slash() {
+        let val1 = PublicKey::new(KEY1);
+        let val2 = PublicKey::new(KEY2);
+        let val3 = PublicKey::new(KEY3);
+        let mut queue: Queue = Default::default();
+        assert_eq!(Ok(()), queue.push(val1, U512::from(10), BlockTime(100)));
+        assert_eq!(Ok(()), queue.push(val2, U512::from(6), BlockTime(101)));
+        assert_eq!(U512::from(5), queue.slash(&val1, 500_000));
+        assert_eq!(U512::from(6), queue.slash(&val2, 1_000_000));
+        assert_eq!(U512::zero(), queue.slash(&val3, 1_000_000));
+        assert_eq!(
+            vec![QueueEntry::new(val1, U512::from(5), BlockTime(100))],
+            queue.0
+        );
+    }
 }
@@ -4,7 +4,7 @@ use core::fmt::Write;
 
 use contract_ffi::contract_api::runtime;
 use contract_ffi::key::Key;
-use contract_ffi::system_contracts::pos::{Error, Result};
+use contract_ffi::system_contracts::pos::{self, Error, Result};
 use contract_ffi::value::{account::PublicKey, U512};
 
 use super::{MAX_DECREASE, MAX_INCREASE, MAX_REL_DECREASE, MAX_REL_INCREASE, MAX_SPREAD};
@@ -141,6 +141,24 @@ impl Stakes {
             .or_insert(amount);
     }
 
+    /// Removes `fraction` millionths of the validator's stakes. If nothing is
+    /// left, the validator is removed.
+    ///
+    /// Returns the amount that was removed, which is zero if the validator
+    /// was not bonded.
+    pub fn slash(&mut self, validator: &PublicKey, fraction: u32) -> U512 {
+        let stake = match self.0.get_mut(validator) {
+            Some(stake) => stake,
+            None => return U512::zero(),
+        };
+        let amount = pos::share_of(*stake, fraction);
+        *stake -= amount;
+        if stake.is_zero() {
+            self.0.remove(validator);
+        }
+        amount
+    }
+
     /// Returns an error if bonding the specified amount is not allowed.
     pub fn validate_bonding(&self, validator: &PublicKey, amount: U512) -> Result<()> {
         let max = self
@@ -305,4 +323,20 @@ mod tests {
             "Failed to unbond the maximum amount."
         );
     }
+
+    #[test]
+    fn test_slash() {
+        let mut stakes = new_stakes(&[(KEY1, 999), (KEY2, 100)]);
+        assert_eq!(
+            U512::from(499),
+            stakes.slash(&PublicKey::new(KEY1), 500_000)
+        );
+        assert_eq!(new_stakes(&[(KEY1, 500), (KEY2, 100)]), stakes);
+        assert_eq!(
+            U512::from(100),
+            stakes.slash(&PublicKey::new(KEY2), 1_000_000)
+        );
+        assert_eq!(new_stakes(&[(KEY1, 500)]), stakes);
+        assert_eq!(U512::zero(), stakes.slash(&PublicKey::new(KEY2), 500_000));
+    }
 }
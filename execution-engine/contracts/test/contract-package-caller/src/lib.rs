@@ -0,0 +1,73 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use contract_ffi::contract_api::{runtime, storage, ContractRef, Error, TURef};
+use contract_ffi::key::Key;
+use contract_ffi::named_args::NamedArgs;
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::value::{ContractPackage, ContractVersion};
+
+const PACKAGE_NAME: &str = "contract_package";
+
+#[repr(u16)]
+enum CustomError {
+    UnexpectedVersion = 0,
+    UnknownAction = 1,
+}
+
+fn check_version(actual: ContractVersion) {
+    let expected: ContractVersion = runtime::get_named_arg("expected");
+    if actual != expected {
+        runtime::revert(Error::User(CustomError::UnexpectedVersion as u16))
+    }
+}
+
+fn call_version(package: TURef<ContractPackage>, version: ContractVersion) {
+    check_version(runtime::call_versioned_contract(
+        package,
+        version,
+        "get_version",
+        &NamedArgs::new(),
+        &Vec::new(),
+    ))
+}
+
+/// Calls the package itself, which runs its latest version.
+fn call_package(package: TURef<ContractPackage>) {
+    let contract = TURef::from_uref(package.into()).unwrap_or_revert();
+    check_version(runtime::call_entry_point(
+        ContractRef::TURef(contract),
+        "get_version",
+        &NamedArgs::new(),
+        &Vec::new(),
+    ))
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let package: TURef<ContractPackage> =
+        match runtime::get_key(PACKAGE_NAME).unwrap_or_revert_with(Error::GetKey) {
+            Key::URef(uref) => TURef::from_uref(uref).unwrap_or_revert(),
+            _ => runtime::revert(Error::UnexpectedKeyVariant),
+        };
+
+    let action: String = runtime::get_named_arg("action");
+    match action.as_str() {
+        "call" => call_version(package, runtime::get_named_arg("version")),
+        "call_latest" => call_package(package),
+        "disable" => storage::disable_contract_version(package, runtime::get_named_arg("version"))
+            .unwrap_or_revert(),
+        "enable" => storage::enable_contract_version(package, runtime::get_named_arg("version"))
+            .unwrap_or_revert(),
+        "set_latest" => {
+            storage::set_latest_contract_version(package, runtime::get_named_arg("version"))
+                .unwrap_or_revert()
+        }
+        _ => runtime::revert(Error::User(CustomError::UnknownAction as u16)),
+    }
+}
@@ -0,0 +1,36 @@
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+use contract_ffi::contract_api::{runtime, storage};
+use contract_ffi::value::{ContractVersion, EntryPoint, EntryPointAccess, EntryPoints};
+
+const PACKAGE_NAME: &str = "contract_package";
+const VERSION: ContractVersion = 1;
+
+#[no_mangle]
+pub extern "C" fn get_version() {
+    runtime::ret(VERSION, vec![])
+}
+
+fn entry_points() -> EntryPoints {
+    let mut entry_points = BTreeMap::new();
+    entry_points.insert(
+        String::from("get_version"),
+        EntryPoint::new(vec![], EntryPointAccess::Public),
+    );
+    entry_points
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let package = storage::new_contract_package();
+    let version = storage::add_contract_version(package.clone(), entry_points(), BTreeMap::new());
+    assert_eq!(version, VERSION);
+    runtime::put_key(PACKAGE_NAME, &package.into());
+}
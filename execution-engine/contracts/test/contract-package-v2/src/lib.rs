@@ -0,0 +1,40 @@
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+use contract_ffi::contract_api::{runtime, storage, Error, TURef};
+use contract_ffi::key::Key;
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::value::{ContractVersion, EntryPoint, EntryPointAccess, EntryPoints};
+
+const PACKAGE_NAME: &str = "contract_package";
+const VERSION: ContractVersion = 2;
+
+#[no_mangle]
+pub extern "C" fn get_version() {
+    runtime::ret(VERSION, vec![])
+}
+
+fn entry_points() -> EntryPoints {
+    let mut entry_points = BTreeMap::new();
+    entry_points.insert(
+        String::from("get_version"),
+        EntryPoint::new(vec![], EntryPointAccess::Public),
+    );
+    entry_points
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let package = match runtime::get_key(PACKAGE_NAME).unwrap_or_revert_with(Error::GetKey) {
+        Key::URef(uref) => TURef::from_uref(uref).unwrap_or_revert(),
+        _ => runtime::revert(Error::UnexpectedKeyVariant),
+    };
+    let version = storage::add_contract_version(package, entry_points(), BTreeMap::new());
+    assert_eq!(version, VERSION);
+}
@@ -0,0 +1,69 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::vec::Vec;
+
+use contract_ffi::contract_api::{runtime, Error};
+use contract_ffi::crypto::{HashAlgorithm, SignatureAlgorithm};
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+
+#[repr(u16)]
+enum CustomError {
+    Blake2b256Mismatch = 0,
+    Sha256Mismatch,
+    Keccak256Mismatch,
+    ValidSignatureRejected,
+    InvalidSignatureAccepted,
+}
+
+fn get_bytes_arg(index: u32) -> Vec<u8> {
+    runtime::get_arg(index)
+        .unwrap_or_revert_with(Error::MissingArgument)
+        .unwrap_or_revert_with(Error::InvalidArgument)
+}
+
+fn check(condition: bool, error: CustomError) {
+    if !condition {
+        runtime::revert(Error::User(error as u16));
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let data = get_bytes_arg(0);
+    let blake2b_256 = get_bytes_arg(1);
+    let sha256 = get_bytes_arg(2);
+    let keccak256 = get_bytes_arg(3);
+    let public_key = get_bytes_arg(4);
+    let message = get_bytes_arg(5);
+    let signature = get_bytes_arg(6);
+
+    check(
+        runtime::hash(HashAlgorithm::Blake2b256, &data)[..] == blake2b_256[..],
+        CustomError::Blake2b256Mismatch,
+    );
+    check(
+        runtime::hash(HashAlgorithm::Sha256, &data)[..] == sha256[..],
+        CustomError::Sha256Mismatch,
+    );
+    check(
+        runtime::hash(HashAlgorithm::Keccak256, &data)[..] == keccak256[..],
+        CustomError::Keccak256Mismatch,
+    );
+
+    check(
+        runtime::verify_signature(
+            SignatureAlgorithm::Ed25519,
+            &public_key,
+            &message,
+            &signature,
+        ),
+        CustomError::ValidSignatureRejected,
+    );
+    check(
+        !runtime::verify_signature(SignatureAlgorithm::Ed25519, &public_key, &data, &signature),
+        CustomError::InvalidSignatureAccepted,
+    );
+}
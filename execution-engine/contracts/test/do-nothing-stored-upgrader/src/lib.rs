@@ -1,12 +1,15 @@
 #![no_std]
 
+extern crate alloc;
 extern crate contract_ffi;
 extern crate create_purse_01;
 
-use contract_ffi::contract_api::{runtime, Error, TURef};
+use alloc::collections::BTreeMap;
+
+use contract_ffi::contract_api::{runtime, storage, Error, TURef};
 use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
 use contract_ffi::uref::URef;
-use contract_ffi::value::Contract;
+use contract_ffi::value::ContractPackage;
 
 const ENTRY_FUNCTION_NAME: &str = "delegate";
 
@@ -26,8 +29,8 @@ pub extern "C" fn call() {
         .unwrap_or_revert_with(Error::MissingArgument)
         .unwrap_or_revert_with(Error::InvalidArgument);
 
-    let turef: TURef<Contract> = TURef::from_uref(uref).unwrap_or_revert();
+    let package: TURef<ContractPackage> = TURef::from_uref(uref).unwrap_or_revert();
 
-    // this should overwrite the previous contract obj with the new contract obj at the same uref
-    runtime::upgrade_contract_at_uref(ENTRY_FUNCTION_NAME, turef);
+    // the new version becomes the latest version of the package under the same uref
+    storage::add_function_version(package, ENTRY_FUNCTION_NAME, BTreeMap::new());
 }
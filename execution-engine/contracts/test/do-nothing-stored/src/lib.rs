@@ -5,8 +5,7 @@ extern crate contract_ffi;
 
 use alloc::collections::BTreeMap;
 
-use contract_ffi::contract_api::{runtime, storage, Error};
-use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::contract_api::{runtime, storage};
 
 const ENTRY_FUNCTION_NAME: &str = "delegate";
 const CONTRACT_NAME: &str = "do_nothing_stored";
@@ -16,10 +15,8 @@ pub extern "C" fn delegate() {}
 
 #[no_mangle]
 pub extern "C" fn call() {
-    let key = storage::store_function(ENTRY_FUNCTION_NAME, BTreeMap::new())
-        .into_turef()
-        .unwrap_or_revert_with(Error::UnexpectedContractRefVariant)
-        .into();
+    let package = storage::new_contract_package();
+    storage::add_function_version(package.clone(), ENTRY_FUNCTION_NAME, BTreeMap::new());
 
-    runtime::put_key(CONTRACT_NAME, &key);
+    runtime::put_key(CONTRACT_NAME, &package.into());
 }
@@ -0,0 +1,32 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::string::String;
+
+use contract_ffi::contract_api::{runtime, Error};
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::value::U512;
+
+#[repr(u16)]
+enum CustomError {
+    Reverted = 0,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U512 = runtime::get_arg(0)
+        .unwrap_or_revert_with(Error::MissingArgument)
+        .unwrap_or_revert_with(Error::InvalidArgument);
+    let should_revert: bool = runtime::get_arg(1)
+        .unwrap_or_revert_with(Error::MissingArgument)
+        .unwrap_or_revert_with(Error::InvalidArgument);
+
+    runtime::emit("transfer", amount);
+    runtime::emit("memo", String::from("Hello, world!"));
+
+    if should_revert {
+        runtime::revert(Error::User(CustomError::Reverted as u16));
+    }
+}
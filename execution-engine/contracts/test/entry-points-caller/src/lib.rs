@@ -0,0 +1,30 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use contract_ffi::contract_api::{runtime, Error};
+use contract_ffi::named_args::NamedArgs;
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+
+const CONTRACT_NAME: &str = "entry_points";
+const ENTRY_POINT_NAME: &str = "check_name";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_ref = runtime::get_key(CONTRACT_NAME)
+        .unwrap_or_revert_with(Error::GetKey)
+        .to_c_ptr()
+        .unwrap_or_revert_with(Error::UnexpectedKeyVariant);
+
+    let name: String = runtime::get_named_arg("name");
+    let mut named_args = NamedArgs::new();
+    named_args
+        .insert("name", name)
+        .unwrap_or_revert_with(Error::InvalidArgument);
+
+    runtime::call_entry_point::<()>(contract_ref, ENTRY_POINT_NAME, &named_args, &Vec::new());
+}
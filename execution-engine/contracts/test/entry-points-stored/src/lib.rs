@@ -0,0 +1,50 @@
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+use contract_ffi::contract_api::{runtime, storage, Error};
+use contract_ffi::value::{CLType, EntryPoint, EntryPointAccess, Parameter};
+
+const CONTRACT_NAME: &str = "entry_points";
+const EXPECTED_NAME: &str = "Alice";
+
+#[repr(u16)]
+enum CustomError {
+    UnexpectedName = 0,
+}
+
+#[no_mangle]
+pub extern "C" fn check_name() {
+    let name: String = runtime::get_named_arg("name");
+    if name != EXPECTED_NAME {
+        runtime::revert(Error::User(CustomError::UnexpectedName as u16))
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn system_only() {
+    runtime::ret(String::from(CONTRACT_NAME), vec![])
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut entry_points = BTreeMap::new();
+    entry_points.insert(
+        String::from("check_name"),
+        EntryPoint::new(
+            vec![Parameter::new("name", CLType::String)],
+            EntryPointAccess::Public,
+        ),
+    );
+    entry_points.insert(
+        String::from("system_only"),
+        EntryPoint::new(vec![], EntryPointAccess::System),
+    );
+    let contract_ref = storage::store_contract_at_hash(entry_points, BTreeMap::new());
+    runtime::put_key(CONTRACT_NAME, &contract_ref.into());
+}
@@ -0,0 +1,18 @@
+#![no_std]
+
+extern crate alloc;
+extern crate contract_ffi;
+
+use alloc::string::String;
+
+use contract_ffi::contract_api::runtime;
+use contract_ffi::value::U512;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let name: String = runtime::get_named_arg("name");
+    assert_eq!(name, "Hello, world!");
+
+    let amount: U512 = runtime::get_named_arg("amount");
+    assert_eq!(amount, U512::from(42));
+}
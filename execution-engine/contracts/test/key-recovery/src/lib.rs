@@ -0,0 +1,38 @@
+#![no_std]
+
+extern crate alloc;
+
+extern crate contract_ffi;
+
+use alloc::string::String;
+
+use contract_ffi::contract_api::{account, runtime, Error};
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::value::account::{ActionType, PublicKey, Weight, MIN_INACTIVITY_PERIOD_TIME};
+
+const RECOVERY_KEY: [u8; 32] = [42; 32];
+const NEW_KEY: [u8; 32] = [43; 32];
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let stage: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(Error::MissingArgument)
+        .unwrap_or_revert_with(Error::InvalidArgument);
+
+    if stage == "init" {
+        // executed by the account owner with weight 1
+        account::add_associated_key(PublicKey::new(RECOVERY_KEY), Weight::new(1))
+            .unwrap_or_revert();
+        account::update_associated_key(runtime::get_caller(), Weight::new(2)).unwrap_or_revert();
+        account::set_action_threshold(ActionType::KeyManagement, Weight::new(2)).unwrap_or_revert();
+        account::set_action_threshold(ActionType::KeyRecovery, Weight::new(1)).unwrap_or_revert();
+        account::set_inactivity_period(MIN_INACTIVITY_PERIOD_TIME).unwrap_or_revert();
+    } else if stage == "activity" {
+        // executed by the account owner, only to use the account
+    } else if stage == "recover" {
+        // executed by the recovery key only
+        account::add_associated_key(PublicKey::new(NEW_KEY), Weight::new(2)).unwrap_or_revert();
+    } else {
+        runtime::revert(Error::User(1))
+    }
+}
@@ -3,8 +3,7 @@
 extern crate contract_ffi;
 extern crate local_state;
 
-use contract_ffi::contract_api::{runtime, storage, Error};
-use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::contract_api::{runtime, storage};
 
 const ENTRY_FUNCTION_NAME: &str = "delegate";
 const CONTRACT_NAME: &str = "local_state_stored";
@@ -16,10 +15,8 @@ pub extern "C" fn delegate() {
 
 #[no_mangle]
 pub extern "C" fn call() {
-    let key = storage::store_function(ENTRY_FUNCTION_NAME, Default::default())
-        .into_turef()
-        .unwrap_or_revert_with(Error::UnexpectedContractRefVariant)
-        .into();
+    let package = storage::new_contract_package();
+    storage::add_function_version(package.clone(), ENTRY_FUNCTION_NAME, Default::default());
 
-    runtime::put_key(CONTRACT_NAME, &key);
+    runtime::put_key(CONTRACT_NAME, &package.into());
 }
@@ -1,9 +1,14 @@
 #![no_std]
 
+extern crate alloc;
 extern crate contract_ffi;
 extern crate modified_mint;
 
-use contract_ffi::contract_api::{runtime, system, ContractRef, Error};
+use alloc::collections::BTreeMap;
+
+use contract_ffi::contract_api::{runtime, storage, system, ContractRef, Error, TURef};
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::value::ContractPackage;
 
 #[repr(u16)]
 enum CustomError {
@@ -28,5 +33,9 @@ pub extern "C" fn call() {
         ContractRef::TURef(turef) => turef,
     };
 
-    runtime::upgrade_contract_at_uref(EXT_FUNCTION_NAME, mint_turef);
+    // the mint contract becomes version 1 of a package under the same uref, so the modified mint
+    // keeps its named keys and local state
+    let mint_package: TURef<ContractPackage> =
+        TURef::from_uref(mint_turef.into()).unwrap_or_revert();
+    storage::add_function_version(mint_package, EXT_FUNCTION_NAME, BTreeMap::new());
 }
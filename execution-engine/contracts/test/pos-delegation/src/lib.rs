@@ -0,0 +1,64 @@
+#![no_std]
+
+extern crate alloc;
+
+extern crate contract_ffi;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use contract_ffi::contract_api::{account, runtime, system, Error as ApiError};
+use contract_ffi::system_contracts::pos::RewardsConfig;
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+
+const TEST_DELEGATE: &str = "delegate";
+const TEST_UNDELEGATE: &str = "undelegate";
+const TEST_DISTRIBUTE_REWARDS: &str = "distribute_rewards";
+
+#[repr(u16)]
+enum Error {
+    UnexpectedDelegation = 0,
+    UnknownCommand,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let command: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    if command == TEST_DELEGATE {
+        let validator: PublicKey = runtime::get_arg(1)
+            .unwrap_or_revert_with(ApiError::MissingArgument)
+            .unwrap_or_revert_with(ApiError::InvalidArgument);
+        let amount: U512 = runtime::get_arg(2)
+            .unwrap_or_revert_with(ApiError::MissingArgument)
+            .unwrap_or_revert_with(ApiError::InvalidArgument);
+        let delegator = runtime::get_caller();
+        let delegation_before = system::get_delegation(validator, delegator);
+
+        system::delegate(validator, amount, account::get_main_purse());
+
+        if system::get_delegation(validator, delegator) != delegation_before + amount {
+            runtime::revert(ApiError::User(Error::UnexpectedDelegation as u16));
+        }
+    } else if command == TEST_UNDELEGATE {
+        let validator: PublicKey = runtime::get_arg(1)
+            .unwrap_or_revert_with(ApiError::MissingArgument)
+            .unwrap_or_revert_with(ApiError::InvalidArgument);
+        let maybe_amount: Option<U512> = runtime::get_arg(2)
+            .unwrap_or_revert_with(ApiError::MissingArgument)
+            .unwrap_or_revert_with(ApiError::InvalidArgument);
+        system::undelegate(validator, maybe_amount);
+    } else if command == TEST_DISTRIBUTE_REWARDS {
+        runtime::call_contract::<_, ()>(
+            system::get_proof_of_stake(),
+            &(TEST_DISTRIBUTE_REWARDS, RewardsConfig::default()),
+            &Vec::new(),
+        );
+    } else {
+        runtime::revert(ApiError::User(Error::UnknownCommand as u16));
+    }
+}
@@ -4,12 +4,14 @@
 extern crate alloc;
 extern crate contract_ffi;
 
+use alloc::collections::BTreeMap;
 use alloc::string::{String, ToString};
 
 use contract_ffi::contract_api::TURef;
 use contract_ffi::contract_api::{runtime, storage, system, Error};
 use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
 use contract_ffi::uref::URef;
+use contract_ffi::value::ContractPackage;
 
 const ENTRY_FUNCTION_NAME: &str = "apply_method";
 pub const METHOD_ADD: &str = "add";
@@ -77,10 +79,11 @@ pub extern "C" fn call() {
         .unwrap_or_revert_with(CustomError::MissingPurseHolderURefArg)
         .unwrap_or_revert_with(CustomError::InvalidPurseHolderURefArg);
 
-    let turef = TURef::from_uref(uref).unwrap_or_revert();
+    let package: TURef<ContractPackage> = TURef::from_uref(uref).unwrap_or_revert();
 
-    // this should overwrite the previous contract obj with the new contract obj at the same uref
-    runtime::upgrade_contract_at_uref(ENTRY_FUNCTION_NAME, turef);
+    // the new version becomes the latest version of the package under the same uref, and keeps
+    // the package's named keys
+    storage::add_function_version(package, ENTRY_FUNCTION_NAME, BTreeMap::new());
 
     // set new version
     let version_key = storage::new_turef(VERSION.to_string()).into();
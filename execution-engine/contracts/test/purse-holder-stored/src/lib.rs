@@ -57,12 +57,10 @@ pub extern "C" fn apply_method() {
 #[cfg(not(feature = "lib"))]
 #[no_mangle]
 pub extern "C" fn call() {
-    let key = storage::store_function(ENTRY_FUNCTION_NAME, BTreeMap::new())
-        .into_turef()
-        .unwrap_or_revert_with(Error::UnexpectedContractRefVariant)
-        .into();
+    let package = storage::new_contract_package();
+    storage::add_function_version(package.clone(), ENTRY_FUNCTION_NAME, BTreeMap::new());
 
-    runtime::put_key(CONTRACT_NAME, &key);
+    runtime::put_key(CONTRACT_NAME, &package.into());
 
     // set version
     let version_key = storage::new_turef(VERSION.to_string()).into();
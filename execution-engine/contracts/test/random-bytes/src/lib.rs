@@ -0,0 +1,47 @@
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+
+extern crate contract_ffi;
+
+use alloc::collections::btree_map::BTreeMap;
+use alloc::string::String;
+
+use contract_ffi::contract_api::{runtime, storage, ContractRef, Error};
+use contract_ffi::key::Key;
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::uref::URef;
+use contract_ffi::value::U512;
+
+fn random_key() -> Key {
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&runtime::random_bytes(32));
+    Key::Hash(hash)
+}
+
+#[no_mangle]
+pub extern "C" fn random_in_subcall() {
+    runtime::ret(random_key(), vec![])
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let flag: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(Error::MissingArgument)
+        .unwrap_or_revert_with(Error::InvalidArgument);
+    let random_in_subcall: ContractRef =
+        storage::store_function_at_hash("random_in_subcall", BTreeMap::new());
+
+    let uref1: URef = storage::new_turef(U512::from(0)).into();
+    runtime::put_key("uref1", &Key::URef(uref1));
+    if flag == "random" {
+        // Drawing random bytes, directly or in a subcall, SHOULD not forward the internal RNG
+        // used for URef addresses.
+        runtime::put_key("random0", &random_key());
+        let random1: Key = runtime::call_contract(random_in_subcall, &(), &vec![]);
+        runtime::put_key("random1", &random1);
+    }
+    let uref2: URef = storage::new_turef(U512::from(1)).into();
+    runtime::put_key("uref2", &Key::URef(uref2));
+}
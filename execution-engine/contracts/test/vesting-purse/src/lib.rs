@@ -0,0 +1,78 @@
+#![no_std]
+
+extern crate alloc;
+
+extern crate contract_ffi;
+
+use alloc::string::String;
+
+use contract_ffi::contract_api::{account, runtime, system, Error as ApiError};
+use contract_ffi::key::Key;
+use contract_ffi::unwrap_or_revert::UnwrapOrRevert;
+use contract_ffi::value::account::{BlockTime, PurseId};
+use contract_ffi::value::U512;
+
+const VESTING_PURSE_NAME: &str = "vesting_purse";
+const VESTING_AMOUNT: u64 = 1000;
+const CLIFF: BlockTime = BlockTime(100);
+const DURATION: BlockTime = BlockTime(1000);
+
+#[repr(u16)]
+enum Error {
+    UnexpectedLockedBalance = 0,
+    UnexpectedUnlockedBalance,
+    TransferredLockedFunds,
+    InvalidStage,
+}
+
+fn assert_balances(purse: PurseId, expected_locked: u64) {
+    if system::get_locked_balance(purse) != Some(U512::from(expected_locked)) {
+        runtime::revert(ApiError::User(Error::UnexpectedLockedBalance as u16));
+    }
+    let expected_unlocked = U512::from(VESTING_AMOUNT - expected_locked);
+    if system::get_unlocked_balance(purse) != Some(expected_unlocked) {
+        runtime::revert(ApiError::User(Error::UnexpectedUnlockedBalance as u16));
+    }
+}
+
+fn assert_transfer_rejected(purse: PurseId, amount: U512) {
+    let main_purse = account::get_main_purse();
+    if system::transfer_from_purse_to_purse(purse, main_purse, amount).is_ok() {
+        runtime::revert(ApiError::User(Error::TransferredLockedFunds as u16));
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let stage: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    if stage == "create" {
+        // executed at block time 0
+        let purse = system::create_vesting_purse(
+            account::get_main_purse(),
+            U512::from(VESTING_AMOUNT),
+            CLIFF,
+            DURATION,
+        )
+        .unwrap_or_revert();
+        runtime::put_key(VESTING_PURSE_NAME, &Key::URef(purse.value()));
+
+        assert_balances(purse, VESTING_AMOUNT);
+        assert_transfer_rejected(purse, U512::one());
+    } else if stage == "withdraw" {
+        // executed at block time 500
+        let purse = runtime::get_key(VESTING_PURSE_NAME)
+            .and_then(|key| key.as_uref().cloned())
+            .map(PurseId::new)
+            .unwrap_or_revert_with(ApiError::GetKey);
+
+        assert_balances(purse, 500);
+        assert_transfer_rejected(purse, U512::from(501));
+        system::transfer_from_purse_to_purse(purse, account::get_main_purse(), U512::from(500))
+            .unwrap_or_revert();
+    } else {
+        runtime::revert(ApiError::User(Error::InvalidStage as u16))
+    }
+}
@@ -0,0 +1,193 @@
+//! Detection of conflicts between the effects of deploys executed against the same prestate.
+//!
+//! Two [`ExecutionEffect`]s conflict if, for some key they both touch, their [`Op`]s on that key
+//! do not commute (see [`Op::commutes_with`]).  Effects which do not conflict can be committed in
+//! any order, or merged into a single effect, with the same result.
+
+use std::collections::BTreeSet;
+
+use contract_ffi::key::Key;
+
+use super::execution_effect::ExecutionEffect;
+use super::op::Op;
+
+/// A pair of conflicting effects, identified by their indices in the input slice, along with
+/// the keys on which they conflict.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Conflict {
+    pub first: usize,
+    pub second: usize,
+    pub keys: BTreeSet<Key>,
+}
+
+/// The result of [`detect_conflicts`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConflictReport {
+    /// All pairs of conflicting effects, ordered by `first` and then `second`.
+    pub conflicts: Vec<Conflict>,
+    /// Indices of a maximal set of effects which pairwise commute, in ascending order.
+    pub commutative_subset: Vec<usize>,
+}
+
+impl ConflictReport {
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+}
+
+/// Returns the keys on which `first` and `second` conflict.
+pub fn conflicting_keys(first: &ExecutionEffect, second: &ExecutionEffect) -> BTreeSet<Key> {
+    // Iterate over the smaller of the two maps
+    let (smaller, larger) = if first.ops.len() <= second.ops.len() {
+        (&first.ops, &second.ops)
+    } else {
+        (&second.ops, &first.ops)
+    };
+    smaller
+        .iter()
+        .filter_map(|(key, op)| match larger.get(key) {
+            Some(other_op) if !op.commutes_with(other_op) => Some(*key),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns `true` if `first` and `second` do not conflict on any key.
+pub fn commute(first: &ExecutionEffect, second: &ExecutionEffect) -> bool {
+    let (smaller, larger) = if first.ops.len() <= second.ops.len() {
+        (&first.ops, &second.ops)
+    } else {
+        (&second.ops, &first.ops)
+    };
+    smaller.iter().all(|(key, op)| {
+        larger
+            .get(key)
+            .map_or(true, |other_op: &Op| op.commutes_with(other_op))
+    })
+}
+
+/// Reports all pairs of conflicting effects in `effects`, along with a maximal subset of them
+/// which pairwise commute.
+///
+/// The commutative subset is chosen greedily in input order, so that earlier effects (e.g. those
+/// of deploys appearing earlier in a block) are preferred over later ones.  It is maximal, in
+/// that none of the excluded effects commutes with all of the included ones, but it is not
+/// necessarily the largest such subset.
+pub fn detect_conflicts(effects: &[ExecutionEffect]) -> ConflictReport {
+    let mut conflicts = Vec::new();
+    for (first, first_effect) in effects.iter().enumerate() {
+        for (second, second_effect) in effects.iter().enumerate().skip(first + 1) {
+            let keys = conflicting_keys(first_effect, second_effect);
+            if !keys.is_empty() {
+                conflicts.push(Conflict {
+                    first,
+                    second,
+                    keys,
+                });
+            }
+        }
+    }
+
+    let mut commutative_subset: Vec<usize> = Vec::new();
+    for index in 0..effects.len() {
+        let conflicts_with_included = conflicts.iter().any(|conflict| {
+            conflict.second == index && commutative_subset.binary_search(&conflict.first).is_ok()
+        });
+        if !conflicts_with_included {
+            commutative_subset.push(index);
+        }
+    }
+
+    ConflictReport {
+        conflicts,
+        commutative_subset,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeSet, HashMap};
+
+    use contract_ffi::key::Key;
+
+    use super::{commute, conflicting_keys, detect_conflicts, Conflict};
+    use crate::engine_state::execution_effect::ExecutionEffect;
+    use crate::engine_state::op::Op;
+
+    const KEY_1: Key = Key::Hash([1u8; 32]);
+    const KEY_2: Key = Key::Hash([2u8; 32]);
+    const KEY_3: Key = Key::Hash([3u8; 32]);
+
+    fn effect(ops: &[(Key, Op)]) -> ExecutionEffect {
+        let ops: HashMap<Key, Op> = ops.iter().cloned().collect();
+        ExecutionEffect::new(ops, HashMap::new())
+    }
+
+    #[test]
+    fn should_commute_reads_and_adds() {
+        let first = effect(&[(KEY_1, Op::Read), (KEY_2, Op::Add)]);
+        let second = effect(&[(KEY_1, Op::Read), (KEY_2, Op::Add), (KEY_3, Op::Write)]);
+        assert!(commute(&first, &second));
+        assert!(conflicting_keys(&first, &second).is_empty());
+    }
+
+    #[test]
+    fn should_detect_read_write_and_write_write_conflicts() {
+        let first = effect(&[(KEY_1, Op::Read), (KEY_2, Op::Write), (KEY_3, Op::Add)]);
+        let second = effect(&[(KEY_1, Op::Write), (KEY_2, Op::Write), (KEY_3, Op::Read)]);
+        assert!(!commute(&first, &second));
+        let expected: BTreeSet<Key> = vec![KEY_1, KEY_2, KEY_3].into_iter().collect();
+        assert_eq!(conflicting_keys(&first, &second), expected);
+        assert_eq!(conflicting_keys(&second, &first), expected);
+    }
+
+    #[test]
+    fn should_ignore_noops() {
+        let first = effect(&[(KEY_1, Op::NoOp)]);
+        let second = effect(&[(KEY_1, Op::Write)]);
+        assert!(commute(&first, &second));
+    }
+
+    #[test]
+    fn should_report_conflicting_pairs_and_maximal_commutative_subset() {
+        let effects = vec![
+            effect(&[(KEY_1, Op::Write)]),
+            effect(&[(KEY_1, Op::Read), (KEY_2, Op::Add)]),
+            effect(&[(KEY_2, Op::Add), (KEY_3, Op::Read)]),
+            effect(&[(KEY_3, Op::Write)]),
+        ];
+
+        let report = detect_conflicts(&effects);
+        assert!(report.has_conflicts());
+        assert_eq!(
+            report.conflicts,
+            vec![
+                Conflict {
+                    first: 0,
+                    second: 1,
+                    keys: vec![KEY_1].into_iter().collect(),
+                },
+                Conflict {
+                    first: 2,
+                    second: 3,
+                    keys: vec![KEY_3].into_iter().collect(),
+                },
+            ]
+        );
+        // 1 conflicts with 0, and 3 conflicts with 2
+        assert_eq!(report.commutative_subset, vec![0, 2]);
+    }
+
+    #[test]
+    fn should_include_all_effects_when_none_conflict() {
+        let effects = vec![
+            effect(&[(KEY_1, Op::Read)]),
+            effect(&[(KEY_1, Op::Read), (KEY_2, Op::Add)]),
+            effect(&[(KEY_2, Op::Add), (KEY_3, Op::Write)]),
+        ];
+
+        let report = detect_conflicts(&effects);
+        assert!(!report.has_conflicts());
+        assert_eq!(report.commutative_subset, vec![0, 1, 2]);
+    }
+}
@@ -0,0 +1,87 @@
+use std::fmt;
+
+use contract_ffi::key::Key;
+use contract_ffi::value::account::BlockTime;
+use contract_ffi::value::ProtocolVersion;
+use engine_shared::newtypes::Blake2bHash;
+use engine_shared::transform::TypeMismatch;
+use engine_storage::global_state::CommitResult;
+
+use crate::engine_state::execution_effect::ExecutionEffect;
+
+pub enum DistributeRewardsResult {
+    RootNotFound,
+    KeyNotFound(Key),
+    TypeMismatch(TypeMismatch),
+    Success {
+        post_state_hash: Blake2bHash,
+        effect: ExecutionEffect,
+    },
+}
+
+impl fmt::Display for DistributeRewardsResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            DistributeRewardsResult::RootNotFound => write!(f, "Root not found"),
+            DistributeRewardsResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
+            DistributeRewardsResult::TypeMismatch(type_mismatch) => {
+                write!(f, "Type mismatch: {:?}", type_mismatch)
+            }
+            DistributeRewardsResult::Success {
+                post_state_hash,
+                effect,
+            } => write!(f, "Success: {} {:?}", post_state_hash, effect),
+        }
+    }
+}
+
+impl DistributeRewardsResult {
+    pub fn from_commit_result(commit_result: CommitResult, effect: ExecutionEffect) -> Self {
+        match commit_result {
+            CommitResult::RootNotFound => DistributeRewardsResult::RootNotFound,
+            CommitResult::KeyNotFound(key) => DistributeRewardsResult::KeyNotFound(key),
+            CommitResult::TypeMismatch(type_mismatch) => {
+                DistributeRewardsResult::TypeMismatch(type_mismatch)
+            }
+            CommitResult::Success { state_root, .. } => DistributeRewardsResult::Success {
+                post_state_hash: state_root,
+                effect,
+            },
+        }
+    }
+}
+
+/// A request, made once per block, to pay out the validators' rewards if the distribution
+/// interval has passed by `blocktime`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistributeRewardsConfig {
+    pre_state_hash: Blake2bHash,
+    protocol_version: ProtocolVersion,
+    blocktime: BlockTime,
+}
+
+impl DistributeRewardsConfig {
+    pub fn new(
+        pre_state_hash: Blake2bHash,
+        protocol_version: ProtocolVersion,
+        blocktime: BlockTime,
+    ) -> Self {
+        DistributeRewardsConfig {
+            pre_state_hash,
+            protocol_version,
+            blocktime,
+        }
+    }
+
+    pub fn pre_state_hash(&self) -> Blake2bHash {
+        self.pre_state_hash
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
+    pub fn blocktime(&self) -> BlockTime {
+        self.blocktime
+    }
+}
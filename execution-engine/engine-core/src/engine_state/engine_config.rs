@@ -2,6 +2,7 @@
 #[derive(Debug, Clone)]
 pub struct EngineConfig {
     use_payment_code: bool,
+    deploy_workers: usize,
 }
 
 impl EngineConfig {
@@ -19,12 +20,26 @@ impl EngineConfig {
     pub fn use_payment_code(&self) -> bool {
         self.use_payment_code
     }
+
+    /// Sets the `deploy_workers` field to the given arg.
+    ///
+    /// Values greater than 1 allow the deploys of a block to be executed concurrently on a pool of
+    /// that many worker threads.
+    pub fn set_deploy_workers(mut self, arg: usize) -> EngineConfig {
+        self.deploy_workers = arg;
+        self
+    }
+
+    pub fn deploy_workers(&self) -> usize {
+        self.deploy_workers
+    }
 }
 
 impl Default for EngineConfig {
     fn default() -> Self {
         EngineConfig {
             use_payment_code: false,
+            deploy_workers: 1,
         }
     }
 }
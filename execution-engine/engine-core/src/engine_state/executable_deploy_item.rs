@@ -1,19 +1,28 @@
+use contract_ffi::value::contract::DEFAULT_ENTRY_POINT_NAME;
+
 pub enum ExecutableDeployItem {
     ModuleBytes {
         module_bytes: Vec<u8>,
         args: Vec<u8>,
+        named_args: Vec<u8>,
     },
     StoredContractByHash {
         hash: Vec<u8>,
+        entry_point: String,
         args: Vec<u8>,
+        named_args: Vec<u8>,
     },
     StoredContractByName {
         name: String,
+        entry_point: String,
         args: Vec<u8>,
+        named_args: Vec<u8>,
     },
     StoredContractByURef {
         uref: Vec<u8>,
+        entry_point: String,
         args: Vec<u8>,
+        named_args: Vec<u8>,
     },
 }
 
@@ -26,4 +35,30 @@ impl ExecutableDeployItem {
             ExecutableDeployItem::StoredContractByURef { args, .. } => args,
         }
     }
+
+    pub fn named_args(&self) -> &[u8] {
+        match self {
+            ExecutableDeployItem::ModuleBytes { named_args, .. } => named_args,
+            ExecutableDeployItem::StoredContractByHash { named_args, .. } => named_args,
+            ExecutableDeployItem::StoredContractByName { named_args, .. } => named_args,
+            ExecutableDeployItem::StoredContractByURef { named_args, .. } => named_args,
+        }
+    }
+
+    /// The name of the entry point of the stored contract to call.  Module bytes, and stored
+    /// contracts called without naming an entry point, are called through
+    /// `DEFAULT_ENTRY_POINT_NAME`.
+    pub fn entry_point(&self) -> &str {
+        match self {
+            ExecutableDeployItem::ModuleBytes { .. } => DEFAULT_ENTRY_POINT_NAME,
+            ExecutableDeployItem::StoredContractByHash { entry_point, .. }
+            | ExecutableDeployItem::StoredContractByName { entry_point, .. }
+            | ExecutableDeployItem::StoredContractByURef { entry_point, .. }
+                if !entry_point.is_empty() =>
+            {
+                entry_point
+            }
+            _ => DEFAULT_ENTRY_POINT_NAME,
+        }
+    }
 }
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use contract_ffi::event::Event;
 use contract_ffi::key::Key;
 use engine_shared::transform::Transform;
 
@@ -9,10 +10,21 @@ use super::op::Op;
 pub struct ExecutionEffect {
     pub ops: HashMap<Key, Op>,
     pub transforms: HashMap<Key, Transform>,
+    /// Events emitted during execution, in emission order.
+    pub events: Vec<Event>,
 }
 
 impl ExecutionEffect {
     pub fn new(ops: HashMap<Key, Op>, transforms: HashMap<Key, Transform>) -> Self {
-        ExecutionEffect { ops, transforms }
+        ExecutionEffect {
+            ops,
+            transforms,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn with_events(mut self, events: Vec<Event>) -> Self {
+        self.events = events;
+        self
     }
 }
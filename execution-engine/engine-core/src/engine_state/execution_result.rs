@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use crate::tracking_copy;
 
+use contract_ffi::event::Event;
 use contract_ffi::key::Key;
 use contract_ffi::value::Value;
 use engine_shared::gas::Gas;
@@ -216,6 +217,7 @@ impl ExecutionResultBuilder {
         let cost = self.total_cost();
         let mut ops = HashMap::new();
         let mut transforms = HashMap::new();
+        let mut events = Vec::new();
 
         let mut ret: ExecutionResult = ExecutionResult::Success {
             effect: Default::default(),
@@ -227,7 +229,7 @@ impl ExecutionResultBuilder {
                 if result.is_failure() {
                     return Ok(result);
                 } else {
-                    Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    Self::add_effects(&mut ops, &mut transforms, &mut events, result.effect());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingPaymentExecutionResult),
@@ -240,7 +242,7 @@ impl ExecutionResultBuilder {
                 if result.is_failure() {
                     ret = result.with_cost(cost);
                 } else {
-                    Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    Self::add_effects(&mut ops, &mut transforms, &mut events, result.effect());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingSessionExecutionResult),
@@ -254,14 +256,15 @@ impl ExecutionResultBuilder {
                         error::Error::FinalizationError,
                     ));
                 } else {
-                    Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    Self::add_effects(&mut ops, &mut transforms, &mut events, result.effect());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingFinalizeExecutionResult),
         }
 
         // Remove redundant writes to allow more opportunity to commute
-        let reduced_effect = Self::reduce_identity_writes(ops, transforms, reader, correlation_id);
+        let reduced_effect = Self::reduce_identity_writes(ops, transforms, reader, correlation_id)
+            .with_events(events);
 
         Ok(ret.with_effect(reduced_effect))
     }
@@ -269,6 +272,7 @@ impl ExecutionResultBuilder {
     fn add_effects(
         ops: &mut HashMap<Key, Op>,
         transforms: &mut HashMap<Key, Transform>,
+        events: &mut Vec<Event>,
         effect: &ExecutionEffect,
     ) {
         for (k, op) in effect.ops.iter() {
@@ -277,6 +281,7 @@ impl ExecutionResultBuilder {
         for (k, t) in effect.transforms.iter() {
             tracking_copy::utils::add(transforms, *k, t.clone());
         }
+        events.extend(effect.events.iter().cloned());
     }
 
     /// In the case we are writing the same value as was there originally,
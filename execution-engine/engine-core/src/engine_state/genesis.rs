@@ -4,6 +4,7 @@ use num_traits::Zero;
 
 use contract_ffi::key::Key;
 
+use contract_ffi::system_contracts::pos::RewardsConfig;
 use contract_ffi::value::account::PublicKey;
 use contract_ffi::value::ProtocolVersion;
 use engine_shared::motes::Motes;
@@ -17,6 +18,8 @@ use crate::engine_state::execution_effect::ExecutionEffect;
 pub const POS_BONDING_PURSE: &str = "pos_bonding_purse";
 pub const POS_PAYMENT_PURSE: &str = "pos_payment_purse";
 pub const POS_REWARDS_PURSE: &str = "pos_rewards_purse";
+pub const POS_BURN_PURSE: &str = "pos_burn_purse";
+pub const POS_TREASURY_PURSE: &str = "pos_treasury_purse";
 
 pub enum GenesisResult {
     RootNotFound,
@@ -96,9 +99,11 @@ pub struct GenesisConfig {
     proof_of_stake_installer_bytes: Vec<u8>,
     accounts: Vec<GenesisAccount>,
     wasm_costs: WasmCosts,
+    rewards_config: RewardsConfig,
 }
 
 impl GenesisConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         name: String,
         timestamp: u64,
@@ -107,6 +112,7 @@ impl GenesisConfig {
         proof_of_stake_installer_bytes: Vec<u8>,
         accounts: Vec<GenesisAccount>,
         wasm_costs: WasmCosts,
+        rewards_config: RewardsConfig,
     ) -> Self {
         GenesisConfig {
             name,
@@ -116,6 +122,7 @@ impl GenesisConfig {
             proof_of_stake_installer_bytes,
             accounts,
             wasm_costs,
+            rewards_config,
         }
     }
 
@@ -143,6 +150,10 @@ impl GenesisConfig {
         self.wasm_costs
     }
 
+    pub fn rewards_config(&self) -> RewardsConfig {
+        self.rewards_config
+    }
+
     pub fn get_bonded_validators(&self) -> impl Iterator<Item = (PublicKey, Motes)> + '_ {
         let zero = Motes::zero();
         self.accounts.iter().filter_map(move |genesis_account| {
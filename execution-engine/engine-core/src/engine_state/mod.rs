@@ -1,3 +1,5 @@
+pub mod conflicts;
+pub mod distribute_rewards;
 pub mod engine_config;
 pub mod error;
 pub mod executable_deploy_item;
@@ -5,6 +7,8 @@ pub mod execution_effect;
 pub mod execution_result;
 pub mod genesis;
 pub mod op;
+pub mod simulation;
+pub mod slash;
 pub mod upgrade;
 pub mod utils;
 
@@ -15,35 +19,43 @@ use std::rc::Rc;
 use num_traits::Zero;
 
 use contract_ffi::args_parser::ArgsParser;
-use contract_ffi::bytesrepr::ToBytes;
+use contract_ffi::bytesrepr::{self, ToBytes};
 use contract_ffi::execution::Phase;
 use contract_ffi::key::{Key, HASH_SIZE};
+use contract_ffi::named_args::NamedArgs;
 use contract_ffi::system_contracts::mint;
+use contract_ffi::system_contracts::pos::RewardsConfig;
 use contract_ffi::uref::URef;
 use contract_ffi::uref::{AccessRights, UREF_ADDR_SIZE};
 use contract_ffi::value::account::{BlockTime, PublicKey, PurseId};
-use contract_ffi::value::{Account, ProtocolVersion, SemVer, Value, U512};
+use contract_ffi::value::{Account, Contract, ProtocolVersion, SemVer, Value, U512};
 use engine_shared::gas::Gas;
 use engine_shared::motes::Motes;
 use engine_shared::newtypes::{Blake2bHash, CorrelationId, Validated};
 use engine_shared::transform::Transform;
-use engine_storage::global_state::{CommitResult, StateProvider, StateReader};
+use engine_storage::global_state::{
+    CommitResult, DiffResult, PruneResult, StateProvider, StateReader,
+};
 use engine_storage::protocol_data::ProtocolData;
 use engine_wasm_prep::wasm_costs::WasmCosts;
 use engine_wasm_prep::{Preprocessor, WasmiPreprocessor};
 
+use self::distribute_rewards::{DistributeRewardsConfig, DistributeRewardsResult};
 pub use self::engine_config::EngineConfig;
 use self::error::{Error, RootNotFound};
 use self::executable_deploy_item::ExecutableDeployItem;
+use self::execution_effect::ExecutionEffect;
 use self::execution_result::ExecutionResult;
 use self::genesis::{
     GenesisAccount, GenesisConfig, GenesisResult, POS_PAYMENT_PURSE, POS_REWARDS_PURSE,
 };
+use self::simulation::{PhaseCosts, SimulationResult};
+use self::slash::{SlashConfig, SlashResult};
 use crate::engine_state::error::Error::MissingSystemContractError;
 use crate::engine_state::upgrade::{UpgradeConfig, UpgradeResult};
 use crate::execution::AddressGenerator;
 use crate::execution::{self, Executor, WasmiExecutor, MINT_NAME, POS_NAME};
-use crate::tracking_copy::{TrackingCopy, TrackingCopyExt};
+use crate::tracking_copy::{AddResult, TrackingCopy, TrackingCopyExt};
 use crate::KnownKeys;
 
 // TODO?: MAX_PAYMENT && CONV_RATE values are currently arbitrary w/ real values
@@ -65,6 +77,42 @@ const MINT_INSTALL_BYTES: &[u8] =
 const PROOF_OF_STAKE_INSTALL_BYTES: &[u8] =
     include_bytes!("../../../target/wasm32-unknown-unknown/release/pos_install.wasm");
 
+/// Returns the module bytes of the entry point of `contract` which `deploy_item` calls.
+fn stored_contract_module_bytes(
+    contract: &Contract,
+    deploy_item: &ExecutableDeployItem,
+    account: &Account,
+) -> Result<Vec<u8>, Error> {
+    // As in the executor, a deploy without named arguments may send no bytes at all
+    let named_args: NamedArgs = if deploy_item.named_args().is_empty() {
+        NamedArgs::new()
+    } else {
+        bytesrepr::deserialize(deploy_item.named_args())?
+    };
+    let module_bytes = execution::entry_point_module_bytes(
+        contract,
+        deploy_item.entry_point(),
+        &named_args,
+        account,
+    )?;
+    Ok(module_bytes)
+}
+
+/// Returns the "virtual system account" which genesis, payment finalization and calls to the
+/// system contracts are executed as.  It has no named keys and a purse which doesn't exist.
+fn virtual_system_account() -> Account {
+    let named_keys = BTreeMap::new();
+    let purse = PurseId::new(URef::new(Default::default(), AccessRights::READ_ADD_WRITE));
+    Account::create(SYSTEM_ACCOUNT_ADDR, named_keys, purse)
+}
+
+/// Returns the authorization keys system calls are executed with.
+fn system_authorization_keys() -> BTreeSet<PublicKey> {
+    let mut ret = BTreeSet::new();
+    ret.insert(PublicKey::new(SYSTEM_ACCOUNT_ADDR));
+    ret
+}
+
 #[derive(Debug)]
 pub struct EngineState<S> {
     config: EngineConfig,
@@ -133,6 +181,10 @@ where
             max_stack_height: 64 * 1024,
             opcodes_mul: 3,
             opcodes_div: 8,
+            hash: 100,
+            hash_byte: 1,
+            verify_signature: 10_000,
+            verify_signature_byte: 1,
         };
 
         let mut accounts = Vec::new();
@@ -165,6 +217,7 @@ where
             proof_of_stake_install_bytes,
             accounts,
             wasm_costs,
+            RewardsConfig::default(),
         );
 
         self.commit_genesis_with_chainspec(correlation_id, genesis_config)
@@ -188,11 +241,7 @@ where
         let preprocessor = WasmiPreprocessor::new(wasm_costs);
 
         // Spec #3: Create "virtual system account" object.
-        let virtual_system_account = {
-            let named_keys = BTreeMap::new();
-            let purse = PurseId::new(URef::new(Default::default(), AccessRights::READ_ADD_WRITE));
-            Account::create(SYSTEM_ACCOUNT_ADDR, named_keys, purse)
-        };
+        let virtual_system_account = virtual_system_account();
 
         // Spec #4: Create a runtime.
         let tracking_copy = match self.tracking_copy(initial_root_hash) {
@@ -304,6 +353,7 @@ where
                 mint_reference,
                 // This is used as unknown key
                 URef::new([0; 32], AccessRights::READ),
+                Default::default(),
             );
 
             executor.better_exec(
@@ -326,7 +376,12 @@ where
         };
 
         // Spec #2: Associate given CostTable with given ProtocolVersion.
-        let protocol_data = ProtocolData::new(wasm_costs, mint_reference, proof_of_stake_reference);
+        let protocol_data = ProtocolData::new(
+            wasm_costs,
+            mint_reference,
+            proof_of_stake_reference,
+            genesis_config.rewards_config(),
+        );
 
         self.state
             .put_protocol_data(protocol_version, &protocol_data)
@@ -388,7 +443,7 @@ where
                 let contract = tracking_copy
                     .borrow_mut()
                     .get_contract(correlation_id, Key::URef(mint_reference).normalize())?;
-                let (bytes, _, _) = contract.destructure();
+                let (bytes, _, _, _) = contract.destructure();
                 preprocessor.deserialize(&bytes)?
             };
 
@@ -517,6 +572,7 @@ where
             new_wasm_costs,
             current_protocol_data.mint(),
             current_protocol_data.proof_of_stake(),
+            current_protocol_data.rewards_config(),
         );
 
         self.state
@@ -554,11 +610,7 @@ where
             let mut keys = BTreeMap::new();
 
             let initial_base_key = Key::Account(SYSTEM_ACCOUNT_ADDR);
-            let authorization_keys = {
-                let mut ret = BTreeSet::new();
-                ret.insert(PublicKey::new(SYSTEM_ACCOUNT_ADDR));
-                ret
-            };
+            let authorization_keys = system_authorization_keys();
 
             let blocktime = BlockTime::default();
 
@@ -616,6 +668,155 @@ where
         Ok(UpgradeResult::from_commit_result(commit_result, effects))
     }
 
+    /// Calls the Proof of Stake contract's `slash` method as the system account, in the system
+    /// phase, and commits its effects on top of the slash config's pre state hash.  Nothing is
+    /// slashed, and an error is returned, if slashing would leave no bonded validators.
+    pub fn slash(
+        &self,
+        correlation_id: CorrelationId,
+        slash_config: SlashConfig,
+    ) -> Result<SlashResult, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let protocol_version = slash_config.protocol_version();
+        let protocol_data = match self.state.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+
+        let args = (
+            "slash",
+            slash_config.validators().to_vec(),
+            slash_config.fraction(),
+            slash_config.destination(),
+        );
+        let (commit_result, effects) = self.call_proof_of_stake_as_system(
+            correlation_id,
+            slash_config.pre_state_hash(),
+            protocol_version,
+            protocol_data,
+            slash_config.blocktime(),
+            &args,
+        )?;
+        Ok(SlashResult::from_commit_result(commit_result, effects))
+    }
+
+    /// Calls the Proof of Stake contract's `distribute_rewards` method as the system account, in
+    /// the system phase, and commits its effects on top of the config's pre state hash.  The
+    /// rewards are only paid out if the distribution interval of the protocol's rewards config
+    /// has passed since the last distribution.
+    pub fn distribute_rewards(
+        &self,
+        correlation_id: CorrelationId,
+        distribute_rewards_config: DistributeRewardsConfig,
+    ) -> Result<DistributeRewardsResult, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let protocol_version = distribute_rewards_config.protocol_version();
+        let protocol_data = match self.state.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+
+        let args = ("distribute_rewards", protocol_data.rewards_config());
+        let (commit_result, effects) = self.call_proof_of_stake_as_system(
+            correlation_id,
+            distribute_rewards_config.pre_state_hash(),
+            protocol_version,
+            protocol_data,
+            distribute_rewards_config.blocktime(),
+            &args,
+        )?;
+        Ok(DistributeRewardsResult::from_commit_result(
+            commit_result,
+            effects,
+        ))
+    }
+
+    /// Runs the Proof of Stake contract with `args` as the system account, in the system phase,
+    /// and commits its effects on top of `pre_state_hash`.
+    fn call_proof_of_stake_as_system<A: ArgsParser>(
+        &self,
+        correlation_id: CorrelationId,
+        pre_state_hash: Blake2bHash,
+        protocol_version: ProtocolVersion,
+        protocol_data: ProtocolData,
+        blocktime: BlockTime,
+        args: &A,
+    ) -> Result<(CommitResult, ExecutionEffect), Error>
+    where
+        Error: From<S::Error>,
+    {
+        let tracking_copy = match self.tracking_copy(pre_state_hash)? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Ok((CommitResult::RootNotFound, ExecutionEffect::default())),
+        };
+
+        let proof_of_stake_info = {
+            let proof_of_stake_public_uref = Key::from(protocol_data.proof_of_stake()).normalize();
+            tracking_copy
+                .borrow_mut()
+                .get_system_contract_info(correlation_id, proof_of_stake_public_uref)?
+        };
+
+        let proof_of_stake_module = {
+            let preprocessor = WasmiPreprocessor::new(*protocol_data.wasm_costs());
+            preprocessor.deserialize(&proof_of_stake_info.module_bytes())?
+        };
+
+        let proof_of_stake_args = ArgsParser::parse(args).and_then(|args| args.to_bytes())?;
+
+        let mut proof_of_stake_keys = proof_of_stake_info.contract().named_keys().clone();
+        let base_key = proof_of_stake_info.key();
+
+        // execute as system account
+        let system_account = virtual_system_account();
+        let authorization_keys = system_authorization_keys();
+
+        // seeds address generator w/ the args and the block time
+        let deploy_hash = {
+            let mut seed = proof_of_stake_args.clone();
+            seed.extend_from_slice(&blocktime.0.to_le_bytes());
+            Blake2bHash::new(&seed).into()
+        };
+
+        // system calls have no gas limit; approximating with MAX
+        let gas_limit = Gas::new(std::u64::MAX.into());
+
+        let execution_result = WasmiExecutor.exec_direct(
+            proof_of_stake_module,
+            &proof_of_stake_args,
+            &mut proof_of_stake_keys,
+            base_key,
+            &system_account,
+            authorization_keys,
+            blocktime,
+            deploy_hash,
+            gas_limit,
+            protocol_version,
+            correlation_id,
+            Rc::clone(&tracking_copy),
+            Phase::System,
+            protocol_data,
+        );
+        if let ExecutionResult::Failure { error, .. } = execution_result {
+            return Err(error);
+        }
+
+        let effects = tracking_copy.borrow().effect();
+
+        let commit_result = self.apply_effect(
+            correlation_id,
+            protocol_version,
+            pre_state_hash,
+            effects.transforms.to_owned(),
+        )?;
+
+        Ok((commit_result, effects))
+    }
+
     pub fn tracking_copy(
         &self,
         hash: Blake2bHash,
@@ -626,14 +827,17 @@ where
         }
     }
 
-    pub fn get_module<A, P: Preprocessor<A>>(
+    pub fn get_module<A, P: Preprocessor<A>, R: StateReader<Key, Value>>(
         &self,
-        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
         deploy_item: &ExecutableDeployItem,
         account: &Account,
         correlation_id: CorrelationId,
         preprocessor: &P,
-    ) -> Result<A, error::Error> {
+    ) -> Result<A, error::Error>
+    where
+        R::Error: Into<execution::Error>,
+    {
         match deploy_item {
             ExecutableDeployItem::ModuleBytes { module_bytes, .. } => {
                 let module = preprocessor.preprocess(&module_bytes)?;
@@ -655,8 +859,8 @@ where
                 let contract = tracking_copy
                     .borrow_mut()
                     .get_contract(correlation_id, stored_contract_key)?;
-                let (ret, _, _) = contract.destructure();
-                let module = preprocessor.deserialize(&ret)?;
+                let module_bytes = stored_contract_module_bytes(&contract, deploy_item, account)?;
+                let module = preprocessor.deserialize(&module_bytes)?;
                 Ok(module)
             }
             ExecutableDeployItem::StoredContractByName { name, .. } => {
@@ -673,8 +877,8 @@ where
                 let contract = tracking_copy
                     .borrow_mut()
                     .get_contract(correlation_id, stored_contract_key.normalize())?;
-                let (ret, _, _) = contract.destructure();
-                let module = preprocessor.deserialize(&ret)?;
+                let module_bytes = stored_contract_module_bytes(&contract, deploy_item, account)?;
+                let module = preprocessor.deserialize(&module_bytes)?;
                 Ok(module)
             }
             ExecutableDeployItem::StoredContractByURef { uref, .. } => {
@@ -721,8 +925,8 @@ where
                 let contract = tracking_copy
                     .borrow_mut()
                     .get_contract(correlation_id, stored_contract_key)?;
-                let (ret, _, _) = contract.destructure();
-                let module = preprocessor.deserialize(&ret)?;
+                let module_bytes = stored_contract_module_bytes(&contract, deploy_item, account)?;
+                let module = preprocessor.deserialize(&module_bytes)?;
                 Ok(module)
             }
         }
@@ -743,8 +947,6 @@ where
         executor: &E,
         preprocessor: &P,
     ) -> Result<ExecutionResult, RootNotFound> {
-        // spec: https://casperlabs.atlassian.net/wiki/spaces/EN/pages/123404576/Payment+code+execution+specification
-
         // Create tracking copy (which functions as a deploy context)
         // validation_spec_2: prestate_hash check
         let tracking_copy = match self.tracking_copy(prestate_hash) {
@@ -753,15 +955,163 @@ where
             Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
         };
 
+        Ok(self.deploy_with_tracking_copy(
+            tracking_copy,
+            session,
+            payment,
+            address,
+            authorization_keys,
+            blocktime,
+            deploy_hash,
+            protocol_version,
+            correlation_id,
+            executor,
+            preprocessor,
+            true,
+            &mut PhaseCosts::default(),
+        ))
+    }
+
+    /// Executes a deploy against the global state at `prestate_hash` without committing its
+    /// effects, returning the gas spent by its payment and session code.
+    ///
+    /// If `check_thresholds` is `false`, the deploy is executed even if the weight of
+    /// `authorization_keys` does not reach the deploy threshold of the account.  The keys must
+    /// still be associated with the account.
+    #[allow(clippy::too_many_arguments)]
+    pub fn simulate<A, P: Preprocessor<A>, E: Executor<A>>(
+        &self,
+        session: ExecutableDeployItem,
+        payment: ExecutableDeployItem,
+        address: Key,
+        authorization_keys: BTreeSet<PublicKey>,
+        blocktime: BlockTime,
+        deploy_hash: [u8; 32],
+        prestate_hash: Blake2bHash,
+        protocol_version: ProtocolVersion,
+        correlation_id: CorrelationId,
+        executor: &E,
+        preprocessor: &P,
+        check_thresholds: bool,
+    ) -> Result<SimulationResult, RootNotFound> {
+        let tracking_copy = match self.tracking_copy(prestate_hash) {
+            Err(error) => {
+                let execution_result = ExecutionResult::precondition_failure(error);
+                return Ok(SimulationResult::new(
+                    execution_result,
+                    PhaseCosts::default(),
+                ));
+            }
+            Ok(None) => return Err(RootNotFound(prestate_hash)),
+            Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
+        };
+
+        let mut phase_costs = PhaseCosts::default();
+        let execution_result = self.deploy_with_tracking_copy(
+            tracking_copy,
+            session,
+            payment,
+            address,
+            authorization_keys,
+            blocktime,
+            deploy_hash,
+            protocol_version,
+            correlation_id,
+            executor,
+            preprocessor,
+            check_thresholds,
+            &mut phase_costs,
+        );
+
+        Ok(SimulationResult::new(execution_result, phase_costs))
+    }
+
+    /// Executes a deploy on top of `tracking_copy` and then applies the effects of that deploy to
+    /// it, so that subsequent deploys executed against the same `tracking_copy` observe them.
+    ///
+    /// This allows a sequence of deploys to be executed without committing the effects of each
+    /// one before executing the next.  The returned [`ExecutionResult`] contains only the effects
+    /// of this deploy, while [`TrackingCopy::effect`] accumulates the effects of all of them.  If
+    /// the effects can't be applied to `tracking_copy`, the result is a failure with no effects
+    /// which still carries the cost of executing the deploy.
+    #[allow(clippy::too_many_arguments)]
+    pub fn deploy_chained<A, P: Preprocessor<A>, E: Executor<A>>(
+        &self,
+        tracking_copy: &mut TrackingCopy<S::Reader>,
+        session: ExecutableDeployItem,
+        payment: ExecutableDeployItem,
+        address: Key,
+        authorization_keys: BTreeSet<PublicKey>,
+        blocktime: BlockTime,
+        deploy_hash: [u8; 32],
+        protocol_version: ProtocolVersion,
+        correlation_id: CorrelationId,
+        executor: &E,
+        preprocessor: &P,
+    ) -> ExecutionResult {
+        let execution_result = {
+            let fork = Rc::new(RefCell::new(tracking_copy.fork()));
+            self.deploy_with_tracking_copy(
+                fork,
+                session,
+                payment,
+                address,
+                authorization_keys,
+                blocktime,
+                deploy_hash,
+                protocol_version,
+                correlation_id,
+                executor,
+                preprocessor,
+                true,
+                &mut PhaseCosts::default(),
+            )
+        };
+
+        let effect = execution_result.effect().to_owned();
+        let error = match tracking_copy.apply_effect(correlation_id, effect) {
+            Ok(AddResult::Success) => return execution_result,
+            Ok(AddResult::KeyNotFound(key)) => execution::Error::KeyNotFound(key),
+            Ok(AddResult::TypeMismatch(type_mismatch)) => {
+                execution::Error::TypeMismatch(type_mismatch)
+            }
+            Err(error) => error.into(),
+        };
+        // None of the deploy's effects are kept, but it was executed, so its cost still stands
+        ExecutionResult::precondition_failure(Error::ExecError(error))
+            .with_cost(execution_result.cost())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn deploy_with_tracking_copy<A, P, E, R>(
+        &self,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+        session: ExecutableDeployItem,
+        payment: ExecutableDeployItem,
+        address: Key,
+        authorization_keys: BTreeSet<PublicKey>,
+        blocktime: BlockTime,
+        deploy_hash: [u8; 32],
+        protocol_version: ProtocolVersion,
+        correlation_id: CorrelationId,
+        executor: &E,
+        preprocessor: &P,
+        check_thresholds: bool,
+        phase_costs: &mut PhaseCosts,
+    ) -> ExecutionResult
+    where
+        P: Preprocessor<A>,
+        E: Executor<A>,
+        R: StateReader<Key, Value>,
+        R::Error: Into<execution::Error>,
+    {
+        // spec: https://casperlabs.atlassian.net/wiki/spaces/EN/pages/123404576/Payment+code+execution+specification
+
         // Get addr bytes from `address` (which is actually a Key)
         // validation_spec_3: account validity
         let account_addr = match address.as_account() {
             Some(account_addr) => account_addr,
-            None => {
-                return Ok(ExecutionResult::precondition_failure(
-                    error::Error::AuthorizationError,
-                ))
-            }
+            None => return ExecutionResult::precondition_failure(error::Error::AuthorizationError),
         };
 
         // Get account from tracking copy
@@ -772,27 +1122,25 @@ where
         {
             Ok(account) => account,
             Err(_) => {
-                return Ok(ExecutionResult::precondition_failure(
-                    error::Error::AuthorizationError,
-                ));
+                return ExecutionResult::precondition_failure(error::Error::AuthorizationError);
             }
         };
 
         // Authorize using provided authorization keys
         // validation_spec_3: account validity
         if authorization_keys.is_empty() || !account.can_authorize(&authorization_keys) {
-            return Ok(ExecutionResult::precondition_failure(
+            return ExecutionResult::precondition_failure(
                 crate::engine_state::error::Error::AuthorizationError,
-            ));
+            );
         }
 
         // Check total key weight against deploy threshold
         // validation_spec_4: deploy validity
-        if !account.can_deploy_with(&authorization_keys) {
-            return Ok(ExecutionResult::precondition_failure(
+        if check_thresholds && !account.can_deploy_with(&authorization_keys) {
+            return ExecutionResult::precondition_failure(
                 // TODO?:this doesn't happen in execution any longer, should error variant be moved
                 execution::Error::DeploymentAuthorizationFailure.into(),
-            ));
+            );
         }
 
         // Create session code `A` from provided session bytes
@@ -806,7 +1154,7 @@ where
         ) {
             Ok(module) => module,
             Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(error));
+                return ExecutionResult::precondition_failure(error);
             }
         };
 
@@ -815,12 +1163,10 @@ where
             Ok(Some(protocol_data)) => protocol_data,
             Ok(None) => {
                 let error = Error::InvalidProtocolVersion(protocol_version);
-                return Ok(ExecutionResult::precondition_failure(error));
+                return ExecutionResult::precondition_failure(error);
             }
             Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(Error::ExecError(
-                    error.into(),
-                )));
+                return ExecutionResult::precondition_failure(Error::ExecError(error.into()));
             }
         };
 
@@ -837,6 +1183,7 @@ where
             let session_result = executor.exec(
                 session_module,
                 session.args(),
+                session.named_args(),
                 address,
                 &account,
                 authorization_keys,
@@ -850,7 +1197,9 @@ where
                 protocol_data,
             );
 
-            return Ok(session_result);
+            phase_costs.session = session_result.cost();
+
+            return session_result;
         }
 
         // --- REMOVE ABOVE --- //
@@ -872,7 +1221,7 @@ where
             {
                 Ok(contract_info) => contract_info,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return ExecutionResult::precondition_failure(error.into());
                 }
             };
 
@@ -894,7 +1243,7 @@ where
         {
             Ok(contract_info) => contract_info,
             Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(error.into()));
+                return ExecutionResult::precondition_failure(error.into());
             }
         };
 
@@ -910,7 +1259,7 @@ where
             {
                 Some(key) => *key,
                 None => {
-                    return Ok(ExecutionResult::precondition_failure(Error::DeployError));
+                    return ExecutionResult::precondition_failure(Error::DeployError);
                 }
             };
 
@@ -921,7 +1270,7 @@ where
             ) {
                 Ok(key) => key,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return ExecutionResult::precondition_failure(error.into());
                 }
             }
         };
@@ -937,7 +1286,7 @@ where
             ) {
                 Ok(key) => key,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return ExecutionResult::precondition_failure(error.into());
                 }
             }
         };
@@ -949,27 +1298,18 @@ where
             .get_purse_balance(correlation_id, account_main_purse_balance_key)
         {
             Ok(balance) => balance,
-            Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+            Err(error) => return ExecutionResult::precondition_failure(error.into()),
         };
 
         // Enforce minimum main purse balance validation
         // validation_spec_5: account main purse minimum balance
         if account_main_purse_balance < max_payment_cost {
-            return Ok(ExecutionResult::precondition_failure(
-                Error::InsufficientPaymentError,
-            ));
+            return ExecutionResult::precondition_failure(Error::InsufficientPaymentError);
         }
 
         // Finalization is executed by system account (currently genesis account)
         // payment_code_spec_5: system executes finalization
-        let system_account = Account::new(
-            SYSTEM_ACCOUNT_ADDR,
-            Default::default(),
-            PurseId::new(URef::new(Default::default(), AccessRights::READ_ADD_WRITE)),
-            Default::default(),
-            Default::default(),
-            Default::default(),
-        );
+        let system_account = virtual_system_account();
 
         // `[ExecutionResultBuilder]` handles merging of multiple execution results
         let mut execution_result_builder = execution_result::ExecutionResultBuilder::new();
@@ -991,7 +1331,7 @@ where
             ) {
                 Ok(module) => module,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error));
+                    return ExecutionResult::precondition_failure(error);
                 }
             };
 
@@ -999,6 +1339,7 @@ where
             executor.exec(
                 payment_module,
                 payment.args(),
+                payment.named_args(),
                 address,
                 &account,
                 authorization_keys.clone(),
@@ -1014,6 +1355,7 @@ where
         };
 
         let payment_result_cost = payment_result.cost();
+        phase_costs.payment = payment_result_cost;
 
         // payment_code_spec_3: fork based upon payment purse balance and cost of
         // payment code execution
@@ -1026,7 +1368,7 @@ where
                 .get(POS_PAYMENT_PURSE)
             {
                 Some(key) => *key,
-                None => return Ok(ExecutionResult::precondition_failure(Error::DeployError)),
+                None => return ExecutionResult::precondition_failure(Error::DeployError),
             };
 
             let purse_balance_key = match tracking_copy.borrow_mut().get_purse_balance_key(
@@ -1036,7 +1378,7 @@ where
             ) {
                 Ok(key) => key,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return ExecutionResult::precondition_failure(error.into());
                 }
             };
 
@@ -1046,7 +1388,7 @@ where
             {
                 Ok(balance) => balance,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return ExecutionResult::precondition_failure(error.into());
                 }
             }
         };
@@ -1061,7 +1403,7 @@ where
                 rewards_purse_balance_key,
             )
         {
-            return Ok(failure);
+            return failure;
         }
 
         let post_payment_tc = tracking_copy.borrow();
@@ -1080,6 +1422,7 @@ where
             executor.exec(
                 session_module,
                 session.args(),
+                session.named_args(),
                 address,
                 &account,
                 authorization_keys.clone(),
@@ -1094,6 +1437,8 @@ where
             )
         };
 
+        phase_costs.session = session_result.cost();
+
         let post_session_rc = if session_result.is_failure() {
             // If session code fails we do not include its effects,
             // so we start again from the post-payment state.
@@ -1102,6 +1447,12 @@ where
             session_tc
         };
 
+        // Only a successful deploy whose authorization keys meet the deployment threshold counts
+        // as account activity, which keeps the key recovery threshold from applying to an account
+        // still in use.
+        let is_account_active =
+            session_result.is_success() && account.can_deploy_with(&authorization_keys);
+
         // NOTE: session_code_spec_3: (do not include session execution effects in
         // results) is enforced in execution_result_builder.build()
         execution_result_builder.set_session_execution_result(session_result);
@@ -1111,17 +1462,45 @@ where
             let post_session_tc = post_session_rc.borrow();
             let finalization_tc = Rc::new(RefCell::new(post_session_tc.fork()));
 
+            // The session may have changed the account, so it is read again after the session.
+            // Accounts without key recovery aren't written.
+            if is_account_active {
+                let mut active_account = match finalization_tc
+                    .borrow_mut()
+                    .get_account(correlation_id, account_addr)
+                {
+                    Ok(account) => account,
+                    Err(error) => return ExecutionResult::precondition_failure(error.into()),
+                };
+                if active_account.has_key_recovery() {
+                    active_account
+                        .account_activity_mut()
+                        .update_deployment_last_used(blocktime);
+                    let key = Key::Account(account_addr);
+                    let value = Value::Account(active_account);
+                    finalization_tc.borrow_mut().write(
+                        Validated::new(key, Validated::valid).unwrap(), // safe to unwrap
+                        Validated::new(value, Validated::valid).unwrap(), // safe to unwrap
+                    );
+                }
+            }
+
             // validation_spec_1: valid wasm bytes
             let proof_of_stake_module =
                 match preprocessor.deserialize(&proof_of_stake_info.module_bytes()) {
-                    Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+                    Err(error) => return ExecutionResult::precondition_failure(error.into()),
                     Ok(module) => module,
                 };
 
             let proof_of_stake_args = {
                 //((gas spent during payment code execution) + (gas spent during session code execution)) * conv_rate
                 let finalize_cost_motes: Motes = Motes::from_gas(execution_result_builder.total_cost(), CONV_RATE).expect("motes overflow");
-                let args = ("finalize_payment", finalize_cost_motes.value(), account_addr);
+                let args = (
+                    "finalize_payment",
+                    finalize_cost_motes.value(),
+                    account_addr,
+                    protocol_data.rewards_config(),
+                );
                 ArgsParser::parse(&args)
                     .and_then(|args| args.to_bytes())
                     .expect("args should parse")
@@ -1134,7 +1513,7 @@ where
                 .get_system_contract_info(correlation_id, proof_of_stake_public_uref)
             {
                 Ok(info) => info,
-                Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+                Err(error) => return ExecutionResult::precondition_failure(error.into()),
             };
 
             let mut proof_of_stake_keys = proof_of_stake_info.contract().named_keys().clone();
@@ -1170,7 +1549,7 @@ where
         // NOTE: payment_code_spec_5_a is enforced in execution_result_builder.build()
         // payment_code_spec_6: return properly combined set of transforms and
         // appropriate error
-        Ok(ret)
+        ret
     }
 
     pub fn apply_effect(
@@ -1196,6 +1575,35 @@ where
         }
     }
 
+    /// Returns the differences between the global state at `old_root` and at `new_root`.
+    pub fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        old_root: Blake2bHash,
+        new_root: Blake2bHash,
+    ) -> Result<DiffResult<Key, Value>, Error>
+    where
+        Error: From<S::Error>,
+    {
+        self.state
+            .diff(correlation_id, old_root, new_root)
+            .map_err(Into::into)
+    }
+
+    /// Deletes all global state which is not reachable from one of `roots_to_keep`.
+    pub fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        roots_to_keep: &[Blake2bHash],
+    ) -> Result<PruneResult, Error>
+    where
+        Error: From<S::Error>,
+    {
+        self.state
+            .prune(correlation_id, roots_to_keep)
+            .map_err(Into::into)
+    }
+
     /// Calculates bonded validators at `root_hash` state.
     ///
     /// Should only be called with a valid root hash after a successful call to
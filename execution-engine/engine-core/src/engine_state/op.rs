@@ -6,6 +6,22 @@ pub enum Op {
     NoOp,
 }
 
+impl Op {
+    /// Returns `true` if performing `self` and `other` on the same key gives the same result
+    /// regardless of the order in which they are performed.
+    ///
+    /// Reads commute with reads, and adds commute with adds, but any other combination involving
+    /// a read, write or add conflicts.
+    pub fn commutes_with(&self, other: &Op) -> bool {
+        match (self, other) {
+            (Op::NoOp, _) | (_, Op::NoOp) => true,
+            (Op::Read, Op::Read) => true,
+            (Op::Add, Op::Add) => true,
+            _ => false,
+        }
+    }
+}
+
 impl std::ops::Add for Op {
     type Output = Op;
 
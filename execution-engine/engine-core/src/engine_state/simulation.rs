@@ -0,0 +1,56 @@
+use engine_shared::gas::Gas;
+use engine_shared::motes::Motes;
+
+use super::execution_result::ExecutionResult;
+use super::CONV_RATE;
+
+/// The gas spent by the payment and session code of a single deploy.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct PhaseCosts {
+    pub payment: Gas,
+    pub session: Gas,
+}
+
+impl PhaseCosts {
+    pub fn total(&self) -> Gas {
+        self.payment + self.session
+    }
+}
+
+/// The outcome of simulating a deploy.  The effects of a simulated deploy are never committed.
+#[derive(Debug)]
+pub struct SimulationResult {
+    execution_result: ExecutionResult,
+    phase_costs: PhaseCosts,
+}
+
+impl SimulationResult {
+    pub fn new(execution_result: ExecutionResult, phase_costs: PhaseCosts) -> Self {
+        SimulationResult {
+            execution_result,
+            phase_costs,
+        }
+    }
+
+    pub fn execution_result(&self) -> &ExecutionResult {
+        &self.execution_result
+    }
+
+    pub fn payment_cost(&self) -> Gas {
+        self.phase_costs.payment
+    }
+
+    pub fn session_cost(&self) -> Gas {
+        self.phase_costs.session
+    }
+
+    /// Returns the amount of motes which covers the gas spent by both the payment and the session
+    /// code, or `None` on overflow.
+    pub fn suggested_payment(&self) -> Option<Motes> {
+        Motes::from_gas(self.phase_costs.total(), CONV_RATE)
+    }
+
+    pub fn take_execution_result(self) -> ExecutionResult {
+        self.execution_result
+    }
+}
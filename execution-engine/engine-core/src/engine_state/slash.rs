@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use contract_ffi::key::Key;
+use contract_ffi::system_contracts::pos::SlashDestination;
+use contract_ffi::value::account::{BlockTime, PublicKey};
+use contract_ffi::value::{ProtocolVersion, U512};
+use engine_shared::newtypes::Blake2bHash;
+use engine_shared::transform::TypeMismatch;
+use engine_storage::global_state::CommitResult;
+
+use crate::engine_state::execution_effect::ExecutionEffect;
+
+pub enum SlashResult {
+    RootNotFound,
+    KeyNotFound(Key),
+    TypeMismatch(TypeMismatch),
+    Success {
+        post_state_hash: Blake2bHash,
+        effect: ExecutionEffect,
+        bonded_validators: HashMap<PublicKey, U512>,
+    },
+}
+
+impl fmt::Display for SlashResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            SlashResult::RootNotFound => write!(f, "Root not found"),
+            SlashResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
+            SlashResult::TypeMismatch(type_mismatch) => {
+                write!(f, "Type mismatch: {:?}", type_mismatch)
+            }
+            SlashResult::Success {
+                post_state_hash,
+                effect,
+                bonded_validators,
+            } => write!(
+                f,
+                "Success: {} {:?} {:?}",
+                post_state_hash, effect, bonded_validators
+            ),
+        }
+    }
+}
+
+impl SlashResult {
+    pub fn from_commit_result(commit_result: CommitResult, effect: ExecutionEffect) -> Self {
+        match commit_result {
+            CommitResult::RootNotFound => SlashResult::RootNotFound,
+            CommitResult::KeyNotFound(key) => SlashResult::KeyNotFound(key),
+            CommitResult::TypeMismatch(type_mismatch) => SlashResult::TypeMismatch(type_mismatch),
+            CommitResult::Success {
+                state_root,
+                bonded_validators,
+            } => SlashResult::Success {
+                post_state_hash: state_root,
+                effect,
+                bonded_validators,
+            },
+        }
+    }
+}
+
+/// A request to penalize `validators`, e.g. for equivocating, by slashing `fraction` millionths
+/// of their stakes, the motes delegated to them and the pending requests to withdraw either.
+///
+/// Either all of `validators` are slashed or none of them: the request fails if it would leave no
+/// bonded validators.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlashConfig {
+    pre_state_hash: Blake2bHash,
+    protocol_version: ProtocolVersion,
+    blocktime: BlockTime,
+    validators: Vec<PublicKey>,
+    fraction: u32,
+    destination: SlashDestination,
+}
+
+impl SlashConfig {
+    pub fn new(
+        pre_state_hash: Blake2bHash,
+        protocol_version: ProtocolVersion,
+        blocktime: BlockTime,
+        validators: Vec<PublicKey>,
+        fraction: u32,
+        destination: SlashDestination,
+    ) -> Self {
+        SlashConfig {
+            pre_state_hash,
+            protocol_version,
+            blocktime,
+            validators,
+            fraction,
+            destination,
+        }
+    }
+
+    pub fn pre_state_hash(&self) -> Blake2bHash {
+        self.pre_state_hash
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
+    pub fn blocktime(&self) -> BlockTime {
+        self.blocktime
+    }
+
+    pub fn validators(&self) -> &[PublicKey] {
+        &self.validators
+    }
+
+    pub fn fraction(&self) -> u32 {
+        self.fraction
+    }
+
+    pub fn destination(&self) -> SlashDestination {
+        self.destination
+    }
+}
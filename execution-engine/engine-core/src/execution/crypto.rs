@@ -0,0 +1,135 @@
+use blake2::digest::{Input, VariableOutput};
+use blake2::VarBlake2b;
+use sha2::{Digest, Sha256};
+
+use contract_ffi::crypto::{HashAlgorithm, SignatureAlgorithm, HASH_LENGTH};
+
+/// Hashes `data` with the given algorithm.
+pub fn hash(algorithm: HashAlgorithm, data: &[u8]) -> [u8; HASH_LENGTH] {
+    let mut digest = [0u8; HASH_LENGTH];
+    match algorithm {
+        HashAlgorithm::Blake2b256 => {
+            // Safe to unwrap here because our digest length is constant and valid
+            let mut hasher = VarBlake2b::new(HASH_LENGTH).unwrap();
+            hasher.input(data);
+            hasher.variable_result(|hash| digest.clone_from_slice(hash));
+        }
+        HashAlgorithm::Sha256 => digest.clone_from_slice(&Sha256::digest(data)),
+        HashAlgorithm::Keccak256 => digest = tiny_keccak::keccak256(data),
+    }
+    digest
+}
+
+/// Returns `true` if `signature` is a valid signature of `message` by `public_key`.  Malformed
+/// public keys and signatures are never valid.
+///
+/// Secp256k1 signatures are expected in 64-byte compact form and are checked against the SHA-256
+/// digest of `message`.
+pub fn verify_signature(
+    algorithm: SignatureAlgorithm,
+    public_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> bool {
+    match algorithm {
+        SignatureAlgorithm::Ed25519 => {
+            let public_key = match ed25519_dalek::PublicKey::from_bytes(public_key) {
+                Ok(public_key) => public_key,
+                Err(_) => return false,
+            };
+            let signature = match ed25519_dalek::Signature::from_bytes(signature) {
+                Ok(signature) => signature,
+                Err(_) => return false,
+            };
+            public_key.verify(message, &signature).is_ok()
+        }
+        SignatureAlgorithm::Secp256k1 => {
+            let public_key = match secp256k1::PublicKey::from_slice(public_key) {
+                Ok(public_key) => public_key,
+                Err(_) => return false,
+            };
+            let signature = match secp256k1::Signature::from_compact(signature) {
+                Ok(signature) => signature,
+                Err(_) => return false,
+            };
+            let digest = hash(HashAlgorithm::Sha256, message);
+            // Can't fail, as the digest has the required length.
+            let message = secp256k1::Message::from_slice(&digest).unwrap();
+            secp256k1::Secp256k1::verification_only()
+                .verify(&message, &signature, &public_key)
+                .is_ok()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use contract_ffi::base16;
+    use contract_ffi::crypto::{HashAlgorithm, SignatureAlgorithm};
+
+    use super::{hash, verify_signature};
+
+    fn hex_digest(algorithm: HashAlgorithm, data: &[u8]) -> String {
+        base16::encode_lower(&hash(algorithm, data))
+    }
+
+    #[test]
+    fn should_hash_empty_input_with_known_digests() {
+        assert_eq!(
+            hex_digest(HashAlgorithm::Blake2b256, b""),
+            "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8"
+        );
+        assert_eq!(
+            hex_digest(HashAlgorithm::Sha256, b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hex_digest(HashAlgorithm::Keccak256, b""),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+    }
+
+    #[test]
+    fn should_verify_ed25519_signature() {
+        // Test 2 from RFC 8032, section 7.1
+        let public_key = base16::decode_lower(
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+        )
+        .unwrap();
+        let signature = base16::decode_lower(
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+             085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+        )
+        .unwrap();
+        let message = [0x72];
+
+        assert!(verify_signature(
+            SignatureAlgorithm::Ed25519,
+            &public_key,
+            &message,
+            &signature
+        ));
+        assert!(!verify_signature(
+            SignatureAlgorithm::Ed25519,
+            &public_key,
+            &[0x73],
+            &signature
+        ));
+        assert!(!verify_signature(
+            SignatureAlgorithm::Ed25519,
+            &public_key[1..],
+            &message,
+            &signature
+        ));
+    }
+
+    #[test]
+    fn should_reject_malformed_secp256k1_input() {
+        assert!(!verify_signature(
+            SignatureAlgorithm::Secp256k1,
+            &[0u8; 33],
+            b"message",
+            &[0u8; 64]
+        ));
+    }
+}
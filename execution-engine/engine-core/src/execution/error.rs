@@ -10,6 +10,7 @@ use contract_ffi::uref::{AccessRights, URef};
 use contract_ffi::value::account::{
     AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure,
 };
+use contract_ffi::value::ContractPackageError;
 use engine_shared::transform::TypeMismatch;
 
 use crate::resolvers::error::ResolverError;
@@ -43,6 +44,17 @@ pub enum Error {
     DeploymentAuthorizationFailure,
     ExpectedReturnValue,
     UnexpectedReturnValue,
+    /// The called contract has no entry point with the given name.
+    NoSuchEntryPoint(String),
+    /// The entry point with the given name may only be called by the system account.
+    EntryPointAccessDenied(String),
+    /// A parameter of the entry point was not passed, or was passed with a different type.
+    InvalidEntryPointArgument(String),
+    ContractPackage(ContractPackageError),
+    /// The authorization keys don't meet the account's key management or key recovery threshold.
+    KeyManagementDenied,
+    /// The inactivity period limit is shorter than the minimum an account may set.
+    InvalidInactivityPeriod,
 }
 
 impl fmt::Display for Error {
@@ -118,3 +130,9 @@ impl From<system_contracts::Error> for Error {
         Error::SystemContractError(error)
     }
 }
+
+impl From<ContractPackageError> for Error {
+    fn from(error: ContractPackageError) -> Error {
+        Error::ContractPackage(error)
+    }
+}
@@ -7,6 +7,7 @@ use parity_wasm::elements::Module;
 use contract_ffi::bytesrepr::{self, FromBytes};
 use contract_ffi::execution::Phase;
 use contract_ffi::key::Key;
+use contract_ffi::named_args::NamedArgs;
 use contract_ffi::value::account::{BlockTime, PublicKey};
 use contract_ffi::value::{Account, ProtocolVersion, Value};
 use engine_shared::gas::Gas;
@@ -18,6 +19,7 @@ use super::Error;
 use super::{extract_access_rights_from_keys, instance_and_memory, Runtime};
 use crate::engine_state::execution_result::ExecutionResult;
 use crate::execution::address_generator::AddressGenerator;
+use crate::execution::random_generator::RandomGenerator;
 use crate::execution::FN_STORE_ID_INITIAL;
 use crate::runtime_context::{self, RuntimeContext};
 use crate::tracking_copy::TrackingCopy;
@@ -28,6 +30,7 @@ pub trait Executor<A> {
         &self,
         parity_module: A,
         args: &[u8],
+        named_args: &[u8],
         base_key: Key,
         account: &Account,
         authorized_keys: BTreeSet<PublicKey>,
@@ -133,6 +136,7 @@ impl Executor<Module> for WasmiExecutor {
         &self,
         parity_module: Module,
         args: &[u8],
+        named_args: &[u8],
         base_key: Key,
         account: &Account,
         authorized_keys: BTreeSet<PublicKey>,
@@ -163,6 +167,7 @@ impl Executor<Module> for WasmiExecutor {
             };
 
         let address_generator = AddressGenerator::new(deploy_hash, phase);
+        let random_generator = RandomGenerator::new(deploy_hash, phase);
         let gas_counter: Gas = Gas::default();
 
         // Snapshot of effects before execution, so in case of error
@@ -181,11 +186,22 @@ impl Executor<Module> for WasmiExecutor {
             )
         };
 
+        let named_args: NamedArgs = if named_args.is_empty() {
+            NamedArgs::new()
+        } else {
+            on_fail_charge!(
+                bytesrepr::deserialize(named_args),
+                Gas::new(named_args.len().into()),
+                effects_snapshot
+            )
+        };
+
         let context = RuntimeContext::new(
             tc,
             &mut named_keys,
             access_rights,
             arguments,
+            named_args,
             authorized_keys,
             &account,
             base_key,
@@ -195,6 +211,8 @@ impl Executor<Module> for WasmiExecutor {
             gas_counter,
             FN_STORE_ID_INITIAL,
             Rc::new(RefCell::new(address_generator)),
+            Rc::new(RefCell::new(random_generator)),
+            0,
             protocol_version,
             correlation_id,
             phase,
@@ -248,6 +266,7 @@ impl Executor<Module> for WasmiExecutor {
             let address_generator = AddressGenerator::new(deploy_hash, phase);
             Rc::new(RefCell::new(address_generator))
         };
+        let random_generator = Rc::new(RefCell::new(RandomGenerator::new(deploy_hash, phase)));
         let gas_counter = Gas::default(); // maybe const?
 
         // Snapshot of effects before execution, so in case of error only nonce update
@@ -269,6 +288,7 @@ impl Executor<Module> for WasmiExecutor {
             &mut named_keys,
             access_rights,
             args,
+            NamedArgs::new(),
             authorization_keys,
             &account,
             base_key,
@@ -278,6 +298,8 @@ impl Executor<Module> for WasmiExecutor {
             gas_counter,
             FN_STORE_ID_INITIAL,
             address_generator,
+            random_generator,
+            0,
             protocol_version,
             correlation_id,
             phase,
@@ -371,12 +393,14 @@ impl Executor<Module> for WasmiExecutor {
         };
 
         let gas_counter = Gas::default();
+        let random_generator = Rc::new(RefCell::new(RandomGenerator::new(deploy_hash, phase)));
 
         let runtime_context = RuntimeContext::new(
             state,
             keys,
             access_rights.clone(),
             args,
+            NamedArgs::new(),
             authorization_keys.clone(),
             account,
             base_key,
@@ -386,6 +410,8 @@ impl Executor<Module> for WasmiExecutor {
             gas_counter,
             FN_STORE_ID_INITIAL,
             address_generator,
+            random_generator,
+            0,
             protocol_version,
             correlation_id,
             phase,
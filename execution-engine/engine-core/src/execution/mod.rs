@@ -1,7 +1,9 @@
 mod address_generator;
+mod crypto;
 mod error;
 #[macro_use]
 mod executor;
+mod random_generator;
 mod runtime;
 #[cfg(test)]
 mod tests;
@@ -9,8 +11,10 @@ mod tests;
 pub use self::address_generator::AddressGenerator;
 pub use self::error::Error;
 pub use self::executor::{Executor, WasmiExecutor};
+pub use self::random_generator::RandomGenerator;
 pub use self::runtime::{
-    extract_access_rights_from_keys, extract_access_rights_from_urefs, instance_and_memory, Runtime,
+    entry_point_module_bytes, extract_access_rights_from_keys, extract_access_rights_from_urefs,
+    instance_and_memory, Runtime,
 };
 
 pub const MINT_NAME: &str = "mint";
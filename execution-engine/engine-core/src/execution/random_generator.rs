@@ -0,0 +1,119 @@
+use std::collections::BTreeMap;
+
+use blake2::digest::{Input, VariableOutput};
+use blake2::VarBlake2b;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaChaRng;
+
+use contract_ffi::execution::Phase;
+
+const SEED_LENGTH: usize = 32;
+
+/// Prefixed to the seed material so that the streams never coincide with the one used by
+/// [`AddressGenerator`](super::AddressGenerator) for the same deploy and phase.
+const DOMAIN: &[u8] = b"casperlabs-random-bytes";
+
+/// A [`RandomGenerator`] produces the bytes returned to contracts by `runtime::random_bytes`.
+///
+/// Each call depth draws from its own ChaCha stream, seeded from the deploy hash, [`Phase`] and
+/// depth.  Streams are created on first use and then shared by every call made at that depth
+/// during the deploy, so two sibling calls don't see the same bytes.  The generator is entirely
+/// separate from [`AddressGenerator`](super::AddressGenerator), so drawing random bytes never
+/// changes the addresses of URefs created afterwards.
+pub struct RandomGenerator {
+    hash: [u8; 32],
+    phase: Phase,
+    streams: BTreeMap<u32, ChaChaRng>,
+}
+
+impl RandomGenerator {
+    /// Creates a [`RandomGenerator`] from a 32-byte hash digest and [`Phase`].
+    pub fn new(hash: [u8; 32], phase: Phase) -> RandomGenerator {
+        RandomGenerator {
+            hash,
+            phase,
+            streams: BTreeMap::new(),
+        }
+    }
+
+    /// Fills `dest` with the next bytes of the stream for `call_depth`.
+    pub fn fill_bytes(&mut self, call_depth: u32, dest: &mut [u8]) {
+        let hash = self.hash;
+        let phase = self.phase;
+        self.streams
+            .entry(call_depth)
+            .or_insert_with(|| Self::stream(hash, phase, call_depth))
+            .fill_bytes(dest)
+    }
+
+    fn stream(hash: [u8; 32], phase: Phase, call_depth: u32) -> ChaChaRng {
+        let mut seed: [u8; SEED_LENGTH] = [0u8; SEED_LENGTH];
+        // Safe to unwrap here because our seed length is constant and valid
+        let mut hasher = VarBlake2b::new(SEED_LENGTH).unwrap();
+        hasher.input(DOMAIN);
+        hasher.input(hash);
+        hasher.input([phase as u8]);
+        hasher.input(call_depth.to_le_bytes());
+        hasher.variable_result(|hash| seed.clone_from_slice(hash));
+        ChaChaRng::from_seed(seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use contract_ffi::execution::Phase;
+
+    use super::RandomGenerator;
+    use crate::execution::AddressGenerator;
+
+    const DEPLOY_HASH_1: [u8; 32] = [1u8; 32];
+    const DEPLOY_HASH_2: [u8; 32] = [2u8; 32];
+
+    fn next_bytes(generator: &mut RandomGenerator, call_depth: u32) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        generator.fill_bytes(call_depth, &mut bytes);
+        bytes
+    }
+
+    #[test]
+    fn should_generate_same_bytes_for_same_seed() {
+        let mut rg_a = RandomGenerator::new(DEPLOY_HASH_1, Phase::Session);
+        let mut rg_b = RandomGenerator::new(DEPLOY_HASH_1, Phase::Session);
+
+        assert_eq!(next_bytes(&mut rg_a, 0), next_bytes(&mut rg_b, 0));
+        assert_eq!(next_bytes(&mut rg_a, 1), next_bytes(&mut rg_b, 1));
+    }
+
+    #[test]
+    fn should_generate_different_bytes_for_different_seeds() {
+        let mut rg_a = RandomGenerator::new(DEPLOY_HASH_1, Phase::Session);
+        let mut rg_b = RandomGenerator::new(DEPLOY_HASH_2, Phase::Session);
+        let mut rg_c = RandomGenerator::new(DEPLOY_HASH_1, Phase::Payment);
+
+        let bytes_a = next_bytes(&mut rg_a, 0);
+        assert_ne!(bytes_a, next_bytes(&mut rg_b, 0));
+        assert_ne!(bytes_a, next_bytes(&mut rg_c, 0));
+        assert_ne!(bytes_a, next_bytes(&mut rg_a, 1));
+    }
+
+    #[test]
+    fn should_continue_stream_for_same_call_depth() {
+        let mut generator = RandomGenerator::new(DEPLOY_HASH_1, Phase::Session);
+        let first = next_bytes(&mut generator, 1);
+        next_bytes(&mut generator, 0);
+        let second = next_bytes(&mut generator, 1);
+
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn should_not_coincide_with_address_generator() {
+        let mut address_generator = AddressGenerator::new(DEPLOY_HASH_1, Phase::Session);
+        let mut random_generator = RandomGenerator::new(DEPLOY_HASH_1, Phase::Session);
+
+        assert_ne!(
+            address_generator.create_address(),
+            next_bytes(&mut random_generator, 0)
+        );
+    }
+}
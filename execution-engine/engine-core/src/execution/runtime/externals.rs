@@ -116,6 +116,14 @@ where
                 Ok(None)
             }
 
+            FunctionIndex::LoadNamedArgFuncIndex => {
+                // args(0) = pointer to argument name in Wasm memory
+                // args(1) = size of argument name
+                let (name_ptr, name_size) = Args::parse(args)?;
+                let size = self.load_named_arg(name_ptr, name_size)?;
+                Ok(Some(RuntimeValue::I32(size as i32)))
+            }
+
             FunctionIndex::RetFuncIndex => {
                 // args(0) = pointer to value
                 // args(1) = size of value
@@ -156,6 +164,111 @@ where
                 Ok(Some(RuntimeValue::I32(size as i32)))
             }
 
+            FunctionIndex::CallContractWithNamedArgsFuncIndex => {
+                // args(0) = pointer to key where contract is at in global state
+                // args(1) = size of key
+                // args(2) = pointer to named arguments in Wasm memory
+                // args(3) = size of named arguments
+                // args(4) = pointer to extra supplied urefs
+                // args(5) = size of extra urefs
+                let (
+                    key_ptr,
+                    key_size,
+                    named_args_ptr,
+                    named_args_size,
+                    extra_urefs_ptr,
+                    extra_urefs_size,
+                ): (_, _, _, u32, _, u32) = Args::parse(args)?;
+
+                let key_contract: Key = self.key_from_mem(key_ptr, key_size)?;
+                let named_args_bytes =
+                    self.bytes_from_mem(named_args_ptr, named_args_size as usize)?;
+                let urefs_bytes =
+                    self.bytes_from_mem(extra_urefs_ptr, extra_urefs_size as usize)?;
+
+                let size = self.call_contract_with_named_args(
+                    key_contract,
+                    named_args_bytes,
+                    urefs_bytes,
+                )?;
+                Ok(Some(RuntimeValue::I32(size as i32)))
+            }
+
+            FunctionIndex::CallEntryPointFuncIndex => {
+                // args(0) = pointer to key where contract is at in global state
+                // args(1) = size of key
+                // args(2) = pointer to entry point name in Wasm memory
+                // args(3) = size of entry point name
+                // args(4) = pointer to named arguments in Wasm memory
+                // args(5) = size of named arguments
+                // args(6) = pointer to extra supplied urefs
+                // args(7) = size of extra urefs
+                let (
+                    key_ptr,
+                    key_size,
+                    entry_point_ptr,
+                    entry_point_size,
+                    named_args_ptr,
+                    named_args_size,
+                    extra_urefs_ptr,
+                    extra_urefs_size,
+                ): (_, _, _, _, _, u32, _, u32) = Args::parse(args)?;
+
+                let key_contract: Key = self.key_from_mem(key_ptr, key_size)?;
+                let entry_point_name = self.string_from_mem(entry_point_ptr, entry_point_size)?;
+                let named_args_bytes =
+                    self.bytes_from_mem(named_args_ptr, named_args_size as usize)?;
+                let urefs_bytes =
+                    self.bytes_from_mem(extra_urefs_ptr, extra_urefs_size as usize)?;
+
+                let size = self.call_entry_point(
+                    key_contract,
+                    entry_point_name,
+                    named_args_bytes,
+                    urefs_bytes,
+                )?;
+                Ok(Some(RuntimeValue::I32(size as i32)))
+            }
+
+            FunctionIndex::CallVersionedContractFuncIndex => {
+                // args(0) = pointer to key of the contract package in global state
+                // args(1) = size of key
+                // args(2) = version of the contract to call
+                // args(3) = pointer to entry point name in Wasm memory
+                // args(4) = size of entry point name
+                // args(5) = pointer to named arguments in Wasm memory
+                // args(6) = size of named arguments
+                // args(7) = pointer to extra supplied urefs
+                // args(8) = size of extra urefs
+                let (
+                    key_ptr,
+                    key_size,
+                    version,
+                    entry_point_ptr,
+                    entry_point_size,
+                    named_args_ptr,
+                    named_args_size,
+                    extra_urefs_ptr,
+                    extra_urefs_size,
+                ): (_, _, _, _, _, _, u32, _, u32) = Args::parse(args)?;
+
+                let package_key: Key = self.key_from_mem(key_ptr, key_size)?;
+                let entry_point_name = self.string_from_mem(entry_point_ptr, entry_point_size)?;
+                let named_args_bytes =
+                    self.bytes_from_mem(named_args_ptr, named_args_size as usize)?;
+                let urefs_bytes =
+                    self.bytes_from_mem(extra_urefs_ptr, extra_urefs_size as usize)?;
+
+                let size = self.call_versioned_contract(
+                    package_key,
+                    version,
+                    entry_point_name,
+                    named_args_bytes,
+                    urefs_bytes,
+                )?;
+                Ok(Some(RuntimeValue::I32(size as i32)))
+            }
+
             FunctionIndex::GetCallResultFuncIndex => {
                 // args(0) = pointer to destination in Wasm memory
                 let dest_ptr = Args::parse(args)?;
@@ -265,6 +378,119 @@ where
                 Ok(None)
             }
 
+            FunctionIndex::StoreContractAtHashIndex => {
+                // args(0) = pointer to entry points in Wasm memory
+                // args(1) = size of entry points
+                // args(2) = pointer to additional unforgable names
+                //           to be saved with the contract
+                // args(3) = size of the additional unforgable names
+                // args(4) = pointer to a Wasm memory where we will save
+                //           hash of the new contract
+                let (entry_points_ptr, entry_points_size, urefs_ptr, urefs_size, hash_ptr): (
+                    _,
+                    u32,
+                    _,
+                    u32,
+                    _,
+                ) = Args::parse(args)?;
+                let entry_points_bytes =
+                    self.bytes_from_mem(entry_points_ptr, entry_points_size as usize)?;
+                let entry_points =
+                    bytesrepr::deserialize(&entry_points_bytes).map_err(Error::BytesRepr)?;
+                let uref_bytes = self.bytes_from_mem(urefs_ptr, urefs_size as usize)?;
+                let urefs = bytesrepr::deserialize(&uref_bytes).map_err(Error::BytesRepr)?;
+                let contract_hash = self.store_contract_at_hash(entry_points, urefs)?;
+                self.function_address(contract_hash, hash_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::AddContractVersionIndex => {
+                // args(0) = pointer to key of the contract package in global state
+                // args(1) = size of key
+                // args(2) = pointer to entry points in Wasm memory
+                // args(3) = size of entry points
+                // args(4) = pointer to additional unforgable names
+                //           to be saved with the contract
+                // args(5) = size of the additional unforgable names
+                // args(6) = pointer to a Wasm memory where we will save
+                //           the number of the new version
+                let (
+                    key_ptr,
+                    key_size,
+                    entry_points_ptr,
+                    entry_points_size,
+                    urefs_ptr,
+                    urefs_size,
+                    version_ptr,
+                ): (_, _, _, u32, _, u32, _) = Args::parse(args)?;
+                let package_key = self.key_from_mem(key_ptr, key_size)?;
+                let entry_points_bytes =
+                    self.bytes_from_mem(entry_points_ptr, entry_points_size as usize)?;
+                let entry_points =
+                    bytesrepr::deserialize(&entry_points_bytes).map_err(Error::BytesRepr)?;
+                let uref_bytes = self.bytes_from_mem(urefs_ptr, urefs_size as usize)?;
+                let urefs = bytesrepr::deserialize(&uref_bytes).map_err(Error::BytesRepr)?;
+                let version = self.add_contract_version(package_key, entry_points, urefs)?;
+                let version_bytes = version.to_bytes().map_err(Error::BytesRepr)?;
+                self.memory
+                    .set(version_ptr, &version_bytes)
+                    .map_err(Error::Interpreter)?;
+                Ok(None)
+            }
+
+            FunctionIndex::AddFunctionVersionIndex => {
+                // args(0) = pointer to key of the contract package in global state
+                // args(1) = size of key
+                // args(2) = pointer to function name in Wasm memory
+                // args(3) = size of the name
+                // args(4) = pointer to additional unforgable names
+                //           to be saved with the contract
+                // args(5) = size of the additional unforgable names
+                // args(6) = pointer to a Wasm memory where we will save
+                //           the number of the new version
+                let (key_ptr, key_size, name_ptr, name_size, urefs_ptr, urefs_size, version_ptr): (
+                    _,
+                    _,
+                    _,
+                    _,
+                    _,
+                    u32,
+                    _,
+                ) = Args::parse(args)?;
+                let package_key = self.key_from_mem(key_ptr, key_size)?;
+                let fn_bytes = self.get_function_by_name(name_ptr, name_size)?;
+                let uref_bytes = self.bytes_from_mem(urefs_ptr, urefs_size as usize)?;
+                let urefs = bytesrepr::deserialize(&uref_bytes).map_err(Error::BytesRepr)?;
+                let version = self.add_function_version(package_key, fn_bytes, urefs)?;
+                let version_bytes = version.to_bytes().map_err(Error::BytesRepr)?;
+                self.memory
+                    .set(version_ptr, &version_bytes)
+                    .map_err(Error::Interpreter)?;
+                Ok(None)
+            }
+
+            FunctionIndex::SetContractVersionEnabledIndex => {
+                // args(0) = pointer to key of the contract package in global state
+                // args(1) = size of key
+                // args(2) = version to enable or disable
+                // args(3) = 1 to enable the version, 0 to disable it
+                let (key_ptr, key_size, version, is_enabled): (_, _, _, u32) = Args::parse(args)?;
+                let package_key = self.key_from_mem(key_ptr, key_size)?;
+                let ret =
+                    self.set_contract_version_enabled(package_key, version, is_enabled != 0)?;
+                Ok(Some(RuntimeValue::I32(contract_api::i32_from(ret))))
+            }
+
+            FunctionIndex::SetLatestContractVersionIndex => {
+                // args(0) = pointer to key of the contract package in global state
+                // args(1) = size of key
+                // args(2) = version to designate as the latest
+                let (key_ptr, key_size, version) = Args::parse(args)?;
+                let package_key = self.key_from_mem(key_ptr, key_size)?;
+                let ret = self.set_latest_contract_version(package_key, version)?;
+                Ok(Some(RuntimeValue::I32(contract_api::i32_from(ret))))
+            }
+
             FunctionIndex::IsValidFnIndex => {
                 // args(0) = pointer to value to validate
                 // args(1) = size of value
@@ -447,6 +673,71 @@ where
                 let ret = self.get_system_contract(system_contract_index, dest_ptr, dest_size)?;
                 Ok(Some(RuntimeValue::I32(contract_api::i32_from(ret))))
             }
+
+            FunctionIndex::EmitEventIndex => {
+                // args(0) = pointer to topic in Wasm memory
+                // args(1) = size of topic
+                // args(2) = pointer to serialized payload in Wasm memory
+                // args(3) = size of payload
+                let (topic_ptr, topic_size, payload_ptr, payload_size) = Args::parse(args)?;
+                self.emit_event(topic_ptr, topic_size, payload_ptr, payload_size)?;
+                Ok(None)
+            }
+
+            FunctionIndex::HashIndex => {
+                // args(0) = hash algorithm index
+                // args(1) = pointer to input in Wasm memory
+                // args(2) = size of input
+                // args(3) = pointer to destination for the digest in Wasm memory
+                // args(4) = size of destination
+                let (algorithm, input_ptr, input_size, dest_ptr, dest_size) = Args::parse(args)?;
+                let ret = self.hash(algorithm, input_ptr, input_size, dest_ptr, dest_size)?;
+                Ok(Some(RuntimeValue::I32(contract_api::i32_from(ret))))
+            }
+
+            FunctionIndex::VerifySignatureIndex => {
+                // args(0) = signature algorithm index
+                // args(1) = pointer to public key in Wasm memory
+                // args(2) = size of public key
+                // args(3) = pointer to message in Wasm memory
+                // args(4) = size of message
+                // args(5) = pointer to signature in Wasm memory
+                // args(6) = size of signature
+                let (
+                    algorithm,
+                    public_key_ptr,
+                    public_key_size,
+                    message_ptr,
+                    message_size,
+                    signature_ptr,
+                    signature_size,
+                ) = Args::parse(args)?;
+                let ret = self.verify_signature(
+                    algorithm,
+                    public_key_ptr,
+                    public_key_size,
+                    message_ptr,
+                    message_size,
+                    signature_ptr,
+                    signature_size,
+                )?;
+                Ok(Some(RuntimeValue::I32(contract_api::i32_from(ret))))
+            }
+
+            FunctionIndex::RandomBytesIndex => {
+                // args(0) = pointer to destination in Wasm memory
+                // args(1) = number of bytes to write
+                let (dest_ptr, dest_size) = Args::parse(args)?;
+                self.random_bytes(dest_ptr, dest_size)?;
+                Ok(None)
+            }
+
+            FunctionIndex::SetInactivityPeriodIndex => {
+                // args(0) = pointer to serialized inactivity period in Wasm memory
+                let inactivity_period_ptr = Args::parse(args)?;
+                let ret = self.set_inactivity_period(inactivity_period_ptr)?;
+                Ok(Some(RuntimeValue::I32(contract_api::i32_from(ret))))
+            }
         }
     }
 }
@@ -13,15 +13,26 @@ use contract_ffi::args_parser::ArgsParser;
 use contract_ffi::bytesrepr::{deserialize, ToBytes, U32_SIZE};
 use contract_ffi::contract_api::system::{TransferResult, TransferredTo};
 use contract_ffi::contract_api::Error as ApiError;
+use contract_ffi::crypto::{HashAlgorithm, SignatureAlgorithm, HASH_LENGTH};
+use contract_ffi::event::Event;
 use contract_ffi::key::Key;
+use contract_ffi::named_args::NamedArgs;
 use contract_ffi::system_contracts::{self, mint, SystemContract};
 use contract_ffi::uref::{AccessRights, URef};
-use contract_ffi::value::account::{ActionType, PublicKey, PurseId, Weight, PUBLIC_KEY_SIZE};
-use contract_ffi::value::{Account, ProtocolVersion, Value, U512};
+use contract_ffi::value::account::{
+    ActionType, BlockTime, PublicKey, PurseId, Weight, BLOCKTIME_SER_SIZE, PUBLIC_KEY_SIZE,
+};
+use contract_ffi::value::contract::DEFAULT_ENTRY_POINT_NAME;
+use contract_ffi::value::{
+    Account, CLValue, Contract, ContractPackage, ContractVersion, EntryPointAccess, EntryPoints,
+    ProtocolVersion, Value, U512,
+};
 use engine_shared::gas::Gas;
+use engine_shared::transform::TypeMismatch;
 use engine_storage::global_state::StateReader;
 
-use super::{Error, MINT_NAME, POS_NAME};
+use super::{crypto, Error, MINT_NAME, POS_NAME};
+use crate::engine_state::SYSTEM_ACCOUNT_ADDR;
 use crate::resolvers::create_module_resolver;
 use crate::resolvers::memory_resolver::MemoryResolver;
 use crate::runtime_context::RuntimeContext;
@@ -51,6 +62,49 @@ pub fn rename_export_to_call(module: &mut Module, name: String) {
     main_export.push_str("call");
 }
 
+/// Returns the module to execute when `entry_point_name` of `contract` is called by `account`
+/// with `named_args`.
+///
+/// A contract stored without entry points can only be called through
+/// `DEFAULT_ENTRY_POINT_NAME`, in which case its bytes are returned as they are.  Otherwise the
+/// entry point's access and parameters are checked, and the module is reduced to the code
+/// reachable from the entry point, which is renamed to `call`.
+pub fn entry_point_module_bytes(
+    contract: &Contract,
+    entry_point_name: &str,
+    named_args: &NamedArgs,
+    account: &Account,
+) -> Result<Vec<u8>, Error> {
+    if contract.entry_points().is_empty() && entry_point_name == DEFAULT_ENTRY_POINT_NAME {
+        return Ok(contract.bytes().to_vec());
+    }
+
+    let entry_point = contract
+        .entry_points()
+        .get(entry_point_name)
+        .ok_or_else(|| Error::NoSuchEntryPoint(entry_point_name.to_string()))?;
+
+    if entry_point.access() == EntryPointAccess::System && account.pub_key() != SYSTEM_ACCOUNT_ADDR
+    {
+        return Err(Error::EntryPointAccessDenied(entry_point_name.to_string()));
+    }
+
+    for parameter in entry_point.parameters() {
+        let cl_type = named_args.get(parameter.name()).map(CLValue::cl_type);
+        if cl_type != Some(parameter.cl_type()) {
+            return Err(Error::InvalidEntryPointArgument(
+                parameter.name().to_string(),
+            ));
+        }
+    }
+
+    let mut module: Module = parity_wasm::deserialize_buffer(contract.bytes())?;
+    pwasm_utils::optimize(&mut module, vec![entry_point_name])
+        .map_err(|_| Error::NoSuchEntryPoint(entry_point_name.to_string()))?;
+    rename_export_to_call(&mut module, entry_point_name.to_string());
+    parity_wasm::serialize(module).map_err(Into::into)
+}
+
 pub fn instance_and_memory(
     parity_module: Module,
     protocol_version: ProtocolVersion,
@@ -121,9 +175,11 @@ pub fn extract_access_rights_from_keys<I: IntoIterator<Item = Key>>(
         .collect()
 }
 
+#[allow(clippy::too_many_arguments)]
 fn sub_call<R: StateReader<Key, Value>>(
     parity_module: Module,
     args: Vec<Vec<u8>>,
+    named_args: NamedArgs,
     named_keys: &mut BTreeMap<String, Key>,
     key: Key,
     current_runtime: &mut Runtime<R>,
@@ -155,6 +211,7 @@ where
             named_keys,
             access_rights,
             args,
+            named_args,
             current_runtime.context.authorization_keys().clone(),
             &current_runtime.context.account(),
             key,
@@ -164,6 +221,8 @@ where
             current_runtime.context.gas_counter(),
             current_runtime.context.fn_store_id(),
             current_runtime.context.address_generator(),
+            current_runtime.context.random_generator(),
+            current_runtime.context.call_depth() + 1,
             protocol_version,
             current_runtime.context.correlation_id(),
             current_runtime.context.phase(),
@@ -251,6 +310,12 @@ where
         }
     }
 
+    /// Charges `base` plus `per_byte` for each of `size` bytes.
+    fn gas_per_byte(&mut self, base: u32, per_byte: u32, size: u64) -> Result<(), Trap> {
+        let cost = U512::from(base) + U512::from(per_byte) * U512::from(size);
+        self.gas(Gas::new(cost))
+    }
+
     fn bytes_from_mem(&self, ptr: u32, size: usize) -> Result<Vec<u8>, Error> {
         self.memory.get(ptr, size).map_err(Into::into)
     }
@@ -318,6 +383,23 @@ where
         }
     }
 
+    /// Load the argument passed under the given name into the runtime buffer
+    /// so that a subsequent `get_arg` can return it to the caller.  The
+    /// argument is serialized as a `CLValue`, so the caller can check its type.
+    pub fn load_named_arg(&mut self, name_ptr: u32, name_size: u32) -> Result<isize, Trap> {
+        let name = self.string_from_mem(name_ptr, name_size)?;
+        match self.context.named_args().get(&name) {
+            Some(cl_value) => {
+                self.host_buf = cl_value.to_bytes().map_err(Error::BytesRepr)?;
+                Ok(self.host_buf.len() as isize)
+            }
+            None => {
+                self.host_buf.clear();
+                Ok(-1)
+            }
+        }
+    }
+
     /// Load the uref known by the given name into the Wasm memory
     pub fn get_key(&mut self, name_ptr: u32, name_size: u32) -> Result<usize, Trap> {
         let name = self.string_from_mem(name_ptr, name_size)?;
@@ -442,34 +524,138 @@ where
         args_bytes: Vec<u8>,
         urefs_bytes: Vec<u8>,
     ) -> Result<usize, Error> {
-        let (args, module, mut refs, protocol_version) = {
-            match self.context.read_gs(&key)? {
-                None => Err(Error::KeyNotFound(key)),
-                Some(value) => {
-                    if let Value::Contract(contract) = value {
-                        let args: Vec<Vec<u8>> = deserialize(&args_bytes)?;
-                        let module = parity_wasm::deserialize_buffer(contract.bytes())?;
-
-                        Ok((
-                            args,
-                            module,
-                            contract.named_keys().clone(),
-                            contract.protocol_version(),
-                        ))
-                    } else {
-                        Err(Error::FunctionNotFound(format!(
-                            "Value at {:?} is not a contract",
-                            key
-                        )))
-                    }
-                }
+        let args: Vec<Vec<u8>> = deserialize(&args_bytes)?;
+        self.call_contract_with_args(
+            key,
+            DEFAULT_ENTRY_POINT_NAME,
+            args,
+            NamedArgs::new(),
+            urefs_bytes,
+        )
+    }
+
+    /// Calls contract living under a `key`, with supplied named arguments and
+    /// extra `urefs`.
+    pub fn call_contract_with_named_args(
+        &mut self,
+        key: Key,
+        named_args_bytes: Vec<u8>,
+        urefs_bytes: Vec<u8>,
+    ) -> Result<usize, Error> {
+        let named_args: NamedArgs = deserialize(&named_args_bytes)?;
+        self.call_contract_with_args(
+            key,
+            DEFAULT_ENTRY_POINT_NAME,
+            Vec::new(),
+            named_args,
+            urefs_bytes,
+        )
+    }
+
+    /// Calls the entry point named `entry_point_name` of the contract living
+    /// under a `key`, with supplied named arguments and extra `urefs`.
+    pub fn call_entry_point(
+        &mut self,
+        key: Key,
+        entry_point_name: String,
+        named_args_bytes: Vec<u8>,
+        urefs_bytes: Vec<u8>,
+    ) -> Result<usize, Error> {
+        let named_args: NamedArgs = deserialize(&named_args_bytes)?;
+        self.call_contract_with_args(key, &entry_point_name, Vec::new(), named_args, urefs_bytes)
+    }
+
+    /// Calls the entry point named `entry_point_name` of the given version of
+    /// the contract package living under a `package_key`, with supplied named
+    /// arguments and extra `urefs`.
+    pub fn call_versioned_contract(
+        &mut self,
+        package_key: Key,
+        version: ContractVersion,
+        entry_point_name: String,
+        named_args_bytes: Vec<u8>,
+        urefs_bytes: Vec<u8>,
+    ) -> Result<usize, Error> {
+        let package: ContractPackage = self.context.read_gs_typed(&package_key)?;
+        let contract_key = package.enabled_version(version)?;
+        let contract: Contract = self.context.read_gs_typed(&contract_key)?;
+        let contract = package.version_contract(contract);
+        let named_args: NamedArgs = deserialize(&named_args_bytes)?;
+        self.call_stored_contract(
+            package_key,
+            contract,
+            &entry_point_name,
+            Vec::new(),
+            named_args,
+            urefs_bytes,
+        )
+    }
+
+    /// Returns the contract run when calling the key `key`: either the
+    /// contract stored under it, or the latest version of the contract
+    /// package stored under it.
+    fn contract_to_call(&mut self, key: Key) -> Result<Contract, Error> {
+        match self.context.read_gs(&key)? {
+            None => Err(Error::KeyNotFound(key)),
+            Some(Value::Contract(contract)) => Ok(contract),
+            Some(Value::ContractPackage(package)) => {
+                let contract_key = package.latest_version_key()?;
+                let contract: Contract = self.context.read_gs_typed(&contract_key)?;
+                Ok(package.version_contract(contract))
             }
-        }?;
+            Some(_) => Err(Error::FunctionNotFound(format!(
+                "Value at {:?} is not a contract",
+                key
+            ))),
+        }
+    }
+
+    fn call_contract_with_args(
+        &mut self,
+        key: Key,
+        entry_point_name: &str,
+        args: Vec<Vec<u8>>,
+        named_args: NamedArgs,
+        urefs_bytes: Vec<u8>,
+    ) -> Result<usize, Error> {
+        let contract = self.contract_to_call(key)?;
+        self.call_stored_contract(
+            key,
+            contract,
+            entry_point_name,
+            args,
+            named_args,
+            urefs_bytes,
+        )
+    }
+
+    /// Runs `contract` with `key` as its base key.  For a version of a
+    /// contract package, the key of the package is the base key, so that all
+    /// versions share its named keys and local state.
+    fn call_stored_contract(
+        &mut self,
+        key: Key,
+        contract: Contract,
+        entry_point_name: &str,
+        args: Vec<Vec<u8>>,
+        named_args: NamedArgs,
+        urefs_bytes: Vec<u8>,
+    ) -> Result<usize, Error> {
+        let module_bytes = entry_point_module_bytes(
+            &contract,
+            entry_point_name,
+            &named_args,
+            self.context.account(),
+        )?;
+        let module = parity_wasm::deserialize_buffer(&module_bytes)?;
+        let protocol_version = contract.protocol_version();
+        let mut refs = contract.named_keys().clone();
 
         let extra_urefs = self.context.deserialize_keys(&urefs_bytes)?;
         let result = sub_call(
             module,
             args,
+            named_args,
             &mut refs,
             key,
             self,
@@ -522,6 +708,154 @@ where
         Ok(new_hash)
     }
 
+    /// Returns a contract made of the current module, reduced to the code
+    /// reachable from the given entry points.  Every entry point must name a
+    /// function exported by the module.
+    fn contract_with_entry_points(
+        &self,
+        entry_points: EntryPoints,
+        named_keys: BTreeMap<String, Key>,
+    ) -> Result<Contract, Error> {
+        let mut module = self.module.clone();
+        for name in entry_points.keys() {
+            let is_exported = module
+                .export_section()
+                .and_then(|es| es.entries().iter().find(|e| e.field() == name))
+                .is_some();
+            if !is_exported {
+                return Err(Error::FunctionNotFound(name.clone()));
+            }
+        }
+        let exports: Vec<&str> = entry_points.keys().map(String::as_str).collect();
+        pwasm_utils::optimize(&mut module, exports)
+            .map_err(|_| Error::FunctionNotFound(format!("{:?}", entry_points.keys())))?;
+        let bytes = parity_wasm::serialize(module)?;
+
+        Ok(
+            Contract::new(bytes, named_keys, self.context.protocol_version())
+                .with_entry_points(entry_points),
+        )
+    }
+
+    /// Stores the current module, reduced to the code reachable from the given
+    /// entry points, into the GlobalState under a new hash.
+    pub fn store_contract_at_hash(
+        &mut self,
+        entry_points: EntryPoints,
+        named_keys: BTreeMap<String, Key>,
+    ) -> Result<[u8; 32], Error> {
+        let contract = self.contract_with_entry_points(entry_points, named_keys)?;
+        let new_hash = self.context.store_function_at_hash(contract.into())?;
+        Ok(new_hash)
+    }
+
+    /// Stores the current module, reduced to the code reachable from the given
+    /// entry points, under a new hash and adds it as a new version of the
+    /// contract package living under `package_key`.
+    pub fn add_contract_version(
+        &mut self,
+        package_key: Key,
+        entry_points: EntryPoints,
+        named_keys: BTreeMap<String, Key>,
+    ) -> Result<ContractVersion, Error> {
+        let contract = self.contract_with_entry_points(entry_points, BTreeMap::new())?;
+        self.add_version(package_key, contract, named_keys)
+    }
+
+    /// Stores a function, already serialized as a module by
+    /// `get_function_by_name`, under a new hash and adds it as a new version
+    /// of the contract package living under `package_key`.
+    pub fn add_function_version(
+        &mut self,
+        package_key: Key,
+        fn_bytes: Vec<u8>,
+        named_keys: BTreeMap<String, Key>,
+    ) -> Result<ContractVersion, Error> {
+        let contract = Contract::new(fn_bytes, BTreeMap::new(), self.context.protocol_version());
+        self.add_version(package_key, contract, named_keys)
+    }
+
+    /// Stores `contract` under a new hash, adds it to the contract package
+    /// living under `package_key` as its latest version, and adds
+    /// `named_keys` to the named keys of the package.
+    fn add_version(
+        &mut self,
+        package_key: Key,
+        contract: Contract,
+        mut named_keys: BTreeMap<String, Key>,
+    ) -> Result<ContractVersion, Error> {
+        named_keys
+            .values()
+            .try_for_each(|key| self.context.validate_key(key))?;
+        let mut package = self.read_package_for_update(package_key)?;
+        let contract_hash = self.context.store_function_at_hash(contract.into())?;
+        let version = package.add_version(Key::Hash(contract_hash));
+        package.named_keys_append(&mut named_keys);
+        self.context.write_contract_package(package_key, package)?;
+        Ok(version)
+    }
+
+    /// Reads the contract package living under `package_key`.  A contract
+    /// stored there before contract packages existed becomes version 1 of a
+    /// new package, which takes over its named keys.
+    fn read_package_for_update(&mut self, package_key: Key) -> Result<ContractPackage, Error> {
+        match self.context.read_gs(&package_key)? {
+            None => Err(Error::KeyNotFound(package_key)),
+            Some(Value::ContractPackage(package)) => Ok(package),
+            Some(Value::Contract(contract)) => {
+                let (bytes, mut named_keys, entry_points, protocol_version) =
+                    contract.destructure();
+                let contract = Contract::new(bytes, BTreeMap::new(), protocol_version)
+                    .with_entry_points(entry_points);
+                let contract_hash = self.context.store_function_at_hash(contract.into())?;
+                let mut package = ContractPackage::new();
+                package.add_version(Key::Hash(contract_hash));
+                package.named_keys_append(&mut named_keys);
+                Ok(package)
+            }
+            Some(other) => Err(Error::TypeMismatch(TypeMismatch::new(
+                "ContractPackage".to_string(),
+                other.type_string(),
+            ))),
+        }
+    }
+
+    /// Enables or disables `version` of the contract package living under
+    /// `package_key`.
+    pub fn set_contract_version_enabled(
+        &mut self,
+        package_key: Key,
+        version: ContractVersion,
+        is_enabled: bool,
+    ) -> Result<Result<(), ApiError>, Error> {
+        let mut package: ContractPackage = self.context.read_gs_typed(&package_key)?;
+        let result = if is_enabled {
+            package.enable_version(version)
+        } else {
+            package.disable_version(version)
+        };
+        if let Err(error) = result {
+            return Ok(Err(error.into()));
+        }
+        self.context.write_contract_package(package_key, package)?;
+        Ok(Ok(()))
+    }
+
+    /// Designates `version` as the latest version of the contract package
+    /// living under `package_key`.
+    pub fn set_latest_contract_version(
+        &mut self,
+        package_key: Key,
+        version: ContractVersion,
+    ) -> Result<Result<(), ApiError>, Error> {
+        let mut package: ContractPackage = self.context.read_gs_typed(&package_key)?;
+        if let Err(error) = package.set_latest_version(version) {
+            return Ok(Err(error.into()));
+        }
+        self.context.write_contract_package(package_key, package)?;
+        Ok(Ok(()))
+    }
+
     /// Writes function address (`hash_bytes`) into the Wasm memory (at
     /// `dest_ptr` pointer).
     fn function_address(&mut self, hash_bytes: [u8; 32], dest_ptr: u32) -> Result<(), Trap> {
@@ -985,4 +1319,120 @@ where
             Err(error) => Err(Error::Interpreter(error).into()),
         }
     }
+
+    /// Charges gas for an event of `topic_size + payload_size` bytes and records the event
+    /// read from Wasm memory.  Both the topic and payload must deserialize, the latter as a
+    /// `CLValue`.
+    fn emit_event(
+        &mut self,
+        topic_ptr: u32,
+        topic_size: u32,
+        payload_ptr: u32,
+        payload_size: u32,
+    ) -> Result<(), Trap> {
+        let wasm_costs = *self.context.protocol_data().wasm_costs();
+        let event_size = u64::from(topic_size) + u64::from(payload_size);
+        self.gas_per_byte(wasm_costs.regular, wasm_costs.memcpy, event_size)?;
+
+        let topic = self.string_from_mem(topic_ptr, topic_size)?;
+        let payload_bytes = self.bytes_from_mem(payload_ptr, payload_size as usize)?;
+        let payload: CLValue = deserialize(&payload_bytes).map_err(Error::BytesRepr)?;
+        self.context.emit_event(Event::new(topic, payload));
+        Ok(())
+    }
+
+    /// Hashes the input read from Wasm memory and writes the digest to `dest_ptr`.
+    fn hash(
+        &mut self,
+        algorithm: u32,
+        input_ptr: u32,
+        input_size: u32,
+        dest_ptr: u32,
+        dest_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let algorithm = match HashAlgorithm::try_from(algorithm) {
+            Ok(algorithm) => algorithm,
+            Err(error) => return Ok(Err(error)),
+        };
+        if dest_size as usize != HASH_LENGTH {
+            return Ok(Err(ApiError::InvalidArgument));
+        }
+
+        let wasm_costs = *self.context.protocol_data().wasm_costs();
+        self.gas_per_byte(wasm_costs.hash, wasm_costs.hash_byte, input_size.into())?;
+
+        let input = self.bytes_from_mem(input_ptr, input_size as usize)?;
+        let digest = crypto::hash(algorithm, &input);
+        self.memory
+            .set(dest_ptr, &digest)
+            .map_err(Error::Interpreter)?;
+        Ok(Ok(()))
+    }
+
+    /// Verifies a signature read from Wasm memory, returning `ApiError::InvalidSignature` if it is
+    /// malformed or doesn't match the public key and message.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_signature(
+        &mut self,
+        algorithm: u32,
+        public_key_ptr: u32,
+        public_key_size: u32,
+        message_ptr: u32,
+        message_size: u32,
+        signature_ptr: u32,
+        signature_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        // Every verification pays the fixed cost, even one rejected for its algorithm or size
+        let wasm_costs = *self.context.protocol_data().wasm_costs();
+        self.gas(Gas::new(wasm_costs.verify_signature.into()))?;
+
+        let algorithm = match SignatureAlgorithm::try_from(algorithm) {
+            Ok(algorithm) => algorithm,
+            Err(error) => return Ok(Err(error)),
+        };
+
+        let input_size =
+            u64::from(public_key_size) + u64::from(message_size) + u64::from(signature_size);
+        self.gas_per_byte(0, wasm_costs.verify_signature_byte, input_size)?;
+
+        let public_key = self.bytes_from_mem(public_key_ptr, public_key_size as usize)?;
+        let message = self.bytes_from_mem(message_ptr, message_size as usize)?;
+        let signature = self.bytes_from_mem(signature_ptr, signature_size as usize)?;
+        if crypto::verify_signature(algorithm, &public_key, &message, &signature) {
+            Ok(Ok(()))
+        } else {
+            Ok(Err(ApiError::InvalidSignature))
+        }
+    }
+
+    /// Writes `dest_size` deterministic random bytes to `dest_ptr`.
+    fn random_bytes(&mut self, dest_ptr: u32, dest_size: u32) -> Result<(), Trap> {
+        let wasm_costs = *self.context.protocol_data().wasm_costs();
+        self.gas_per_byte(wasm_costs.regular, wasm_costs.memcpy, dest_size.into())?;
+
+        let mut bytes = vec![0u8; dest_size as usize];
+        self.context.random_bytes(&mut bytes);
+        self.memory
+            .set(dest_ptr, &bytes)
+            .map_err(|e| Error::Interpreter(e).into())
+    }
+
+    /// Sets the inactivity period limit of the account, after which the key recovery threshold
+    /// applies to key management.
+    fn set_inactivity_period(
+        &mut self,
+        inactivity_period_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let inactivity_period_bytes =
+            self.bytes_from_mem(inactivity_period_ptr, BLOCKTIME_SER_SIZE)?;
+        let inactivity_period: BlockTime =
+            deserialize(&inactivity_period_bytes).map_err(Error::BytesRepr)?;
+
+        match self.context.set_inactivity_period(inactivity_period) {
+            Ok(()) => Ok(Ok(())),
+            Err(Error::KeyManagementDenied) => Ok(Err(ApiError::PermissionDenied)),
+            Err(Error::InvalidInactivityPeriod) => Ok(Err(ApiError::InvalidArgument)),
+            Err(e) => Err(e.into()),
+        }
+    }
 }
@@ -6,6 +6,7 @@ extern crate core;
 
 // third-party dependencies
 extern crate blake2;
+extern crate ed25519_dalek;
 extern crate failure;
 extern crate itertools;
 extern crate linked_hash_map;
@@ -14,6 +15,9 @@ extern crate parking_lot;
 extern crate pwasm_utils;
 extern crate rand;
 extern crate rand_chacha;
+extern crate secp256k1;
+extern crate sha2;
+extern crate tiny_keccak;
 extern crate wasmi;
 
 // internal dependencies
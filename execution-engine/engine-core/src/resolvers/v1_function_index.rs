@@ -42,6 +42,20 @@ pub enum FunctionIndex {
     GetPhaseIndex = 35,
     UpgradeContractAtURef = 36,
     GetSystemContractIndex = 37,
+    LoadNamedArgFuncIndex = 38,
+    CallContractWithNamedArgsFuncIndex = 39,
+    CallEntryPointFuncIndex = 40,
+    StoreContractAtHashIndex = 41,
+    AddContractVersionIndex = 42,
+    SetContractVersionEnabledIndex = 43,
+    CallVersionedContractFuncIndex = 44,
+    EmitEventIndex = 45,
+    HashIndex = 46,
+    VerifySignatureIndex = 47,
+    RandomBytesIndex = 48,
+    SetInactivityPeriodIndex = 49,
+    AddFunctionVersionIndex = 50,
+    SetLatestContractVersionIndex = 51,
 }
 
 impl Into<usize> for FunctionIndex {
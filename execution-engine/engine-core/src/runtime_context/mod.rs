@@ -7,15 +7,17 @@ use std::rc::Rc;
 use blake2::digest::{Input, VariableOutput};
 use blake2::VarBlake2b;
 
-use contract_ffi::bytesrepr::{deserialize, ToBytes};
+use contract_ffi::bytesrepr::{deserialize, ToBytes, Write};
+use contract_ffi::event::Event;
 use contract_ffi::execution::Phase;
 use contract_ffi::key::{Key, LOCAL_SEED_SIZE};
+use contract_ffi::named_args::NamedArgs;
 use contract_ffi::uref::{AccessRights, URef};
 use contract_ffi::value::account::{
     Account, ActionType, AddKeyFailure, BlockTime, PublicKey, RemoveKeyFailure,
-    SetThresholdFailure, UpdateKeyFailure, Weight,
+    SetThresholdFailure, UpdateKeyFailure, Weight, MIN_INACTIVITY_PERIOD_TIME,
 };
-use contract_ffi::value::{Contract, ProtocolVersion, Value};
+use contract_ffi::value::{Contract, ContractPackage, ProtocolVersion, Value};
 use engine_shared::gas::Gas;
 use engine_shared::newtypes::{CorrelationId, Validated};
 use engine_storage::global_state::StateReader;
@@ -23,7 +25,7 @@ use engine_storage::protocol_data::ProtocolData;
 
 use crate::engine_state::execution_effect::ExecutionEffect;
 use crate::engine_state::SYSTEM_ACCOUNT_ADDR;
-use crate::execution::{AddressGenerator, Error};
+use crate::execution::{AddressGenerator, Error, RandomGenerator};
 use crate::tracking_copy::{AddResult, TrackingCopy};
 use crate::Address;
 
@@ -54,6 +56,7 @@ pub struct RuntimeContext<'a, R> {
     // Original account for read only tasks taken before execution
     account: &'a Account,
     args: Vec<Vec<u8>>,
+    named_args: NamedArgs,
     authorization_keys: BTreeSet<PublicKey>,
     // Key pointing to the entity we are currently running
     //(could point at an account or contract in the global state)
@@ -64,6 +67,9 @@ pub struct RuntimeContext<'a, R> {
     gas_counter: Gas,
     fn_store_id: u32,
     address_generator: Rc<RefCell<AddressGenerator>>,
+    random_generator: Rc<RefCell<RandomGenerator>>,
+    // Number of contract calls between the deploy and the running code, 0 for the deploy itself
+    call_depth: u32,
     protocol_version: ProtocolVersion,
     correlation_id: CorrelationId,
     phase: Phase,
@@ -80,6 +86,7 @@ where
         named_keys: &'a mut BTreeMap<String, Key>,
         access_rights: HashMap<Address, HashSet<AccessRights>>,
         args: Vec<Vec<u8>>,
+        named_args: NamedArgs,
         authorization_keys: BTreeSet<PublicKey>,
         account: &'a Account,
         base_key: Key,
@@ -89,6 +96,8 @@ where
         gas_counter: Gas,
         fn_store_id: u32,
         address_generator: Rc<RefCell<AddressGenerator>>,
+        random_generator: Rc<RefCell<RandomGenerator>>,
+        call_depth: u32,
         protocol_version: ProtocolVersion,
         correlation_id: CorrelationId,
         phase: Phase,
@@ -99,6 +108,7 @@ where
             named_keys,
             access_rights,
             args,
+            named_args,
             account,
             authorization_keys,
             blocktime,
@@ -108,6 +118,8 @@ where
             gas_counter,
             fn_store_id,
             address_generator,
+            random_generator,
+            call_depth,
             protocol_version,
             correlation_id,
             phase,
@@ -179,24 +191,34 @@ where
                 // is always able to remove keys from its own named_keys.
                 let contract_key = Validated::new(contract_uref, Validated::valid)?;
 
-                let contract: Contract = {
-                    let value: Value = self
-                        .state
-                        .borrow_mut()
-                        .read(self.correlation_id, &contract_key)
-                        .map_err(Into::into)?
-                        .ok_or_else(|| Error::KeyNotFound(contract_uref))?;
-
-                    value.try_into().map_err(|found| {
-                        Error::TypeMismatch(engine_shared::transform::TypeMismatch {
+                let value: Value = self
+                    .state
+                    .borrow_mut()
+                    .read(self.correlation_id, &contract_key)
+                    .map_err(Into::into)?
+                    .ok_or_else(|| Error::KeyNotFound(contract_uref))?;
+
+                match value {
+                    Value::Contract(contract) => {
+                        self.named_keys.remove(name);
+                        self.remove_key_from_contract(contract_uref, contract, name)
+                    }
+                    // A version of a contract package runs with the package as its base key.
+                    Value::ContractPackage(mut package) => {
+                        self.named_keys.remove(name);
+                        package.named_keys_mut().remove(name);
+                        let validated_value =
+                            Validated::new(Value::ContractPackage(package), Validated::valid)?;
+                        self.state.borrow_mut().write(contract_key, validated_value);
+                        Ok(())
+                    }
+                    other => Err(Error::TypeMismatch(
+                        engine_shared::transform::TypeMismatch {
                             expected: "Contract".to_owned(),
-                            found,
-                        })
-                    })?
-                };
-
-                self.named_keys.remove(name);
-                self.remove_key_from_contract(contract_uref, contract, name)
+                            found: other.type_string(),
+                        },
+                    )),
+                }
             }
             contract_hash @ Key::Hash(_) => {
                 let contract: Contract = self.read_gs_typed(&contract_hash)?;
@@ -235,10 +257,29 @@ where
         &self.args
     }
 
+    pub fn named_args(&self) -> &NamedArgs {
+        &self.named_args
+    }
+
     pub fn address_generator(&self) -> Rc<RefCell<AddressGenerator>> {
         Rc::clone(&self.address_generator)
     }
 
+    pub fn random_generator(&self) -> Rc<RefCell<RandomGenerator>> {
+        Rc::clone(&self.random_generator)
+    }
+
+    pub fn call_depth(&self) -> u32 {
+        self.call_depth
+    }
+
+    /// Fills `dest` with deterministic random bytes for the current deploy, phase and call depth.
+    pub fn random_bytes(&mut self, dest: &mut [u8]) {
+        self.random_generator
+            .borrow_mut()
+            .fill_bytes(self.call_depth, dest)
+    }
+
     pub fn state(&self) -> Rc<RefCell<TrackingCopy<R>>> {
         Rc::clone(&self.state)
     }
@@ -291,9 +332,12 @@ where
     /// account's public key and deploy's nonce, then all function addresses
     /// generated within one deploy would have been the same.
     pub fn new_function_address(&mut self) -> Result<[u8; 32], Error> {
-        let mut pre_hash_bytes = Vec::with_capacity(36); //32 bytes for deploy hash + 4 bytes ID
-        pre_hash_bytes.extend_from_slice(&self.deploy_hash);
-        pre_hash_bytes.append(&mut self.fn_store_id().to_bytes()?);
+        let mut pre_hash_bytes = [0u8; 36]; //32 bytes for deploy hash + 4 bytes ID
+        {
+            let mut writer: &mut [u8] = &mut pre_hash_bytes;
+            writer.write_all(&self.deploy_hash)?;
+            self.fn_store_id().write_bytes(&mut writer)?;
+        }
 
         self.inc_fn_store_id();
 
@@ -414,6 +458,24 @@ where
         Ok(())
     }
 
+    /// Writes `package` under `package_key`, which must be writeable.  Unlike `write_gs`, this
+    /// doesn't validate the keys held by the package: its named keys belong to the package, not
+    /// the caller, and were validated when they were added.
+    pub fn write_contract_package(
+        &mut self,
+        package_key: Key,
+        package: ContractPackage,
+    ) -> Result<(), Error> {
+        let validated_key: Validated<Key> = Validated::new(package_key, |key| {
+            self.validate_writeable(&key).and(self.validate_key(&key))
+        })?;
+        let validated_value = Validated::new(Value::ContractPackage(package), Validated::valid)?;
+        self.state
+            .borrow_mut()
+            .write(validated_key, validated_value);
+        Ok(())
+    }
+
     pub fn read_account(&mut self, key: &Key) -> Result<Option<Value>, Error> {
         if let Key::Account(_) = key {
             let validated_key = Validated::new(*key, |key| self.validate_key(&key))?;
@@ -481,6 +543,10 @@ where
         self.state.borrow_mut().effect()
     }
 
+    pub fn emit_event(&mut self, event: Event) {
+        self.state.borrow_mut().emit_event(event)
+    }
+
     /// Validates whether keys used in the `value` are not forged.
     pub fn validate_keys(&self, value: &Value) -> Result<(), Error> {
         match value {
@@ -508,6 +574,15 @@ where
                 .named_keys()
                 .values()
                 .try_for_each(|key| self.validate_key(key)),
+            Value::CLValue(cl_value) => cl_value
+                .keys()?
+                .iter()
+                .try_for_each(|key| self.validate_key(key)),
+            Value::ContractPackage(package) => package
+                .versions()
+                .values()
+                .chain(package.named_keys().values())
+                .try_for_each(|key| self.validate_key(key)),
         }
     }
 
@@ -658,6 +733,17 @@ where
         }
     }
 
+    /// Checks whether the authorization keys may manage the account's keys, either by meeting the
+    /// key management threshold or, once the account has been inactive for its inactivity period
+    /// limit, the key recovery threshold.
+    fn can_manage_keys(&self) -> bool {
+        self.account()
+            .can_manage_keys_with(&self.authorization_keys)
+            || self
+                .account()
+                .can_recover_keys_with(&self.authorization_keys, self.blocktime)
+    }
+
     pub fn add_associated_key(
         &mut self,
         public_key: PublicKey,
@@ -669,10 +755,7 @@ where
             return Err(AddKeyFailure::PermissionDenied.into());
         }
 
-        if !self
-            .account()
-            .can_manage_keys_with(&self.authorization_keys)
-        {
+        if !self.can_manage_keys() {
             // Exit early if authorization keys weight doesn't exceed required
             // key management threshold
             return Err(AddKeyFailure::PermissionDenied.into());
@@ -688,6 +771,9 @@ where
         account
             .add_associated_key(public_key, weight)
             .map_err(Error::from)?;
+        account
+            .account_activity_mut()
+            .update_key_management_last_used(self.blocktime);
 
         let validated_uref = Validated::new(key, Validated::valid)?;
         let validated_value =
@@ -707,10 +793,7 @@ where
             return Err(RemoveKeyFailure::PermissionDenied.into());
         }
 
-        if !self
-            .account()
-            .can_manage_keys_with(&self.authorization_keys)
-        {
+        if !self.can_manage_keys() {
             // Exit early if authorization keys weight doesn't exceed required
             // key management threshold
             return Err(RemoveKeyFailure::PermissionDenied.into());
@@ -726,6 +809,9 @@ where
         account
             .remove_associated_key(public_key)
             .map_err(Error::from)?;
+        account
+            .account_activity_mut()
+            .update_key_management_last_used(self.blocktime);
 
         let validated_uref = Validated::new(key, Validated::valid)?;
         let validated_value =
@@ -749,10 +835,7 @@ where
             return Err(UpdateKeyFailure::PermissionDenied.into());
         }
 
-        if !self
-            .account()
-            .can_manage_keys_with(&self.authorization_keys)
-        {
+        if !self.can_manage_keys() {
             // Exit early if authorization keys weight doesn't exceed required
             // key management threshold
             return Err(UpdateKeyFailure::PermissionDenied.into());
@@ -768,6 +851,9 @@ where
         account
             .update_associated_key(public_key, weight)
             .map_err(Error::from)?;
+        account
+            .account_activity_mut()
+            .update_key_management_last_used(self.blocktime);
 
         let validated_uref = Validated::new(key, Validated::valid)?;
         let validated_value =
@@ -791,10 +877,7 @@ where
             return Err(SetThresholdFailure::PermissionDeniedError.into());
         }
 
-        if !self
-            .account()
-            .can_manage_keys_with(&self.authorization_keys)
-        {
+        if !self.can_manage_keys() {
             // Exit early if authorization keys weight doesn't exceed required
             // key management threshold
             return Err(SetThresholdFailure::PermissionDeniedError.into());
@@ -810,6 +893,42 @@ where
         account
             .set_action_threshold(action_type, threshold)
             .map_err(Error::from)?;
+        account
+            .account_activity_mut()
+            .update_key_management_last_used(self.blocktime);
+
+        let validated_uref = Validated::new(key, Validated::valid)?;
+        let validated_value =
+            Validated::new(Value::Account(account), |value| self.validate_keys(value))?;
+
+        self.state
+            .borrow_mut()
+            .write(validated_uref, validated_value);
+
+        Ok(())
+    }
+
+    pub fn set_inactivity_period(&mut self, inactivity_period: BlockTime) -> Result<(), Error> {
+        // Check permission to modify associated keys
+        if self.base_key() != Key::Account(self.account().pub_key()) || !self.can_manage_keys() {
+            // Exit early with error to avoid mutations
+            return Err(Error::KeyManagementDenied);
+        }
+
+        // A short period would let the key recovery threshold apply to an account in regular use
+        if inactivity_period < MIN_INACTIVITY_PERIOD_TIME {
+            return Err(Error::InvalidInactivityPeriod);
+        }
+
+        // Converts an account's public key into a URef
+        let key = Key::Account(self.account().pub_key());
+
+        // Take an account out of the global state
+        let mut account: Account = self.read_gs_typed(&key)?;
+
+        let account_activity = account.account_activity_mut();
+        account_activity.update_inactivity_period_limit(inactivity_period);
+        account_activity.update_key_management_last_used(self.blocktime);
 
         let validated_uref = Validated::new(key, Validated::valid)?;
         let validated_value =
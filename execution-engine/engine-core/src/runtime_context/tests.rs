@@ -7,12 +7,13 @@ use rand::RngCore;
 
 use contract_ffi::execution::Phase;
 use contract_ffi::key::{Key, LOCAL_SEED_SIZE};
+use contract_ffi::named_args::NamedArgs;
 use contract_ffi::uref::{AccessRights, URef};
 use contract_ffi::value::account::{
     AccountActivity, ActionType, AddKeyFailure, AssociatedKeys, BlockTime, PublicKey, PurseId,
-    RemoveKeyFailure, SetThresholdFailure, Weight,
+    RemoveKeyFailure, SetThresholdFailure, Weight, MIN_INACTIVITY_PERIOD_TIME,
 };
-use contract_ffi::value::{self, Account, Contract, ProtocolVersion, Value};
+use contract_ffi::value::{self, Account, CLValue, Contract, ProtocolVersion, Value};
 use engine_shared::gas::Gas;
 use engine_shared::newtypes::CorrelationId;
 use engine_shared::transform::Transform;
@@ -23,7 +24,7 @@ use super::attenuate_uref_for_account;
 use super::{Address, Error, RuntimeContext, Validated};
 use crate::engine_state::SYSTEM_ACCOUNT_ADDR;
 use crate::execution::extract_access_rights_from_keys;
-use crate::execution::AddressGenerator;
+use crate::execution::{AddressGenerator, RandomGenerator};
 use crate::tracking_copy::TrackingCopy;
 
 const DEPLOY_HASH: [u8; 32] = [1u8; 32];
@@ -112,6 +113,7 @@ fn mock_runtime_context<'a>(
         named_keys,
         access_rights,
         Vec::new(),
+        NamedArgs::new(),
         BTreeSet::from_iter(vec![PublicKey::new([0; 32])]),
         &account,
         base_key,
@@ -121,6 +123,8 @@ fn mock_runtime_context<'a>(
         Gas::default(),
         0,
         Rc::new(RefCell::new(address_generator)),
+        Rc::new(RefCell::new(RandomGenerator::new(DEPLOY_HASH, PHASE))),
+        0,
         ProtocolVersion::V1_0_0,
         CorrelationId::new(),
         Phase::Session,
@@ -189,6 +193,34 @@ fn use_uref_forged() {
     assert_forged_reference(query_result);
 }
 
+#[test]
+fn store_cl_value_with_uref_valid() {
+    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let uref = create_uref(&mut rng, AccessRights::READ_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![uref]);
+    let cl_value = CLValue::from_t(&(String::from("uref"), Some(uref))).unwrap();
+
+    let query_result = test(access_rights, |mut rc| {
+        rc.write_gs(uref, Value::CLValue(cl_value.clone()))
+    });
+    query_result.expect("writing a value holding a valid uref should succeed");
+}
+
+#[test]
+fn store_cl_value_with_uref_forged() {
+    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let uref = create_uref(&mut rng, AccessRights::READ_WRITE);
+    let forged_uref = create_uref(&mut rng, AccessRights::READ_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![uref]);
+    let cl_value = CLValue::from_t(&vec![forged_uref]).unwrap();
+
+    let query_result = test(access_rights, |mut rc| {
+        rc.write_gs(uref, Value::CLValue(cl_value.clone()))
+    });
+
+    assert_forged_reference(query_result);
+}
+
 #[test]
 fn store_contract_with_uref_valid() {
     let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
@@ -426,6 +458,7 @@ fn contract_key_addable_valid() {
         &mut uref_map,
         access_rights,
         Vec::new(),
+        NamedArgs::new(),
         BTreeSet::from_iter(vec![PublicKey::new(base_acc_addr)]),
         &account,
         contract_key,
@@ -435,6 +468,8 @@ fn contract_key_addable_valid() {
         Gas::default(),
         0,
         Rc::new(RefCell::new(address_generator)),
+        Rc::new(RefCell::new(RandomGenerator::new(DEPLOY_HASH, PHASE))),
+        0,
         ProtocolVersion::V1_0_0,
         CorrelationId::new(),
         PHASE,
@@ -488,6 +523,7 @@ fn contract_key_addable_invalid() {
         &mut uref_map,
         access_rights,
         Vec::new(),
+        NamedArgs::new(),
         BTreeSet::from_iter(vec![PublicKey::new(base_acc_addr)]),
         &account,
         other_contract_key,
@@ -497,6 +533,8 @@ fn contract_key_addable_invalid() {
         Gas::default(),
         0,
         Rc::new(RefCell::new(address_generator)),
+        Rc::new(RefCell::new(RandomGenerator::new(DEPLOY_HASH, PHASE))),
+        0,
         ProtocolVersion::V1_0_0,
         CorrelationId::new(),
         PHASE,
@@ -831,6 +869,44 @@ fn should_verify_ownership_before_setting_action_threshold() {
     let _ = test(access_rights, query);
 }
 
+#[test]
+fn inactivity_period_management() {
+    let access_rights = HashMap::new();
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        match runtime_context.set_inactivity_period(BlockTime(MIN_INACTIVITY_PERIOD_TIME.0 - 1)) {
+            Err(Error::InvalidInactivityPeriod) => {}
+            other => panic!("Invalid result: {:?}", other),
+        }
+
+        runtime_context
+            .set_inactivity_period(MIN_INACTIVITY_PERIOD_TIME)
+            .expect("Unable to set inactivity period");
+
+        let effect = runtime_context.effect();
+        let transform = effect.transforms.get(&runtime_context.base_key()).unwrap();
+        let mutated_account = match transform {
+            Transform::Write(Value::Account(account)) => account,
+            _ => panic!("Invalid transform operation found"),
+        };
+
+        assert_eq!(
+            mutated_account.account_activity().inactivity_period_limit(),
+            MIN_INACTIVITY_PERIOD_TIME
+        );
+
+        // Overwrites a `base_key` to a different one to lose ownership of the account
+        runtime_context.base_key = Key::Hash([1; 32]);
+
+        match runtime_context.set_inactivity_period(BlockTime(1)) {
+            Err(Error::KeyManagementDenied) => {}
+            other => panic!("Invalid result: {:?}", other),
+        }
+
+        Ok(())
+    };
+    let _ = test(access_rights, query);
+}
+
 #[test]
 fn can_roundtrip_key_value_pairs_into_local_state() {
     let access_rights = HashMap::new();
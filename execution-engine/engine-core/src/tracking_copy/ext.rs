@@ -68,7 +68,7 @@ pub trait TrackingCopyExt<R> {
         outer_key: Key,
     ) -> Result<SystemContractInfo, Self::Error>;
 
-    /// Gets a contract by Key
+    /// Gets a contract by Key.  For a contract package, gets its latest version.
     fn get_contract(
         &mut self,
         correlation_id: CorrelationId,
@@ -157,6 +157,11 @@ where
     ) -> Result<Contract, Self::Error> {
         let contract = match self.get(correlation_id, &key).map_err(Into::into)? {
             Some(Value::Contract(contract)) => contract,
+            Some(Value::ContractPackage(package)) => {
+                let contract_key = package.latest_version_key()?;
+                let contract = self.get_contract(correlation_id, contract_key)?;
+                package.version_contract(contract)
+            }
             Some(other) => {
                 return Err(execution::Error::TypeMismatch(TypeMismatch::new(
                     "Value::Contract".to_string(),
@@ -10,11 +10,13 @@ use std::collections::{BTreeMap, HashMap};
 use linked_hash_map::LinkedHashMap;
 use parking_lot::Mutex;
 
+use contract_ffi::event::Event;
 use contract_ffi::key::Key;
 use contract_ffi::value::Value;
 use engine_shared::newtypes::{CorrelationId, Validated};
 use engine_shared::transform::{self, Transform, TypeMismatch};
 use engine_storage::global_state::StateReader;
+use engine_storage::trie::merkle_proof::{TrieMerkleAbsenceProof, TrieMerkleProof};
 
 use crate::engine_state::execution_effect::ExecutionEffect;
 use crate::engine_state::op::Op;
@@ -29,6 +31,18 @@ pub enum QueryResult {
     ValueNotFound(String),
 }
 
+/// The result of [`TrackingCopy::query_with_proofs`].
+#[derive(Debug)]
+pub enum ProvenQueryResult {
+    /// The value found at the end of the query path, along with a proof for every key read along
+    /// the way, ordered from the base key to the final key.
+    Success {
+        value: Value,
+        proofs: Vec<TrieMerkleProof<Key, Value>>,
+    },
+    ValueNotFound(String),
+}
+
 /// Keeps track of already accessed keys.
 /// We deliberately separate cached Reads from cached mutations
 /// because we want to invalidate Reads' cache so it doesn't grow too fast.
@@ -95,6 +109,7 @@ pub struct TrackingCopy<R> {
     cache: TrackingCopyCache<HeapSize>,
     ops: HashMap<Key, Op>,
     fns: HashMap<Key, Transform>,
+    events: Vec<Event>,
 }
 
 #[derive(Debug)]
@@ -113,6 +128,7 @@ impl<R: StateReader<Key, Value>> TrackingCopy<R> {
                                                                  * limit? */
             ops: HashMap::new(),
             fns: HashMap::new(),
+            events: Vec::new(),
         }
     }
 
@@ -221,8 +237,56 @@ impl<R: StateReader<Key, Value>> TrackingCopy<R> {
         }
     }
 
+    /// Records an event emitted during execution.  Events are not written to global state; they
+    /// are only returned as part of [`TrackingCopy::effect`].
+    pub fn emit_event(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
     pub fn effect(&self) -> ExecutionEffect {
-        ExecutionEffect::new(self.ops.clone(), self.fns.clone())
+        ExecutionEffect::new(self.ops.clone(), self.fns.clone()).with_events(self.events.clone())
+    }
+
+    /// Applies an [`ExecutionEffect`] computed elsewhere (e.g. against a [`TrackingCopy::fork`] of
+    /// this `TrackingCopy`) on top of the current state, as if its ops and transforms had been
+    /// performed directly on this `TrackingCopy`.
+    ///
+    /// The effect is applied atomically: if any of its transforms cannot be applied, the state of
+    /// this `TrackingCopy` is left unchanged.
+    pub fn apply_effect(
+        &mut self,
+        correlation_id: CorrelationId,
+        effect: ExecutionEffect,
+    ) -> Result<AddResult, R::Error> {
+        let mut new_values: Vec<(Key, Value)> = Vec::with_capacity(effect.transforms.len());
+        for (key, transform) in effect.transforms.iter() {
+            let new_value = match transform {
+                Transform::Identity => continue,
+                Transform::Write(value) => value.to_owned(),
+                transform => match self.get(correlation_id, key)? {
+                    None => return Ok(AddResult::KeyNotFound(*key)),
+                    Some(current_value) => match transform.clone().apply(current_value) {
+                        Ok(new_value) => new_value,
+                        Err(transform::Error::TypeMismatch(type_mismatch)) => {
+                            return Ok(AddResult::TypeMismatch(type_mismatch))
+                        }
+                    },
+                },
+            };
+            new_values.push((*key, new_value));
+        }
+
+        for (key, value) in new_values {
+            self.cache.insert_write(key, value);
+        }
+        for (key, op) in effect.ops {
+            utils::add(&mut self.ops, key, op);
+        }
+        for (key, transform) in effect.transforms {
+            utils::add(&mut self.fns, key, transform);
+        }
+        self.events.extend(effect.events);
+        Ok(AddResult::Success)
     }
 
     pub fn query(
@@ -267,6 +331,15 @@ impl<R: StateReader<Key, Value>> TrackingCopy<R> {
                                 }
                             }
 
+                            Value::ContractPackage(package) => {
+                                if let Some(key) = package.named_keys().get(name) {
+                                    let validated_key = Validated::new(*key, Validated::valid)?;
+                                    self.read_key_or_stop(correlation_id, validated_key, i)
+                                } else {
+                                    Err(Ok((i, format!("Name {} not found in ContractPackage at path:", name))))
+                                }
+                            }
+
                             other => Err(
                                 Ok((i, format!("Name {} cannot be followed from value {:?} because it is neither an account nor contract. Value found at path:", name, other)))
                                 ),
@@ -285,6 +358,94 @@ impl<R: StateReader<Key, Value>> TrackingCopy<R> {
         }
     }
 
+    /// Performs the same query as [`TrackingCopy::query`], but reads directly from the underlying
+    /// state and returns a Merkle proof for each key read along `path`.
+    ///
+    /// Since proofs can only be produced against committed state, any effects accumulated by this
+    /// `TrackingCopy` are ignored, and the reads are not recorded as ops.
+    pub fn query_with_proofs(
+        &self,
+        correlation_id: CorrelationId,
+        base_key: Key,
+        path: &[String],
+    ) -> Result<ProvenQueryResult, R::Error> {
+        let mut proofs: Vec<TrieMerkleProof<Key, Value>> = Vec::with_capacity(path.len() + 1);
+
+        match self
+            .reader
+            .read_with_proof(correlation_id, &base_key.normalize())?
+        {
+            Some(proof) => proofs.push(proof),
+            None => {
+                return Ok(ProvenQueryResult::ValueNotFound(self.error_path_msg(
+                    base_key,
+                    path,
+                    "".to_owned(),
+                    0 as usize,
+                )))
+            }
+        }
+
+        for (i, name) in path.iter().enumerate() {
+            // Safe to unwrap: `proofs` always contains at least the proof for the base key
+            let current_value = proofs.last().unwrap().value();
+            let maybe_key = match current_value {
+                Value::Account(account) => account.named_keys().get(name).ok_or_else(|| {
+                    format!("Name {} not found in Account at path:", name)
+                }),
+                Value::Contract(contract) => contract.named_keys().get(name).ok_or_else(|| {
+                    format!("Name {} not found in Contract at path:", name)
+                }),
+                Value::ContractPackage(package) => {
+                    package.named_keys().get(name).ok_or_else(|| {
+                        format!("Name {} not found in ContractPackage at path:", name)
+                    })
+                }
+                other => Err(format!(
+                    "Name {} cannot be followed from value {:?} because it is neither an account nor contract. Value found at path:",
+                    name, other
+                )),
+            };
+            let key = match maybe_key {
+                Ok(key) => key.normalize(),
+                Err(msg) => {
+                    return Ok(ProvenQueryResult::ValueNotFound(
+                        self.error_path_msg(base_key, path, msg, i),
+                    ))
+                }
+            };
+            match self.reader.read_with_proof(correlation_id, &key)? {
+                Some(proof) => proofs.push(proof),
+                None => {
+                    return Ok(ProvenQueryResult::ValueNotFound(self.error_path_msg(
+                        base_key,
+                        path,
+                        format!("Name {:?} not found: ", key),
+                        i,
+                    )))
+                }
+            }
+        }
+
+        // Safe to unwrap: `proofs` always contains at least the proof for the base key
+        let value = proofs.last().unwrap().value().to_owned();
+        Ok(ProvenQueryResult::Success { value, proofs })
+    }
+
+    /// Returns a Merkle proof that `key` is absent from the underlying state, or `None` if it is
+    /// present.
+    ///
+    /// Like [`TrackingCopy::query_with_proofs`], this ignores any effects accumulated by this
+    /// `TrackingCopy`.
+    pub fn query_absence_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: Key,
+    ) -> Result<Option<TrieMerkleAbsenceProof<Key, Value>>, R::Error> {
+        self.reader
+            .read_absence_proof(correlation_id, &key.normalize())
+    }
+
     fn read_key_or_stop(
         &mut self,
         correlation_id: CorrelationId,
@@ -336,4 +497,20 @@ impl<R: StateReader<Key, Value>> StateReader<Key, Value> for &TrackingCopy<R> {
             Ok(None)
         }
     }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, Value>>, Self::Error> {
+        self.reader.read_with_proof(correlation_id, key)
+    }
+
+    fn read_absence_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleAbsenceProof<Key, Value>>, Self::Error> {
+        self.reader.read_absence_proof(correlation_id, key)
+    }
 }
@@ -1,5 +1,5 @@
 use std::cell::Cell;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::iter;
 use std::rc::Rc;
 
@@ -17,11 +17,13 @@ use engine_shared::newtypes::CorrelationId;
 use engine_shared::transform::Transform;
 use engine_storage::global_state::in_memory::InMemoryGlobalState;
 use engine_storage::global_state::{StateProvider, StateReader};
+use engine_storage::trie::merkle_proof::{TrieMerkleAbsenceProof, TrieMerkleProof};
 
+use crate::engine_state::execution_effect::ExecutionEffect;
 use crate::engine_state::op::Op;
 
 use super::meter::count_meter::Count;
-use super::{AddResult, QueryResult, Validated};
+use super::{AddResult, ProvenQueryResult, QueryResult, Validated};
 use super::{TrackingCopy, TrackingCopyCache};
 
 struct CountingDb {
@@ -60,6 +62,22 @@ impl StateReader<Key, Value> for CountingDb {
         self.count.set(count + 1);
         Ok(Some(value))
     }
+
+    fn read_with_proof(
+        &self,
+        _correlation_id: CorrelationId,
+        _key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, Value>>, Self::Error> {
+        Ok(None)
+    }
+
+    fn read_absence_proof(
+        &self,
+        _correlation_id: CorrelationId,
+        _key: &Key,
+    ) -> Result<Option<TrieMerkleAbsenceProof<Key, Value>>, Self::Error> {
+        Ok(None)
+    }
 }
 
 #[test]
@@ -495,9 +513,97 @@ proptest! {
         } else {
             panic!("Query failed when it should not have!");
         }
+
+        if let Ok(ProvenQueryResult::Success { value, proofs }) =
+            tc.query_with_proofs(correlation_id, account_key, &path)
+        {
+            assert_eq!(v, value);
+            assert_eq!(proofs.len(), path.len() + 1);
+            for proof in proofs.iter() {
+                assert!(proof.verify(&root_hash).is_ok());
+            }
+            assert_eq!(proofs.last().unwrap().value(), &v);
+        } else {
+            panic!("Query with proofs failed when it should not have!");
+        }
     }
 }
 
+#[test]
+fn tracking_copy_apply_effect_from_fork() {
+    let correlation_id = CorrelationId::new();
+    let k1 = Key::Hash([1u8; 32]);
+    let k2 = Key::Hash([2u8; 32]);
+    let (global_state, root_hash) = InMemoryGlobalState::from_pairs(
+        correlation_id,
+        &[(k1, Value::Int32(1)), (k2, Value::Int32(2))],
+    )
+    .unwrap();
+    let view = global_state.checkout(root_hash).unwrap().unwrap();
+    let mut tc = TrackingCopy::new(view);
+
+    let effect = {
+        let mut fork = tc.fork();
+        let _ = fork.read(
+            correlation_id,
+            &Validated::new(k1, Validated::valid).unwrap(),
+        );
+        let add = fork.add(
+            correlation_id,
+            Validated::new(k2, Validated::valid).unwrap(),
+            Validated::new(Value::Int32(3), Validated::valid).unwrap(),
+        );
+        assert_matches!(add, Ok(AddResult::Success));
+        fork.effect()
+    };
+
+    let result = tc.apply_effect(correlation_id, effect.clone());
+    assert_matches!(result, Ok(AddResult::Success));
+    assert_eq!(tc.effect(), effect);
+    assert_eq!(tc.get(correlation_id, &k2).unwrap(), Some(Value::Int32(5)));
+
+    // a second fork observes the effect applied to its parent
+    let mut fork = tc.fork();
+    assert_eq!(
+        fork.get(correlation_id, &k2).unwrap(),
+        Some(Value::Int32(5))
+    );
+    fork.write(
+        Validated::new(k2, Validated::valid).unwrap(),
+        Validated::new(Value::Int32(7), Validated::valid).unwrap(),
+    );
+    let effect = fork.effect();
+    let result = tc.apply_effect(correlation_id, effect);
+    assert_matches!(result, Ok(AddResult::Success));
+    assert_eq!(tc.fns.get(&k2), Some(&Transform::Write(Value::Int32(7))));
+    assert_eq!(tc.ops.get(&k2), Some(&Op::Write));
+    assert_eq!(tc.ops.get(&k1), Some(&Op::Read));
+}
+
+#[test]
+fn tracking_copy_apply_effect_is_atomic() {
+    let correlation_id = CorrelationId::new();
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(counter);
+    let mut tc = TrackingCopy::new(db);
+    let k1 = Key::Hash([1u8; 32]);
+    let k2 = Key::Hash([2u8; 32]);
+
+    let mut ops = HashMap::new();
+    ops.insert(k1, Op::Write);
+    ops.insert(k2, Op::Add);
+    let mut transforms = HashMap::new();
+    transforms.insert(k1, Transform::Write(Value::Int32(1)));
+    transforms.insert(k2, Transform::AddKeys(BTreeMap::new()));
+
+    // `CountingDb` holds `Int32`s, to which keys cannot be added
+    let result = tc.apply_effect(correlation_id, ExecutionEffect::new(ops, transforms));
+    assert_matches!(result, Ok(AddResult::TypeMismatch(_)));
+    assert!(tc.ops.is_empty());
+    assert!(tc.fns.is_empty());
+    assert!(tc.cache.muts_cached.is_empty());
+}
+
 #[test]
 fn cache_reads_invalidation() {
     let mut tc_cache = TrackingCopyCache::new(2, Count);
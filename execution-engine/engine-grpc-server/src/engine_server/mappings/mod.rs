@@ -1,27 +1,36 @@
 use std::collections::{BTreeMap, HashMap};
 use std::convert::{TryFrom, TryInto};
 use std::fmt::{self, Display, Formatter};
+use std::mem;
 use std::string::ToString;
 
 use protobuf::{ProtobufEnum, RepeatedField};
 
+use contract_ffi::bytesrepr::{self, ToBytes};
+use contract_ffi::event::Event;
+use contract_ffi::system_contracts::pos::{RewardsConfig, SlashDestination};
 use contract_ffi::uref::URef;
 use contract_ffi::value::account::{
     AccountActivity, ActionThresholds, AssociatedKeys, BlockTime, PublicKey, PurseId, Weight,
     KEY_SIZE,
 };
-use contract_ffi::value::{ProtocolVersion, U512};
+use contract_ffi::value::{
+    ContractPackage, EntryPoint, EntryPointAccess, EntryPoints, Parameter, ProtocolVersion, U512,
+};
+use engine_core::engine_state::distribute_rewards::DistributeRewardsConfig;
 use engine_core::engine_state::error::{Error as EngineError, RootNotFound};
 use engine_core::engine_state::executable_deploy_item::ExecutableDeployItem;
 use engine_core::engine_state::execution_effect::ExecutionEffect;
 use engine_core::engine_state::execution_result::ExecutionResult;
 use engine_core::engine_state::genesis::{GenesisAccount, GenesisConfig};
 use engine_core::engine_state::op::Op;
+use engine_core::engine_state::slash::SlashConfig;
 use engine_core::engine_state::upgrade::UpgradeConfig;
 use engine_core::execution::Error as ExecutionError;
 use engine_core::tracking_copy::utils;
 use engine_shared::motes::Motes;
 use engine_shared::transform::{self, TypeMismatch};
+use engine_storage::global_state::DiffEntry;
 use engine_wasm_prep::wasm_costs::WasmCosts;
 
 use crate::engine_server::ipc::{ChainSpec_CostTable, ChainSpec_GenesisAccount};
@@ -171,11 +180,20 @@ impl TryFrom<&super::transforms::Transform> for transform::Transform {
 
 impl From<contract_ffi::value::Contract> for super::state::Contract {
     fn from(contract: contract_ffi::value::Contract) -> Self {
-        let (bytes, named_keys, protocol_version) = contract.destructure();
+        let (bytes, named_keys, entry_points, protocol_version) = contract.destructure();
         let mut contract = super::state::Contract::new();
         let named_keys = KnownKeys(named_keys).into();
+        let entry_points = entry_points
+            .into_iter()
+            .map(|(name, entry_point)| {
+                let mut result: super::state::Contract_EntryPoint = entry_point.into();
+                result.set_name(name);
+                result
+            })
+            .collect();
         contract.set_body(bytes);
         contract.set_named_keys(protobuf::RepeatedField::from_vec(named_keys));
+        contract.set_entry_points(protobuf::RepeatedField::from_vec(entry_points));
         contract.set_protocol_version(protocol_version.into());
         contract
     }
@@ -189,10 +207,109 @@ impl TryFrom<&super::state::Contract> for contract_ffi::value::Contract {
         let protocol_version =
             ProtocolVersion::from_parts(input.get_major(), input.get_minor(), input.get_patch());
         let named_keys: KnownKeys = value.get_named_keys().try_into()?;
+        let entry_points = value
+            .get_entry_points()
+            .iter()
+            .map(|entry_point| Ok((entry_point.get_name().to_string(), entry_point.try_into()?)))
+            .collect::<Result<EntryPoints, ParsingError>>()?;
         Ok(contract_ffi::value::Contract::new(
             value.get_body().to_vec(),
             named_keys.0,
             protocol_version,
+        )
+        .with_entry_points(entry_points))
+    }
+}
+
+impl From<EntryPoint> for super::state::Contract_EntryPoint {
+    fn from(entry_point: EntryPoint) -> Self {
+        let parameters = entry_point
+            .parameters()
+            .iter()
+            .map(|parameter| {
+                let mut result = super::state::Contract_EntryPoint_Parameter::new();
+                result.set_name(parameter.name().to_string());
+                // A `CLType` is a small tree of tags, so serializing it cannot run out of memory.
+                result.set_cl_type(
+                    parameter
+                        .cl_type()
+                        .to_bytes()
+                        .expect("should serialize CLType"),
+                );
+                result
+            })
+            .collect();
+        let access = match entry_point.access() {
+            EntryPointAccess::Public => super::state::Contract_EntryPoint_Access::PUBLIC,
+            EntryPointAccess::System => super::state::Contract_EntryPoint_Access::SYSTEM,
+        };
+        let mut result = super::state::Contract_EntryPoint::new();
+        result.set_parameters(protobuf::RepeatedField::from_vec(parameters));
+        result.set_access(access);
+        result
+    }
+}
+
+impl TryFrom<&super::state::Contract_EntryPoint> for EntryPoint {
+    type Error = ParsingError;
+
+    fn try_from(value: &super::state::Contract_EntryPoint) -> Result<Self, Self::Error> {
+        let parameters = value
+            .get_parameters()
+            .iter()
+            .map(|parameter| {
+                let cl_type = bytesrepr::deserialize(parameter.get_cl_type())
+                    .map_err(|error| ParsingError(format!("Couldn't parse CLType: {:?}", error)))?;
+                Ok(Parameter::new(parameter.get_name(), cl_type))
+            })
+            .collect::<Result<Vec<Parameter>, ParsingError>>()?;
+        let access = match value.get_access() {
+            super::state::Contract_EntryPoint_Access::PUBLIC => EntryPointAccess::Public,
+            super::state::Contract_EntryPoint_Access::SYSTEM => EntryPointAccess::System,
+        };
+        Ok(EntryPoint::new(parameters, access))
+    }
+}
+
+impl From<contract_ffi::value::CLValue> for super::state::CLValue {
+    fn from(cl_value: contract_ffi::value::CLValue) -> Self {
+        let (cl_type, value) = cl_value.destructure();
+        let mut result = super::state::CLValue::new();
+        // A `CLType` is a small tree of tags, so serializing it cannot run out of memory.
+        result.set_cl_type(cl_type.to_bytes().expect("should serialize CLType"));
+        result.set_value(value);
+        result
+    }
+}
+
+impl From<Event> for ipc::Event {
+    fn from(event: Event) -> Self {
+        let (topic, payload) = event.destructure();
+        let mut result = ipc::Event::new();
+        result.set_topic(topic);
+        result.set_payload(payload.into());
+        result
+    }
+}
+
+impl TryFrom<&ipc::Event> for Event {
+    type Error = ParsingError;
+
+    fn try_from(event: &ipc::Event) -> Result<Self, Self::Error> {
+        let payload = event.get_payload().try_into()?;
+        Ok(Event::new(event.get_topic().to_string(), payload))
+    }
+}
+
+impl TryFrom<&super::state::CLValue> for contract_ffi::value::CLValue {
+    type Error = ParsingError;
+
+    fn try_from(value: &super::state::CLValue) -> Result<Self, Self::Error> {
+        let cl_type: contract_ffi::value::CLType = bytesrepr::deserialize(value.get_cl_type())
+            .map_err(|error| ParsingError(format!("Couldn't parse CLType: {:?}", error)))?;
+        Ok(contract_ffi::value::CLValue::from_components(
+            cl_type,
+            value.get_value().to_vec(),
         ))
     }
 }
@@ -241,6 +358,10 @@ impl From<contract_ffi::value::Value> for super::state::Value {
             }
             contract_ffi::value::Value::Unit => tv.set_unit(state::Unit::new()),
             contract_ffi::value::Value::UInt64(num) => tv.set_long_value(num),
+            contract_ffi::value::Value::CLValue(cl_value) => tv.set_cl_value(cl_value.into()),
+            contract_ffi::value::Value::ContractPackage(package) => {
+                tv.set_contract_package(package.into())
+            }
         };
         tv
     }
@@ -286,6 +407,14 @@ impl TryFrom<&super::state::Value> for contract_ffi::value::Value {
             Ok(contract_ffi::value::Value::Unit)
         } else if value.has_long_value() {
             Ok(contract_ffi::value::Value::UInt64(value.get_long_value()))
+        } else if value.has_cl_value() {
+            Ok(contract_ffi::value::Value::CLValue(
+                value.get_cl_value().try_into()?,
+            ))
+        } else if value.has_contract_package() {
+            Ok(contract_ffi::value::Value::ContractPackage(
+                value.get_contract_package().try_into()?,
+            ))
         } else {
             parse_error(format!(
                 "IPC Value {:?} couldn't be parsed to domain representation.",
@@ -295,6 +424,71 @@ impl TryFrom<&super::state::Value> for contract_ffi::value::Value {
     }
 }
 
+impl From<ContractPackage> for super::state::ContractPackage {
+    fn from(package: ContractPackage) -> Self {
+        let versions = package
+            .versions()
+            .iter()
+            .map(|(version, contract_key)| {
+                let mut result = super::state::ContractPackage_Version::new();
+                result.set_version(*version);
+                result.set_contract_key((*contract_key).into());
+                result.set_is_enabled(package.is_version_enabled(*version));
+                result
+            })
+            .collect();
+        let named_keys: Vec<super::state::NamedKey> =
+            KnownKeys(package.named_keys().clone()).into();
+        let mut result = super::state::ContractPackage::new();
+        result.set_versions(protobuf::RepeatedField::from_vec(versions));
+        result.set_latest_version(package.latest_version().unwrap_or_default());
+        result.set_named_keys(protobuf::RepeatedField::from_vec(named_keys));
+        result
+    }
+}
+
+impl TryFrom<&super::state::ContractPackage> for ContractPackage {
+    type Error = ParsingError;
+
+    fn try_from(value: &super::state::ContractPackage) -> Result<Self, Self::Error> {
+        let mut package = ContractPackage::new();
+        for version in value.get_versions() {
+            let contract_key = version.get_contract_key().try_into()?;
+            // Versions are numbered consecutively, so re-adding them in order restores their
+            // numbers.
+            if package.add_version(contract_key) != version.get_version() {
+                return parse_error(format!(
+                    "Contract package versions are not consecutive: {:?}",
+                    value.get_versions()
+                ));
+            }
+        }
+        if value.get_latest_version() != 0 {
+            if let Err(error) = package.set_latest_version(value.get_latest_version()) {
+                return parse_error(format!(
+                    "Invalid latest contract package version {}: {}",
+                    value.get_latest_version(),
+                    error
+                ));
+            }
+        }
+        for version in value.get_versions() {
+            if !version.get_is_enabled() {
+                if let Err(error) = package.disable_version(version.get_version()) {
+                    return parse_error(format!(
+                        "Can't disable contract package version {}: {}",
+                        version.get_version(),
+                        error
+                    ));
+                }
+            }
+        }
+        let mut named_keys: KnownKeys = value.get_named_keys().try_into()?;
+        package.named_keys_append(&mut named_keys.0);
+        Ok(package)
+    }
+}
+
 impl From<contract_ffi::value::account::Account> for super::state::Account {
     fn from(account: contract_ffi::value::account::Account) -> Self {
         let mut ipc_account = super::state::Account::new();
@@ -317,6 +511,10 @@ impl From<contract_ffi::value::account::Account> for super::state::Account {
             tmp.set_deployment_threshold(u32::from(
                 account.action_thresholds().deployment().value(),
             ));
+            // An unset (zero) key recovery threshold means the account has no key recovery
+            if let Some(key_recovery) = account.action_thresholds().key_recovery() {
+                tmp.set_key_recovery_threshold(u32::from(key_recovery.value()));
+            }
             tmp
         };
         ipc_account.set_action_thresholds(action_thresholds);
@@ -374,11 +572,18 @@ impl TryFrom<&super::state::Account> for contract_ffi::value::account::Account {
             };
             let action_thresholds_ipc = value.get_action_thresholds();
 
-            ActionThresholds::new(
+            let mut tmp = ActionThresholds::new(
                 Weight::new(action_thresholds_ipc.get_deployment_threshold() as u8),
                 Weight::new(action_thresholds_ipc.get_key_management_threshold() as u8),
             )
-            .map_err(ParsingError::custom)?
+            .map_err(ParsingError::custom)?;
+            // Without a key recovery threshold, key management keeps requiring its own threshold
+            let key_recovery_threshold = action_thresholds_ipc.get_key_recovery_threshold();
+            if key_recovery_threshold != 0 {
+                tmp.set_key_recovery_threshold(Weight::new(key_recovery_threshold as u8))
+                    .map_err(ParsingError::custom)?;
+            }
+            tmp
         };
         let account_activity: AccountActivity = {
             if !value.has_account_activity() {
@@ -679,6 +884,32 @@ impl From<(contract_ffi::key::Key, transform::Transform)> for super::transforms:
     }
 }
 
+impl From<DiffEntry<contract_ffi::key::Key, contract_ffi::value::Value>> for ipc::KeyDiff {
+    fn from(diff_entry: DiffEntry<contract_ffi::key::Key, contract_ffi::value::Value>) -> Self {
+        let mut key_diff = ipc::KeyDiff::new();
+        match diff_entry {
+            DiffEntry::Added { key, value } => {
+                key_diff.set_key(key.into());
+                key_diff.set_new_value(value.into());
+            }
+            DiffEntry::Removed { key, value } => {
+                key_diff.set_key(key.into());
+                key_diff.set_old_value(value.into());
+            }
+            DiffEntry::Changed {
+                key,
+                old_value,
+                new_value,
+            } => {
+                key_diff.set_key(key.into());
+                key_diff.set_old_value(old_value.into());
+                key_diff.set_new_value(new_value.into());
+            }
+        }
+        key_diff
+    }
+}
+
 impl From<ExecutionEffect> for super::ipc::ExecutionEffect {
     fn from(ee: ExecutionEffect) -> super::ipc::ExecutionEffect {
         let mut eff = super::ipc::ExecutionEffect::new();
@@ -725,13 +956,17 @@ impl From<ExecutionResult> for ipc::DeployResult {
     fn from(er: ExecutionResult) -> ipc::DeployResult {
         match er {
             ExecutionResult::Success {
-                effect: effects,
+                effect: mut effects,
                 cost,
             } => {
+                let events = mem::replace(&mut effects.events, Vec::new());
                 let ipc_ee = effects.into();
                 let mut deploy_result = ipc::DeployResult::new();
                 let mut execution_result = ipc::DeployResult_ExecutionResult::new();
                 execution_result.set_effects(ipc_ee);
+                execution_result.set_events(RepeatedField::from_vec(
+                    events.into_iter().map(Into::into).collect(),
+                ));
                 execution_result.set_cost(cost.value().into());
                 deploy_result.set_execution_result(execution_result);
                 deploy_result
@@ -874,24 +1109,31 @@ impl From<ipc::DeployPayload_oneof_payload> for ExecutableDeployItem {
                 ExecutableDeployItem::ModuleBytes {
                     module_bytes: deploy_code.code,
                     args: deploy_code.args,
+                    named_args: deploy_code.named_args,
                 }
             }
             ipc::DeployPayload_oneof_payload::stored_contract_hash(stored_contract_hash) => {
                 ExecutableDeployItem::StoredContractByHash {
                     hash: stored_contract_hash.hash,
+                    entry_point: stored_contract_hash.entry_point,
                     args: stored_contract_hash.args,
+                    named_args: stored_contract_hash.named_args,
                 }
             }
             ipc::DeployPayload_oneof_payload::stored_contract_name(stored_contract_name) => {
                 ExecutableDeployItem::StoredContractByName {
                     name: stored_contract_name.stored_contract_name,
+                    entry_point: stored_contract_name.entry_point,
                     args: stored_contract_name.args,
+                    named_args: stored_contract_name.named_args,
                 }
             }
             ipc::DeployPayload_oneof_payload::stored_contract_uref(stored_contract_uref) => {
                 ExecutableDeployItem::StoredContractByURef {
                     uref: stored_contract_uref.uref,
+                    entry_point: stored_contract_uref.entry_point,
                     args: stored_contract_uref.args,
+                    named_args: stored_contract_uref.named_args,
                 }
             }
         }
@@ -950,7 +1192,7 @@ impl From<ipc::ChainSpec_CostTable_WasmCosts> for WasmCosts {
         let max_stack_height = wasm_costs.get_max_stack_height();
         let opcodes_mul = wasm_costs.get_opcodes_mul();
         let opcodes_div = wasm_costs.get_opcodes_div();
-        WasmCosts {
+        let costs = WasmCosts {
             regular,
             div,
             mul,
@@ -961,6 +1203,18 @@ impl From<ipc::ChainSpec_CostTable_WasmCosts> for WasmCosts {
             max_stack_height,
             opcodes_mul,
             opcodes_div,
+            ..WasmCosts::default()
+        };
+        if !wasm_costs.has_crypto() {
+            return costs.with_default_crypto_costs();
+        }
+        let crypto_costs = wasm_costs.get_crypto();
+        WasmCosts {
+            hash: crypto_costs.get_hash(),
+            hash_byte: crypto_costs.get_hash_byte(),
+            verify_signature: crypto_costs.get_verify_signature(),
+            verify_signature_byte: crypto_costs.get_verify_signature_byte(),
+            ..costs
         }
     }
 }
@@ -978,10 +1232,48 @@ impl From<WasmCosts> for ipc::ChainSpec_CostTable_WasmCosts {
         cost_table.set_max_stack_height(wasm_costs.max_stack_height);
         cost_table.set_opcodes_mul(wasm_costs.opcodes_mul);
         cost_table.set_opcodes_div(wasm_costs.opcodes_div);
+        let mut crypto_costs = ipc::ChainSpec_CostTable_WasmCosts_CryptoCosts::new();
+        crypto_costs.set_hash(wasm_costs.hash);
+        crypto_costs.set_hash_byte(wasm_costs.hash_byte);
+        crypto_costs.set_verify_signature(wasm_costs.verify_signature);
+        crypto_costs.set_verify_signature_byte(wasm_costs.verify_signature_byte);
+        cost_table.set_crypto(crypto_costs);
         cost_table
     }
 }
 
+impl TryFrom<ipc::ChainSpec_RewardsConfig> for RewardsConfig {
+    type Error = MappingError;
+
+    fn try_from(rewards_config: ipc::ChainSpec_RewardsConfig) -> Result<Self, Self::Error> {
+        let distribution_interval = BlockTime(rewards_config.get_distribution_interval());
+        let validator_commission = rewards_config.get_validator_commission();
+        let burn = rewards_config.get_burn();
+        let treasury = rewards_config.get_treasury();
+        RewardsConfig::new(distribution_interval, validator_commission, burn, treasury).ok_or_else(
+            || {
+                ParsingError(format!(
+                    "Rewards shares should add up to 1,000,000: validator commission {}, burn {}, \
+                     treasury {}",
+                    validator_commission, burn, treasury
+                ))
+                .into()
+            },
+        )
+    }
+}
+
+impl From<RewardsConfig> for ipc::ChainSpec_RewardsConfig {
+    fn from(rewards_config: RewardsConfig) -> Self {
+        let mut ret = ipc::ChainSpec_RewardsConfig::new();
+        ret.set_distribution_interval(rewards_config.distribution_interval().0);
+        ret.set_validator_commission(rewards_config.validator_commission());
+        ret.set_burn(rewards_config.burn());
+        ret.set_treasury(rewards_config.treasury());
+        ret
+    }
+}
+
 impl TryFrom<ipc::ChainSpec_GenesisConfig> for GenesisConfig {
     type Error = MappingError;
 
@@ -998,6 +1290,11 @@ impl TryFrom<ipc::ChainSpec_GenesisConfig> for GenesisConfig {
             .map(TryInto::try_into)
             .collect::<Result<Vec<GenesisAccount>, Self::Error>>()?;
         let wasm_costs = genesis_config.get_costs().get_wasm().to_owned().into();
+        let rewards_config = if genesis_config.has_rewards_config() {
+            genesis_config.get_rewards_config().to_owned().try_into()?
+        } else {
+            RewardsConfig::default()
+        };
         Ok(GenesisConfig::new(
             name,
             timestamp,
@@ -1010,6 +1307,7 @@ impl TryFrom<ipc::ChainSpec_GenesisConfig> for GenesisConfig {
             proof_of_stake_initializer_bytes,
             accounts,
             wasm_costs,
+            rewards_config,
         ))
     }
 }
@@ -1038,6 +1336,7 @@ impl From<GenesisConfig> for ipc::ChainSpec_GenesisConfig {
             cost_table.set_wasm(genesis_config.wasm_costs().into());
             ret.set_costs(cost_table);
         }
+        ret.set_rewards_config(genesis_config.rewards_config().into());
         ret
     }
 }
@@ -1090,6 +1389,82 @@ impl TryFrom<ipc::UpgradeRequest> for UpgradeConfig {
     }
 }
 
+impl From<ipc::SlashRequest_Destination> for SlashDestination {
+    fn from(destination: ipc::SlashRequest_Destination) -> Self {
+        match destination {
+            ipc::SlashRequest_Destination::BURN => SlashDestination::Burn,
+            ipc::SlashRequest_Destination::TREASURY => SlashDestination::Treasury,
+        }
+    }
+}
+
+impl From<SlashDestination> for ipc::SlashRequest_Destination {
+    fn from(destination: SlashDestination) -> Self {
+        match destination {
+            SlashDestination::Burn => ipc::SlashRequest_Destination::BURN,
+            SlashDestination::Treasury => ipc::SlashRequest_Destination::TREASURY,
+        }
+    }
+}
+
+impl TryFrom<ipc::SlashRequest> for SlashConfig {
+    type Error = MappingError;
+
+    fn try_from(slash_request: ipc::SlashRequest) -> Result<Self, Self::Error> {
+        let pre_state_hash = slash_request
+            .get_parent_state_hash()
+            .try_into()
+            .map_err(|_| MappingError::InvalidHash("pre_state_hash".to_string()))?;
+
+        let protocol_version = slash_request.get_protocol_version().into();
+
+        let blocktime = BlockTime(slash_request.get_block_time());
+
+        let validators = slash_request
+            .get_validator_public_keys()
+            .iter()
+            .map(|bytes| {
+                bytes
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| MappingError::invalid_public_key_length(bytes.len()))
+            })
+            .collect::<Result<Vec<PublicKey>, _>>()?;
+
+        Ok(SlashConfig::new(
+            pre_state_hash,
+            protocol_version,
+            blocktime,
+            validators,
+            slash_request.get_fraction(),
+            slash_request.get_destination().into(),
+        ))
+    }
+}
+
+impl TryFrom<ipc::DistributeRewardsRequest> for DistributeRewardsConfig {
+    type Error = MappingError;
+
+    fn try_from(
+        distribute_rewards_request: ipc::DistributeRewardsRequest,
+    ) -> Result<Self, Self::Error> {
+        let pre_state_hash = distribute_rewards_request
+            .get_parent_state_hash()
+            .try_into()
+            .map_err(|_| MappingError::InvalidHash("pre_state_hash".to_string()))?;
+
+        let protocol_version = distribute_rewards_request.get_protocol_version().into();
+
+        let blocktime = BlockTime(distribute_rewards_request.get_block_time());
+
+        Ok(DistributeRewardsConfig::new(
+            pre_state_hash,
+            protocol_version,
+            blocktime,
+        ))
+    }
+}
+
 impl From<&state::ProtocolVersion> for contract_ffi::value::ProtocolVersion {
     fn from(protocol_version: &state::ProtocolVersion) -> Self {
         contract_ffi::value::ProtocolVersion::from_parts(
@@ -1123,7 +1498,8 @@ fn precondition_failure(msg: String) -> ipc::DeployResult {
 
 /// Constructs an instance of [[ipc::DeployResult]] with error set to
 /// [[ipc::DeployError_ExecutionError]].
-fn execution_error(msg: String, cost: U512, effect: ExecutionEffect) -> ipc::DeployResult {
+fn execution_error(msg: String, cost: U512, mut effect: ExecutionEffect) -> ipc::DeployResult {
+    let events = mem::replace(&mut effect.events, Vec::new());
     let mut deploy_result = ipc::DeployResult::new();
     let deploy_error = {
         let mut tmp = ipc::DeployError::new();
@@ -1137,6 +1513,9 @@ fn execution_error(msg: String, cost: U512, effect: ExecutionEffect) -> ipc::Dep
         tmp.set_error(deploy_error);
         tmp.set_cost(cost.into());
         tmp.set_effects(effect.into());
+        tmp.set_events(RepeatedField::from_vec(
+            events.into_iter().map(Into::into).collect(),
+        ));
         tmp
     };
     deploy_result.set_execution_result(execution_result);
@@ -1176,19 +1555,31 @@ mod tests {
     use std::convert::TryInto;
 
     use proptest::prelude::*;
+    use protobuf::RepeatedField;
 
-    use contract_ffi::gens::{account_arb, contract_arb, key_arb, named_keys_arb, value_arb};
+    use contract_ffi::gens::{
+        account_arb, cl_value_arb, contract_arb, key_arb, named_keys_arb, value_arb,
+    };
     use contract_ffi::key::Key;
+    use contract_ffi::system_contracts::pos::{RewardsConfig, SlashDestination};
     use contract_ffi::uref::{AccessRights, URef};
+    use contract_ffi::value::account::{BlockTime, PublicKey};
+    use contract_ffi::value::ProtocolVersion;
+    use engine_core::engine_state::distribute_rewards::DistributeRewardsConfig;
     use engine_core::engine_state::error::Error::ExecError;
     use engine_core::engine_state::error::{Error as EngineError, RootNotFound};
     use engine_core::engine_state::execution_effect::ExecutionEffect;
     use engine_core::engine_state::execution_result::ExecutionResult;
+    use engine_core::engine_state::slash::SlashConfig;
     use engine_core::execution::Error;
     use engine_shared::gas::Gas;
     use engine_shared::newtypes::Blake2bHash;
     use engine_shared::transform::gens::transform_arb;
     use engine_shared::transform::Transform;
+    use engine_wasm_prep::wasm_costs::gens::wasm_costs_arb;
+    use engine_wasm_prep::wasm_costs::{
+        WasmCosts, DEFAULT_HASH_COST, DEFAULT_VERIFY_SIGNATURE_COST,
+    };
 
     use crate::engine_server::mappings::CommitTransforms;
 
@@ -1360,6 +1751,89 @@ mod tests {
         );
     }
 
+    #[test]
+    fn slash_request_to_slash_config() {
+        let mut slash_request = ipc::SlashRequest::new();
+        slash_request.set_parent_state_hash(vec![1u8; 32]);
+        slash_request.set_protocol_version(ProtocolVersion::V1_0_0.into());
+        slash_request
+            .set_validator_public_keys(RepeatedField::from_vec(vec![vec![2u8; 32], vec![3u8; 32]]));
+        slash_request.set_fraction(250_000);
+        slash_request.set_destination(ipc::SlashRequest_Destination::TREASURY);
+        slash_request.set_block_time(100);
+
+        let slash_config: SlashConfig = slash_request
+            .clone()
+            .try_into()
+            .expect("should map to SlashConfig");
+        assert_eq!(slash_config.blocktime(), BlockTime(100));
+        assert_eq!(
+            slash_config.validators(),
+            &[PublicKey::new([2u8; 32]), PublicKey::new([3u8; 32])]
+        );
+        assert_eq!(slash_config.fraction(), 250_000);
+        assert_eq!(slash_config.destination(), SlashDestination::Treasury);
+
+        let mut invalid_slash_request = slash_request;
+        invalid_slash_request
+            .set_validator_public_keys(RepeatedField::from_vec(vec![vec![2u8; 31]]));
+        let result: Result<SlashConfig, _> = invalid_slash_request.try_into();
+        assert!(result.is_err(), "invalid public key should be rejected");
+    }
+
+    #[test]
+    fn distribute_rewards_request_to_distribute_rewards_config() {
+        let mut distribute_rewards_request = ipc::DistributeRewardsRequest::new();
+        distribute_rewards_request.set_parent_state_hash(vec![1u8; 32]);
+        distribute_rewards_request.set_protocol_version(ProtocolVersion::V1_0_0.into());
+        distribute_rewards_request.set_block_time(100);
+
+        let distribute_rewards_config: DistributeRewardsConfig = distribute_rewards_request
+            .clone()
+            .try_into()
+            .expect("should map to DistributeRewardsConfig");
+        assert_eq!(distribute_rewards_config.blocktime(), BlockTime(100));
+
+        let mut invalid_distribute_rewards_request = distribute_rewards_request;
+        invalid_distribute_rewards_request.set_parent_state_hash(vec![1u8; 31]);
+        let result: Result<DistributeRewardsConfig, _> =
+            invalid_distribute_rewards_request.try_into();
+        assert!(result.is_err(), "invalid pre state hash should be rejected");
+    }
+
+    #[test]
+    fn rewards_config_roundtrip() {
+        let rewards_config = RewardsConfig::new(BlockTime(100), 500_000, 300_000, 200_000)
+            .expect("should create rewards config");
+        let ipc_rewards_config: ipc::ChainSpec_RewardsConfig = rewards_config.into();
+        let rewards_config_back: RewardsConfig = ipc_rewards_config
+            .clone()
+            .try_into()
+            .expect("should map to RewardsConfig");
+        assert_eq!(rewards_config_back, rewards_config);
+
+        let mut invalid_rewards_config = ipc_rewards_config;
+        invalid_rewards_config.set_burn(300_001);
+        let result: Result<RewardsConfig, _> = invalid_rewards_config.try_into();
+        assert!(result.is_err(), "shares not adding up should be rejected");
+    }
+
+    #[test]
+    fn wasm_costs_without_crypto_costs_should_use_defaults() {
+        let mut ipc_wasm_costs = ipc::ChainSpec_CostTable_WasmCosts::new();
+        ipc_wasm_costs.set_regular(1);
+        let wasm_costs: WasmCosts = ipc_wasm_costs.clone().into();
+        assert_eq!(wasm_costs.regular, 1);
+        assert_eq!(wasm_costs.hash, DEFAULT_HASH_COST);
+        assert_eq!(wasm_costs.verify_signature, DEFAULT_VERIFY_SIGNATURE_COST);
+
+        // Configured costs are kept, even if they are zero
+        ipc_wasm_costs.set_crypto(ipc::ChainSpec_CostTable_WasmCosts_CryptoCosts::new());
+        let wasm_costs: WasmCosts = ipc_wasm_costs.into();
+        assert_eq!(wasm_costs.hash, 0);
+        assert_eq!(wasm_costs.verify_signature, 0);
+    }
+
     proptest! {
         #[test]
         fn key_roundtrip(key in key_arb()) {
@@ -1394,6 +1868,21 @@ mod tests {
             assert_eq!(contract, contract_back)
         }
 
+        #[test]
+        fn wasm_costs_roundtrip(wasm_costs in wasm_costs_arb()) {
+            let ipc_wasm_costs: ipc::ChainSpec_CostTable_WasmCosts = wasm_costs.into();
+            let wasm_costs_back: WasmCosts = ipc_wasm_costs.into();
+            assert_eq!(wasm_costs, wasm_costs_back)
+        }
+
+        #[test]
+        fn cl_value_roundtrip(cl_value in cl_value_arb()) {
+            let ipc_cl_value: super::state::CLValue = cl_value.clone().into();
+            let cl_value_back = (&ipc_cl_value).try_into()
+                .expect("Transforming state::CLValue into domain CLValue should succeed.");
+            assert_eq!(cl_value, cl_value_back)
+        }
+
         #[test]
         fn value_roundtrip(value in value_arb()) {
             let ipc_value: super::state::Value = value.clone().into();
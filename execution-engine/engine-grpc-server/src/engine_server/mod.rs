@@ -7,24 +7,34 @@ use std::marker::{Send, Sync};
 use std::time::Instant;
 
 use grpc::SingleResponse;
+use rayon::prelude::*;
 
+use contract_ffi::bytesrepr::ToBytes;
 use contract_ffi::key::Key;
 use contract_ffi::value::account::{BlockTime, PublicKey};
-use contract_ffi::value::{ProtocolVersion, U512};
-use engine_core::engine_state::error::Error as EngineError;
+use contract_ffi::value::{ProtocolVersion, Value, U512};
+use engine_core::engine_state::distribute_rewards::{
+    DistributeRewardsConfig, DistributeRewardsResult,
+};
+use engine_core::engine_state::error::{Error as EngineError, RootNotFound};
+use engine_core::engine_state::executable_deploy_item::ExecutableDeployItem;
+use engine_core::engine_state::execution_effect::ExecutionEffect;
 use engine_core::engine_state::execution_result::ExecutionResult;
 use engine_core::engine_state::genesis::{GenesisConfig, GenesisResult};
+use engine_core::engine_state::simulation::{PhaseCosts, SimulationResult};
 use engine_core::engine_state::EngineState;
 use engine_core::execution::{Executor, WasmiExecutor};
-use engine_core::tracking_copy::QueryResult;
+use engine_core::tracking_copy::ProvenQueryResult;
+use engine_shared::cl_value_json;
 use engine_shared::logging;
 use engine_shared::logging::{log_duration, log_info};
 use engine_shared::newtypes::{Blake2bHash, CorrelationId};
-use engine_storage::global_state::{CommitResult, StateProvider};
+use engine_storage::global_state::{CommitResult, DiffResult, PruneResult, StateProvider};
 use engine_wasm_prep::{Preprocessor, WasmiPreprocessor};
 
 use self::ipc_grpc::ExecutionEngineService;
 use self::mappings::*;
+use engine_core::engine_state::slash::{SlashConfig, SlashResult};
 use engine_core::engine_state::upgrade::{UpgradeConfig, UpgradeResult};
 use engine_shared::logging::log_level::LogLevel;
 
@@ -39,16 +49,28 @@ const EXPECTED_PUBLIC_KEY_LENGTH: usize = 32;
 const METRIC_DURATION_COMMIT: &str = "commit_duration";
 const METRIC_DURATION_EXEC: &str = "exec_duration";
 const METRIC_DURATION_QUERY: &str = "query_duration";
+const METRIC_DURATION_QUERY_ABSENCE: &str = "query_absence_duration";
 const METRIC_DURATION_VALIDATE: &str = "validate_duration";
 const METRIC_DURATION_GENESIS: &str = "genesis_duration";
 const METRIC_DURATION_UPGRADE: &str = "upgrade_duration";
+const METRIC_DURATION_PRUNE: &str = "prune_duration";
+const METRIC_DURATION_DIFF: &str = "diff_duration";
+const METRIC_DURATION_SIMULATE: &str = "simulate_duration";
+const METRIC_DURATION_SLASH: &str = "slash_duration";
+const METRIC_DURATION_DISTRIBUTE_REWARDS: &str = "distribute_rewards_duration";
 
 const TAG_RESPONSE_COMMIT: &str = "commit_response";
 const TAG_RESPONSE_EXEC: &str = "exec_response";
 const TAG_RESPONSE_QUERY: &str = "query_response";
+const TAG_RESPONSE_QUERY_ABSENCE: &str = "query_absence_response";
 const TAG_RESPONSE_VALIDATE: &str = "validate_response";
 const TAG_RESPONSE_GENESIS: &str = "genesis_response";
 const TAG_RESPONSE_UPGRADE: &str = "upgrade_response";
+const TAG_RESPONSE_PRUNE: &str = "prune_response";
+const TAG_RESPONSE_DIFF: &str = "diff_response";
+const TAG_RESPONSE_SIMULATE: &str = "simulate_response";
+const TAG_RESPONSE_SLASH: &str = "slash_response";
+const TAG_RESPONSE_DISTRIBUTE_REWARDS: &str = "distribute_rewards_response";
 
 const DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
 
@@ -59,7 +81,7 @@ const DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
 // (outer layer) leading to cleaner design.
 impl<S> ipc_grpc::ExecutionEngineService for EngineState<S>
 where
-    S: StateProvider,
+    S: StateProvider + Sync,
     EngineError: From<S::Error>,
     S::Error: Into<engine_core::execution::Error> + Debug,
 {
@@ -73,7 +95,7 @@ where
         // TODO: don't unwrap
         let state_hash: Blake2bHash = query_request.get_state_hash().try_into().unwrap();
 
-        let mut tracking_copy = match self.tracking_copy(state_hash) {
+        let tracking_copy = match self.tracking_copy(state_hash) {
             Err(storage_error) => {
                 let mut result = ipc::QueryResponse::new();
                 let error = format!("Error during checkout out Trie: {:?}", storage_error);
@@ -121,7 +143,7 @@ where
 
         let path = query_request.get_path();
 
-        let response = match tracking_copy.query(correlation_id, key, path) {
+        let response = match tracking_copy.query_with_proofs(correlation_id, key, path) {
             Err(err) => {
                 let mut result = ipc::QueryResponse::new();
                 let error = format!("{:?}", err);
@@ -129,16 +151,39 @@ where
                 result.set_failure(error);
                 result
             }
-            Ok(QueryResult::ValueNotFound(full_path)) => {
+            Ok(ProvenQueryResult::ValueNotFound(full_path)) => {
                 let mut result = ipc::QueryResponse::new();
                 let error = format!("Value not found: {:?}", full_path);
                 logging::log_warning(&error);
                 result.set_failure(error);
                 result
             }
-            Ok(QueryResult::Success(value)) => {
+            Ok(ProvenQueryResult::Success { value, proofs }) => {
                 let mut result = ipc::QueryResponse::new();
-                result.set_success(value.into());
+                match proofs
+                    .iter()
+                    .map(ToBytes::to_bytes)
+                    .collect::<Result<Vec<Vec<u8>>, _>>()
+                {
+                    Ok(proofs) => {
+                        if let Value::CLValue(cl_value) = &value {
+                            match cl_value_json::cl_value_to_json(cl_value) {
+                                Ok(json) => result.set_json(json.to_string()),
+                                Err(error) => logging::log_warning(&format!(
+                                    "Error rendering value as JSON: {:?}",
+                                    error
+                                )),
+                            }
+                        }
+                        result.set_success(value.into());
+                        result.set_merkle_proofs(protobuf::RepeatedField::from_vec(proofs));
+                    }
+                    Err(err) => {
+                        let error = format!("Error serializing merkle proofs: {:?}", err);
+                        logging::log_error(&error);
+                        result.set_failure(error);
+                    }
+                }
                 result
             }
         };
@@ -153,6 +198,112 @@ where
         grpc::SingleResponse::completed(response)
     }
 
+    fn query_absence(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        query_absence_request: ipc::QueryAbsenceRequest,
+    ) -> grpc::SingleResponse<ipc::QueryAbsenceResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let state_hash: Blake2bHash = match query_absence_request.get_state_hash().try_into() {
+            Err(_) => {
+                let mut result = ipc::QueryAbsenceResponse::new();
+                let error = format!(
+                    "Invalid state hash: {:?}",
+                    query_absence_request.get_state_hash()
+                );
+                logging::log_error(&error);
+                result.set_failure(error);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_QUERY_ABSENCE,
+                    "state_hash_parsing_error",
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
+            }
+            Ok(state_hash) => state_hash,
+        };
+
+        let tracking_copy = match self.tracking_copy(state_hash) {
+            Err(storage_error) => {
+                let mut result = ipc::QueryAbsenceResponse::new();
+                let error = format!("Error during checkout out Trie: {:?}", storage_error);
+                logging::log_error(&error);
+                result.set_failure(error);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_QUERY_ABSENCE,
+                    "tracking_copy_error",
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
+            }
+            Ok(None) => {
+                let mut result = ipc::QueryAbsenceResponse::new();
+                let error = format!("Root not found: {:?}", state_hash);
+                logging::log_warning(&error);
+                result.set_failure(error);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_QUERY_ABSENCE,
+                    "tracking_copy_root_not_found",
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
+            }
+            Ok(Some(tracking_copy)) => tracking_copy,
+        };
+
+        let key: Key = match query_absence_request.get_key().try_into() {
+            Err(ParsingError(err_msg)) => {
+                logging::log_error(&err_msg);
+                let mut result = ipc::QueryAbsenceResponse::new();
+                result.set_failure(err_msg);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_QUERY_ABSENCE,
+                    "key_parsing_error",
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
+            }
+            Ok(key) => key,
+        };
+
+        let mut result = ipc::QueryAbsenceResponse::new();
+        match tracking_copy.query_absence_proof(correlation_id, key) {
+            Err(err) => {
+                let error = format!("{:?}", err);
+                logging::log_error(&error);
+                result.set_failure(error);
+            }
+            Ok(None) => {
+                let error = format!("Key is present: {:?}", key);
+                logging::log_warning(&error);
+                result.set_failure(error);
+            }
+            Ok(Some(proof)) => match proof.to_bytes() {
+                Ok(proof_bytes) => result.set_absence_proof(proof_bytes),
+                Err(err) => {
+                    let error = format!("Error serializing absence proof: {:?}", err);
+                    logging::log_error(&error);
+                    result.set_failure(error);
+                }
+            },
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_QUERY_ABSENCE,
+            TAG_RESPONSE_QUERY_ABSENCE,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(result)
+    }
+
     fn execute(
         &self,
         _request_options: ::grpc::RequestOptions,
@@ -177,22 +328,43 @@ where
 
         let executor = WasmiExecutor;
 
-        let deploys_result: Result<Vec<ipc::DeployResult>, ipc::RootNotFound> = execute_deploys(
-            &self,
-            &executor,
-            &preprocessor,
-            prestate_hash,
-            blocktime,
-            deploys,
-            protocol_version,
-            correlation_id,
-        );
+        let deploys_result: Result<
+            (Vec<ipc::DeployResult>, Option<ExecutionEffect>),
+            ipc::RootNotFound,
+        > = if exec_request.get_chained() {
+            execute_deploys_chained(
+                &self,
+                &executor,
+                &preprocessor,
+                prestate_hash,
+                blocktime,
+                deploys,
+                protocol_version,
+                correlation_id,
+            )
+            .map(|(deploy_results, cumulative_effect)| (deploy_results, Some(cumulative_effect)))
+        } else {
+            execute_deploys(
+                &self,
+                &executor,
+                &preprocessor,
+                prestate_hash,
+                blocktime,
+                deploys,
+                protocol_version,
+                correlation_id,
+            )
+            .map(|deploy_results| (deploy_results, None))
+        };
 
         let exec_response = match deploys_result {
-            Ok(deploy_results) => {
+            Ok((deploy_results, maybe_cumulative_effect)) => {
                 let mut exec_response = ipc::ExecuteResponse::new();
                 let mut exec_result = ipc::ExecResult::new();
                 exec_result.set_deploy_results(protobuf::RepeatedField::from_vec(deploy_results));
+                if let Some(cumulative_effect) = maybe_cumulative_effect {
+                    exec_result.set_cumulative_effects(cumulative_effect.into());
+                }
                 exec_response.set_success(exec_result);
                 exec_response
             }
@@ -687,6 +859,376 @@ where
 
         grpc::SingleResponse::completed(upgrade_response)
     }
+
+    fn prune(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        prune_request: ipc::PruneRequest,
+    ) -> grpc::SingleResponse<ipc::PruneResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut roots_to_keep: Vec<Blake2bHash> = Vec::new();
+        for state_hash in prune_request.get_state_hashes_to_keep() {
+            match state_hash.as_slice().try_into() {
+                Ok(state_hash) => roots_to_keep.push(state_hash),
+                Err(_) => {
+                    let mut result = ipc::PruneResponse::new();
+                    let error = format!("Invalid state hash: {:?}", state_hash);
+                    logging::log_error(&error);
+                    result.set_failure(error);
+                    log_duration(
+                        correlation_id,
+                        METRIC_DURATION_PRUNE,
+                        "state_hash_parsing_error",
+                        start.elapsed(),
+                    );
+                    return grpc::SingleResponse::completed(result);
+                }
+            }
+        }
+
+        let mut result = ipc::PruneResponse::new();
+        match EngineState::prune(self, correlation_id, &roots_to_keep) {
+            Ok(PruneResult::Pruned { retained, deleted }) => {
+                let message = format!("pruned {} trie nodes, retained {}", deleted, retained);
+                log_info(&message);
+                let mut success = ipc::PruneResponse_PruneSuccess::new();
+                success.set_retained(retained as u64);
+                success.set_deleted(deleted as u64);
+                result.set_success(success);
+            }
+            Ok(PruneResult::RootNotFound(missing_root)) => {
+                let error = format!("Root not found: {:?}", missing_root);
+                logging::log_warning(&error);
+                let mut root_not_found = ipc::RootNotFound::new();
+                root_not_found.set_hash(missing_root.to_vec());
+                result.set_missing_state_hash(root_not_found);
+            }
+            Ok(PruneResult::NoRootsToKeep) => {
+                let error = "No state hashes to keep were given".to_string();
+                logging::log_warning(&error);
+                result.set_failure(error);
+            }
+            Err(err) => {
+                let error = format!("{:?}", err);
+                logging::log_error(&error);
+                result.set_failure(error);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_PRUNE,
+            TAG_RESPONSE_PRUNE,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(result)
+    }
+
+    fn diff(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        diff_request: ipc::DiffRequest,
+    ) -> grpc::SingleResponse<ipc::DiffResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut state_hashes: Vec<Blake2bHash> = Vec::new();
+        for state_hash in &[
+            diff_request.get_old_state_hash(),
+            diff_request.get_new_state_hash(),
+        ] {
+            match (*state_hash).try_into() {
+                Ok(state_hash) => state_hashes.push(state_hash),
+                Err(_) => {
+                    let mut result = ipc::DiffResponse::new();
+                    let error = format!("Invalid state hash: {:?}", state_hash);
+                    logging::log_error(&error);
+                    result.set_failure(error);
+                    log_duration(
+                        correlation_id,
+                        METRIC_DURATION_DIFF,
+                        "state_hash_parsing_error",
+                        start.elapsed(),
+                    );
+                    return grpc::SingleResponse::completed(result);
+                }
+            }
+        }
+
+        let mut result = ipc::DiffResponse::new();
+        match EngineState::diff(self, correlation_id, state_hashes[0], state_hashes[1]) {
+            Ok(DiffResult::Diff(diff_entries)) => {
+                let diffs: Vec<ipc::KeyDiff> = diff_entries.into_iter().map(Into::into).collect();
+                let mut success = ipc::DiffResponse_DiffResult::new();
+                success.set_diffs(protobuf::RepeatedField::from_vec(diffs));
+                result.set_success(success);
+            }
+            Ok(DiffResult::RootNotFound(missing_root)) => {
+                let error = format!("Root not found: {:?}", missing_root);
+                logging::log_warning(&error);
+                let mut root_not_found = ipc::RootNotFound::new();
+                root_not_found.set_hash(missing_root.to_vec());
+                result.set_missing_state_hash(root_not_found);
+            }
+            Err(err) => {
+                let error = format!("{:?}", err);
+                logging::log_error(&error);
+                result.set_failure(error);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_DIFF,
+            TAG_RESPONSE_DIFF,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(result)
+    }
+
+    fn simulate(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        simulate_request: ipc::SimulateRequest,
+    ) -> grpc::SingleResponse<ipc::SimulateResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let prestate_hash: Blake2bHash = match simulate_request.get_parent_state_hash().try_into() {
+            Ok(prestate_hash) => prestate_hash,
+            Err(_) => {
+                let mut result = ipc::SimulateResponse::new();
+                let error = format!(
+                    "Invalid state hash: {:?}",
+                    simulate_request.get_parent_state_hash()
+                );
+                logging::log_error(&error);
+                result.set_failure(error);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_SIMULATE,
+                    "state_hash_parsing_error",
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
+            }
+        };
+
+        let protocol_version = simulate_request.get_protocol_version().into();
+
+        let wasm_costs = match self.wasm_costs(protocol_version) {
+            Ok(Some(wasm_costs)) => wasm_costs,
+            Ok(None) => {
+                let mut result = ipc::SimulateResponse::new();
+                let error = format!("Unsupported protocol version: {:?}", protocol_version);
+                logging::log_error(&error);
+                result.set_failure(error);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_SIMULATE,
+                    TAG_RESPONSE_SIMULATE,
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
+            }
+            Err(err) => {
+                let mut result = ipc::SimulateResponse::new();
+                let error = format!("{:?}", err);
+                logging::log_error(&error);
+                result.set_failure(error);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_SIMULATE,
+                    TAG_RESPONSE_SIMULATE,
+                    start.elapsed(),
+                );
+                return grpc::SingleResponse::completed(result);
+            }
+        };
+
+        let preprocessor: WasmiPreprocessor = WasmiPreprocessor::new(wasm_costs);
+
+        let executor = WasmiExecutor;
+
+        let simulation_result = match parse_deploy_item(simulate_request.get_deploy()) {
+            Ok(ParsedDeployItem {
+                session,
+                payment,
+                address,
+                authorization_keys,
+                deploy_hash,
+            }) => EngineState::simulate(
+                self,
+                session,
+                payment,
+                address,
+                authorization_keys,
+                BlockTime(simulate_request.get_block_time()),
+                deploy_hash,
+                prestate_hash,
+                protocol_version,
+                correlation_id,
+                &executor,
+                &preprocessor,
+                !simulate_request.get_skip_authorization_thresholds(),
+            ),
+            Err(error) => Ok(SimulationResult::new(
+                ExecutionResult::precondition_failure(error),
+                PhaseCosts::default(),
+            )),
+        };
+
+        let mut result = ipc::SimulateResponse::new();
+        match simulation_result {
+            Ok(simulation_result) => {
+                let mut success = ipc::SimulateResponse_SimulateResult::new();
+                success.set_payment_cost(simulation_result.payment_cost().value().into());
+                success.set_session_cost(simulation_result.session_cost().value().into());
+                if let Some(suggested_payment) = simulation_result.suggested_payment() {
+                    success.set_suggested_payment(suggested_payment.value().into());
+                }
+                success.set_deploy_result(simulation_result.take_execution_result().into());
+                result.set_success(success);
+            }
+            Err(RootNotFound(missing_root)) => {
+                let error = format!("Root not found: {:?}", missing_root);
+                logging::log_warning(&error);
+                let mut root_not_found = ipc::RootNotFound::new();
+                root_not_found.set_hash(missing_root.to_vec());
+                result.set_missing_parent(root_not_found);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_SIMULATE,
+            TAG_RESPONSE_SIMULATE,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(result)
+    }
+
+    fn slash(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        slash_request: ipc::SlashRequest,
+    ) -> grpc::SingleResponse<ipc::SlashResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let slash_error_response = |err_msg: String| {
+            logging::log_error(&err_msg);
+
+            let mut slash_error = ipc::SlashError::new();
+            slash_error.set_message(err_msg);
+
+            let mut ret = ipc::SlashResponse::new();
+            ret.set_failed_slash(slash_error);
+            ret
+        };
+
+        let slash_response = match SlashConfig::try_from(slash_request) {
+            Ok(slash_config) => match EngineState::slash(self, correlation_id, slash_config) {
+                Ok(SlashResult::Success {
+                    post_state_hash,
+                    effect,
+                    bonded_validators,
+                }) => {
+                    let success_message = format!("slash successful: {}", post_state_hash);
+                    log_info(&success_message);
+
+                    let mut slash_result = ipc::SlashResult::new();
+                    slash_result.set_post_state_hash(post_state_hash.to_vec());
+                    slash_result.set_effect(effect.into());
+                    let bonds = bonded_validators.into_iter().map(Into::into).collect();
+                    slash_result.set_bonded_validators(bonds);
+
+                    let mut ret = ipc::SlashResponse::new();
+                    ret.set_success(slash_result);
+                    ret
+                }
+                Ok(slash_result) => slash_error_response(slash_result.to_string()),
+                Err(err) => slash_error_response(err.to_string()),
+            },
+            Err(error) => slash_error_response(error.to_string()),
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_SLASH,
+            TAG_RESPONSE_SLASH,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(slash_response)
+    }
+
+    fn distribute_rewards(
+        &self,
+        _request_options: ::grpc::RequestOptions,
+        distribute_rewards_request: ipc::DistributeRewardsRequest,
+    ) -> grpc::SingleResponse<ipc::DistributeRewardsResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let distribute_rewards_error_response = |err_msg: String| {
+            logging::log_error(&err_msg);
+
+            let mut distribute_rewards_error = ipc::DistributeRewardsError::new();
+            distribute_rewards_error.set_message(err_msg);
+
+            let mut ret = ipc::DistributeRewardsResponse::new();
+            ret.set_failed_distribution(distribute_rewards_error);
+            ret
+        };
+
+        let distribute_rewards_response =
+            match DistributeRewardsConfig::try_from(distribute_rewards_request) {
+                Ok(distribute_rewards_config) => {
+                    match EngineState::distribute_rewards(
+                        self,
+                        correlation_id,
+                        distribute_rewards_config,
+                    ) {
+                        Ok(DistributeRewardsResult::Success {
+                            post_state_hash,
+                            effect,
+                        }) => {
+                            let success_message =
+                                format!("distribute rewards successful: {}", post_state_hash);
+                            log_info(&success_message);
+
+                            let mut distribute_rewards_result = ipc::DistributeRewardsResult::new();
+                            distribute_rewards_result.set_post_state_hash(post_state_hash.to_vec());
+                            distribute_rewards_result.set_effect(effect.into());
+
+                            let mut ret = ipc::DistributeRewardsResponse::new();
+                            ret.set_success(distribute_rewards_result);
+                            ret
+                        }
+                        Ok(distribute_rewards_result) => {
+                            distribute_rewards_error_response(distribute_rewards_result.to_string())
+                        }
+                        Err(err) => distribute_rewards_error_response(err.to_string()),
+                    }
+                }
+                Err(error) => distribute_rewards_error_response(error.to_string()),
+            };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_DISTRIBUTE_REWARDS,
+            TAG_RESPONSE_DISTRIBUTE_REWARDS,
+            start.elapsed(),
+        );
+
+        grpc::SingleResponse::completed(distribute_rewards_response)
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -701,101 +1243,230 @@ fn execute_deploys<A, S, E, P>(
     correlation_id: CorrelationId,
 ) -> Result<Vec<ipc::DeployResult>, ipc::RootNotFound>
 where
-    S: StateProvider,
-    E: Executor<A>,
-    P: Preprocessor<A>,
+    S: StateProvider + Sync,
+    E: Executor<A> + Sync,
+    P: Preprocessor<A> + Sync,
     EngineError: From<S::Error>,
     S::Error: Into<engine_core::execution::Error>,
 {
+    let run_deploy = |deploy: &ipc::DeployItem| {
+        execute_deploy(
+            engine_state,
+            executor,
+            preprocessor,
+            prestate_hash,
+            blocktime,
+            deploy,
+            protocol_version,
+            correlation_id,
+        )
+    };
+
     // We want to treat RootNotFound error differently b/c it should short-circuit
     // the execution of ALL deploys within the block. This is because all of them
     // share the same prestate and all of them would fail.
     // Iterator (Result<_, _> + collect()) will short circuit the execution
     // when run_deploy returns Err.
-    deploys
-        .iter()
-        .map(|deploy| {
-            let session = match deploy.get_session().to_owned().payload {
-                Some(payload) => payload.into(),
-                None => {
-                    return Ok(
-                        ExecutionResult::precondition_failure(EngineError::DeployError).into(),
-                    )
-                }
-            };
-
-            let payment = match deploy.get_payment().to_owned().payload {
-                Some(payload) => payload.into(),
-                None => {
-                    return Ok(
-                        ExecutionResult::precondition_failure(EngineError::DeployError).into(),
-                    )
-                }
-            };
-
-            let address = {
-                let address_len = deploy.address.len();
-                if address_len != EXPECTED_PUBLIC_KEY_LENGTH {
-                    let err = EngineError::InvalidPublicKeyLength {
-                        expected: EXPECTED_PUBLIC_KEY_LENGTH,
-                        actual: address_len,
-                    };
-                    let failure = ExecutionResult::precondition_failure(err);
-                    return Ok(failure.into());
-                }
-                let mut dest = [0; EXPECTED_PUBLIC_KEY_LENGTH];
-                dest.copy_from_slice(&deploy.address);
-                Key::Account(dest)
-            };
+    if engine_state.config().deploy_workers() > 1 && deploys.len() > 1 {
+        // Every deploy is executed against the same prestate, so they are independent of each
+        // other and can run concurrently on the pool set up by `init_deploy_worker_pool`.
+        // `collect` preserves the order of the input deploys.
+        return deploys.par_iter().map(run_deploy).collect();
+    }
 
-            // Parse all authorization keys from IPC into a vector
-            let authorization_keys: BTreeSet<PublicKey> = {
-                let maybe_keys: Result<BTreeSet<_>, EngineError> = deploy
-                    .authorization_keys
-                    .iter()
-                    .map(|key_bytes| {
-                        // Try to convert an element of bytes into a possibly
-                        // valid PublicKey with error handling
-                        PublicKey::try_from(key_bytes.as_slice()).map_err(|_| {
-                            EngineError::InvalidPublicKeyLength {
-                                expected: EXPECTED_PUBLIC_KEY_LENGTH,
-                                actual: key_bytes.len(),
-                            }
-                        })
-                    })
-                    .collect();
+    deploys.iter().map(run_deploy).collect()
+}
 
-                match maybe_keys {
-                    Ok(keys) => keys,
-                    Err(error) => return Ok(ExecutionResult::precondition_failure(error).into()),
-                }
-            };
+#[allow(clippy::too_many_arguments)]
+fn execute_deploy<A, S, E, P>(
+    engine_state: &EngineState<S>,
+    executor: &E,
+    preprocessor: &P,
+    prestate_hash: Blake2bHash,
+    blocktime: BlockTime,
+    deploy: &ipc::DeployItem,
+    protocol_version: ProtocolVersion,
+    correlation_id: CorrelationId,
+) -> Result<ipc::DeployResult, ipc::RootNotFound>
+where
+    S: StateProvider,
+    E: Executor<A>,
+    P: Preprocessor<A>,
+    EngineError: From<S::Error>,
+    S::Error: Into<engine_core::execution::Error>,
+{
+    let ParsedDeployItem {
+        session,
+        payment,
+        address,
+        authorization_keys,
+        deploy_hash,
+    } = match parse_deploy_item(deploy) {
+        Ok(parsed_deploy_item) => parsed_deploy_item,
+        Err(error) => return Ok(ExecutionResult::precondition_failure(error).into()),
+    };
+
+    engine_state
+        .deploy(
+            session,
+            payment,
+            address,
+            authorization_keys,
+            blocktime,
+            deploy_hash,
+            prestate_hash,
+            protocol_version,
+            correlation_id,
+            executor,
+            preprocessor,
+        )
+        .map(Into::into)
+        .map_err(Into::into)
+}
 
-            let deploy_hash = {
-                let mut buff = [0u8; 32];
-                let hash_slice = deploy.get_deploy_hash();
-                buff.copy_from_slice(hash_slice);
-                buff
-            };
+/// Executes `deploys` one after another on a single `TrackingCopy`, so that each deploy observes
+/// the effects of the deploys preceding it.  Returns the result of each deploy along with the
+/// cumulative effects of all of them.
+#[allow(clippy::too_many_arguments)]
+fn execute_deploys_chained<A, S, E, P>(
+    engine_state: &EngineState<S>,
+    executor: &E,
+    preprocessor: &P,
+    prestate_hash: Blake2bHash,
+    blocktime: BlockTime,
+    deploys: &[ipc::DeployItem],
+    protocol_version: ProtocolVersion,
+    correlation_id: CorrelationId,
+) -> Result<(Vec<ipc::DeployResult>, ExecutionEffect), ipc::RootNotFound>
+where
+    S: StateProvider,
+    E: Executor<A>,
+    P: Preprocessor<A>,
+    EngineError: From<S::Error>,
+    S::Error: Into<engine_core::execution::Error>,
+{
+    let mut tracking_copy = match engine_state.tracking_copy(prestate_hash) {
+        Err(error) => {
+            let failure: ipc::DeployResult = ExecutionResult::precondition_failure(error).into();
+            return Ok((vec![failure; deploys.len()], ExecutionEffect::default()));
+        }
+        Ok(None) => return Err(RootNotFound(prestate_hash).into()),
+        Ok(Some(tracking_copy)) => tracking_copy,
+    };
 
-            engine_state
-                .deploy(
+    let deploy_results = deploys
+        .iter()
+        .map(|deploy| {
+            let execution_result = match parse_deploy_item(deploy) {
+                Ok(ParsedDeployItem {
+                    session,
+                    payment,
+                    address,
+                    authorization_keys,
+                    deploy_hash,
+                }) => engine_state.deploy_chained(
+                    &mut tracking_copy,
                     session,
                     payment,
                     address,
                     authorization_keys,
                     blocktime,
                     deploy_hash,
-                    prestate_hash,
                     protocol_version,
                     correlation_id,
                     executor,
                     preprocessor,
-                )
-                .map(Into::into)
-                .map_err(Into::into)
+                ),
+                Err(error) => ExecutionResult::precondition_failure(error),
+            };
+            execution_result.into()
         })
-        .collect()
+        .collect();
+
+    Ok((deploy_results, tracking_copy.effect()))
+}
+
+/// The components of an [`ipc::DeployItem`] which are needed to execute it.
+struct ParsedDeployItem {
+    session: ExecutableDeployItem,
+    payment: ExecutableDeployItem,
+    address: Key,
+    authorization_keys: BTreeSet<PublicKey>,
+    deploy_hash: [u8; 32],
+}
+
+fn parse_deploy_item(deploy: &ipc::DeployItem) -> Result<ParsedDeployItem, EngineError> {
+    let session = match deploy.get_session().to_owned().payload {
+        Some(payload) => payload.into(),
+        None => return Err(EngineError::DeployError),
+    };
+
+    let payment = match deploy.get_payment().to_owned().payload {
+        Some(payload) => payload.into(),
+        None => return Err(EngineError::DeployError),
+    };
+
+    let address = {
+        let address_len = deploy.address.len();
+        if address_len != EXPECTED_PUBLIC_KEY_LENGTH {
+            return Err(EngineError::InvalidPublicKeyLength {
+                expected: EXPECTED_PUBLIC_KEY_LENGTH,
+                actual: address_len,
+            });
+        }
+        let mut dest = [0; EXPECTED_PUBLIC_KEY_LENGTH];
+        dest.copy_from_slice(&deploy.address);
+        Key::Account(dest)
+    };
+
+    // Parse all authorization keys from IPC into a vector
+    let authorization_keys: BTreeSet<PublicKey> = deploy
+        .authorization_keys
+        .iter()
+        .map(|key_bytes| {
+            // Try to convert an element of bytes into a possibly
+            // valid PublicKey with error handling
+            PublicKey::try_from(key_bytes.as_slice()).map_err(|_| {
+                EngineError::InvalidPublicKeyLength {
+                    expected: EXPECTED_PUBLIC_KEY_LENGTH,
+                    actual: key_bytes.len(),
+                }
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    let deploy_hash = {
+        let mut buff = [0u8; 32];
+        let hash_slice = deploy.get_deploy_hash();
+        buff.copy_from_slice(hash_slice);
+        buff
+    };
+
+    Ok(ParsedDeployItem {
+        session,
+        payment,
+        address,
+        authorization_keys,
+        deploy_hash,
+    })
+}
+
+/// Sets up the pool of `deploy_workers` threads on which the deploys of a block are executed
+/// concurrently.  This must be called once, at server setup, before any deploys are executed.
+pub fn init_deploy_worker_pool(deploy_workers: usize) {
+    if deploy_workers <= 1 {
+        return;
+    }
+    if let Err(error) = rayon::ThreadPoolBuilder::new()
+        .num_threads(deploy_workers)
+        .build_global()
+    {
+        let error = format!(
+            "Could not build deploy worker pool, using the default pool: {:?}",
+            error
+        );
+        logging::log_warning(&error);
+    }
 }
 
 // Helper method which returns single DeployResult that is set to be a
@@ -7,6 +7,7 @@ extern crate grpc;
 extern crate lmdb;
 extern crate proptest;
 extern crate protobuf;
+extern crate rayon;
 extern crate wabt;
 
 #[cfg(test)]
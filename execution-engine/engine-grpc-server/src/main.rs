@@ -7,12 +7,15 @@ extern crate lazy_static;
 extern crate lmdb;
 
 extern crate casperlabs_engine_grpc_server;
+extern crate contract_ffi;
 extern crate engine_core;
 extern crate engine_shared;
 extern crate engine_storage;
 
 use std::collections::btree_map::BTreeMap;
-use std::fs;
+use std::convert::TryFrom;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -20,15 +23,19 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use clap::{App, Arg, ArgMatches};
+use contract_ffi::base16;
 use dirs::home_dir;
 use engine_core::engine_state::{EngineConfig, EngineState};
 use lmdb::DatabaseFlags;
 
 use engine_shared::logging::log_settings::{LogLevelFilter, LogSettings};
 use engine_shared::logging::{log_level, log_settings};
+use engine_shared::newtypes::{Blake2bHash, CorrelationId};
 use engine_shared::os::get_page_size;
 use engine_shared::{logging, socket};
 use engine_storage::global_state::lmdb::LmdbGlobalState;
+use engine_storage::global_state::PruneResult;
+use engine_storage::snapshot::{self, DEFAULT_CHUNK_SIZE};
 use engine_storage::transaction_source::lmdb::LmdbEnvironment;
 use engine_storage::trie_store::lmdb::LmdbTrieStore;
 
@@ -85,6 +92,45 @@ const ARG_USE_PAYMENT_CODE: &str = "use-payment-code";
 const ARG_USE_PAYMENT_CODE_SHORT: &str = "x";
 const ARG_USE_PAYMENT_CODE_HELP: &str = "Enables the use of payment code";
 
+// deploy-workers
+const ARG_DEPLOY_WORKERS: &str = "deploy-workers";
+const ARG_DEPLOY_WORKERS_SHORT: &str = "w";
+const ARG_DEPLOY_WORKERS_VALUE: &str = "NUM";
+const ARG_DEPLOY_WORKERS_HELP: &str =
+    "Sets the number of threads used to execute the deploys of a block concurrently";
+const GET_DEPLOY_WORKERS_EXPECT: &str = "Could not parse deploy-workers argument";
+const DEFAULT_DEPLOY_WORKERS: usize = 1;
+
+// prune
+const ARG_PRUNE: &str = "prune-keep";
+const ARG_PRUNE_VALUE: &str = "STATE_HASH";
+const ARG_PRUNE_HELP: &str =
+    "Prunes global state unreachable from the given base16 state hashes, then exits";
+const GET_PRUNE_EXPECT: &str = "Could not parse prune-keep argument";
+const PRUNE_START_MESSAGE: &str = "pruning global state";
+const PRUNE_EXPECT: &str = "failed to prune global state";
+const PRUNE_ROOT_NOT_FOUND: &str = "Root not found, nothing was pruned";
+const PRUNE_NO_ROOTS_TO_KEEP: &str = "No state hashes to keep were given, nothing was pruned";
+
+// snapshot
+const ARG_EXPORT_SNAPSHOT: &str = "export-snapshot";
+const ARG_EXPORT_SNAPSHOT_VALUE: &str = "STATE_HASH";
+const ARG_EXPORT_SNAPSHOT_HELP: &str =
+    "Exports a snapshot of the given base16 state hash to the snapshot file, then exits";
+const ARG_IMPORT_SNAPSHOT: &str = "import-snapshot";
+const ARG_IMPORT_SNAPSHOT_VALUE: &str = "STATE_HASH";
+const ARG_IMPORT_SNAPSHOT_HELP: &str =
+    "Imports a snapshot of the given base16 state hash from the snapshot file, then exits";
+const ARG_SNAPSHOT_FILE: &str = "snapshot-file";
+const ARG_SNAPSHOT_FILE_VALUE: &str = "FILE";
+const ARG_SNAPSHOT_FILE_HELP: &str = "Sets the file to export a snapshot to or import it from";
+const GET_SNAPSHOT_STATE_HASH_EXPECT: &str = "Could not parse snapshot state hash";
+const GET_SNAPSHOT_FILE_EXPECT: &str = "snapshot-file required";
+const SNAPSHOT_FILE_EXPECT: &str = "Could not open snapshot file";
+const EXPORT_SNAPSHOT_EXPECT: &str = "failed to export snapshot";
+const FLUSH_SNAPSHOT_EXPECT: &str = "failed to write snapshot file";
+const IMPORT_SNAPSHOT_EXPECT: &str = "failed to import snapshot";
+
 // runnable
 const SIGINT_HANDLE_EXPECT: &str = "Error setting Ctrl-C handler";
 const RUNNABLE_CHECK_INTERVAL_SECONDS: u64 = 3;
@@ -108,6 +154,30 @@ fn main() {
 
     let matches: &clap::ArgMatches = &*ARG_MATCHES;
 
+    if let Some(roots_to_keep) = get_prune_roots(matches) {
+        let data_dir = get_data_dir(matches);
+        let map_size = get_map_size(matches);
+        let engine_config: EngineConfig = get_engine_config(matches);
+        prune(data_dir, map_size, engine_config, &roots_to_keep);
+        return;
+    }
+
+    if let Some(state_hash) = get_snapshot_state_hash(matches, ARG_EXPORT_SNAPSHOT) {
+        let data_dir = get_data_dir(matches);
+        let map_size = get_map_size(matches);
+        let snapshot_file = get_snapshot_file(matches);
+        export_snapshot(data_dir, map_size, state_hash, snapshot_file);
+        return;
+    }
+
+    if let Some(state_hash) = get_snapshot_state_hash(matches, ARG_IMPORT_SNAPSHOT) {
+        let data_dir = get_data_dir(matches);
+        let map_size = get_map_size(matches);
+        let snapshot_file = get_snapshot_file(matches);
+        import_snapshot(data_dir, map_size, state_hash, snapshot_file);
+        return;
+    }
+
     let socket = get_socket(matches);
 
     match socket.remove_file() {
@@ -190,9 +260,51 @@ fn get_args() -> ArgMatches<'static> {
                 .long(ARG_USE_PAYMENT_CODE)
                 .help(ARG_USE_PAYMENT_CODE_HELP),
         )
+        .arg(
+            Arg::with_name(ARG_DEPLOY_WORKERS)
+                .short(ARG_DEPLOY_WORKERS_SHORT)
+                .long(ARG_DEPLOY_WORKERS)
+                .value_name(ARG_DEPLOY_WORKERS_VALUE)
+                .help(ARG_DEPLOY_WORKERS_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_PRUNE)
+                .long(ARG_PRUNE)
+                .value_name(ARG_PRUNE_VALUE)
+                .help(ARG_PRUNE_HELP)
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+        )
+        .arg(
+            Arg::with_name(ARG_EXPORT_SNAPSHOT)
+                .long(ARG_EXPORT_SNAPSHOT)
+                .value_name(ARG_EXPORT_SNAPSHOT_VALUE)
+                .help(ARG_EXPORT_SNAPSHOT_HELP)
+                .takes_value(true)
+                .requires(ARG_SNAPSHOT_FILE)
+                .conflicts_with_all(&[ARG_PRUNE, ARG_IMPORT_SNAPSHOT]),
+        )
+        .arg(
+            Arg::with_name(ARG_IMPORT_SNAPSHOT)
+                .long(ARG_IMPORT_SNAPSHOT)
+                .value_name(ARG_IMPORT_SNAPSHOT_VALUE)
+                .help(ARG_IMPORT_SNAPSHOT_HELP)
+                .takes_value(true)
+                .requires(ARG_SNAPSHOT_FILE)
+                .conflicts_with(ARG_PRUNE),
+        )
+        .arg(
+            Arg::with_name(ARG_SNAPSHOT_FILE)
+                .long(ARG_SNAPSHOT_FILE)
+                .value_name(ARG_SNAPSHOT_FILE_VALUE)
+                .help(ARG_SNAPSHOT_FILE_HELP)
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name(ARG_SOCKET)
-                .required(true)
+                .required_unless_one(&[ARG_PRUNE, ARG_EXPORT_SNAPSHOT, ARG_IMPORT_SNAPSHOT])
                 .help(ARG_SOCKET_HELP)
                 .index(1),
         )
@@ -242,10 +354,121 @@ fn get_map_size(matches: &ArgMatches) -> usize {
     page_size * pages
 }
 
-/// Parses `use-payment-code` argument and returns an [`EngineConfig`].
+/// Parses `use-payment-code` and `deploy-workers` arguments and returns an [`EngineConfig`].
 fn get_engine_config(matches: &ArgMatches) -> EngineConfig {
     let use_payment_code = matches.is_present(ARG_USE_PAYMENT_CODE);
-    EngineConfig::new().set_use_payment_code(use_payment_code)
+    let deploy_workers = matches
+        .value_of(ARG_DEPLOY_WORKERS)
+        .map_or(Ok(DEFAULT_DEPLOY_WORKERS), usize::from_str)
+        .expect(GET_DEPLOY_WORKERS_EXPECT);
+    EngineConfig::new()
+        .set_use_payment_code(use_payment_code)
+        .set_deploy_workers(deploy_workers)
+}
+
+/// Parses `prune-keep` arguments, if any, into state hashes
+fn get_prune_roots(matches: &ArgMatches) -> Option<Vec<Blake2bHash>> {
+    matches.values_of(ARG_PRUNE).map(|values| {
+        values
+            .map(|value| {
+                let bytes = base16::decode_lower(value).expect(GET_PRUNE_EXPECT);
+                Blake2bHash::try_from(bytes.as_slice()).expect(GET_PRUNE_EXPECT)
+            })
+            .collect()
+    })
+}
+
+/// Deletes all global state not reachable from `roots_to_keep`.
+fn prune(
+    data_dir: PathBuf,
+    map_size: usize,
+    engine_config: EngineConfig,
+    roots_to_keep: &[Blake2bHash],
+) {
+    logging::log_info(PRUNE_START_MESSAGE);
+
+    let engine_state = get_engine_state(data_dir, map_size, engine_config);
+
+    match engine_state
+        .prune(CorrelationId::new(), roots_to_keep)
+        .expect(PRUNE_EXPECT)
+    {
+        PruneResult::Pruned { retained, deleted } => logging::log_info(&format!(
+            "pruned {} trie nodes, retained {}",
+            deleted, retained
+        )),
+        PruneResult::RootNotFound(missing_root) => {
+            panic!("{}: {}", PRUNE_ROOT_NOT_FOUND, missing_root)
+        }
+        PruneResult::NoRootsToKeep => panic!("{}", PRUNE_NO_ROOTS_TO_KEEP),
+    }
+}
+
+/// Parses the state hash given to `arg_name`, if any
+fn get_snapshot_state_hash(matches: &ArgMatches, arg_name: &str) -> Option<Blake2bHash> {
+    matches.value_of(arg_name).map(|value| {
+        let bytes = base16::decode_lower(value).expect(GET_SNAPSHOT_STATE_HASH_EXPECT);
+        Blake2bHash::try_from(bytes.as_slice()).expect(GET_SNAPSHOT_STATE_HASH_EXPECT)
+    })
+}
+
+/// Gets value of snapshot-file argument
+fn get_snapshot_file(matches: &ArgMatches) -> PathBuf {
+    matches
+        .value_of(ARG_SNAPSHOT_FILE)
+        .map(PathBuf::from)
+        .expect(GET_SNAPSHOT_FILE_EXPECT)
+}
+
+/// Writes a snapshot of the global state at `state_hash` to `snapshot_file`.
+fn export_snapshot(
+    data_dir: PathBuf,
+    map_size: usize,
+    state_hash: Blake2bHash,
+    snapshot_file: PathBuf,
+) {
+    let global_state = get_global_state(data_dir, map_size);
+    let mut writer = BufWriter::new(File::create(&snapshot_file).expect(SNAPSHOT_FILE_EXPECT));
+
+    let summary = snapshot::export(
+        global_state.environment.as_ref(),
+        global_state.trie_store.as_ref(),
+        &state_hash,
+        DEFAULT_CHUNK_SIZE,
+        &mut writer,
+    )
+    .expect(EXPORT_SNAPSHOT_EXPECT);
+    // Dropping a `BufWriter` discards any error from writing out its buffer
+    writer.flush().expect(FLUSH_SNAPSHOT_EXPECT);
+
+    logging::log_info(&format!(
+        "exported {} trie nodes of {} in {} chunks to {:?}",
+        summary.tries, summary.root, summary.chunks, snapshot_file
+    ));
+}
+
+/// Reads a snapshot of the global state at `state_hash` from `snapshot_file`.
+fn import_snapshot(
+    data_dir: PathBuf,
+    map_size: usize,
+    state_hash: Blake2bHash,
+    snapshot_file: PathBuf,
+) {
+    let global_state = get_global_state(data_dir, map_size);
+    let mut reader = BufReader::new(File::open(&snapshot_file).expect(SNAPSHOT_FILE_EXPECT));
+
+    let summary = snapshot::import(
+        global_state.environment.as_ref(),
+        global_state.trie_store.as_ref(),
+        &state_hash,
+        &mut reader,
+    )
+    .expect(IMPORT_SNAPSHOT_EXPECT);
+
+    logging::log_info(&format!(
+        "imported {} trie nodes of {} in {} chunks from {:?}",
+        summary.tries, summary.root, summary.chunks, snapshot_file
+    ));
 }
 
 /// Builds and returns a gRPC server.
@@ -255,6 +478,8 @@ fn get_grpc_server(
     map_size: usize,
     engine_config: EngineConfig,
 ) -> grpc::Server {
+    engine_server::init_deploy_worker_pool(engine_config.deploy_workers());
+
     let engine_state = get_engine_state(data_dir, map_size, engine_config);
 
     engine_server::new(socket.as_str(), engine_state)
@@ -268,6 +493,13 @@ fn get_engine_state(
     map_size: usize,
     engine_config: EngineConfig,
 ) -> EngineState<LmdbGlobalState> {
+    let global_state = get_global_state(data_dir, map_size);
+
+    EngineState::new(global_state, engine_config)
+}
+
+/// Builds and returns LMDB-backed global state
+fn get_global_state(data_dir: PathBuf, map_size: usize) -> LmdbGlobalState {
     let environment = {
         let ret = LmdbEnvironment::new(&data_dir, map_size).expect(LMDB_ENVIRONMENT_EXPECT);
         Arc::new(ret)
@@ -285,10 +517,8 @@ fn get_engine_state(
         Arc::new(ret)
     };
 
-    let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
-        .expect(LMDB_GLOBAL_STATE_EXPECT);
-
-    EngineState::new(global_state, engine_config)
+    LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
+        .expect(LMDB_GLOBAL_STATE_EXPECT)
 }
 
 /// Builds and returns log_settings
@@ -0,0 +1,179 @@
+use serde_json::{json, Map, Value as JsonValue};
+
+use contract_ffi::bytesrepr::{self, FromBytes, FromBytesBorrowed};
+use contract_ffi::key::Key;
+use contract_ffi::uref::URef;
+use contract_ffi::value::cl_value::{element_budget, read_collection_length};
+use contract_ffi::value::{CLType, CLValue, U128, U256, U512};
+
+/// Renders the value held by `cl_value` as JSON, guided by its type.
+///
+/// Big integers are rendered as decimal strings, keys and urefs as their formatted names, maps as
+/// arrays of `{"key": .., "value": ..}` objects and results as `{"Ok": ..}` or `{"Err": ..}`.
+pub fn cl_value_to_json(cl_value: &CLValue) -> Result<JsonValue, bytesrepr::Error> {
+    let bytes = cl_value.inner_bytes();
+    let (json, rem) = to_json(cl_value.cl_type(), bytes, &mut element_budget(bytes))?;
+    if !rem.is_empty() {
+        return Err(bytesrepr::Error::LeftOverBytes);
+    }
+    Ok(json)
+}
+
+fn parse<T: FromBytes>(bytes: &[u8]) -> Result<(T, &[u8]), bytesrepr::Error> {
+    FromBytes::from_bytes(bytes)
+}
+
+/// Renders the serialized value described by `cl_type`, charging its list and map elements to
+/// `budget`, and returns the bytes following the value.
+fn to_json<'a>(
+    cl_type: &CLType,
+    bytes: &'a [u8],
+    budget: &mut usize,
+) -> Result<(JsonValue, &'a [u8]), bytesrepr::Error> {
+    match cl_type {
+        CLType::Bool => parse::<bool>(bytes).map(|(b, rem)| (json!(b), rem)),
+        CLType::I32 => parse::<i32>(bytes).map(|(i, rem)| (json!(i), rem)),
+        CLType::I64 => parse::<i64>(bytes).map(|(i, rem)| (json!(i), rem)),
+        CLType::U8 => parse::<u8>(bytes).map(|(u, rem)| (json!(u), rem)),
+        CLType::U32 => parse::<u32>(bytes).map(|(u, rem)| (json!(u), rem)),
+        CLType::U64 => parse::<u64>(bytes).map(|(u, rem)| (json!(u), rem)),
+        CLType::U128 => parse::<U128>(bytes).map(|(u, rem)| (json!(u.to_string()), rem)),
+        CLType::U256 => parse::<U256>(bytes).map(|(u, rem)| (json!(u.to_string()), rem)),
+        CLType::U512 => parse::<U512>(bytes).map(|(u, rem)| (json!(u.to_string()), rem)),
+        CLType::Unit => Ok((JsonValue::Null, bytes)),
+        CLType::String => {
+            let (s, rem): (&str, &[u8]) = FromBytesBorrowed::from_bytes_borrowed(bytes)?;
+            Ok((json!(s), rem))
+        }
+        CLType::Key => parse::<Key>(bytes).map(|(key, rem)| (json!(key.as_string()), rem)),
+        CLType::URef => parse::<URef>(bytes).map(|(uref, rem)| (json!(uref.as_string()), rem)),
+        CLType::Option(inner) => match parse::<u8>(bytes)? {
+            (0, rem) => Ok((JsonValue::Null, rem)),
+            (1, rem) => to_json(inner, rem, budget),
+            _ => Err(bytesrepr::Error::FormattingError),
+        },
+        CLType::List(inner) => {
+            let (len, mut stream) = read_collection_length(bytes, budget)?;
+            let mut items = Vec::new();
+            for _ in 0..len {
+                let (item, rem) = to_json(inner, stream, budget)?;
+                items.push(item);
+                stream = rem;
+            }
+            Ok((JsonValue::Array(items), stream))
+        }
+        CLType::Result { ok, err } => {
+            let (tag, rem) = parse::<u8>(bytes)?;
+            let (variant, inner) = match tag {
+                0 => ("Err", err),
+                1 => ("Ok", ok),
+                _ => return Err(bytesrepr::Error::FormattingError),
+            };
+            let (value, rem) = to_json(inner, rem, budget)?;
+            let mut object = Map::new();
+            object.insert(variant.to_string(), value);
+            Ok((JsonValue::Object(object), rem))
+        }
+        CLType::Map { key, value } => {
+            let (len, mut stream) = read_collection_length(bytes, budget)?;
+            let mut entries = Vec::new();
+            for _ in 0..len {
+                let (k, rem) = to_json(key, stream, budget)?;
+                let (v, rem) = to_json(value, rem, budget)?;
+                entries.push(json!({ "key": k, "value": v }));
+                stream = rem;
+            }
+            Ok((JsonValue::Array(entries), stream))
+        }
+        CLType::Tuple(types) => {
+            let mut items = Vec::with_capacity(types.len());
+            let mut stream = bytes;
+            for cl_type in types {
+                let (item, rem) = to_json(cl_type, stream, budget)?;
+                items.push(item);
+                stream = rem;
+            }
+            Ok((JsonValue::Array(items), stream))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde_json::json;
+
+    use contract_ffi::bytesrepr;
+    use contract_ffi::key::Key;
+    use contract_ffi::uref::{AccessRights, URef};
+    use contract_ffi::value::cl_value::ZERO_SIZED_ELEMENT_ALLOWANCE;
+    use contract_ffi::value::{CLType, CLValue, U512};
+
+    use super::cl_value_to_json;
+
+    #[test]
+    fn should_render_primitives() {
+        let cl_value = CLValue::from_t(&(true, -3i64, U512::from(1_000u64), ())).unwrap();
+        assert_eq!(
+            cl_value_to_json(&cl_value),
+            Ok(json!([true, -3, "1000", null]))
+        );
+    }
+
+    #[test]
+    fn should_render_collections() {
+        let uref = URef::new([1; 32], AccessRights::READ);
+        let mut map = BTreeMap::new();
+        map.insert(String::from("a"), vec![Some(Key::URef(uref)), None]);
+        let cl_value = CLValue::from_t(&map).unwrap();
+        assert_eq!(
+            cl_value_to_json(&cl_value),
+            Ok(json!([{ "key": "a", "value": [uref.as_string(), null] }]))
+        );
+
+        let result: Result<u32, String> = Err(String::from("failed"));
+        let cl_value = CLValue::from_t(&result).unwrap();
+        assert_eq!(cl_value_to_json(&cl_value), Ok(json!({ "Err": "failed" })));
+    }
+
+    #[test]
+    fn should_fail_if_bytes_do_not_match_type() {
+        let cl_value = CLValue::from_components(CLType::U32, vec![1, 2]);
+        assert_eq!(
+            cl_value_to_json(&cl_value),
+            Err(bytesrepr::Error::EarlyEndOfStream)
+        );
+    }
+
+    #[test]
+    fn should_fail_if_length_exceeds_budget() {
+        let cl_value = CLValue::from_components(
+            CLType::List(Box::new(CLType::Unit)),
+            u32::max_value().to_le_bytes().to_vec(),
+        );
+        assert_eq!(
+            cl_value_to_json(&cl_value),
+            Err(bytesrepr::Error::FormattingError)
+        );
+
+        // Each inner list is within the budget on its own, but not all of them together.
+        let inner_length = ZERO_SIZED_ELEMENT_ALLOWANCE as u32;
+        let mut bytes = 8u32.to_le_bytes().to_vec();
+        for _ in 0..8 {
+            bytes.extend_from_slice(&inner_length.to_le_bytes());
+        }
+        let cl_type = CLType::List(Box::new(CLType::List(Box::new(CLType::Unit))));
+        let cl_value = CLValue::from_components(cl_type, bytes);
+        assert_eq!(
+            cl_value_to_json(&cl_value),
+            Err(bytesrepr::Error::FormattingError)
+        );
+    }
+
+    #[test]
+    fn should_render_list_of_units() {
+        let cl_value = CLValue::from_t(&vec![(); 3]).unwrap();
+        assert_eq!(cl_value_to_json(&cl_value), Ok(json!([null, null, null])));
+    }
+}
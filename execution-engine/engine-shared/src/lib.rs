@@ -11,6 +11,7 @@ extern crate log;
 extern crate num;
 extern crate parity_wasm;
 
+pub mod cl_value_json;
 #[macro_use]
 pub mod gas;
 pub mod logging;
@@ -92,6 +92,10 @@ impl ToBytes for Blake2bHash {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         ToBytes::to_bytes(&self.0)
     }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
 }
 
 impl FromBytes for Blake2bHash {
@@ -39,6 +39,10 @@ pub fn wasm_costs_mock() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 3,
         opcodes_div: 8,
+        hash: 100,
+        hash_byte: 1,
+        verify_signature: 10_000,
+        verify_signature_byte: 1,
     }
 }
 
@@ -54,5 +58,9 @@ pub fn wasm_costs_free() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 1,
         opcodes_div: 1,
+        hash: 0,
+        hash_byte: 0,
+        verify_signature: 0,
+        verify_signature_byte: 0,
     }
 }
@@ -171,8 +171,12 @@ impl Transform {
                     a.named_keys_append(&mut keys);
                     Ok(Value::Account(a))
                 }
+                Value::ContractPackage(mut p) => {
+                    p.named_keys_append(&mut keys);
+                    Ok(Value::ContractPackage(p))
+                }
                 other => {
-                    let expected = String::from("Contract or Account");
+                    let expected = String::from("Contract, ContractPackage or Account");
                     Err(TypeMismatch {
                         expected,
                         found: other.type_string(),
@@ -3,6 +3,9 @@ use std::sync;
 use failure::Fail;
 
 use contract_ffi::bytesrepr;
+use engine_shared::newtypes::Blake2bHash;
+
+use super::MissingTrie;
 
 #[derive(Debug, Fail, PartialEq, Eq)]
 pub enum Error {
@@ -11,6 +14,9 @@ pub enum Error {
 
     #[fail(display = "Another thread panicked while holding a lock")]
     PoisonError,
+
+    #[fail(display = "Trie element missing from store: {}", _0)]
+    MissingTrie(Blake2bHash),
 }
 
 impl From<bytesrepr::Error> for Error {
@@ -19,6 +25,12 @@ impl From<bytesrepr::Error> for Error {
     }
 }
 
+impl From<MissingTrie> for Error {
+    fn from(MissingTrie(hash): MissingTrie) -> Self {
+        Error::MissingTrie(hash)
+    }
+}
+
 impl<T> From<sync::PoisonError<T>> for Error {
     fn from(_error: sync::PoisonError<T>) -> Self {
         Error::PoisonError
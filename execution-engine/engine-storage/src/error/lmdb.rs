@@ -5,8 +5,9 @@ use lmdb as lmdb_external;
 use wasmi;
 
 use contract_ffi::bytesrepr;
+use engine_shared::newtypes::Blake2bHash;
 
-use super::in_memory;
+use super::{in_memory, MissingTrie};
 
 #[derive(Debug, Clone, Fail, PartialEq, Eq)]
 pub enum Error {
@@ -18,6 +19,9 @@ pub enum Error {
 
     #[fail(display = "Another thread panicked while holding a lock")]
     PoisonError,
+
+    #[fail(display = "Trie element missing from store: {}", _0)]
+    MissingTrie(Blake2bHash),
 }
 
 impl wasmi::HostError for Error {}
@@ -34,6 +38,12 @@ impl From<bytesrepr::Error> for Error {
     }
 }
 
+impl From<MissingTrie> for Error {
+    fn from(MissingTrie(hash): MissingTrie) -> Self {
+        Error::MissingTrie(hash)
+    }
+}
+
 impl<T> From<sync::PoisonError<T>> for Error {
     fn from(_error: sync::PoisonError<T>) -> Self {
         Error::PoisonError
@@ -45,6 +55,7 @@ impl From<in_memory::Error> for Error {
         match error {
             in_memory::Error::BytesRepr(error) => Error::BytesRepr(error),
             in_memory::Error::PoisonError => Error::PoisonError,
+            in_memory::Error::MissingTrie(hash) => Error::MissingTrie(hash),
         }
     }
 }
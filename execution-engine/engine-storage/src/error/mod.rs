@@ -1,4 +1,10 @@
 pub mod in_memory;
 pub mod lmdb;
 
+use engine_shared::newtypes::Blake2bHash;
+
 pub use self::lmdb::Error;
+
+/// A trie element which is referenced by another one was not found in the store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingTrie(pub Blake2bHash);
@@ -2,24 +2,29 @@ use std::collections::HashMap;
 use std::ops::Deref;
 use std::sync::Arc;
 
-use contract_ffi::key::Key;
+use contract_ffi::key::{Key, KeyTag};
 use contract_ffi::value::{ProtocolVersion, Value};
 use engine_shared::newtypes::{Blake2bHash, CorrelationId};
 use engine_shared::transform::Transform;
 
 use crate::error::{self, in_memory};
 use crate::global_state::StateReader;
-use crate::global_state::{commit, CommitResult, StateProvider};
+use crate::global_state::{
+    commit, diff, prune, CommitResult, DiffResult, PrefixIterator, StateProvider,
+};
 use crate::protocol_data::ProtocolData;
 use crate::protocol_data_store::in_memory::InMemoryProtocolDataStore;
 use crate::store::Store;
 use crate::transaction_source::in_memory::{InMemoryEnvironment, InMemoryReadTransaction};
 use crate::transaction_source::{Transaction, TransactionSource};
+use crate::trie::merkle_proof::{TrieMerkleAbsenceProof, TrieMerkleProof};
 use crate::trie::operations::create_hashed_empty_trie;
 use crate::trie::Trie;
 use crate::trie_store::in_memory::InMemoryTrieStore;
 use crate::trie_store::operations;
-use crate::trie_store::operations::{read, ReadResult, WriteResult};
+use crate::trie_store::operations::{
+    iter_prefix, prove, read, read_with_proof, ProofResult, PruneResult, ReadResult, WriteResult,
+};
 
 pub struct InMemoryGlobalState {
     pub environment: Arc<InMemoryEnvironment>,
@@ -105,6 +110,37 @@ impl InMemoryGlobalState {
     }
 }
 
+impl InMemoryGlobalStateView {
+    /// Returns an iterator over the key-value pairs in this state whose serialized keys start
+    /// with `prefix`, in ascending order of their serialized keys.
+    pub fn iter_prefix(
+        &self,
+        prefix: &[u8],
+    ) -> Result<PrefixIterator<Key, Value, InMemoryReadTransaction, InMemoryTrieStore>, error::Error>
+    {
+        let txn = self.environment.create_read_txn()?;
+        match iter_prefix::<Key, Value, _, _, error::Error>(
+            txn,
+            self.store.deref(),
+            &self.root_hash,
+            prefix,
+        )? {
+            Some(iterator) => Ok(iterator),
+            None => panic!("InMemoryGlobalState has invalid root"),
+        }
+    }
+
+    /// Returns an iterator over the key-value pairs in this state whose keys are of the given
+    /// variant.
+    pub fn iter_tag(
+        &self,
+        tag: KeyTag,
+    ) -> Result<PrefixIterator<Key, Value, InMemoryReadTransaction, InMemoryTrieStore>, error::Error>
+    {
+        self.iter_prefix(&[tag as u8])
+    }
+}
+
 impl StateReader<Key, Value> for InMemoryGlobalStateView {
     type Error = error::Error;
 
@@ -124,6 +160,48 @@ impl StateReader<Key, Value> for InMemoryGlobalStateView {
         txn.commit()?;
         Ok(ret)
     }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, Value>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_proof::<Key, Value, _, _, Self::Error>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ReadResult::Found(proof) => Some(proof),
+            ReadResult::NotFound => None,
+            ReadResult::RootNotFound => panic!("InMemoryGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn read_absence_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleAbsenceProof<Key, Value>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match prove::<Key, Value, _, _, Self::Error>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ProofResult::Present(_) => None,
+            ProofResult::Absent(proof) => Some(proof),
+            ProofResult::RootNotFound => panic!("InMemoryGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
 }
 
 impl StateProvider for InMemoryGlobalState {
@@ -183,10 +261,40 @@ impl StateProvider for InMemoryGlobalState {
     fn empty_root(&self) -> Blake2bHash {
         self.empty_root_hash
     }
+
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        old_root: Blake2bHash,
+        new_root: Blake2bHash,
+    ) -> Result<DiffResult<Key, Value>, Self::Error> {
+        diff::<InMemoryEnvironment, InMemoryTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            old_root,
+            new_root,
+        )
+    }
+
+    fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        roots_to_keep: &[Blake2bHash],
+    ) -> Result<PruneResult, Self::Error> {
+        prune::<InMemoryEnvironment, InMemoryTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            self.empty_root_hash,
+            roots_to_keep,
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use contract_ffi::bytesrepr::ToBytes;
     use engine_shared::test_utils;
 
     use super::*;
@@ -338,4 +446,51 @@ mod tests {
         let (_, root_hash) = InMemoryGlobalState::from_pairs(correlation_id, &init_state).unwrap();
         assert_eq!(expected_bytes, root_hash.to_vec())
     }
+
+    #[test]
+    fn iter_prefix_returns_matching_pairs_in_key_order() {
+        let pairs: Vec<(Key, Value)> = vec![
+            (Key::Hash([3u8; 32]), Value::Int32(3)),
+            (Key::Account([2u8; 32]), Value::Int32(2)),
+            (Key::Local([4u8; 32]), Value::Int32(4)),
+            (Key::Account([1u8; 32]), Value::Int32(1)),
+            (Key::Hash([5u8; 32]), Value::Int32(5)),
+        ];
+        let (state, root_hash) =
+            InMemoryGlobalState::from_pairs(CorrelationId::new(), &pairs).unwrap();
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+
+        let all: Vec<(Key, Value)> = checkout
+            .iter_prefix(&[])
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let mut expected = pairs.clone();
+        expected.sort_by_key(|(key, _)| key.to_bytes().unwrap());
+        assert_eq!(all, expected);
+
+        let accounts: Vec<(Key, Value)> = checkout
+            .iter_tag(KeyTag::Account)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            accounts,
+            vec![
+                (Key::Account([1u8; 32]), Value::Int32(1)),
+                (Key::Account([2u8; 32]), Value::Int32(2)),
+            ]
+        );
+
+        assert_eq!(checkout.iter_tag(KeyTag::URef).unwrap().count(), 0);
+
+        let mut hash_5_prefix = Key::Hash([5u8; 32]).to_bytes().unwrap();
+        hash_5_prefix.truncate(10);
+        let hashes: Vec<Key> = checkout
+            .iter_prefix(&hash_5_prefix)
+            .unwrap()
+            .map(|result| result.unwrap().0)
+            .collect();
+        assert_eq!(hashes, vec![Key::Hash([5u8; 32])]);
+    }
 }
@@ -4,23 +4,28 @@ use std::sync::Arc;
 
 use lmdb;
 
-use contract_ffi::key::Key;
+use contract_ffi::key::{Key, KeyTag};
 use contract_ffi::value::{ProtocolVersion, Value};
 use engine_shared::newtypes::{Blake2bHash, CorrelationId};
 use engine_shared::transform::Transform;
 
 use crate::error;
 use crate::global_state::StateReader;
-use crate::global_state::{commit, CommitResult, StateProvider};
+use crate::global_state::{
+    commit, diff, prune, CommitResult, DiffResult, PrefixIterator, StateProvider,
+};
 use crate::protocol_data::ProtocolData;
 use crate::protocol_data_store::lmdb::LmdbProtocolDataStore;
 use crate::store::Store;
 use crate::transaction_source::lmdb::LmdbEnvironment;
 use crate::transaction_source::{Transaction, TransactionSource};
+use crate::trie::merkle_proof::{TrieMerkleAbsenceProof, TrieMerkleProof};
 use crate::trie::operations::create_hashed_empty_trie;
 use crate::trie::Trie;
 use crate::trie_store::lmdb::LmdbTrieStore;
-use crate::trie_store::operations::{read, ReadResult};
+use crate::trie_store::operations::{
+    iter_prefix, prove, read, read_with_proof, ProofResult, PruneResult, ReadResult,
+};
 
 pub struct LmdbGlobalState {
     pub environment: Arc<LmdbEnvironment>,
@@ -75,6 +80,35 @@ impl LmdbGlobalState {
     }
 }
 
+impl LmdbGlobalStateView {
+    /// Returns an iterator over the key-value pairs in this state whose serialized keys start
+    /// with `prefix`, in ascending order of their serialized keys.
+    pub fn iter_prefix(
+        &self,
+        prefix: &[u8],
+    ) -> Result<PrefixIterator<Key, Value, lmdb::RoTransaction, LmdbTrieStore>, error::Error> {
+        let txn = self.environment.create_read_txn()?;
+        match iter_prefix::<Key, Value, _, _, error::Error>(
+            txn,
+            self.store.deref(),
+            &self.root_hash,
+            prefix,
+        )? {
+            Some(iterator) => Ok(iterator),
+            None => panic!("LmdbGlobalState has invalid root"),
+        }
+    }
+
+    /// Returns an iterator over the key-value pairs in this state whose keys are of the given
+    /// variant.
+    pub fn iter_tag(
+        &self,
+        tag: KeyTag,
+    ) -> Result<PrefixIterator<Key, Value, lmdb::RoTransaction, LmdbTrieStore>, error::Error> {
+        self.iter_prefix(&[tag as u8])
+    }
+}
+
 impl StateReader<Key, Value> for LmdbGlobalStateView {
     type Error = error::Error;
 
@@ -94,6 +128,48 @@ impl StateReader<Key, Value> for LmdbGlobalStateView {
         txn.commit()?;
         Ok(ret)
     }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, Value>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_proof::<Key, Value, _, _, Self::Error>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ReadResult::Found(proof) => Some(proof),
+            ReadResult::NotFound => None,
+            ReadResult::RootNotFound => panic!("LmdbGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn read_absence_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleAbsenceProof<Key, Value>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match prove::<Key, Value, _, _, Self::Error>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ProofResult::Present(_) => None,
+            ProofResult::Absent(proof) => Some(proof),
+            ProofResult::RootNotFound => panic!("LmdbGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
 }
 
 impl StateProvider for LmdbGlobalState {
@@ -153,6 +229,35 @@ impl StateProvider for LmdbGlobalState {
     fn empty_root(&self) -> Blake2bHash {
         self.empty_root_hash
     }
+
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        old_root: Blake2bHash,
+        new_root: Blake2bHash,
+    ) -> Result<DiffResult<Key, Value>, Self::Error> {
+        diff::<LmdbEnvironment, LmdbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            old_root,
+            new_root,
+        )
+    }
+
+    fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        roots_to_keep: &[Blake2bHash],
+    ) -> Result<PruneResult, Self::Error> {
+        prune::<LmdbEnvironment, LmdbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            self.empty_root_hash,
+            roots_to_keep,
+        )
+    }
 }
 
 #[cfg(test)]
@@ -160,6 +265,7 @@ mod tests {
     use lmdb::DatabaseFlags;
     use tempfile::tempdir;
 
+    use crate::trie;
     use crate::trie_store::operations::{write, WriteResult};
     use crate::TEST_MAP_SIZE;
 
@@ -249,6 +355,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn iter_prefix_returns_matching_pairs_in_key_order() {
+        let (state, root_hash) = create_test_state();
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+
+        let all: Vec<(Key, Value)> = checkout
+            .iter_prefix(&[])
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let expected: Vec<(Key, Value)> = TEST_PAIRS
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, value))
+            .collect();
+        assert_eq!(all, expected);
+
+        assert_eq!(checkout.iter_tag(KeyTag::Hash).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn iter_prefix_fails_on_missing_trie() {
+        let (state, root_hash) = create_test_state();
+
+        // Delete the first child of the root, which the iterator visits first
+        let missing_hash = {
+            let mut txn = state.environment.create_read_write_txn().unwrap();
+            let root: Trie<Key, Value> = state.trie_store.get(&txn, &root_hash).unwrap().unwrap();
+            let pointer = match root {
+                Trie::Node { pointer_block } => (0..trie::RADIX)
+                    .filter_map(|index| pointer_block[index])
+                    .next()
+                    .unwrap(),
+                Trie::Extension { pointer, .. } => pointer,
+                Trie::Leaf { .. } => panic!("root should not be a leaf"),
+            };
+            Store::<Blake2bHash, Trie<Key, Value>>::delete(
+                state.trie_store.as_ref(),
+                &mut txn,
+                pointer.hash(),
+            )
+            .unwrap();
+            txn.commit().unwrap();
+            *pointer.hash()
+        };
+
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        let mut iterator = checkout.iter_prefix(&[]).unwrap();
+        assert_eq!(
+            iterator.next(),
+            Some(Err(error::Error::MissingTrie(missing_hash)))
+        );
+        assert!(iterator.next().is_none());
+    }
+
     #[test]
     fn checkout_fails_if_unknown_hash_is_given() {
         let (state, _) = create_test_state();
@@ -329,4 +490,41 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[test]
+    fn prune_removes_unretained_states() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (state, root_hash) = create_test_state();
+
+        let effects: HashMap<Key, Transform> = test_pairs_updated
+            .iter()
+            .map(|TestPair { key, value }| (*key, Transform::Write(value.to_owned())))
+            .collect();
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        match state.prune(correlation_id, &[updated_hash]).unwrap() {
+            PruneResult::Pruned { deleted, .. } => assert!(deleted > 0),
+            other => panic!("prune was given a valid root: {:?}", other),
+        }
+        assert_eq!(
+            state.prune(correlation_id, &[]).unwrap(),
+            PruneResult::NoRootsToKeep
+        );
+
+        assert!(state.checkout(root_hash).unwrap().is_none());
+        assert!(state.checkout(state.empty_root()).unwrap().is_some());
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+    }
 }
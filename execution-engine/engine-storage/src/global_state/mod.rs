@@ -13,12 +13,16 @@ use engine_shared::logging::{log_duration, log_metric, GAUGE};
 use engine_shared::newtypes::{Blake2bHash, CorrelationId};
 use engine_shared::transform::{self, Transform, TypeMismatch};
 
+use crate::error::MissingTrie;
 use crate::protocol_data::ProtocolData;
 use crate::transaction_source::{Transaction, TransactionSource};
+use crate::trie::merkle_proof::{TrieMerkleAbsenceProof, TrieMerkleProof};
 use crate::trie::Trie;
-use crate::trie_store::operations::{read, write, ReadResult, WriteResult};
+use crate::trie_store::operations::{self, read, write, ReadResult, WriteResult};
 use crate::trie_store::TrieStore;
 
+pub use crate::trie_store::operations::{DiffEntry, DiffResult, PrefixIterator, PruneResult};
+
 const GLOBAL_STATE_COMMIT_READS: &str = "global_state_commit_reads";
 const GLOBAL_STATE_COMMIT_WRITES: &str = "global_state_commit_writes";
 const GLOBAL_STATE_COMMIT_DURATION: &str = "global_state_commit_duration";
@@ -33,6 +37,22 @@ pub trait StateReader<K, V> {
 
     /// Returns the state value from the corresponding key
     fn read(&self, correlation_id: CorrelationId, key: &K) -> Result<Option<V>, Self::Error>;
+
+    /// Returns the state value from the corresponding key, along with a Merkle proof of its
+    /// inclusion under the root of this state
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &K,
+    ) -> Result<Option<TrieMerkleProof<K, V>>, Self::Error>;
+
+    /// Returns a Merkle proof that the corresponding key is absent under the root of this state,
+    /// or `None` if the key is present
+    fn read_absence_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &K,
+    ) -> Result<Option<TrieMerkleAbsenceProof<K, V>>, Self::Error>;
 }
 
 #[derive(Debug)]
@@ -104,6 +124,22 @@ pub trait StateProvider {
     ) -> Result<Option<ProtocolData>, Self::Error>;
 
     fn empty_root(&self) -> Blake2bHash;
+
+    /// Returns the differences between the state at `old_root` and the state at `new_root`.
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        old_root: Blake2bHash,
+        new_root: Blake2bHash,
+    ) -> Result<DiffResult<Key, Value>, Self::Error>;
+
+    /// Deletes all trie elements which are not reachable from one of the given roots or from the
+    /// empty root.
+    fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        roots_to_keep: &[Blake2bHash],
+    ) -> Result<PruneResult, Self::Error>;
 }
 
 pub fn commit<'a, R, S, H, E>(
@@ -209,3 +245,58 @@ where
         bonded_validators,
     })
 }
+
+pub fn prune<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    correlation_id: CorrelationId,
+    empty_root_hash: Blake2bHash,
+    roots_to_keep: &[Blake2bHash],
+) -> Result<PruneResult, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, Value>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    // Keeping only the empty root would wipe the whole store, which is never what the caller means
+    if roots_to_keep.is_empty() {
+        return Ok(PruneResult::NoRootsToKeep);
+    }
+
+    let mut roots: Vec<Blake2bHash> = roots_to_keep.to_vec();
+    if !roots.contains(&empty_root_hash) {
+        roots.push(empty_root_hash);
+    }
+
+    // All reads and deletes happen in a single read-write transaction, so the prune either
+    // completes or leaves the store untouched. That transaction holds the store's write lock for
+    // the whole run, blocking any commits until it finishes.
+    let mut txn = environment.create_read_write_txn()?;
+    let prune_result =
+        operations::prune::<Key, Value, _, _, E>(correlation_id, &mut txn, store, &roots)?;
+    if let PruneResult::Pruned { .. } = prune_result {
+        txn.commit()?;
+    }
+    Ok(prune_result)
+}
+
+pub fn diff<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    correlation_id: CorrelationId,
+    old_root: Blake2bHash,
+    new_root: Blake2bHash,
+) -> Result<DiffResult<Key, Value>, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, Value>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error> + From<MissingTrie>,
+{
+    let txn = environment.create_read_txn()?;
+    let diff_result =
+        operations::diff::<Key, Value, _, _, E>(correlation_id, &txn, store, &old_root, &new_root)?;
+    txn.commit()?;
+    Ok(diff_result)
+}
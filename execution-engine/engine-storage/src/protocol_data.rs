@@ -1,10 +1,13 @@
 use contract_ffi::bytesrepr;
 use contract_ffi::bytesrepr::{FromBytes, ToBytes};
+use contract_ffi::system_contracts::pos::{RewardsConfig, REWARDS_CONFIG_SIZE_SERIALIZED};
 use contract_ffi::uref::{AccessRights, URef, UREF_SIZE_SERIALIZED};
 use engine_wasm_prep::wasm_costs::{WasmCosts, WASM_COSTS_SIZE_SERIALIZED};
 
-const PROTOCOL_DATA_SIZE_SERIALIZED: usize =
-    WASM_COSTS_SIZE_SERIALIZED + UREF_SIZE_SERIALIZED + UREF_SIZE_SERIALIZED;
+const PROTOCOL_DATA_SIZE_SERIALIZED: usize = WASM_COSTS_SIZE_SERIALIZED
+    + UREF_SIZE_SERIALIZED
+    + UREF_SIZE_SERIALIZED
+    + REWARDS_CONFIG_SIZE_SERIALIZED;
 
 /// Represents a protocol's data. Intended to be associated with a given protocol version.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -12,6 +15,7 @@ pub struct ProtocolData {
     wasm_costs: WasmCosts,
     mint: URef,
     proof_of_stake: URef,
+    rewards_config: RewardsConfig,
 }
 
 /// Provides a default instance with non existing urefs and empty costs table.
@@ -24,17 +28,24 @@ impl Default for ProtocolData {
             wasm_costs: WasmCosts::default(),
             mint: URef::new([0; 32], AccessRights::READ),
             proof_of_stake: URef::new([0; 32], AccessRights::READ),
+            rewards_config: RewardsConfig::default(),
         }
     }
 }
 
 impl ProtocolData {
     /// Creates a new [`ProtocolData`] value from a given [`WasmCosts`] value.
-    pub fn new(wasm_costs: WasmCosts, mint: URef, proof_of_stake: URef) -> Self {
+    pub fn new(
+        wasm_costs: WasmCosts,
+        mint: URef,
+        proof_of_stake: URef,
+        rewards_config: RewardsConfig,
+    ) -> Self {
         ProtocolData {
             wasm_costs,
             mint,
             proof_of_stake,
+            rewards_config,
         }
     }
 
@@ -51,6 +62,11 @@ impl ProtocolData {
         self.proof_of_stake
     }
 
+    /// Gets the [`RewardsConfig`] the PoS contract uses to split and distribute payment.
+    pub fn rewards_config(&self) -> RewardsConfig {
+        self.rewards_config
+    }
+
     /// Retrieves all valid system contracts stored in protocol version
     pub fn system_contracts(&self) -> Vec<URef> {
         let mut vec = Vec::with_capacity(2);
@@ -70,8 +86,16 @@ impl ToBytes for ProtocolData {
         ret.append(&mut self.wasm_costs.to_bytes()?);
         ret.append(&mut self.mint.to_bytes()?);
         ret.append(&mut self.proof_of_stake.to_bytes()?);
+        ret.append(&mut self.rewards_config.to_bytes()?);
         Ok(ret)
     }
+
+    fn serialized_length(&self) -> usize {
+        self.wasm_costs.serialized_length()
+            + self.mint.serialized_length()
+            + self.proof_of_stake.serialized_length()
+            + self.rewards_config.serialized_length()
+    }
 }
 
 impl FromBytes for ProtocolData {
@@ -79,11 +103,18 @@ impl FromBytes for ProtocolData {
         let (wasm_costs, rem): (WasmCosts, &[u8]) = FromBytes::from_bytes(bytes)?;
         let (mint_reference, rem): (URef, &[u8]) = FromBytes::from_bytes(rem)?;
         let (proof_of_stake_reference, rem): (URef, &[u8]) = FromBytes::from_bytes(rem)?;
+        // Protocol data stored before the rewards config was added ends after the PoS reference.
+        let (rewards_config, rem): (RewardsConfig, &[u8]) = if rem.is_empty() {
+            (RewardsConfig::default(), rem)
+        } else {
+            FromBytes::from_bytes(rem)?
+        };
         Ok((
             ProtocolData {
                 wasm_costs,
                 mint: mint_reference,
                 proof_of_stake: proof_of_stake_reference,
+                rewards_config,
             },
             rem,
         ))
@@ -92,9 +123,12 @@ impl FromBytes for ProtocolData {
 
 #[cfg(test)]
 pub(crate) mod gens {
+    use proptest::prelude::any;
     use proptest::prop_compose;
 
     use contract_ffi::gens;
+    use contract_ffi::system_contracts::pos::{RewardsConfig, SHARE_DENOMINATOR};
+    use contract_ffi::value::account::BlockTime;
     use engine_wasm_prep::wasm_costs::gens as wasm_costs_gens;
 
     use super::ProtocolData;
@@ -104,11 +138,24 @@ pub(crate) mod gens {
             wasm_costs in wasm_costs_gens::wasm_costs_arb(),
             mint in gens::uref_arb(),
             proof_of_stake in gens::uref_arb(),
+            distribution_interval in any::<u64>(),
+            burn in 0..=SHARE_DENOMINATOR,
+            treasury in 0..=SHARE_DENOMINATOR,
         ) -> ProtocolData {
+            let treasury = treasury % (SHARE_DENOMINATOR - burn + 1);
+            let validator_commission = SHARE_DENOMINATOR - burn - treasury;
+            let rewards_config = RewardsConfig::new(
+                BlockTime(distribution_interval),
+                validator_commission,
+                burn,
+                treasury,
+            )
+            .expect("shares should add up");
             ProtocolData {
                 wasm_costs,
                 mint,
                 proof_of_stake,
+                rewards_config,
             }
         }
     }
@@ -118,7 +165,8 @@ pub(crate) mod gens {
 mod tests {
     use proptest::proptest;
 
-    use contract_ffi::bytesrepr;
+    use contract_ffi::bytesrepr::{self, ToBytes};
+    use contract_ffi::system_contracts::pos::RewardsConfig;
     use contract_ffi::uref::{AccessRights, URef};
     use engine_shared::test_utils;
 
@@ -130,25 +178,67 @@ mod tests {
             let costs = test_utils::wasm_costs_mock();
             let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
             let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
-            ProtocolData::new(costs, mint_reference, proof_of_stake_reference)
+            ProtocolData::new(
+                costs,
+                mint_reference,
+                proof_of_stake_reference,
+                RewardsConfig::default(),
+            )
         };
         let free = {
             let costs = test_utils::wasm_costs_free();
             let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
             let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
-            ProtocolData::new(costs, mint_reference, proof_of_stake_reference)
+            ProtocolData::new(
+                costs,
+                mint_reference,
+                proof_of_stake_reference,
+                RewardsConfig::default(),
+            )
         };
         bytesrepr::test_serialization_roundtrip(&mock);
         bytesrepr::test_serialization_roundtrip(&free);
     }
 
+    #[test]
+    fn should_read_protocol_data_stored_without_rewards_config() {
+        let costs = test_utils::wasm_costs_mock();
+        let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
+        let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
+
+        let mut legacy_bytes = costs.to_bytes().expect("should serialize");
+        legacy_bytes.append(&mut mint_reference.to_bytes().expect("should serialize"));
+        legacy_bytes.append(
+            &mut proof_of_stake_reference
+                .to_bytes()
+                .expect("should serialize"),
+        );
+
+        let protocol_data: ProtocolData =
+            bytesrepr::deserialize(&legacy_bytes).expect("should deserialize");
+        assert_eq!(
+            protocol_data,
+            ProtocolData::new(
+                costs,
+                mint_reference,
+                proof_of_stake_reference,
+                RewardsConfig::default(),
+            )
+        );
+    }
+
     #[test]
     fn should_return_all_system_contracts() {
         let mint_reference = URef::new([197u8; 32], AccessRights::READ_ADD_WRITE);
         let proof_of_stake_reference = URef::new([198u8; 32], AccessRights::READ_ADD_WRITE);
         let protocol_data = {
             let costs = test_utils::wasm_costs_mock();
-            ProtocolData::new(costs, mint_reference, proof_of_stake_reference)
+            ProtocolData::new(
+                costs,
+                mint_reference,
+                proof_of_stake_reference,
+                RewardsConfig::default(),
+            )
         };
 
         let actual = {
@@ -170,7 +260,12 @@ mod tests {
         let proof_of_stake_reference = URef::new([0u8; 32], AccessRights::READ);
         let protocol_data = {
             let costs = test_utils::wasm_costs_mock();
-            ProtocolData::new(costs, mint_reference, proof_of_stake_reference)
+            ProtocolData::new(
+                costs,
+                mint_reference,
+                proof_of_stake_reference,
+                RewardsConfig::default(),
+            )
         };
 
         let actual = {
@@ -0,0 +1,607 @@
+//! Export and import of global state snapshots.
+//!
+//! A snapshot contains every trie element reachable from a given root, so that a node can
+//! obtain the global state at that root without replaying the blocks which produced it.
+//!
+//! # Format
+//!
+//! A snapshot is a header followed by any number of chunks:
+//!
+//! * header: the 8-byte [`MAGIC`], a little-endian `u32` [`VERSION`] and the 32-byte root hash
+//! * chunk: a little-endian `u32` payload length (at most [`MAX_CHUNK_SIZE`]), the payload, and the
+//!   32-byte BLAKE2b hash of the payload
+//! * payload: a sequence of serialized trie elements, each prefixed by its length as a
+//!   little-endian `u32`
+//!
+//! Trie elements are stored without their hashes, since these are recomputed on import.
+
+use std::collections::HashSet;
+use std::io::{self, Read, Write};
+
+use failure::Fail;
+use lmdb;
+
+use contract_ffi::bytesrepr::{self, ToBytes};
+use contract_ffi::key::Key;
+use contract_ffi::value::Value;
+use engine_shared::newtypes::Blake2bHash;
+
+use crate::error::{self, in_memory};
+use crate::transaction_source::{Readable, Transaction, TransactionSource, Writable};
+use crate::trie::{self, Pointer, Trie};
+use crate::trie_store::TrieStore;
+
+/// Identifies a snapshot file.
+pub const MAGIC: [u8; 8] = *b"CLSNAPSH";
+
+/// The version of the snapshot format.
+pub const VERSION: u32 = 1;
+
+/// The default upper bound on the size of a chunk's payload.
+pub const DEFAULT_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// The largest chunk payload which will be exported or imported. Chunk lengths are read from
+/// untrusted input, so this bounds the memory an import allocates up front.
+pub const MAX_CHUNK_SIZE: usize = 64 * 1024 * 1024;
+
+const HASH_LENGTH: usize = 32;
+const LENGTH_PREFIX_LENGTH: usize = 4;
+
+#[derive(Debug, Fail)]
+pub enum Error {
+    #[fail(display = "{}", _0)]
+    Io(#[fail(cause)] io::Error),
+
+    #[fail(display = "{}", _0)]
+    Storage(#[fail(cause)] error::Error),
+
+    #[fail(display = "Not a snapshot")]
+    InvalidMagic,
+
+    #[fail(display = "Unsupported snapshot version: {}", _0)]
+    UnsupportedVersion(u32),
+
+    #[fail(display = "Snapshot is of {}, expected {}", actual, expected)]
+    RootMismatch {
+        expected: Blake2bHash,
+        actual: Blake2bHash,
+    },
+
+    #[fail(display = "Hash of chunk {} does not match its contents", _0)]
+    ChunkHashMismatch(usize),
+
+    #[fail(display = "Malformed chunk {}", _0)]
+    MalformedChunk(usize),
+
+    #[fail(display = "Chunk {} is too large: {} bytes", chunk, length)]
+    ChunkTooLarge { chunk: usize, length: usize },
+
+    #[fail(display = "Root not found: {}", _0)]
+    RootNotFound(Blake2bHash),
+
+    #[fail(display = "Trie element missing from snapshot: {}", _0)]
+    MissingTrie(Blake2bHash),
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl From<error::Error> for Error {
+    fn from(error: error::Error) -> Self {
+        Error::Storage(error)
+    }
+}
+
+impl From<in_memory::Error> for Error {
+    fn from(error: in_memory::Error) -> Self {
+        Error::Storage(error.into())
+    }
+}
+
+impl From<lmdb::Error> for Error {
+    fn from(error: lmdb::Error) -> Self {
+        Error::Storage(error.into())
+    }
+}
+
+impl From<bytesrepr::Error> for Error {
+    fn from(error: bytesrepr::Error) -> Self {
+        Error::Storage(error.into())
+    }
+}
+
+/// Describes an exported or imported snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotSummary {
+    pub root: Blake2bHash,
+    pub chunks: usize,
+    pub tries: usize,
+}
+
+/// Accumulates serialized trie elements into chunks and writes them out.
+struct ChunkWriter<'w, W> {
+    writer: &'w mut W,
+    chunk_size: usize,
+    payload: Vec<u8>,
+    chunks: usize,
+}
+
+impl<'w, W: Write> ChunkWriter<'w, W> {
+    fn new(writer: &'w mut W, chunk_size: usize) -> Self {
+        ChunkWriter {
+            writer,
+            chunk_size,
+            payload: Vec::new(),
+            chunks: 0,
+        }
+    }
+
+    fn push(&mut self, trie_bytes: &[u8]) -> Result<(), Error> {
+        if !self.payload.is_empty()
+            && self.payload.len() + LENGTH_PREFIX_LENGTH + trie_bytes.len() > self.chunk_size
+        {
+            self.flush()?;
+        }
+        self.payload
+            .extend_from_slice(&(trie_bytes.len() as u32).to_le_bytes());
+        self.payload.extend_from_slice(trie_bytes);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        if self.payload.is_empty() {
+            return Ok(());
+        }
+        if self.payload.len() > MAX_CHUNK_SIZE {
+            return Err(Error::ChunkTooLarge {
+                chunk: self.chunks,
+                length: self.payload.len(),
+            });
+        }
+        let payload_hash = Blake2bHash::new(&self.payload);
+        self.writer
+            .write_all(&(self.payload.len() as u32).to_le_bytes())?;
+        self.writer.write_all(&self.payload)?;
+        self.writer.write_all(&payload_hash.to_vec())?;
+        self.payload.clear();
+        self.chunks += 1;
+        Ok(())
+    }
+}
+
+/// Writes a snapshot of all trie elements reachable from `root` to `writer`, in chunks whose
+/// payloads are no larger than `chunk_size` bytes (unless a single trie element is larger).
+///
+/// Fails if a chunk would exceed [`MAX_CHUNK_SIZE`], since such a snapshot could not be imported.
+pub fn export<'a, R, S, W>(
+    environment: &'a R,
+    store: &S,
+    root: &Blake2bHash,
+    chunk_size: usize,
+    writer: &mut W,
+) -> Result<SnapshotSummary, Error>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, Value>,
+    S::Error: From<R::Error>,
+    Error: From<R::Error> + From<S::Error>,
+    W: Write,
+{
+    let txn = environment.create_read_txn()?;
+
+    writer.write_all(&MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&root.to_vec())?;
+
+    let mut chunk_writer = ChunkWriter::new(writer, chunk_size);
+    let mut visited: HashSet<Blake2bHash> = HashSet::new();
+    let mut to_visit: Vec<Pointer> = vec![Pointer::NodePointer(*root)];
+
+    while let Some(pointer) = to_visit.pop() {
+        let hash = *pointer.hash();
+        if !visited.insert(hash) {
+            continue;
+        }
+        let trie_bytes = match txn.read(store.handle(), &hash.to_bytes()?)? {
+            Some(trie_bytes) => trie_bytes,
+            None if hash == *root => return Err(Error::RootNotFound(hash)),
+            None => return Err(Error::MissingTrie(hash)),
+        };
+        if let Pointer::NodePointer(_) = pointer {
+            to_visit.extend(children(&bytesrepr::deserialize(&trie_bytes)?));
+        }
+        chunk_writer.push(&trie_bytes)?;
+    }
+    chunk_writer.flush()?;
+    let chunks = chunk_writer.chunks;
+
+    txn.commit()?;
+
+    Ok(SnapshotSummary {
+        root: *root,
+        chunks,
+        tries: visited.len(),
+    })
+}
+
+/// Reads a snapshot from `reader` into `store`, verifying that it is a snapshot of
+/// `expected_root` and that every trie element reachable from `expected_root` was either
+/// imported or already present in the store.
+///
+/// All trie elements are written in a single transaction, which is only committed if the
+/// snapshot is valid.
+pub fn import<'a, R, S, Rd>(
+    environment: &'a R,
+    store: &S,
+    expected_root: &Blake2bHash,
+    reader: &mut Rd,
+) -> Result<SnapshotSummary, Error>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, Value>,
+    S::Error: From<R::Error>,
+    Error: From<R::Error> + From<S::Error>,
+    Rd: Read,
+{
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic)?;
+    if magic != MAGIC {
+        return Err(Error::InvalidMagic);
+    }
+    let mut version = [0u8; 4];
+    reader.read_exact(&mut version)?;
+    let version = u32::from_le_bytes(version);
+    if version != VERSION {
+        return Err(Error::UnsupportedVersion(version));
+    }
+    let root = read_hash(reader)?;
+    if root != *expected_root {
+        return Err(Error::RootMismatch {
+            expected: *expected_root,
+            actual: root,
+        });
+    }
+
+    let mut txn = environment.create_read_write_txn()?;
+    let mut chunks: usize = 0;
+    let mut tries: usize = 0;
+
+    while let Some(payload_length) = read_chunk_length(reader)? {
+        let payload_length = payload_length as usize;
+        if payload_length > MAX_CHUNK_SIZE {
+            return Err(Error::ChunkTooLarge {
+                chunk: chunks,
+                length: payload_length,
+            });
+        }
+        let mut payload = vec![0u8; payload_length];
+        reader.read_exact(&mut payload)?;
+        let payload_hash = read_hash(reader)?;
+        if Blake2bHash::new(&payload) != payload_hash {
+            return Err(Error::ChunkHashMismatch(chunks));
+        }
+
+        let mut remainder: &[u8] = &payload;
+        while !remainder.is_empty() {
+            if remainder.len() < LENGTH_PREFIX_LENGTH {
+                return Err(Error::MalformedChunk(chunks));
+            }
+            let (length, rest) = remainder.split_at(LENGTH_PREFIX_LENGTH);
+            let length = u32::from_le_bytes([length[0], length[1], length[2], length[3]]);
+            if rest.len() < length as usize {
+                return Err(Error::MalformedChunk(chunks));
+            }
+            let (trie_bytes, rest) = rest.split_at(length as usize);
+            let trie_hash = Blake2bHash::new(trie_bytes);
+            txn.write(store.handle(), &trie_hash.to_bytes()?, trie_bytes)?;
+            tries += 1;
+            remainder = rest;
+        }
+        chunks += 1;
+    }
+
+    check_complete::<_, S>(&txn, store, expected_root)?;
+
+    txn.commit()?;
+
+    Ok(SnapshotSummary {
+        root,
+        chunks,
+        tries,
+    })
+}
+
+/// Checks that every trie element reachable from `root` is present in `store`.
+fn check_complete<T, S>(txn: &T, store: &S, root: &Blake2bHash) -> Result<(), Error>
+where
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<Key, Value>,
+    S::Error: From<T::Error>,
+    Error: From<T::Error> + From<S::Error>,
+{
+    let mut visited: HashSet<Blake2bHash> = HashSet::new();
+    let mut to_visit: Vec<Pointer> = vec![Pointer::NodePointer(*root)];
+
+    while let Some(pointer) = to_visit.pop() {
+        let hash = *pointer.hash();
+        if !visited.insert(hash) {
+            continue;
+        }
+        match pointer {
+            Pointer::LeafPointer(_) => {
+                if txn.read(store.handle(), &hash.to_bytes()?)?.is_none() {
+                    return Err(Error::MissingTrie(hash));
+                }
+            }
+            Pointer::NodePointer(_) => {
+                let trie: Trie<Key, Value> = match store.get(txn, &hash)? {
+                    Some(trie) => trie,
+                    None => return Err(Error::MissingTrie(hash)),
+                };
+                to_visit.extend(children(&trie));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn children(trie: &Trie<Key, Value>) -> Vec<Pointer> {
+    match trie {
+        Trie::Leaf { .. } => Vec::new(),
+        Trie::Node { pointer_block } => (0..trie::RADIX)
+            .filter_map(|index| pointer_block[index])
+            .collect(),
+        Trie::Extension { pointer, .. } => vec![*pointer],
+    }
+}
+
+fn read_hash<Rd: Read>(reader: &mut Rd) -> Result<Blake2bHash, Error> {
+    let mut hash = [0u8; HASH_LENGTH];
+    reader.read_exact(&mut hash)?;
+    Ok(hash.into())
+}
+
+/// Reads the length prefix of the next chunk, or returns `None` if the reader is exhausted.
+fn read_chunk_length<Rd: Read>(reader: &mut Rd) -> Result<Option<u32>, Error> {
+    let mut length = [0u8; LENGTH_PREFIX_LENGTH];
+    let mut read = 0;
+    while read < LENGTH_PREFIX_LENGTH {
+        match reader.read(&mut length[read..]) {
+            Ok(0) if read == 0 => return Ok(None),
+            Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+            Ok(n) => read += n,
+            Err(ref error) if error.kind() == io::ErrorKind::Interrupted => (),
+            Err(error) => return Err(error.into()),
+        }
+    }
+    Ok(Some(u32::from_le_bytes(length)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use lmdb::DatabaseFlags;
+    use tempfile::tempdir;
+
+    use contract_ffi::key::Key;
+    use contract_ffi::value::Value;
+    use engine_shared::newtypes::{Blake2bHash, CorrelationId};
+    use engine_shared::transform::Transform;
+
+    use super::{export, import, Error, HASH_LENGTH, MAGIC};
+    use crate::global_state::in_memory::InMemoryGlobalState;
+    use crate::global_state::lmdb::LmdbGlobalState;
+    use crate::global_state::{CommitResult, StateProvider, StateReader};
+    use crate::protocol_data_store::lmdb::LmdbProtocolDataStore;
+    use crate::transaction_source::lmdb::LmdbEnvironment;
+    use crate::trie_store::lmdb::LmdbTrieStore;
+    use crate::TEST_MAP_SIZE;
+
+    const SMALL_CHUNK_SIZE: usize = 256;
+    const TEST_PAIRS_COUNT: u8 = 32;
+
+    fn test_pairs() -> Vec<(Key, Value)> {
+        (0..TEST_PAIRS_COUNT)
+            .map(|i| (Key::Hash([i; 32]), Value::Int32(i32::from(i))))
+            .collect()
+    }
+
+    fn create_test_state() -> (InMemoryGlobalState, Blake2bHash) {
+        let correlation_id = CorrelationId::new();
+        let state = InMemoryGlobalState::empty().unwrap();
+        let effects: HashMap<Key, Transform> = test_pairs()
+            .into_iter()
+            .map(|(key, value)| (key, Transform::Write(value)))
+            .collect();
+        match state
+            .commit(correlation_id, state.empty_root_hash, effects)
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => (state, state_root),
+            _ => panic!("commit failed"),
+        }
+    }
+
+    fn export_test_state() -> (Vec<u8>, Blake2bHash) {
+        let (state, root) = create_test_state();
+        let mut snapshot = Vec::new();
+        let summary = export(
+            state.environment.as_ref(),
+            state.trie_store.as_ref(),
+            &root,
+            SMALL_CHUNK_SIZE,
+            &mut snapshot,
+        )
+        .unwrap();
+        assert_eq!(summary.root, root);
+        assert!(summary.chunks > 1);
+        (snapshot, root)
+    }
+
+    fn check_state<S: StateProvider>(state: &S, root: Blake2bHash)
+    where
+        S::Error: std::fmt::Debug,
+    {
+        let correlation_id = CorrelationId::new();
+        let checkout = state.checkout(root).unwrap().expect("should find root");
+        for (key, value) in test_pairs() {
+            assert_eq!(checkout.read(correlation_id, &key).unwrap(), Some(value));
+        }
+    }
+
+    #[test]
+    fn should_round_trip_snapshot_through_in_memory_store() {
+        let (snapshot, root) = export_test_state();
+
+        let state = InMemoryGlobalState::empty().unwrap();
+        let summary = import(
+            state.environment.as_ref(),
+            state.trie_store.as_ref(),
+            &root,
+            &mut snapshot.as_slice(),
+        )
+        .unwrap();
+        assert_eq!(summary.root, root);
+
+        check_state(&state, root);
+    }
+
+    #[test]
+    fn should_round_trip_snapshot_through_lmdb_store() {
+        let (snapshot, root) = export_test_state();
+
+        let temp_dir = tempdir().unwrap();
+        let environment =
+            Arc::new(LmdbEnvironment::new(&temp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap());
+        let trie_store =
+            Arc::new(LmdbTrieStore::new(&environment, None, DatabaseFlags::empty()).unwrap());
+        let protocol_data_store = Arc::new(
+            LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
+        );
+        let state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store).unwrap();
+
+        import(
+            state.environment.as_ref(),
+            state.trie_store.as_ref(),
+            &root,
+            &mut snapshot.as_slice(),
+        )
+        .unwrap();
+
+        check_state(&state, root);
+    }
+
+    #[test]
+    fn should_fail_to_export_unknown_root() {
+        let (state, _) = create_test_state();
+        let unknown_root: Blake2bHash = [1u8; 32].into();
+        let result = export(
+            state.environment.as_ref(),
+            state.trie_store.as_ref(),
+            &unknown_root,
+            SMALL_CHUNK_SIZE,
+            &mut Vec::new(),
+        );
+        match result {
+            Err(Error::RootNotFound(root)) => assert_eq!(root, unknown_root),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_reject_snapshot_of_other_root() {
+        let (snapshot, root) = export_test_state();
+        let other_root: Blake2bHash = [1u8; 32].into();
+
+        let state = InMemoryGlobalState::empty().unwrap();
+        let result = import(
+            state.environment.as_ref(),
+            state.trie_store.as_ref(),
+            &other_root,
+            &mut snapshot.as_slice(),
+        );
+        match result {
+            Err(Error::RootMismatch { expected, actual }) => {
+                assert_eq!(expected, other_root);
+                assert_eq!(actual, root);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_reject_tampered_chunk() {
+        let (mut snapshot, root) = export_test_state();
+        // Flip a byte in the payload of the first chunk
+        let first_payload_byte = MAGIC.len() + 4 + HASH_LENGTH + 4;
+        snapshot[first_payload_byte + 1] ^= 0xff;
+
+        let state = InMemoryGlobalState::empty().unwrap();
+        let result = import(
+            state.environment.as_ref(),
+            state.trie_store.as_ref(),
+            &root,
+            &mut snapshot.as_slice(),
+        );
+        match result {
+            Err(Error::ChunkHashMismatch(0)) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(state.checkout(root).unwrap().is_none());
+    }
+
+    #[test]
+    fn should_reject_oversized_chunk() {
+        let (mut snapshot, root) = export_test_state();
+        // Claim the first chunk is far larger than any valid chunk
+        let header_length = MAGIC.len() + 4 + HASH_LENGTH;
+        snapshot[header_length..header_length + 4].copy_from_slice(&u32::max_value().to_le_bytes());
+
+        let state = InMemoryGlobalState::empty().unwrap();
+        let result = import(
+            state.environment.as_ref(),
+            state.trie_store.as_ref(),
+            &root,
+            &mut snapshot.as_slice(),
+        );
+        match result {
+            Err(Error::ChunkTooLarge { chunk: 0, length }) => {
+                assert_eq!(length, u32::max_value() as usize)
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(state.checkout(root).unwrap().is_none());
+    }
+
+    #[test]
+    fn should_reject_incomplete_snapshot() {
+        let (snapshot, root) = export_test_state();
+
+        // Keep only the header and first chunk
+        let header_length = MAGIC.len() + 4 + HASH_LENGTH;
+        let first_payload_length = {
+            let mut length = [0u8; 4];
+            length.copy_from_slice(&snapshot[header_length..header_length + 4]);
+            u32::from_le_bytes(length) as usize
+        };
+        let truncated = &snapshot[..header_length + 4 + first_payload_length + HASH_LENGTH];
+
+        let state = InMemoryGlobalState::empty().unwrap();
+        let result = import(
+            state.environment.as_ref(),
+            state.trie_store.as_ref(),
+            &root,
+            &mut &truncated[..],
+        );
+        match result {
+            Err(Error::MissingTrie(_)) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(state.checkout(root).unwrap().is_none());
+    }
+}
@@ -42,4 +42,49 @@ pub trait Store<K, V> {
         txn.write(handle, &key.to_bytes()?, &value.to_bytes()?)
             .map_err(Into::into)
     }
+
+    fn delete<T>(&self, txn: &mut T, key: &K) -> Result<(), Self::Error>
+    where
+        T: Writable<Handle = Self::Handle>,
+        K: ToBytes,
+        Self::Error: From<T::Error>,
+    {
+        let handle = self.handle();
+        txn.delete(handle, &key.to_bytes()?).map_err(Into::into)
+    }
+
+    fn keys<T>(&self, txn: &T) -> Result<Vec<K>, Self::Error>
+    where
+        T: Readable<Handle = Self::Handle>,
+        K: FromBytes,
+        Self::Error: From<T::Error>,
+    {
+        let handle = self.handle();
+        txn.keys(handle)?
+            .iter()
+            .map(|key_bytes| bytesrepr::deserialize(key_bytes).map_err(Into::into))
+            .collect()
+    }
+
+    fn keys_after<T>(
+        &self,
+        txn: &T,
+        start_after: Option<&K>,
+        limit: usize,
+    ) -> Result<Vec<K>, Self::Error>
+    where
+        T: Readable<Handle = Self::Handle>,
+        K: ToBytes + FromBytes,
+        Self::Error: From<T::Error>,
+    {
+        let handle = self.handle();
+        let start_after = match start_after {
+            Some(key) => Some(key.to_bytes()?),
+            None => None,
+        };
+        txn.keys_after(handle, start_after.as_ref().map(Vec::as_slice), limit)?
+            .iter()
+            .map(|key_bytes| bytesrepr::deserialize(key_bytes).map_err(Into::into))
+            .collect()
+    }
 }
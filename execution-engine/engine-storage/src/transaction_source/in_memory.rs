@@ -14,6 +14,16 @@ type BytesMap = HashMap<Vec<u8>, Vec<u8>>;
 
 type PoisonError<'a> = sync::PoisonError<MutexGuard<'a, HashMap<Option<String>, BytesMap>>>;
 
+/// Returns at most `limit` keys of `bytes_map` which sort after `start_after`, in ascending order.
+fn keys_after(bytes_map: &BytesMap, start_after: Option<&[u8]>, limit: usize) -> Vec<Vec<u8>> {
+    let mut keys: Vec<&Vec<u8>> = bytes_map
+        .keys()
+        .filter(|key| start_after.map_or(true, |start| key.as_slice() > start))
+        .collect();
+    keys.sort();
+    keys.into_iter().take(limit).cloned().collect()
+}
+
 /// A read transaction for the in-memory trie store.
 pub struct InMemoryReadTransaction {
     view: HashMap<Option<String>, BytesMap>,
@@ -48,6 +58,27 @@ impl Readable for InMemoryReadTransaction {
         };
         Ok(sub_view.get(&key.to_vec()).cloned())
     }
+
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let sub_view = match self.view.get(&handle) {
+            Some(view) => view,
+            None => return Ok(Vec::new()),
+        };
+        Ok(sub_view.keys().cloned().collect())
+    }
+
+    fn keys_after(
+        &self,
+        handle: Self::Handle,
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let sub_view = match self.view.get(&handle) {
+            Some(view) => view,
+            None => return Ok(Vec::new()),
+        };
+        Ok(keys_after(sub_view, start_after, limit))
+    }
 }
 
 /// A read-write transaction for the in-memory trie store.
@@ -93,6 +124,27 @@ impl<'a> Readable for InMemoryReadWriteTransaction<'a> {
         };
         Ok(sub_view.get(&key.to_vec()).cloned())
     }
+
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let sub_view = match self.view.get(&handle) {
+            Some(view) => view,
+            None => return Ok(Vec::new()),
+        };
+        Ok(sub_view.keys().cloned().collect())
+    }
+
+    fn keys_after(
+        &self,
+        handle: Self::Handle,
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let sub_view = match self.view.get(&handle) {
+            Some(view) => view,
+            None => return Ok(Vec::new()),
+        };
+        Ok(keys_after(sub_view, start_after, limit))
+    }
 }
 
 impl<'a> Writable for InMemoryReadWriteTransaction<'a> {
@@ -101,6 +153,13 @@ impl<'a> Writable for InMemoryReadWriteTransaction<'a> {
         sub_view.insert(key.to_vec(), value.to_vec());
         Ok(())
     }
+
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error> {
+        if let Some(sub_view) = self.view.get_mut(&handle) {
+            sub_view.remove(key);
+        }
+        Ok(())
+    }
 }
 
 /// An environment for the in-memory trie store.
@@ -1,10 +1,42 @@
 use std::path::PathBuf;
 
-use lmdb::{self, Database, Environment, RoTransaction, RwTransaction, WriteFlags};
+use lmdb::{self, Cursor, Database, Environment, RoTransaction, RwTransaction, WriteFlags};
+use lmdb_sys::{MDB_FIRST, MDB_SET_RANGE};
 
 use crate::transaction_source::{Readable, Transaction, TransactionSource, Writable};
 use crate::{error, MAX_DBS};
 
+/// Walks a cursor over `handle` from the first key after `start_after`, collecting at most `limit`
+/// keys.
+///
+/// The cursor is positioned with `Cursor::get` rather than `iter_start`/`iter_from`, which panic
+/// if there is no key to start from, e.g. when the database is empty or `start_after` was the last
+/// key.
+fn keys_after<T: lmdb::Transaction>(
+    txn: &T,
+    handle: Database,
+    start_after: Option<&[u8]>,
+    limit: usize,
+) -> Result<Vec<Vec<u8>>, lmdb::Error> {
+    let mut cursor = txn.open_ro_cursor(handle)?;
+    let first = match start_after {
+        Some(start) => cursor.get(Some(start), None, MDB_SET_RANGE),
+        None => cursor.get(None, None, MDB_FIRST),
+    };
+    let first = match first {
+        Ok((key, _)) => key,
+        Err(lmdb::Error::NotFound) => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+    Ok(first
+        .into_iter()
+        .chain(cursor.iter().map(|(key, _)| key))
+        .filter(|key| Some(*key) != start_after)
+        .take(limit)
+        .map(<[u8]>::to_vec)
+        .collect())
+}
+
 impl<'a> Transaction for RoTransaction<'a> {
     type Error = lmdb::Error;
 
@@ -23,6 +55,19 @@ impl<'a> Readable for RoTransaction<'a> {
             Err(e) => Err(e),
         }
     }
+
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error> {
+        keys_after(self, handle, None, usize::max_value())
+    }
+
+    fn keys_after(
+        &self,
+        handle: Self::Handle,
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<Vec<u8>>, Self::Error> {
+        keys_after(self, handle, start_after, limit)
+    }
 }
 
 impl<'a> Transaction for RwTransaction<'a> {
@@ -43,6 +88,19 @@ impl<'a> Readable for RwTransaction<'a> {
             Err(e) => Err(e),
         }
     }
+
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error> {
+        keys_after(self, handle, None, usize::max_value())
+    }
+
+    fn keys_after(
+        &self,
+        handle: Self::Handle,
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<Vec<u8>>, Self::Error> {
+        keys_after(self, handle, start_after, limit)
+    }
 }
 
 impl<'a> Writable for RwTransaction<'a> {
@@ -50,6 +108,13 @@ impl<'a> Writable for RwTransaction<'a> {
         self.put(handle, &key, &value, WriteFlags::empty())
             .map_err(Into::into)
     }
+
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error> {
+        match self.del(handle, &key, None) {
+            Ok(()) | Err(lmdb::Error::NotFound) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
 }
 
 /// The environment for an LMDB-backed trie store.
@@ -0,0 +1,471 @@
+//! Merkle proofs of inclusion and non-inclusion for keys of a [`Trie`].
+//!
+//! A [`TrieMerkleProof`] contains a key, its value, and the minimal set of sibling data needed to
+//! recompute the hash of every trie element between the leaf and the root.  A
+//! [`TrieMerkleAbsenceProof`] instead contains the trie element at which the path of a key
+//! diverges from the trie.  Both can be checked against a state root hash without access to a
+//! [`TrieStore`](crate::trie_store::TrieStore).
+
+use failure::Fail;
+
+use contract_ffi::bytesrepr::{self, FromBytes, ToBytes};
+use engine_shared::newtypes::Blake2bHash;
+
+use crate::trie::{Pointer, PointerBlock, Trie, RADIX};
+
+const U8_SIZE: usize = std::mem::size_of::<u8>();
+const U32_SIZE: usize = std::mem::size_of::<u32>();
+
+#[derive(Debug, Fail, PartialEq, Eq)]
+pub enum ValidationError {
+    #[fail(display = "Proof path does not correspond to its key")]
+    PathMismatch,
+
+    #[fail(
+        display = "Proof contains a node with an occupied hole at index {}",
+        _0
+    )]
+    OccupiedHole(u8),
+
+    #[fail(
+        display = "Computed state hash {} does not match expected state hash {}",
+        actual, expected
+    )]
+    StateHashMismatch {
+        expected: Blake2bHash,
+        actual: Blake2bHash,
+    },
+
+    #[fail(display = "Proof terminal does not diverge from the path of its key")]
+    TerminalNotDivergent,
+
+    #[fail(display = "{}", _0)]
+    BytesRepr(#[fail(cause)] bytesrepr::Error),
+}
+
+impl From<bytesrepr::Error> for ValidationError {
+    fn from(error: bytesrepr::Error) -> Self {
+        ValidationError::BytesRepr(error)
+    }
+}
+
+/// A single step of a [`TrieMerkleProof`], corresponding to one parent of the proven leaf.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrieMerkleProofStep {
+    /// A [`Trie::Node`], with the pointer that leads towards the leaf removed.
+    Node {
+        hole_index: u8,
+        indexed_pointers_with_hole: Vec<(u8, Pointer)>,
+    },
+    /// A [`Trie::Extension`], with its pointer removed.
+    Extension { affix: Vec<u8> },
+}
+
+impl TrieMerkleProofStep {
+    /// Constructs a [`TrieMerkleProofStep::Node`] from a node's pointer block, leaving a hole at
+    /// `hole_index`.
+    pub fn node(hole_index: u8, pointer_block: &PointerBlock) -> Self {
+        let indexed_pointers_with_hole = (0..RADIX)
+            .filter(|index| *index != usize::from(hole_index))
+            .filter_map(|index| pointer_block[index].map(|pointer| (index as u8, pointer)))
+            .collect();
+        TrieMerkleProofStep::Node {
+            hole_index,
+            indexed_pointers_with_hole,
+        }
+    }
+
+    /// Constructs a [`TrieMerkleProofStep::Extension`] from an extension's affix.
+    pub fn extension(affix: Vec<u8>) -> Self {
+        TrieMerkleProofStep::Extension { affix }
+    }
+
+    fn tag(&self) -> u32 {
+        match self {
+            TrieMerkleProofStep::Node { .. } => 0,
+            TrieMerkleProofStep::Extension { .. } => 1,
+        }
+    }
+
+    /// Returns the portion of the path to the leaf covered by this step.
+    fn path_segment(&self) -> &[u8] {
+        match self {
+            TrieMerkleProofStep::Node { hole_index, .. } => std::slice::from_ref(hole_index),
+            TrieMerkleProofStep::Extension { affix } => affix,
+        }
+    }
+
+    /// Fills the hole in this step with `pointer`, returning the hash of the resulting trie
+    /// element.
+    fn hash_with<K, V>(&self, pointer: Pointer) -> Result<Blake2bHash, ValidationError>
+    where
+        K: ToBytes,
+        V: ToBytes,
+    {
+        let trie: Trie<K, V> = match self {
+            TrieMerkleProofStep::Node {
+                hole_index,
+                indexed_pointers_with_hole,
+            } => {
+                let mut indexed_pointers: Vec<(usize, Pointer)> =
+                    Vec::with_capacity(indexed_pointers_with_hole.len() + 1);
+                for (index, sibling) in indexed_pointers_with_hole {
+                    if index == hole_index {
+                        return Err(ValidationError::OccupiedHole(*hole_index));
+                    }
+                    indexed_pointers.push(((*index).into(), *sibling));
+                }
+                indexed_pointers.push(((*hole_index).into(), pointer));
+                Trie::node(&indexed_pointers)
+            }
+            TrieMerkleProofStep::Extension { affix } => Trie::extension(affix.to_owned(), pointer),
+        };
+        let trie_bytes = trie.to_bytes()?;
+        Ok(Blake2bHash::new(&trie_bytes))
+    }
+}
+
+impl ToBytes for TrieMerkleProofStep {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret: Vec<u8> = self.tag().to_bytes()?;
+        match self {
+            TrieMerkleProofStep::Node {
+                hole_index,
+                indexed_pointers_with_hole,
+            } => {
+                ret.push(*hole_index);
+                ret.append(&mut (indexed_pointers_with_hole.len() as u32).to_bytes()?);
+                for (index, pointer) in indexed_pointers_with_hole {
+                    ret.push(*index);
+                    ret.append(&mut pointer.to_bytes()?);
+                }
+            }
+            TrieMerkleProofStep::Extension { affix } => {
+                ret.append(&mut affix.to_bytes()?);
+            }
+        }
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U32_SIZE
+            + match self {
+                TrieMerkleProofStep::Node {
+                    indexed_pointers_with_hole,
+                    ..
+                } => {
+                    U8_SIZE
+                        + U32_SIZE
+                        + indexed_pointers_with_hole
+                            .iter()
+                            .map(|(_, pointer)| U8_SIZE + pointer.serialized_length())
+                            .sum::<usize>()
+                }
+                TrieMerkleProofStep::Extension { affix } => affix.serialized_length(),
+            }
+    }
+}
+
+impl FromBytes for TrieMerkleProofStep {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        match tag {
+            0 => {
+                let (hole_index, rem): (u8, &[u8]) = FromBytes::from_bytes(rem)?;
+                let (count, mut rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+                if count as usize >= RADIX {
+                    return Err(bytesrepr::Error::FormattingError);
+                }
+                let mut indexed_pointers_with_hole = Vec::with_capacity(count as usize);
+                for _ in 0..count {
+                    let (index, next): (u8, &[u8]) = FromBytes::from_bytes(rem)?;
+                    let (pointer, next): (Pointer, &[u8]) = FromBytes::from_bytes(next)?;
+                    indexed_pointers_with_hole.push((index, pointer));
+                    rem = next;
+                }
+                Ok((
+                    TrieMerkleProofStep::Node {
+                        hole_index,
+                        indexed_pointers_with_hole,
+                    },
+                    rem,
+                ))
+            }
+            1 => {
+                let (affix, rem): (Vec<u8>, &[u8]) = FromBytes::from_bytes(rem)?;
+                Ok((TrieMerkleProofStep::Extension { affix }, rem))
+            }
+            _ => Err(bytesrepr::Error::FormattingError),
+        }
+    }
+}
+
+/// A proof that a given key-value pair is stored in a trie with a given root.
+///
+/// Steps are ordered from the parent of the leaf up to the root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieMerkleProof<K, V> {
+    key: K,
+    value: V,
+    proof_steps: Vec<TrieMerkleProofStep>,
+}
+
+impl<K, V> TrieMerkleProof<K, V> {
+    pub fn new(key: K, value: V, proof_steps: Vec<TrieMerkleProofStep>) -> Self {
+        TrieMerkleProof {
+            key,
+            value,
+            proof_steps,
+        }
+    }
+
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+
+    pub fn proof_steps(&self) -> &[TrieMerkleProofStep] {
+        &self.proof_steps
+    }
+
+    pub fn into_value(self) -> V {
+        self.value
+    }
+}
+
+impl<K, V> TrieMerkleProof<K, V>
+where
+    K: ToBytes + Clone,
+    V: ToBytes + Clone,
+{
+    /// Recomputes the state root hash implied by this proof.
+    ///
+    /// Fails if the path described by the proof steps is not a prefix of the serialized key.
+    pub fn compute_state_hash(&self) -> Result<Blake2bHash, ValidationError> {
+        let path = self.key.to_bytes()?;
+        check_path(&path, &self.proof_steps)?;
+
+        let leaf: Trie<K, V> = Trie::leaf(self.key.to_owned(), self.value.to_owned());
+        hash_up::<K, V>(&leaf, &self.proof_steps)
+    }
+
+    /// Checks that this proof is valid for a trie with the given root hash.
+    pub fn verify(&self, state_hash: &Blake2bHash) -> Result<(), ValidationError> {
+        check_state_hash(self.compute_state_hash()?, state_hash)
+    }
+}
+
+/// Checks that the path from the root described by `proof_steps` (which are ordered from the
+/// bottom up) is a prefix of `path`, returning the length of that prefix.
+pub(crate) fn check_path(
+    path: &[u8],
+    proof_steps: &[TrieMerkleProofStep],
+) -> Result<usize, ValidationError> {
+    let mut depth: usize = 0;
+    for step in proof_steps.iter().rev() {
+        let segment = step.path_segment();
+        let end = depth + segment.len();
+        if end > path.len() || &path[depth..end] != segment {
+            return Err(ValidationError::PathMismatch);
+        }
+        depth = end;
+    }
+    Ok(depth)
+}
+
+/// Hashes `trie` and then each of `proof_steps` in turn, returning the resulting root hash.
+fn hash_up<K, V>(
+    trie: &Trie<K, V>,
+    proof_steps: &[TrieMerkleProofStep],
+) -> Result<Blake2bHash, ValidationError>
+where
+    K: ToBytes,
+    V: ToBytes,
+{
+    let mut hash = {
+        let trie_bytes = trie.to_bytes()?;
+        Blake2bHash::new(&trie_bytes)
+    };
+    let mut pointer = match trie {
+        Trie::Leaf { .. } => Pointer::LeafPointer(hash),
+        Trie::Node { .. } | Trie::Extension { .. } => Pointer::NodePointer(hash),
+    };
+    for step in proof_steps {
+        hash = step.hash_with::<K, V>(pointer)?;
+        pointer = Pointer::NodePointer(hash);
+    }
+    Ok(hash)
+}
+
+/// Returns the serialized length of a count-prefixed list of proof steps.
+fn proof_steps_length(proof_steps: &[TrieMerkleProofStep]) -> usize {
+    U32_SIZE
+        + proof_steps
+            .iter()
+            .map(ToBytes::serialized_length)
+            .sum::<usize>()
+}
+
+fn check_state_hash(actual: Blake2bHash, expected: &Blake2bHash) -> Result<(), ValidationError> {
+    if actual != *expected {
+        return Err(ValidationError::StateHashMismatch {
+            expected: *expected,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+impl<K: ToBytes, V: ToBytes> ToBytes for TrieMerkleProof<K, V> {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut key_bytes = self.key.to_bytes()?;
+        let mut value_bytes = self.value.to_bytes()?;
+        let mut ret: Vec<u8> = Vec::with_capacity(key_bytes.len() + value_bytes.len() + U32_SIZE);
+        ret.append(&mut key_bytes);
+        ret.append(&mut value_bytes);
+        ret.append(&mut (self.proof_steps.len() as u32).to_bytes()?);
+        for step in &self.proof_steps {
+            ret.append(&mut step.to_bytes()?);
+        }
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.key.serialized_length()
+            + self.value.serialized_length()
+            + proof_steps_length(&self.proof_steps)
+    }
+}
+
+impl<K: FromBytes, V: FromBytes> FromBytes for TrieMerkleProof<K, V> {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (key, rem): (K, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (value, rem): (V, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (count, mut rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let mut proof_steps = Vec::new();
+        for _ in 0..count {
+            let (step, next): (TrieMerkleProofStep, &[u8]) = FromBytes::from_bytes(rem)?;
+            proof_steps.push(step);
+            rem = next;
+        }
+        Ok((TrieMerkleProof::new(key, value, proof_steps), rem))
+    }
+}
+
+/// A proof that a given key is not stored in a trie with a given root.
+///
+/// The proof consists of the trie element at which the path of the key diverges from the trie,
+/// i.e. one of:
+/// * a [`Trie::Node`] with no pointer at the next index of the path,
+/// * a [`Trie::Extension`] whose affix is not a prefix of the rest of the path, or
+/// * a [`Trie::Leaf`] holding a different key,
+///
+/// followed by the steps from the parent of that element up to the root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieMerkleAbsenceProof<K, V> {
+    key: K,
+    terminal: Trie<K, V>,
+    proof_steps: Vec<TrieMerkleProofStep>,
+}
+
+impl<K, V> TrieMerkleAbsenceProof<K, V> {
+    pub fn new(key: K, terminal: Trie<K, V>, proof_steps: Vec<TrieMerkleProofStep>) -> Self {
+        TrieMerkleAbsenceProof {
+            key,
+            terminal,
+            proof_steps,
+        }
+    }
+
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn terminal(&self) -> &Trie<K, V> {
+        &self.terminal
+    }
+
+    pub fn proof_steps(&self) -> &[TrieMerkleProofStep] {
+        &self.proof_steps
+    }
+}
+
+impl<K, V> TrieMerkleAbsenceProof<K, V>
+where
+    K: ToBytes,
+    V: ToBytes,
+{
+    /// Recomputes the state root hash implied by this proof.
+    ///
+    /// Fails if the path described by the proof steps is not a prefix of the serialized key, or if
+    /// the terminal element does not diverge from the rest of that path.
+    pub fn compute_state_hash(&self) -> Result<Blake2bHash, ValidationError> {
+        let path = self.key.to_bytes()?;
+        let depth = check_path(&path, &self.proof_steps)?;
+
+        match &self.terminal {
+            Trie::Leaf { key, .. } => {
+                if key.to_bytes()? == path {
+                    return Err(ValidationError::TerminalNotDivergent);
+                }
+            }
+            Trie::Node { pointer_block } => {
+                let index = match path.get(depth) {
+                    Some(index) => usize::from(*index),
+                    None => return Err(ValidationError::PathMismatch),
+                };
+                if pointer_block[index].is_some() {
+                    return Err(ValidationError::TerminalNotDivergent);
+                }
+            }
+            Trie::Extension { affix, .. } => {
+                if path.get(depth..depth + affix.len()) == Some(affix.as_slice()) {
+                    return Err(ValidationError::TerminalNotDivergent);
+                }
+            }
+        }
+
+        hash_up(&self.terminal, &self.proof_steps)
+    }
+
+    /// Checks that this proof is valid for a trie with the given root hash.
+    pub fn verify(&self, state_hash: &Blake2bHash) -> Result<(), ValidationError> {
+        check_state_hash(self.compute_state_hash()?, state_hash)
+    }
+}
+
+impl<K: ToBytes, V: ToBytes> ToBytes for TrieMerkleAbsenceProof<K, V> {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret: Vec<u8> = self.key.to_bytes()?;
+        ret.append(&mut self.terminal.to_bytes()?);
+        ret.append(&mut (self.proof_steps.len() as u32).to_bytes()?);
+        for step in &self.proof_steps {
+            ret.append(&mut step.to_bytes()?);
+        }
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.key.serialized_length()
+            + self.terminal.serialized_length()
+            + proof_steps_length(&self.proof_steps)
+    }
+}
+
+impl<K: FromBytes, V: FromBytes> FromBytes for TrieMerkleAbsenceProof<K, V> {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (key, rem): (K, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (terminal, rem): (Trie<K, V>, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (count, mut rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let mut proof_steps = Vec::new();
+        for _ in 0..count {
+            let (step, next): (TrieMerkleProofStep, &[u8]) = FromBytes::from_bytes(rem)?;
+            proof_steps.push(step);
+            rem = next;
+        }
+        Ok((TrieMerkleAbsenceProof::new(key, terminal, proof_steps), rem))
+    }
+}
@@ -8,6 +8,7 @@ use engine_shared::newtypes::Blake2bHash;
 
 #[cfg(test)]
 pub mod gens;
+pub mod merkle_proof;
 
 #[cfg(test)]
 mod tests;
@@ -57,6 +58,10 @@ impl ToBytes for Pointer {
         ret.append(&mut hash_bytes);
         Ok(ret)
     }
+
+    fn serialized_length(&self) -> usize {
+        U32_SIZE + self.hash().serialized_length()
+    }
 }
 
 impl FromBytes for Pointer {
@@ -119,6 +124,10 @@ impl ToBytes for PointerBlock {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         ToBytes::to_bytes(&self.0)
     }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
 }
 
 impl FromBytes for PointerBlock {
@@ -235,6 +244,17 @@ where
             }
         }
     }
+
+    fn serialized_length(&self) -> usize {
+        U32_SIZE
+            + match self {
+                Trie::Leaf { key, value } => key.serialized_length() + value.serialized_length(),
+                Trie::Node { pointer_block } => pointer_block.serialized_length(),
+                Trie::Extension { affix, pointer } => {
+                    affix.serialized_length() + pointer.serialized_length()
+                }
+            }
+    }
 }
 
 impl<K: FromBytes, V: FromBytes> FromBytes for Trie<K, V> {
@@ -1,24 +1,39 @@
 #[cfg(test)]
 mod tests;
 
+use std::collections::HashSet;
+use std::marker::PhantomData;
 use std::time::Instant;
 
 use contract_ffi::bytesrepr::{self, FromBytes, ToBytes};
 use engine_shared::logging::{log_duration, log_metric, GAUGE};
 use engine_shared::newtypes::{Blake2bHash, CorrelationId};
 
+use crate::error::MissingTrie;
 use crate::transaction_source::{Readable, Writable};
+use crate::trie::merkle_proof::{TrieMerkleAbsenceProof, TrieMerkleProof, TrieMerkleProofStep};
 use crate::trie::{self, Parents, Pointer, Trie};
 use crate::trie_store::TrieStore;
 
 const TRIE_STORE_READ_DURATION: &str = "trie_store_read_duration";
 const TRIE_STORE_READ_GETS: &str = "trie_store_read_gets";
+const TRIE_STORE_DIFF_DURATION: &str = "trie_store_diff_duration";
+const TRIE_STORE_PROVE_DURATION: &str = "trie_store_prove_duration";
+const TRIE_STORE_PRUNE_DURATION: &str = "trie_store_prune_duration";
+const TRIE_STORE_PRUNE_DELETES: &str = "trie_store_prune_deletes";
 const TRIE_STORE_SCAN_DURATION: &str = "trie_store_scan_duration";
 const TRIE_STORE_SCAN_GETS: &str = "trie_store_scan_gets";
 const TRIE_STORE_WRITE_DURATION: &str = "trie_store_write_duration";
 const TRIE_STORE_WRITE_PUTS: &str = "trie_store_write_puts";
+
+/// The number of trie keys `prune` reads from the store at a time.
+const PRUNE_BATCH_SIZE: usize = 1024;
 const READ: &str = "read";
 const GET: &str = "get";
+const DIFF: &str = "diff";
+const PROVE: &str = "prove";
+const PRUNE: &str = "prune";
+const DELETE: &str = "delete";
 const SCAN: &str = "scan";
 const WRITE: &str = "write";
 const PUT: &str = "put";
@@ -194,6 +209,149 @@ where
     }
 }
 
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProofResult<K, V> {
+    Present(TrieMerkleProof<K, V>),
+    Absent(TrieMerkleAbsenceProof<K, V>),
+    RootNotFound,
+}
+
+/// Returns a proof of either the presence or the absence of the given key at a given root in a
+/// given store.
+pub fn prove<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+) -> Result<ProofResult<K, V>, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let path: Vec<u8> = key.to_bytes()?;
+
+    let mut depth: usize = 0;
+    let mut proof_steps: Vec<TrieMerkleProofStep> = Vec::new();
+    let mut current: Trie<K, V> = match store.get(txn, root)? {
+        Some(root) => root,
+        None => return Ok(ProofResult::RootNotFound),
+    };
+
+    let start = Instant::now();
+
+    let result = loop {
+        // Steps are collected from the root down, but proofs are built from the bottom up.
+        let pointer = match current {
+            Trie::Leaf {
+                key: leaf_key,
+                value: leaf_value,
+            } => {
+                proof_steps.reverse();
+                if *key != leaf_key {
+                    // Keys may not match in the case of a compressed path from
+                    // a Node directly to a Leaf
+                    let terminal = Trie::leaf(leaf_key, leaf_value);
+                    break ProofResult::Absent(TrieMerkleAbsenceProof::new(
+                        key.to_owned(),
+                        terminal,
+                        proof_steps,
+                    ));
+                }
+                break ProofResult::Present(TrieMerkleProof::new(
+                    leaf_key,
+                    leaf_value,
+                    proof_steps,
+                ));
+            }
+            Trie::Node { pointer_block } => {
+                let index: u8 = {
+                    assert!(depth < path.len(), "depth must be < {}", path.len());
+                    path[depth]
+                };
+                let maybe_pointer: Option<Pointer> = pointer_block[usize::from(index)];
+                match maybe_pointer {
+                    Some(pointer) => {
+                        proof_steps.push(TrieMerkleProofStep::node(index, &pointer_block));
+                        depth += 1;
+                        pointer
+                    }
+                    None => {
+                        proof_steps.reverse();
+                        let terminal = Trie::Node { pointer_block };
+                        break ProofResult::Absent(TrieMerkleAbsenceProof::new(
+                            key.to_owned(),
+                            terminal,
+                            proof_steps,
+                        ));
+                    }
+                }
+            }
+            Trie::Extension { affix, pointer } => {
+                let sub_path = path.get(depth..depth + affix.len());
+                if sub_path != Some(affix.as_slice()) {
+                    proof_steps.reverse();
+                    let terminal = Trie::extension(affix, pointer);
+                    break ProofResult::Absent(TrieMerkleAbsenceProof::new(
+                        key.to_owned(),
+                        terminal,
+                        proof_steps,
+                    ));
+                }
+                depth += affix.len();
+                proof_steps.push(TrieMerkleProofStep::extension(affix));
+                pointer
+            }
+        };
+        current = match store.get(txn, pointer.hash())? {
+            Some(next) => next,
+            None => panic!(
+                "No trie value at key: {:?} (reading from key: {:?})",
+                pointer.hash(),
+                key
+            ),
+        };
+    };
+
+    log_duration(
+        correlation_id,
+        TRIE_STORE_PROVE_DURATION,
+        PROVE,
+        start.elapsed(),
+    );
+
+    Ok(result)
+}
+
+/// Same as [`read`], except that a [`TrieMerkleProof`] of the found leaf is returned instead of
+/// its bare value.
+pub fn read_with_proof<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+) -> Result<ReadResult<TrieMerkleProof<K, V>>, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let result = match prove::<K, V, T, S, E>(correlation_id, txn, store, root, key)? {
+        ProofResult::Present(proof) => ReadResult::Found(proof),
+        ProofResult::Absent(_) => ReadResult::NotFound,
+        ProofResult::RootNotFound => ReadResult::RootNotFound,
+    };
+    Ok(result)
+}
+
 struct TrieScan<K, V> {
     tip: Trie<K, V>,
     parents: Parents<K, V>,
@@ -606,6 +764,328 @@ where
     })
 }
 
+/// Returns `true` if a trie element at `path` may have leaves whose keys start with `prefix`.
+fn path_matches_prefix(path: &[u8], prefix: &[u8]) -> bool {
+    let length = std::cmp::min(path.len(), prefix.len());
+    path[..length] == prefix[..length]
+}
+
+/// An iterator over the leaves of a trie whose serialized keys start with a given prefix, in
+/// ascending order of their serialized keys.
+///
+/// The iterator owns a transaction, so the trie it iterates over is unaffected by writes made
+/// after its creation. If a trie element is missing from the store, the iterator yields a
+/// [`MissingTrie`] error and then ends.
+pub struct PrefixIterator<'a, K, V, T, S> {
+    txn: T,
+    store: &'a S,
+    prefix: Vec<u8>,
+    // Pointers yet to be visited, paired with the path from the root to the element
+    to_visit: Vec<(Vec<u8>, Pointer)>,
+    failed: bool,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<'a, K, V, T, S> Iterator for PrefixIterator<'a, K, V, T, S>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error> + From<MissingTrie>,
+{
+    type Item = Result<(K, V), S::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        while let Some((path, pointer)) = self.to_visit.pop() {
+            let trie: Trie<K, V> = match self.store.get(&self.txn, pointer.hash()) {
+                Ok(Some(trie)) => trie,
+                // The store is corrupt, so any further results would be incomplete
+                Ok(None) => {
+                    self.failed = true;
+                    return Some(Err(MissingTrie(*pointer.hash()).into()));
+                }
+                Err(error) => {
+                    self.failed = true;
+                    return Some(Err(error));
+                }
+            };
+            match trie {
+                Trie::Leaf { key, value } => {
+                    let key_bytes = match key.to_bytes() {
+                        Ok(key_bytes) => key_bytes,
+                        Err(error) => {
+                            self.failed = true;
+                            return Some(Err(error.into()));
+                        }
+                    };
+                    if key_bytes.starts_with(&self.prefix) {
+                        return Some(Ok((key, value)));
+                    }
+                }
+                Trie::Node { pointer_block } => {
+                    // Pushed in descending order so that they are popped in ascending order
+                    for index in (0..trie::RADIX).rev() {
+                        if let Some(pointer) = pointer_block[index] {
+                            let mut child_path = path.clone();
+                            child_path.push(index as u8);
+                            if path_matches_prefix(&child_path, &self.prefix) {
+                                self.to_visit.push((child_path, pointer));
+                            }
+                        }
+                    }
+                }
+                Trie::Extension { affix, pointer } => {
+                    let mut child_path = path;
+                    child_path.extend_from_slice(&affix);
+                    if path_matches_prefix(&child_path, &self.prefix) {
+                        self.to_visit.push((child_path, pointer));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Returns an iterator over the leaves under a given root whose serialized keys start with
+/// `prefix`, or `None` if the root is not in the store.
+pub fn iter_prefix<'a, K, V, T, S, E>(
+    txn: T,
+    store: &'a S,
+    root: &Blake2bHash,
+    prefix: &[u8],
+) -> Result<Option<PrefixIterator<'a, K, V, T, S>>, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error> + From<MissingTrie>,
+    E: From<S::Error>,
+{
+    let maybe_root: Option<Trie<K, V>> = store.get(&txn, root)?;
+    if maybe_root.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(PrefixIterator {
+        txn,
+        store,
+        prefix: prefix.to_vec(),
+        to_visit: vec![(Vec::new(), Pointer::NodePointer(*root))],
+        failed: false,
+        _marker: PhantomData,
+    }))
+}
+
+/// A difference in the value of a single key between two tries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffEntry<K, V> {
+    Added { key: K, value: V },
+    Removed { key: K, value: V },
+    Changed { key: K, old_value: V, new_value: V },
+}
+
+impl<K, V> DiffEntry<K, V> {
+    pub fn key(&self) -> &K {
+        match self {
+            DiffEntry::Added { key, .. }
+            | DiffEntry::Removed { key, .. }
+            | DiffEntry::Changed { key, .. } => key,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DiffResult<K, V> {
+    Diff(Vec<DiffEntry<K, V>>),
+    RootNotFound(Blake2bHash),
+}
+
+/// Returns all leaves under `pointer`, paired with their serialized keys, in ascending order of
+/// their serialized keys.
+///
+/// Fails with [`MissingTrie`] if any trie element under `pointer` is missing from the store.
+fn collect_leaves<K, V, T, S, E>(
+    txn: &T,
+    store: &S,
+    pointer: Pointer,
+) -> Result<Vec<(Vec<u8>, K, V)>, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<contract_ffi::bytesrepr::Error> + From<MissingTrie>,
+{
+    let mut ret = Vec::new();
+    let mut to_visit = vec![pointer];
+    while let Some(pointer) = to_visit.pop() {
+        match store.get(txn, pointer.hash())? {
+            Some(Trie::Leaf { key, value }) => {
+                let key_bytes = key.to_bytes()?;
+                ret.push((key_bytes, key, value));
+            }
+            Some(Trie::Node { pointer_block }) => {
+                // Pushed in descending order so that they are popped in ascending order
+                for index in (0..trie::RADIX).rev() {
+                    if let Some(pointer) = pointer_block[index] {
+                        to_visit.push(pointer);
+                    }
+                }
+            }
+            Some(Trie::Extension { pointer, .. }) => to_visit.push(pointer),
+            None => return Err(MissingTrie(*pointer.hash()).into()),
+        }
+    }
+    Ok(ret)
+}
+
+/// Merges two lists of leaves, each in ascending order of their serialized keys, into a list of
+/// differences.
+fn diff_leaves<K, V>(
+    old_leaves: Vec<(Vec<u8>, K, V)>,
+    new_leaves: Vec<(Vec<u8>, K, V)>,
+    diff: &mut Vec<DiffEntry<K, V>>,
+) where
+    V: Eq,
+{
+    let mut old_leaves = old_leaves.into_iter().peekable();
+    let mut new_leaves = new_leaves.into_iter().peekable();
+    loop {
+        let ordering = match (old_leaves.peek(), new_leaves.peek()) {
+            (None, None) => return,
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (Some((old_key_bytes, _, _)), Some((new_key_bytes, _, _))) => {
+                old_key_bytes.cmp(new_key_bytes)
+            }
+        };
+        match ordering {
+            std::cmp::Ordering::Less => {
+                let (_, key, value) = old_leaves.next().unwrap();
+                diff.push(DiffEntry::Removed { key, value });
+            }
+            std::cmp::Ordering::Greater => {
+                let (_, key, value) = new_leaves.next().unwrap();
+                diff.push(DiffEntry::Added { key, value });
+            }
+            std::cmp::Ordering::Equal => {
+                let (_, key, old_value) = old_leaves.next().unwrap();
+                let (_, _, new_value) = new_leaves.next().unwrap();
+                if old_value != new_value {
+                    diff.push(DiffEntry::Changed {
+                        key,
+                        old_value,
+                        new_value,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Returns the differences between the leaves under `old_root` and those under `new_root`, in
+/// ascending order of their serialized keys.
+///
+/// Both tries are walked in lockstep and subtries with identical hashes are skipped, so the cost
+/// is proportional to the size of the difference rather than the size of the tries.
+pub fn diff<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    old_root: &Blake2bHash,
+    new_root: &Blake2bHash,
+) -> Result<DiffResult<K, V>, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes + Eq,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<contract_ffi::bytesrepr::Error> + From<MissingTrie>,
+{
+    for root in &[old_root, new_root] {
+        let maybe_root: Option<Trie<K, V>> = store.get(txn, root)?;
+        if maybe_root.is_none() {
+            return Ok(DiffResult::RootNotFound(**root));
+        }
+    }
+
+    let start = Instant::now();
+    let mut ret = Vec::new();
+    let mut to_visit: Vec<(Option<Pointer>, Option<Pointer>)> = vec![(
+        Some(Pointer::NodePointer(*old_root)),
+        Some(Pointer::NodePointer(*new_root)),
+    )];
+
+    while let Some(pair) = to_visit.pop() {
+        let (old_pointer, new_pointer) = match pair {
+            (None, None) => continue,
+            // Identical subtries have no differences
+            (Some(old), Some(new)) if old.hash() == new.hash() => continue,
+            (Some(old_pointer), None) => {
+                let old_leaves = collect_leaves::<K, V, T, S, E>(txn, store, old_pointer)?;
+                diff_leaves(old_leaves, Vec::new(), &mut ret);
+                continue;
+            }
+            (None, Some(new_pointer)) => {
+                let new_leaves = collect_leaves::<K, V, T, S, E>(txn, store, new_pointer)?;
+                diff_leaves(Vec::new(), new_leaves, &mut ret);
+                continue;
+            }
+            (Some(old_pointer), Some(new_pointer)) => (old_pointer, new_pointer),
+        };
+
+        let old_trie: Option<Trie<K, V>> = store.get(txn, old_pointer.hash())?;
+        let new_trie: Option<Trie<K, V>> = store.get(txn, new_pointer.hash())?;
+        match (old_trie, new_trie) {
+            (
+                Some(Trie::Node {
+                    pointer_block: old_pointer_block,
+                }),
+                Some(Trie::Node {
+                    pointer_block: new_pointer_block,
+                }),
+            ) => {
+                // Pushed in descending order so that they are popped in ascending order
+                for index in (0..trie::RADIX).rev() {
+                    to_visit.push((old_pointer_block[index], new_pointer_block[index]));
+                }
+            }
+            (
+                Some(Trie::Extension {
+                    affix: old_affix,
+                    pointer: old_child,
+                }),
+                Some(Trie::Extension {
+                    affix: new_affix,
+                    pointer: new_child,
+                }),
+            ) if old_affix == new_affix => to_visit.push((Some(old_child), Some(new_child))),
+            // The tries have diverged in shape, so compare all leaves below this point
+            _ => {
+                let old_leaves = collect_leaves::<K, V, T, S, E>(txn, store, old_pointer)?;
+                let new_leaves = collect_leaves::<K, V, T, S, E>(txn, store, new_pointer)?;
+                diff_leaves(old_leaves, new_leaves, &mut ret);
+            }
+        }
+    }
+
+    log_duration(
+        correlation_id,
+        TRIE_STORE_DIFF_DURATION,
+        DIFF,
+        start.elapsed(),
+    );
+
+    Ok(DiffResult::Diff(ret))
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum WriteResult {
     Written(Blake2bHash),
@@ -725,3 +1205,138 @@ where
         }
     }
 }
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PruneResult {
+    Pruned { retained: usize, deleted: usize },
+    RootNotFound(Blake2bHash),
+    NoRootsToKeep,
+}
+
+/// Returns the hashes of all trie elements reachable from the given roots in a given store, or
+/// the first root which could not be found.
+fn reachable<K, V, T, S, E>(
+    txn: &T,
+    store: &S,
+    roots: &[Blake2bHash],
+) -> Result<Result<HashSet<Blake2bHash>, Blake2bHash>, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let mut visited: HashSet<Blake2bHash> = HashSet::new();
+    let mut to_visit: Vec<Blake2bHash> = Vec::new();
+
+    for root in roots {
+        if store.get(txn, root)?.is_none() {
+            return Ok(Err(*root));
+        }
+        to_visit.push(*root);
+    }
+
+    while let Some(hash) = to_visit.pop() {
+        if !visited.insert(hash) {
+            continue;
+        }
+        let trie: Trie<K, V> = match store.get(txn, &hash)? {
+            Some(trie) => trie,
+            // A dangling pointer can't be followed any further
+            None => continue,
+        };
+        let mut visit = |pointer: &Pointer| match pointer {
+            // Leaves have no children, so there is no need to read them
+            Pointer::LeafPointer(leaf_hash) => {
+                visited.insert(*leaf_hash);
+            }
+            Pointer::NodePointer(node_hash) => {
+                if !visited.contains(node_hash) {
+                    to_visit.push(*node_hash);
+                }
+            }
+        };
+        match trie {
+            Trie::Leaf { .. } => (),
+            Trie::Node { pointer_block } => {
+                for index in 0..trie::RADIX {
+                    if let Some(pointer) = pointer_block[index] {
+                        visit(&pointer);
+                    }
+                }
+            }
+            Trie::Extension { pointer, .. } => visit(&pointer),
+        }
+    }
+
+    Ok(Ok(visited))
+}
+
+/// Deletes every trie element which is not reachable from one of `roots_to_keep` from a given
+/// store.
+///
+/// If `roots_to_keep` is empty, or any of them is not present in the store, nothing is deleted.
+/// Only the reachable hashes are held in memory; the store itself is walked in batches.
+pub fn prune<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    roots_to_keep: &[Blake2bHash],
+) -> Result<PruneResult, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    if roots_to_keep.is_empty() {
+        return Ok(PruneResult::NoRootsToKeep);
+    }
+
+    let start = Instant::now();
+
+    let reachable = match reachable::<K, V, T, S, E>(txn, store, roots_to_keep)? {
+        Ok(reachable) => reachable,
+        Err(missing_root) => return Ok(PruneResult::RootNotFound(missing_root)),
+    };
+
+    let mut deleted: usize = 0;
+    let mut start_after: Option<Blake2bHash> = None;
+    loop {
+        let batch: Vec<Blake2bHash> =
+            store.keys_after(txn, start_after.as_ref(), PRUNE_BATCH_SIZE)?;
+        for hash in &batch {
+            if !reachable.contains(hash) {
+                store.delete(txn, hash)?;
+                deleted += 1;
+            }
+        }
+        if batch.len() < PRUNE_BATCH_SIZE {
+            break;
+        }
+        start_after = batch.last().cloned();
+    }
+
+    log_metric(
+        correlation_id,
+        TRIE_STORE_PRUNE_DELETES,
+        DELETE,
+        GAUGE,
+        deleted as f64,
+    );
+    log_duration(
+        correlation_id,
+        TRIE_STORE_PRUNE_DURATION,
+        PRUNE,
+        start.elapsed(),
+    );
+
+    Ok(PruneResult::Pruned {
+        retained: reachable.len(),
+        deleted,
+    })
+}
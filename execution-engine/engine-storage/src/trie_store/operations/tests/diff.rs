@@ -0,0 +1,253 @@
+use super::*;
+use crate::error::MissingTrie;
+use crate::trie_store::operations::{diff, DiffEntry, DiffResult};
+
+type TestDiff = Vec<DiffEntry<TestKey, TestValue>>;
+
+fn leaves_to_pairs(leaves: &[TestTrie]) -> Vec<(TestKey, TestValue)> {
+    let mut pairs: Vec<(TestKey, TestValue)> = leaves
+        .iter()
+        .map(|leaf| match leaf {
+            Trie::Leaf { key, value } => (*key, *value),
+            _ => panic!("leaves should contain only leaves"),
+        })
+        .collect();
+    // TestKey serializes to its bytes, so this is the order of the serialized keys
+    pairs.sort_by_key(|(key, _)| key.0);
+    pairs
+}
+
+/// Computes the expected difference between two sets of leaves without using the tries.
+fn expected_diff(old_leaves: &[TestTrie], new_leaves: &[TestTrie]) -> TestDiff {
+    let old_pairs = leaves_to_pairs(old_leaves);
+    let new_pairs = leaves_to_pairs(new_leaves);
+    let mut ret = Vec::new();
+    for (key, old_value) in old_pairs.iter() {
+        match new_pairs.iter().find(|(new_key, _)| new_key == key) {
+            None => ret.push(DiffEntry::Removed {
+                key: *key,
+                value: *old_value,
+            }),
+            Some((_, new_value)) if new_value != old_value => ret.push(DiffEntry::Changed {
+                key: *key,
+                old_value: *old_value,
+                new_value: *new_value,
+            }),
+            Some(_) => (),
+        }
+    }
+    for (key, new_value) in new_pairs.iter() {
+        if !old_pairs.iter().any(|(old_key, _)| old_key == key) {
+            ret.push(DiffEntry::Added {
+                key: *key,
+                value: *new_value,
+            });
+        }
+    }
+    ret.sort_by_key(|entry| entry.key().0);
+    ret
+}
+
+fn diff_roots<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    old_root: &Blake2bHash,
+    new_root: &Blake2bHash,
+) -> Result<DiffResult<TestKey, TestValue>, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error> + From<MissingTrie>,
+{
+    let txn = environment.create_read_txn()?;
+    let ret = diff::<_, _, _, _, E>(correlation_id, &txn, store, old_root, new_root)?;
+    txn.commit()?;
+    Ok(ret)
+}
+
+fn diffs_between_roots_are_expected<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error> + From<MissingTrie>,
+{
+    // Each root after the first adds a leaf, then each root after those updates a leaf
+    let leaves: Vec<TestTrie> = TEST_LEAVES
+        .iter()
+        .chain(TEST_LEAVES_UPDATED.iter())
+        .cloned()
+        .collect();
+    let roots = write_roots::<_, _, E>(correlation_id, environment, store, &leaves)?;
+
+    let leaves_at = |index: usize| -> Vec<TestTrie> {
+        let mut ret: Vec<TestTrie> = Vec::new();
+        for leaf in leaves[..index].iter() {
+            if let Trie::Leaf { key, .. } = leaf {
+                ret.retain(|existing| match existing {
+                    Trie::Leaf {
+                        key: existing_key, ..
+                    } => existing_key != key,
+                    _ => true,
+                });
+            }
+            ret.push(leaf.to_owned());
+        }
+        ret
+    };
+
+    for (old_index, old_root) in roots.iter().enumerate() {
+        for (new_index, new_root) in roots.iter().enumerate() {
+            let expected = expected_diff(&leaves_at(old_index), &leaves_at(new_index));
+            let actual =
+                diff_roots::<_, _, E>(correlation_id, environment, store, old_root, new_root)?;
+            assert_eq!(
+                actual,
+                DiffResult::Diff(expected),
+                "unexpected diff from root {} to root {}",
+                old_index,
+                new_index
+            );
+        }
+    }
+    Ok(())
+}
+
+fn diff_with_missing_root_is_root_not_found<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error> + From<MissingTrie>,
+{
+    let roots = write_roots::<_, _, E>(correlation_id, environment, store, &TEST_LEAVES)?;
+    let missing_root: Blake2bHash = [1u8; 32].into();
+    assert_eq!(
+        diff_roots::<_, _, E>(correlation_id, environment, store, &roots[0], &missing_root)?,
+        DiffResult::RootNotFound(missing_root)
+    );
+    assert_eq!(
+        diff_roots::<_, _, E>(correlation_id, environment, store, &missing_root, &roots[0])?,
+        DiffResult::RootNotFound(missing_root)
+    );
+    Ok(())
+}
+
+fn diff_with_missing_trie_fails<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error>
+        + From<S::Error>
+        + From<contract_ffi::bytesrepr::Error>
+        + From<MissingTrie>
+        + PartialEq
+        + std::fmt::Debug,
+{
+    let roots = write_roots::<_, _, E>(correlation_id, environment, store, &TEST_LEAVES)?;
+
+    // The first leaf is under every root but the empty one
+    let missing_leaf = Blake2bHash::new(&TEST_LEAVES[0].to_bytes()?);
+    let mut txn = environment.create_read_write_txn()?;
+    store.delete(&mut txn, &missing_leaf)?;
+    txn.commit()?;
+
+    assert_eq!(
+        diff_roots::<_, _, E>(
+            correlation_id,
+            environment,
+            store,
+            &roots[0],
+            &roots[TEST_LEAVES_LENGTH]
+        ),
+        Err(MissingTrie(missing_leaf).into())
+    );
+    Ok(())
+}
+
+#[test]
+fn lmdb_diffs_between_roots_are_expected() {
+    let correlation_id = CorrelationId::new();
+    let context = LmdbTestContext::new(&[]).unwrap();
+    diffs_between_roots_are_expected::<_, _, error::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_diffs_between_roots_are_expected() {
+    let correlation_id = CorrelationId::new();
+    let context = InMemoryTestContext::new(&[]).unwrap();
+    diffs_between_roots_are_expected::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+    )
+    .unwrap();
+}
+
+#[test]
+fn lmdb_diff_with_missing_root_is_root_not_found() {
+    let correlation_id = CorrelationId::new();
+    let context = LmdbTestContext::new(&[]).unwrap();
+    diff_with_missing_root_is_root_not_found::<_, _, error::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_diff_with_missing_root_is_root_not_found() {
+    let correlation_id = CorrelationId::new();
+    let context = InMemoryTestContext::new(&[]).unwrap();
+    diff_with_missing_root_is_root_not_found::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+    )
+    .unwrap();
+}
+
+#[test]
+fn lmdb_diff_with_missing_trie_fails() {
+    let correlation_id = CorrelationId::new();
+    let context = LmdbTestContext::new(&[]).unwrap();
+    diff_with_missing_trie_fails::<_, _, error::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_diff_with_missing_trie_fails() {
+    let correlation_id = CorrelationId::new();
+    let context = InMemoryTestContext::new(&[]).unwrap();
+    diff_with_missing_trie_fails::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+    )
+    .unwrap();
+}
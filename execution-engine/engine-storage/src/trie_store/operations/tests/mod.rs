@@ -1,4 +1,7 @@
+mod diff;
+mod proofs;
 mod proptests;
+mod prune;
 mod read;
 mod scan;
 mod write;
@@ -33,6 +36,10 @@ impl ToBytes for TestKey {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         Ok(self.0.to_vec())
     }
+
+    fn serialized_length(&self) -> usize {
+        TEST_KEY_LENGTH
+    }
 }
 
 impl FromBytes for TestKey {
@@ -54,6 +61,10 @@ impl ToBytes for TestValue {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         Ok(self.0.to_vec())
     }
+
+    fn serialized_length(&self) -> usize {
+        TEST_VAL_LENGTH
+    }
 }
 
 impl FromBytes for TestValue {
@@ -478,6 +489,40 @@ where
     Ok(())
 }
 
+/// Writes each of `leaves` in turn, starting from an empty trie, and returns the root hash
+/// after each write (the first being the empty root).
+fn write_roots<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    leaves: &[TestTrie],
+) -> Result<Vec<Blake2bHash>, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let (empty_root_hash, empty_trie) = create_0_leaf_trie()?;
+    put_tries::<_, _, E>(environment, store, &empty_trie)?;
+
+    let mut roots = vec![empty_root_hash];
+    let mut txn = environment.create_read_write_txn()?;
+    for leaf in leaves {
+        if let Trie::Leaf { key, value } = leaf {
+            let root = *roots.last().unwrap();
+            match write::<_, _, _, _, E>(correlation_id, &mut txn, store, &root, key, value)? {
+                WriteResult::Written(hash) => roots.push(hash),
+                _ => panic!("write_roots should only write new leaves"),
+            }
+        } else {
+            panic!("leaves should contain only leaves");
+        }
+    }
+    txn.commit()?;
+    Ok(roots)
+}
+
 // A context for holding lmdb-based test resources
 struct LmdbTestContext {
     _temp_dir: TempDir,
@@ -0,0 +1,190 @@
+//! This module contains tests for [`prove`] and [`read_with_proof`], and the verification of the
+//! resulting [`TrieMerkleProof`]s and [`TrieMerkleAbsenceProof`]s.
+
+use super::*;
+use crate::error::{self, in_memory};
+use crate::trie::merkle_proof::{TrieMerkleAbsenceProof, TrieMerkleProof, ValidationError};
+use crate::trie_store::operations::{prove, read_with_proof, ProofResult};
+
+fn check_proofs<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root: &Blake2bHash,
+    present: &[TestTrie],
+    absent: &[TestTrie],
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let txn: R::ReadTransaction = environment.create_read_txn()?;
+
+    for leaf in present {
+        if let Trie::Leaf { key, value } = leaf {
+            let proof =
+                match read_with_proof::<_, _, _, _, E>(correlation_id, &txn, store, root, key)? {
+                    ReadResult::Found(proof) => proof,
+                    _ => panic!("should find a proof for {:?}", key),
+                };
+            assert_eq!(proof.key(), key);
+            assert_eq!(proof.value(), value);
+            assert_eq!(proof.compute_state_hash().unwrap(), *root);
+            assert!(proof.verify(root).is_ok());
+
+            let other_root = Blake2bHash::new(b"not the root");
+            assert_eq!(
+                proof.verify(&other_root),
+                Err(ValidationError::StateHashMismatch {
+                    expected: other_root,
+                    actual: *root
+                })
+            );
+
+            let forged =
+                TrieMerkleProof::new(*key, TestValue(*b"forged"), proof.proof_steps().to_vec());
+            assert!(forged.verify(root).is_err());
+        } else {
+            panic!("leaves should only contain leaves")
+        }
+    }
+
+    for leaf in absent {
+        if let Trie::Leaf { key, .. } = leaf {
+            let result = read_with_proof::<_, _, _, _, E>(correlation_id, &txn, store, root, key)?;
+            assert_eq!(result, ReadResult::NotFound);
+
+            let proof = match prove::<_, _, _, _, E>(correlation_id, &txn, store, root, key)? {
+                ProofResult::Absent(proof) => proof,
+                _ => panic!("should find an absence proof for {:?}", key),
+            };
+            assert_eq!(proof.key(), key);
+            assert_eq!(proof.compute_state_hash().unwrap(), *root);
+            assert!(proof.verify(root).is_ok());
+
+            // The same terminal and steps must not prove the absence of a present key
+            for present_leaf in present {
+                if let Trie::Leaf { key, .. } = present_leaf {
+                    let forged = TrieMerkleAbsenceProof::new(
+                        *key,
+                        proof.terminal().to_owned(),
+                        proof.proof_steps().to_vec(),
+                    );
+                    assert!(forged.verify(root).is_err());
+                }
+            }
+        } else {
+            panic!("leaves should only contain leaves")
+        }
+    }
+
+    for leaf in present {
+        if let Trie::Leaf { key, .. } = leaf {
+            let result = prove::<_, _, _, _, E>(correlation_id, &txn, store, root, key)?;
+            if let ProofResult::Absent(_) = result {
+                panic!("should not find an absence proof for {:?}", key);
+            }
+        }
+    }
+
+    txn.commit()?;
+    Ok(())
+}
+
+#[test]
+fn lmdb_proofs_from_n_leaf_partial_trie_verify_against_root() {
+    for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+        let test_leaves = TEST_LEAVES;
+        let (used, unused) = test_leaves.split_at(num_leaves);
+
+        check_proofs::<_, _, error::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            used,
+            unused,
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn in_memory_proofs_from_n_leaf_partial_trie_verify_against_root() {
+    for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+        let test_leaves = TEST_LEAVES;
+        let (used, unused) = test_leaves.split_at(num_leaves);
+
+        check_proofs::<_, _, in_memory::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            used,
+            unused,
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn proofs_roundtrip_through_bytesrepr() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = create_6_leaf_trie().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let txn = context.environment.create_read_txn().unwrap();
+
+    for leaf in TEST_LEAVES.iter() {
+        if let Trie::Leaf { key, .. } = leaf {
+            let proof: TrieMerkleProof<TestKey, TestValue> =
+                match read_with_proof::<_, _, _, _, in_memory::Error>(
+                    correlation_id,
+                    &txn,
+                    &context.store,
+                    &root_hash,
+                    key,
+                )
+                .unwrap()
+                {
+                    ReadResult::Found(proof) => proof,
+                    _ => panic!("should find a proof for {:?}", key),
+                };
+            bytesrepr::test_serialization_roundtrip(&proof);
+        }
+    }
+}
+
+#[test]
+fn absence_proofs_roundtrip_through_bytesrepr() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = create_3_leaf_trie().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let txn = context.environment.create_read_txn().unwrap();
+
+    for leaf in TEST_LEAVES[3..].iter() {
+        if let Trie::Leaf { key, .. } = leaf {
+            let proof: TrieMerkleAbsenceProof<TestKey, TestValue> =
+                match prove::<_, _, _, _, in_memory::Error>(
+                    correlation_id,
+                    &txn,
+                    &context.store,
+                    &root_hash,
+                    key,
+                )
+                .unwrap()
+                {
+                    ProofResult::Absent(proof) => proof,
+                    _ => panic!("should find an absence proof for {:?}", key),
+                };
+            bytesrepr::test_serialization_roundtrip(&proof);
+        }
+    }
+}
@@ -0,0 +1,394 @@
+use super::*;
+use crate::trie_store::operations::{prune, PruneResult, PRUNE_BATCH_SIZE};
+
+/// Returns `count` leaves whose keys spread over every byte, so that each node in the trie has
+/// few children and every write stores several new tries.
+fn spread_leaves(count: usize) -> Vec<TestTrie> {
+    (0..count)
+        .map(|i| {
+            let mut key = [0u8; TEST_KEY_LENGTH];
+            for (digit, byte) in key.iter_mut().enumerate() {
+                *byte = ((i >> (2 * digit)) & 3) as u8;
+            }
+            Trie::Leaf {
+                key: TestKey(key),
+                value: TestValue(*b"valueS"),
+            }
+        })
+        .collect()
+}
+
+fn prune_keeps_only_reachable_tries<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let roots = write_roots::<_, _, E>(correlation_id, environment, store, &TEST_LEAVES)?;
+    let middle_root = roots[TEST_LEAVES_LENGTH / 2];
+    let last_root = roots[TEST_LEAVES_LENGTH];
+    let roots_to_keep = [middle_root, last_root];
+
+    let mut txn = environment.create_read_write_txn()?;
+    let prune_result = prune::<_, _, _, _, E>(correlation_id, &mut txn, store, &roots_to_keep)?;
+    txn.commit()?;
+
+    let (retained, deleted) = match prune_result {
+        PruneResult::Pruned { retained, deleted } => (retained, deleted),
+        other => panic!("prune was given valid roots: {:?}", other),
+    };
+    assert!(deleted > 0);
+
+    let txn = environment.create_read_txn()?;
+    assert_eq!(store.keys(&txn)?.len(), retained);
+    for root in roots.iter() {
+        let maybe_root: Option<TestTrie> = store.get(&txn, root)?;
+        assert_eq!(maybe_root.is_some(), roots_to_keep.contains(root));
+    }
+    txn.commit()?;
+
+    check_leaves::<_, _, E>(
+        correlation_id,
+        environment,
+        store,
+        &middle_root,
+        &TEST_LEAVES[..TEST_LEAVES_LENGTH / 2],
+        &TEST_LEAVES[TEST_LEAVES_LENGTH / 2..],
+    )?;
+    check_leaves::<_, _, E>(
+        correlation_id,
+        environment,
+        store,
+        &last_root,
+        &TEST_LEAVES,
+        &[],
+    )?;
+
+    // Pruning again is a no-op
+    let mut txn = environment.create_read_write_txn()?;
+    let prune_result = prune::<_, _, _, _, E>(correlation_id, &mut txn, store, &roots_to_keep)?;
+    txn.commit()?;
+    assert_eq!(
+        prune_result,
+        PruneResult::Pruned {
+            retained,
+            deleted: 0
+        }
+    );
+    Ok(())
+}
+
+fn prune_with_missing_root_deletes_nothing<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let roots = write_roots::<_, _, E>(correlation_id, environment, store, &TEST_LEAVES)?;
+    let missing_root: Blake2bHash = [1u8; 32].into();
+
+    let txn = environment.create_read_txn()?;
+    let count_before = store.keys(&txn)?.len();
+    txn.commit()?;
+
+    let mut txn = environment.create_read_write_txn()?;
+    let prune_result = prune::<_, _, _, _, E>(
+        correlation_id,
+        &mut txn,
+        store,
+        &[roots[TEST_LEAVES_LENGTH], missing_root],
+    )?;
+    txn.commit()?;
+    assert_eq!(prune_result, PruneResult::RootNotFound(missing_root));
+
+    let txn = environment.create_read_txn()?;
+    assert_eq!(store.keys(&txn)?.len(), count_before);
+    txn.commit()?;
+    Ok(())
+}
+
+fn prune_with_no_roots_deletes_nothing<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    write_roots::<_, _, E>(correlation_id, environment, store, &TEST_LEAVES)?;
+
+    let txn = environment.create_read_txn()?;
+    let count_before = store.keys(&txn)?.len();
+    txn.commit()?;
+
+    let mut txn = environment.create_read_write_txn()?;
+    let prune_result = prune::<_, _, _, _, E>(correlation_id, &mut txn, store, &[])?;
+    txn.commit()?;
+    assert_eq!(prune_result, PruneResult::NoRootsToKeep);
+
+    let txn = environment.create_read_txn()?;
+    assert_eq!(store.keys(&txn)?.len(), count_before);
+    txn.commit()?;
+    Ok(())
+}
+
+fn prune_crosses_batches<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let leaves = spread_leaves(PRUNE_BATCH_SIZE / 2);
+    let roots = write_roots::<_, _, E>(correlation_id, environment, store, &leaves)?;
+    let last_root = *roots.last().unwrap();
+
+    let txn = environment.create_read_txn()?;
+    let count_before = store.keys(&txn)?.len();
+    txn.commit()?;
+    assert!(count_before > PRUNE_BATCH_SIZE);
+
+    let mut txn = environment.create_read_write_txn()?;
+    let prune_result = prune::<_, _, _, _, E>(correlation_id, &mut txn, store, &[last_root])?;
+    txn.commit()?;
+
+    let (retained, deleted) = match prune_result {
+        PruneResult::Pruned { retained, deleted } => (retained, deleted),
+        other => panic!("prune was given a valid root: {:?}", other),
+    };
+    assert_eq!(retained + deleted, count_before);
+
+    let txn = environment.create_read_txn()?;
+    assert_eq!(store.keys(&txn)?.len(), retained);
+    for root in &roots[..roots.len() - 1] {
+        let maybe_root: Option<TestTrie> = store.get(&txn, root)?;
+        assert!(maybe_root.is_none());
+    }
+    txn.commit()?;
+
+    check_leaves::<_, _, E>(correlation_id, environment, store, &last_root, &leaves, &[])
+}
+
+fn prune_empty_store<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    let missing_root: Blake2bHash = [1u8; 32].into();
+
+    let mut txn = environment.create_read_write_txn()?;
+    let prune_result = prune::<_, _, _, _, E>(correlation_id, &mut txn, store, &[missing_root])?;
+    txn.commit()?;
+    assert_eq!(prune_result, PruneResult::RootNotFound(missing_root));
+
+    let txn = environment.create_read_txn()?;
+    assert!(store.keys(&txn)?.is_empty());
+    let batch: Vec<Blake2bHash> = store.keys_after(&txn, None, PRUNE_BATCH_SIZE)?;
+    assert!(batch.is_empty());
+    let batch: Vec<Blake2bHash> = store.keys_after(&txn, Some(&missing_root), PRUNE_BATCH_SIZE)?;
+    assert!(batch.is_empty());
+    txn.commit()?;
+    Ok(())
+}
+
+fn keys_after_pages_through_every_key<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<contract_ffi::bytesrepr::Error>,
+{
+    write_roots::<_, _, E>(correlation_id, environment, store, &TEST_LEAVES)?;
+
+    let txn = environment.create_read_txn()?;
+    let mut expected: Vec<Blake2bHash> = store.keys(&txn)?;
+    expected.sort();
+
+    let mut paged: Vec<Blake2bHash> = Vec::new();
+    let mut start_after: Option<Blake2bHash> = None;
+    loop {
+        let batch: Vec<Blake2bHash> = store.keys_after(&txn, start_after.as_ref(), 2)?;
+        paged.extend(batch.iter().cloned());
+        if batch.len() < 2 {
+            break;
+        }
+        start_after = batch.last().cloned();
+    }
+    // Nothing sorts after the last key, whether or not it is still in the store.
+    let batch: Vec<Blake2bHash> = store.keys_after(&txn, expected.last(), 2)?;
+    assert!(batch.is_empty());
+    let past_every_key: Blake2bHash = [255u8; 32].into();
+    let batch: Vec<Blake2bHash> = store.keys_after(&txn, Some(&past_every_key), 2)?;
+    assert!(batch.is_empty());
+    txn.commit()?;
+
+    assert_eq!(paged, expected);
+    Ok(())
+}
+
+#[test]
+fn lmdb_prune_keeps_only_reachable_tries() {
+    let correlation_id = CorrelationId::new();
+    let context = LmdbTestContext::new(&[]).unwrap();
+    prune_keeps_only_reachable_tries::<_, _, error::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_prune_keeps_only_reachable_tries() {
+    let correlation_id = CorrelationId::new();
+    let context = InMemoryTestContext::new(&[]).unwrap();
+    prune_keeps_only_reachable_tries::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+    )
+    .unwrap();
+}
+
+#[test]
+fn lmdb_prune_with_missing_root_deletes_nothing() {
+    let correlation_id = CorrelationId::new();
+    let context = LmdbTestContext::new(&[]).unwrap();
+    prune_with_missing_root_deletes_nothing::<_, _, error::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_prune_with_missing_root_deletes_nothing() {
+    let correlation_id = CorrelationId::new();
+    let context = InMemoryTestContext::new(&[]).unwrap();
+    prune_with_missing_root_deletes_nothing::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+    )
+    .unwrap();
+}
+
+#[test]
+fn lmdb_prune_with_no_roots_deletes_nothing() {
+    let correlation_id = CorrelationId::new();
+    let context = LmdbTestContext::new(&[]).unwrap();
+    prune_with_no_roots_deletes_nothing::<_, _, error::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_prune_with_no_roots_deletes_nothing() {
+    let correlation_id = CorrelationId::new();
+    let context = InMemoryTestContext::new(&[]).unwrap();
+    prune_with_no_roots_deletes_nothing::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+    )
+    .unwrap();
+}
+
+#[test]
+fn lmdb_prune_crosses_batches() {
+    let correlation_id = CorrelationId::new();
+    let context = LmdbTestContext::new(&[]).unwrap();
+    prune_crosses_batches::<_, _, error::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_prune_crosses_batches() {
+    let correlation_id = CorrelationId::new();
+    let context = InMemoryTestContext::new(&[]).unwrap();
+    prune_crosses_batches::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+    )
+    .unwrap();
+}
+
+#[test]
+fn lmdb_prune_empty_store() {
+    let correlation_id = CorrelationId::new();
+    let context = LmdbTestContext::new(&[]).unwrap();
+    prune_empty_store::<_, _, error::Error>(correlation_id, &context.environment, &context.store)
+        .unwrap();
+}
+
+#[test]
+fn in_memory_prune_empty_store() {
+    let correlation_id = CorrelationId::new();
+    let context = InMemoryTestContext::new(&[]).unwrap();
+    prune_empty_store::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+    )
+    .unwrap();
+}
+
+#[test]
+fn lmdb_keys_after_pages_through_every_key() {
+    let correlation_id = CorrelationId::new();
+    let context = LmdbTestContext::new(&[]).unwrap();
+    keys_after_pages_through_every_key::<_, _, error::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_keys_after_pages_through_every_key() {
+    let correlation_id = CorrelationId::new();
+    let context = InMemoryTestContext::new(&[]).unwrap();
+    keys_after_pages_through_every_key::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+    )
+    .unwrap();
+}
@@ -7,6 +7,7 @@ use contract_ffi::args_parser::ArgsParser;
 use contract_ffi::bytesrepr::{self, FromBytes};
 use contract_ffi::execution::Phase;
 use contract_ffi::key::Key;
+use contract_ffi::named_args::NamedArgs;
 use contract_ffi::uref::URef;
 use contract_ffi::value::account::BlockTime;
 use contract_ffi::value::{ProtocolVersion, U512};
@@ -14,7 +15,7 @@ use engine_core::engine_state::executable_deploy_item::ExecutableDeployItem;
 use engine_core::engine_state::execution_effect::ExecutionEffect;
 use engine_core::engine_state::EngineState;
 use engine_core::execution;
-use engine_core::execution::AddressGenerator;
+use engine_core::execution::{AddressGenerator, RandomGenerator};
 use engine_core::runtime_context::RuntimeContext;
 use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
 use engine_shared::gas::Gas;
@@ -66,6 +67,10 @@ where
         let address_generator = AddressGenerator::new(deploy_hash, phase);
         Rc::new(RefCell::new(address_generator))
     };
+    let random_generator = {
+        let random_generator = RandomGenerator::new(deploy_hash, phase);
+        Rc::new(RefCell::new(random_generator))
+    };
     let gas_counter = Gas::default();
     let fn_store_id = INIT_FN_STORE_ID;
     let gas_limit = Gas::new(U512::from(std::u64::MAX));
@@ -90,6 +95,7 @@ where
         &mut named_keys,
         access_rights,
         arguments,
+        NamedArgs::new(),
         BTreeSet::new(),
         &account,
         base_key,
@@ -99,6 +105,8 @@ where
         gas_counter,
         fn_store_id,
         address_generator,
+        random_generator,
+        0,
         protocol_version,
         correlation_id,
         phase,
@@ -109,6 +117,7 @@ where
     let deploy_item = ExecutableDeployItem::ModuleBytes {
         module_bytes: wasm_bytes,
         args: Vec::new(),
+        named_args: Vec::new(),
     };
 
     let wasm_costs = *DEFAULT_WASM_COSTS;
@@ -11,20 +11,25 @@ use rand::Rng;
 
 use contract_ffi::args_parser::ArgsParser;
 use contract_ffi::bytesrepr::ToBytes;
+use contract_ffi::event::Event;
 use contract_ffi::key::Key;
+use contract_ffi::named_args::NamedArgs;
+use contract_ffi::system_contracts::pos::{RewardsConfig, SlashDestination};
 use contract_ffi::uref::URef;
 use contract_ffi::value::account::{Account, PublicKey, PurseId};
 use contract_ffi::value::contract::Contract;
-use contract_ffi::value::{SemVer, Value, U512};
+use contract_ffi::value::{ContractPackage, SemVer, Value, U512};
 use engine_core::engine_state::genesis::{GenesisAccount, GenesisConfig};
 use engine_core::engine_state::{EngineConfig, EngineState, SYSTEM_ACCOUNT_ADDR};
 use engine_core::execution;
 use engine_grpc_server::engine_server::ipc::{
-    ChainSpec_ActivationPoint, ChainSpec_CostTable_WasmCosts, ChainSpec_UpgradePoint,
-    CommitRequest, CommitResponse, DeployCode, DeployItem, DeployPayload, DeployResult,
-    DeployResult_ExecutionResult, DeployResult_PreconditionFailure, ExecuteRequest,
-    ExecuteResponse, GenesisResponse, QueryRequest, StoredContractHash, StoredContractName,
-    StoredContractURef, UpgradeRequest, UpgradeResponse, ValidateRequest, ValidateResponse,
+    ChainSpec_ActivationPoint, ChainSpec_CostTable_WasmCosts,
+    ChainSpec_CostTable_WasmCosts_CryptoCosts, ChainSpec_UpgradePoint, CommitRequest,
+    CommitResponse, DeployCode, DeployItem, DeployPayload, DeployResult,
+    DeployResult_ExecutionResult, DeployResult_PreconditionFailure, DistributeRewardsRequest,
+    DistributeRewardsResponse, ExecuteRequest, ExecuteResponse, GenesisResponse, QueryRequest,
+    SlashRequest, SlashResponse, StoredContractHash, StoredContractName, StoredContractURef,
+    UpgradeRequest, UpgradeResponse, ValidateRequest, ValidateResponse,
 };
 use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
 use engine_grpc_server::engine_server::mappings::{CommitTransforms, MappingError};
@@ -150,6 +155,18 @@ impl DeployItemBuilder {
         self
     }
 
+    pub fn with_session_code_named_args(mut self, file_name: &str, named_args: &NamedArgs) -> Self {
+        let wasm_bytes = read_wasm_file_bytes(file_name);
+        let named_args = named_args.to_bytes().expect("should serialize named args");
+        let mut deploy_code = DeployCode::new();
+        deploy_code.set_code(wasm_bytes);
+        deploy_code.set_named_args(named_args);
+        let mut session = DeployPayload::new();
+        session.set_deploy_code(deploy_code);
+        self.deploy_item.set_session(session);
+        self
+    }
+
     pub fn with_stored_session_hash(mut self, hash: Vec<u8>, args: impl ArgsParser) -> Self {
         let args = args
             .parse()
@@ -192,6 +209,23 @@ impl DeployItemBuilder {
         self
     }
 
+    pub fn with_stored_session_entry_point(
+        mut self,
+        uref_name: &str,
+        entry_point: &str,
+        named_args: &NamedArgs,
+    ) -> Self {
+        let named_args = named_args.to_bytes().expect("should serialize named args");
+        let mut item = StoredContractName::new();
+        item.set_named_args(named_args);
+        item.set_stored_contract_name(uref_name.to_owned());
+        item.set_entry_point(entry_point.to_owned());
+        let mut session = DeployPayload::new();
+        session.set_stored_contract_name(item);
+        self.deploy_item.set_session(session);
+        self
+    }
+
     pub fn with_authorization_keys(mut self, authorization_keys: &[PublicKey]) -> Self {
         let authorization_keys = authorization_keys
             .iter()
@@ -365,6 +399,12 @@ impl UpgradeRequestBuilder {
         new_costs.set_regular(wasm_costs.regular);
         new_costs.set_opcodes_mul(wasm_costs.opcodes_mul);
         new_costs.set_opcodes_div(wasm_costs.opcodes_div);
+        let mut crypto_costs = ChainSpec_CostTable_WasmCosts_CryptoCosts::new();
+        crypto_costs.set_hash(wasm_costs.hash);
+        crypto_costs.set_hash_byte(wasm_costs.hash_byte);
+        crypto_costs.set_verify_signature(wasm_costs.verify_signature);
+        crypto_costs.set_verify_signature_byte(wasm_costs.verify_signature_byte);
+        new_costs.set_crypto(crypto_costs);
         new_costs.set_mul(wasm_costs.mul);
         new_costs.set_div(wasm_costs.div);
         new_costs.set_grow_mem(wasm_costs.grow_mem);
@@ -420,6 +460,115 @@ impl Default for UpgradeRequestBuilder {
     }
 }
 
+pub struct SlashRequestBuilder {
+    protocol_version: ProtocolVersion,
+    block_time: u64,
+    validators: Vec<PublicKey>,
+    fraction: u32,
+    destination: SlashDestination,
+}
+
+impl SlashRequestBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_protocol_version(
+        mut self,
+        protocol_version: contract_ffi::value::ProtocolVersion,
+    ) -> Self {
+        self.protocol_version = protocol_version.into();
+        self
+    }
+
+    pub fn with_block_time(mut self, block_time: u64) -> Self {
+        self.block_time = block_time;
+        self
+    }
+
+    pub fn with_validators(mut self, validators: Vec<PublicKey>) -> Self {
+        self.validators = validators;
+        self
+    }
+
+    pub fn with_fraction(mut self, fraction: u32) -> Self {
+        self.fraction = fraction;
+        self
+    }
+
+    pub fn with_destination(mut self, destination: SlashDestination) -> Self {
+        self.destination = destination;
+        self
+    }
+
+    pub fn build(self) -> SlashRequest {
+        let mut slash_request = SlashRequest::new();
+        slash_request.set_protocol_version(self.protocol_version);
+        slash_request.set_block_time(self.block_time);
+        slash_request.set_validator_public_keys(
+            self.validators
+                .iter()
+                .map(|validator| validator.value().to_vec())
+                .collect(),
+        );
+        slash_request.set_fraction(self.fraction);
+        slash_request.set_destination(self.destination.into());
+        slash_request
+    }
+}
+
+impl Default for SlashRequestBuilder {
+    fn default() -> Self {
+        SlashRequestBuilder {
+            protocol_version: get_protocol_version(),
+            block_time: 0,
+            validators: Vec::new(),
+            fraction: 0,
+            destination: SlashDestination::Burn,
+        }
+    }
+}
+
+pub struct DistributeRewardsRequestBuilder {
+    protocol_version: ProtocolVersion,
+    block_time: u64,
+}
+
+impl DistributeRewardsRequestBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_protocol_version(
+        mut self,
+        protocol_version: contract_ffi::value::ProtocolVersion,
+    ) -> Self {
+        self.protocol_version = protocol_version.into();
+        self
+    }
+
+    pub fn with_block_time(mut self, block_time: u64) -> Self {
+        self.block_time = block_time;
+        self
+    }
+
+    pub fn build(self) -> DistributeRewardsRequest {
+        let mut distribute_rewards_request = DistributeRewardsRequest::new();
+        distribute_rewards_request.set_protocol_version(self.protocol_version);
+        distribute_rewards_request.set_block_time(self.block_time);
+        distribute_rewards_request
+    }
+}
+
+impl Default for DistributeRewardsRequestBuilder {
+    fn default() -> Self {
+        DistributeRewardsRequestBuilder {
+            protocol_version: get_protocol_version(),
+            block_time: 0,
+        }
+    }
+}
+
 /// Builder for simple WASM test
 pub struct WasmTestBuilder<S> {
     /// Engine state is wrapped in Rc<> to workaround missing `impl Clone for
@@ -427,6 +576,8 @@ pub struct WasmTestBuilder<S> {
     engine_state: Rc<EngineState<S>>,
     exec_responses: Vec<ExecuteResponse>,
     upgrade_responses: Vec<UpgradeResponse>,
+    slash_responses: Vec<SlashResponse>,
+    distribute_rewards_responses: Vec<DistributeRewardsResponse>,
     genesis_hash: Option<Vec<u8>>,
     post_state_hash: Option<Vec<u8>>,
     /// Cached transform maps after subsequent successful runs
@@ -453,6 +604,8 @@ impl Default for InMemoryWasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
             upgrade_responses: Vec::new(),
+            slash_responses: Vec::new(),
+            distribute_rewards_responses: Vec::new(),
             genesis_hash: None,
             post_state_hash: None,
             transforms: Vec::new(),
@@ -473,6 +626,8 @@ impl<S> Clone for WasmTestBuilder<S> {
             engine_state: Rc::clone(&self.engine_state),
             exec_responses: self.exec_responses.clone(),
             upgrade_responses: self.upgrade_responses.clone(),
+            slash_responses: self.slash_responses.clone(),
+            distribute_rewards_responses: self.distribute_rewards_responses.clone(),
             genesis_hash: self.genesis_hash.clone(),
             post_state_hash: self.post_state_hash.clone(),
             transforms: self.transforms.clone(),
@@ -537,6 +692,8 @@ impl LmdbWasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
             upgrade_responses: Vec::new(),
+            slash_responses: Vec::new(),
+            distribute_rewards_responses: Vec::new(),
             genesis_hash: None,
             post_state_hash: None,
             transforms: Vec::new(),
@@ -595,6 +752,8 @@ impl LmdbWasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
             upgrade_responses: Vec::new(),
+            slash_responses: Vec::new(),
+            distribute_rewards_responses: Vec::new(),
             genesis_hash: None,
             post_state_hash: Some(post_state_hash),
             transforms: Vec::new(),
@@ -619,6 +778,8 @@ where
             engine_state: result.0.engine_state,
             exec_responses: Vec::new(),
             upgrade_responses: Vec::new(),
+            slash_responses: Vec::new(),
+            distribute_rewards_responses: Vec::new(),
             genesis_hash: result.0.genesis_hash,
             post_state_hash: result.0.post_state_hash,
             transforms: Vec::new(),
@@ -825,6 +986,63 @@ where
         self
     }
 
+    /// Slashes on top of the current post state hash. The post state hash and the bonded
+    /// validators are only updated if the slash succeeded.
+    pub fn slash_with_slash_request(&mut self, slash_request: &mut SlashRequest) -> &mut Self {
+        let hash = self
+            .post_state_hash
+            .clone()
+            .expect("expected post_state_hash");
+        slash_request.set_parent_state_hash(hash);
+        let slash_response = self
+            .engine_state
+            .slash(RequestOptions::new(), slash_request.clone())
+            .wait_drop_metadata()
+            .expect("should slash");
+
+        if slash_response.has_success() {
+            let slash_success = slash_response.get_success();
+            self.post_state_hash = Some(slash_success.get_post_state_hash().to_vec());
+            let bonded_validators = slash_success
+                .get_bonded_validators()
+                .iter()
+                .map(TryInto::try_into)
+                .collect::<Result<HashMap<PublicKey, U512>, MappingError>>()
+                .unwrap();
+            self.bonded_validators.push(bonded_validators);
+        }
+
+        self.slash_responses.push(slash_response);
+        self
+    }
+
+    /// Distributes rewards on top of the current post state hash. The post state hash is only
+    /// updated if the distribution succeeded.
+    pub fn distribute_rewards_with_request(
+        &mut self,
+        distribute_rewards_request: &mut DistributeRewardsRequest,
+    ) -> &mut Self {
+        let hash = self
+            .post_state_hash
+            .clone()
+            .expect("expected post_state_hash");
+        distribute_rewards_request.set_parent_state_hash(hash);
+        let distribute_rewards_response = self
+            .engine_state
+            .distribute_rewards(RequestOptions::new(), distribute_rewards_request.clone())
+            .wait_drop_metadata()
+            .expect("should distribute rewards");
+
+        if distribute_rewards_response.has_success() {
+            let distribute_rewards_success = distribute_rewards_response.get_success();
+            self.post_state_hash = Some(distribute_rewards_success.get_post_state_hash().to_vec());
+        }
+
+        self.distribute_rewards_responses
+            .push(distribute_rewards_response);
+        self
+    }
+
     /// Expects a successful run and caches transformations
     pub fn expect_success(&mut self) -> &mut Self {
         // Check first result, as only first result is interesting for a simple test
@@ -918,10 +1136,29 @@ where
         self.exec_responses.get(index)
     }
 
+    pub fn get_exec_responses_count(&self) -> usize {
+        self.exec_responses.len()
+    }
+
     pub fn get_upgrade_response(&self, index: usize) -> Option<&UpgradeResponse> {
         self.upgrade_responses.get(index)
     }
 
+    pub fn get_slash_response(&self, index: usize) -> Option<&SlashResponse> {
+        self.slash_responses.get(index)
+    }
+
+    pub fn get_distribute_rewards_responses_count(&self) -> usize {
+        self.distribute_rewards_responses.len()
+    }
+
+    pub fn get_distribute_rewards_response(
+        &self,
+        index: usize,
+    ) -> Option<&DistributeRewardsResponse> {
+        self.distribute_rewards_responses.get(index)
+    }
+
     pub fn finish(&self) -> WasmTestResult<S> {
         WasmTestResult(self.clone())
     }
@@ -976,6 +1213,18 @@ where
         }
     }
 
+    pub fn get_contract_package(&self, package_uref: URef) -> Option<ContractPackage> {
+        let package_value: Value = self
+            .query(None, Key::URef(package_uref), &[])
+            .expect("should have contract package value");
+
+        if let Value::ContractPackage(package) = package_value {
+            Some(package)
+        } else {
+            None
+        }
+    }
+
     pub fn exec_costs(&self, index: usize) -> Vec<Gas> {
         let exec_response = self
             .get_exec_response(index)
@@ -989,6 +1238,18 @@ where
         Some(get_error_message(execution_result))
     }
 
+    /// Returns the events emitted by the deploy of the exec response at `index`.
+    pub fn get_exec_events(&self, index: usize) -> Option<Vec<Event>> {
+        let response = self.get_exec_response(index)?;
+        let execution_result = get_success_result(&response);
+        let events = execution_result
+            .get_events()
+            .iter()
+            .map(|event| event.try_into().expect("should parse event"))
+            .collect();
+        Some(events)
+    }
+
     pub fn exec_commit_finish(&mut self, execute_request: ExecuteRequest) -> WasmTestResult<S> {
         self.exec(execute_request)
             .expect_success()
@@ -1033,6 +1294,7 @@ pub fn create_genesis_config(accounts: Vec<GenesisAccount>) -> GenesisConfig {
         proof_of_stake_installer_bytes,
         accounts,
         wasm_costs,
+        RewardsConfig::default(),
     )
 }
 
@@ -0,0 +1,125 @@
+use contract_ffi::value::account::{BlockTime, PublicKey, Weight, MIN_INACTIVITY_PERIOD_TIME};
+use engine_grpc_server::engine_server::ipc::ExecuteRequest;
+
+use crate::support::test_support::{
+    DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, STANDARD_PAYMENT_CONTRACT,
+};
+use crate::test::{DEFAULT_ACCOUNT_ADDR, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT};
+
+const CONTRACT_KEY_RECOVERY: &str = "key_recovery.wasm";
+const RECOVERY_KEY: [u8; 32] = [42; 32];
+const NEW_KEY: [u8; 32] = [43; 32];
+const INACTIVITY_PERIOD: u64 = MIN_INACTIVITY_PERIOD_TIME.0;
+
+fn recover_request(block_time: u64, deploy_hash: [u8; 32]) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_session_code(CONTRACT_KEY_RECOVERY, (String::from("recover"),))
+        .with_deploy_hash(deploy_hash)
+        .with_authorization_keys(&[PublicKey::new(RECOVERY_KEY)])
+        .build();
+    ExecuteRequestBuilder::from_deploy_item(deploy)
+        .with_block_time(block_time)
+        .build()
+}
+
+fn owner_request(stage: &str, block_time: u64) -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_KEY_RECOVERY,
+        (stage.to_string(),),
+    )
+    .with_block_time(block_time)
+    .build()
+}
+
+#[ignore]
+#[test]
+fn should_only_recover_keys_after_inactivity_period() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(owner_request("init", 0))
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(account.has_key_recovery());
+    assert_eq!(
+        account.account_activity().inactivity_period_limit(),
+        MIN_INACTIVITY_PERIOD_TIME
+    );
+
+    // The inactivity period hasn't elapsed yet, and the failed attempt doesn't count as activity
+    builder
+        .exec(recover_request(INACTIVITY_PERIOD - 1, [2u8; 32]))
+        .commit();
+    assert!(builder.is_error());
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert_eq!(
+        account.account_activity().deployment_last_used(),
+        BlockTime(0)
+    );
+
+    builder
+        .exec(recover_request(INACTIVITY_PERIOD, [3u8; 32]))
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert_eq!(
+        account.get_associated_key_weight(PublicKey::new(NEW_KEY)),
+        Some(&Weight::new(2))
+    );
+    assert_eq!(
+        account.account_activity().key_management_last_used(),
+        BlockTime(INACTIVITY_PERIOD)
+    );
+    assert_eq!(
+        account.account_activity().deployment_last_used(),
+        BlockTime(INACTIVITY_PERIOD)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_recover_keys_of_account_in_use() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(owner_request("init", 0))
+        .expect_success()
+        .commit()
+        .exec(owner_request("activity", INACTIVITY_PERIOD / 2))
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert_eq!(
+        account.account_activity().deployment_last_used(),
+        BlockTime(INACTIVITY_PERIOD / 2)
+    );
+
+    builder
+        .exec(recover_request(INACTIVITY_PERIOD, [3u8; 32]))
+        .commit();
+    assert!(builder.is_error());
+
+    builder
+        .exec(recover_request(
+            INACTIVITY_PERIOD / 2 + INACTIVITY_PERIOD,
+            [4u8; 32],
+        ))
+        .expect_success()
+        .commit();
+}
@@ -5,4 +5,6 @@ mod authorized_keys;
 #[cfg(test)]
 mod key_management_thresholds;
 #[cfg(test)]
+mod key_recovery;
+#[cfg(test)]
 mod named_keys;
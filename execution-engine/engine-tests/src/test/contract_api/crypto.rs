@@ -0,0 +1,46 @@
+use contract_ffi::base16;
+
+use crate::support::test_support::{ExecuteRequestBuilder, InMemoryWasmTestBuilder};
+use crate::test::{DEFAULT_ACCOUNT_ADDR, DEFAULT_GENESIS_CONFIG};
+
+const CONTRACT_CRYPTO: &str = "crypto.wasm";
+
+// Digests of the empty input.
+const BLAKE2B_256: &str = "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8";
+const SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+const KECCAK256: &str = "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470";
+
+// Test 2 from RFC 8032, section 7.1.
+const ED25519_PUBLIC_KEY: &str = "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c";
+const ED25519_MESSAGE: &str = "72";
+const ED25519_SIGNATURE: &str = "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+                                 085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00";
+
+fn decode(hex: &str) -> Vec<u8> {
+    base16::decode_lower(hex).expect("should decode hex")
+}
+
+#[ignore]
+#[test]
+fn should_hash_and_verify_signatures_on_host() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CRYPTO,
+        (
+            Vec::<u8>::new(),
+            decode(BLAKE2B_256),
+            decode(SHA256),
+            decode(KECCAK256),
+            decode(ED25519_PUBLIC_KEY),
+            decode(ED25519_MESSAGE),
+            decode(ED25519_SIGNATURE),
+        ),
+    )
+    .build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+}
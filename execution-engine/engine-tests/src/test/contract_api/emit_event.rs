@@ -0,0 +1,52 @@
+use contract_ffi::event::Event;
+use contract_ffi::value::U512;
+
+use crate::support::test_support::{ExecuteRequestBuilder, InMemoryWasmTestBuilder};
+use crate::test::{DEFAULT_ACCOUNT_ADDR, DEFAULT_GENESIS_CONFIG};
+
+const CONTRACT_EMIT_EVENT: &str = "emit_event.wasm";
+const MEMO: &str = "Hello, world!";
+
+#[ignore]
+#[test]
+fn should_return_emitted_events_in_order() {
+    let amount = U512::from(42);
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_EMIT_EVENT, (amount, false))
+            .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let events = builder.get_exec_events(0).expect("should have events");
+    let expected = vec![
+        Event::from_t("transfer", amount).expect("should create event"),
+        Event::from_t("memo", String::from(MEMO)).expect("should create event"),
+    ];
+    assert_eq!(events, expected);
+}
+
+#[ignore]
+#[test]
+fn should_discard_session_events_on_failure() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_EMIT_EVENT,
+        (U512::from(42), true),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .commit();
+
+    assert!(builder.is_error());
+    let events = builder.get_exec_events(0).expect("should have events");
+    assert!(events.is_empty());
+}
@@ -0,0 +1,129 @@
+use contract_ffi::bytesrepr::ToBytes;
+use contract_ffi::contract_api::Error;
+use contract_ffi::named_args::NamedArgs;
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::CLTyped;
+use engine_core::execution;
+
+use crate::support::test_support::{
+    DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+};
+use crate::test::{
+    CONTRACT_STANDARD_PAYMENT, DEFAULT_ACCOUNT_ADDR, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT,
+};
+
+const CONTRACT_ENTRY_POINTS_STORED: &str = "entry_points_stored.wasm";
+const CONTRACT_ENTRY_POINTS_CALLER: &str = "entry_points_caller.wasm";
+const CONTRACT_NAME: &str = "entry_points";
+const EXPECTED_NAME: &str = "Alice";
+
+fn named_args_with_name<T: CLTyped + ToBytes>(name: T) -> NamedArgs {
+    let mut named_args = NamedArgs::new();
+    named_args.insert("name", name).expect("should insert name");
+    named_args
+}
+
+fn install_contract() -> InMemoryWasmTestBuilder {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_ENTRY_POINTS_STORED, ())
+            .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+/// Calls `entry_point` of the installed contract and returns the error message if the call failed.
+fn call_entry_point(entry_point: &str, named_args: NamedArgs) -> Result<(), String> {
+    let mut builder = install_contract();
+
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_stored_session_entry_point(CONTRACT_NAME, entry_point, &named_args)
+        .with_payment_code(CONTRACT_STANDARD_PAYMENT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+        .with_deploy_hash([2; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    builder.exec(exec_request).commit();
+
+    if builder.is_error() {
+        Err(builder
+            .exec_error_message(1)
+            .expect("should have error message"))
+    } else {
+        Ok(())
+    }
+}
+
+#[ignore]
+#[test]
+fn should_call_entry_point_from_deploy() {
+    call_entry_point(
+        "check_name",
+        named_args_with_name(EXPECTED_NAME.to_string()),
+    )
+    .expect("should call check_name");
+}
+
+#[ignore]
+#[test]
+fn should_revert_from_entry_point() {
+    assert_eq!(
+        call_entry_point("check_name", named_args_with_name("Bob".to_string()))
+            .expect_err("should fail"),
+        format!("Exit code: {}", u32::from(Error::User(0)))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_call_missing_entry_point() {
+    assert_eq!(
+        call_entry_point("missing", NamedArgs::new()).expect_err("should fail"),
+        execution::Error::NoSuchEntryPoint("missing".to_string()).to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_call_entry_point_with_mistyped_argument() {
+    assert_eq!(
+        call_entry_point("check_name", named_args_with_name(42u64)).expect_err("should fail"),
+        execution::Error::InvalidEntryPointArgument("name".to_string()).to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_deny_user_account_calling_system_entry_point() {
+    assert_eq!(
+        call_entry_point("system_only", NamedArgs::new()).expect_err("should fail"),
+        execution::Error::EntryPointAccessDenied("system_only".to_string()).to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_call_entry_point_from_contract() {
+    let mut builder = install_contract();
+
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code_named_args(
+            CONTRACT_ENTRY_POINTS_CALLER,
+            &named_args_with_name(EXPECTED_NAME.to_string()),
+        )
+        .with_payment_code(CONTRACT_STANDARD_PAYMENT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+        .with_deploy_hash([2; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    builder.exec(exec_request).expect_success().commit();
+}
@@ -0,0 +1,94 @@
+use contract_ffi::contract_api::Error;
+use contract_ffi::named_args::NamedArgs;
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+
+use crate::support::test_support::{
+    self, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+};
+use crate::test::{
+    CONTRACT_STANDARD_PAYMENT, DEFAULT_ACCOUNT_ADDR, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT,
+};
+
+const CONTRACT_GET_NAMED_ARG: &str = "get_named_arg.wasm";
+const NAME_VALUE: &str = "Hello, world!";
+const AMOUNT_VALUE: u64 = 42;
+
+/// Calls get_named_arg contract and returns Ok(()) in case no error, or String which is the error
+/// message returned by the engine
+fn call_get_named_arg(named_args: NamedArgs) -> Result<(), String> {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code_named_args(CONTRACT_GET_NAMED_ARG, &named_args)
+        .with_payment_code(CONTRACT_STANDARD_PAYMENT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+        .with_deploy_hash([1; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    let result = InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .commit()
+        .finish();
+
+    if !result.builder().is_error() {
+        return Ok(());
+    }
+
+    let response = result
+        .builder()
+        .get_exec_response(0)
+        .expect("should have a response")
+        .to_owned();
+
+    let error_message = {
+        let execution_result = test_support::get_success_result(&response);
+        test_support::get_error_message(execution_result)
+    };
+
+    Err(error_message)
+}
+
+fn named_args(name: Option<&str>, amount: Option<U512>) -> NamedArgs {
+    let mut named_args = NamedArgs::new();
+    if let Some(name) = name {
+        named_args
+            .insert("name", String::from(name))
+            .expect("should insert name");
+    }
+    if let Some(amount) = amount {
+        named_args
+            .insert("amount", amount)
+            .expect("should insert amount");
+    }
+    named_args
+}
+
+#[ignore]
+#[test]
+fn should_use_named_arguments() {
+    call_get_named_arg(named_args(Some(NAME_VALUE), Some(U512::from(AMOUNT_VALUE))))
+        .expect("should successfully call get_named_arg with valid args");
+}
+
+#[ignore]
+#[test]
+fn should_revert_with_missing_named_arg() {
+    assert_eq!(
+        call_get_named_arg(named_args(Some(NAME_VALUE), None)).expect_err("should fail"),
+        format!("Exit code: {}", u32::from(Error::MissingArgument))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_revert_with_mistyped_named_arg() {
+    let mut args = named_args(Some(NAME_VALUE), None);
+    args.insert("amount", AMOUNT_VALUE)
+        .expect("should insert amount");
+    assert_eq!(
+        call_get_named_arg(args).expect_err("should fail"),
+        format!("Exit code: {}", u32::from(Error::InvalidArgument))
+    );
+}
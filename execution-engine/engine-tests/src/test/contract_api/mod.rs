@@ -1,12 +1,20 @@
 #[cfg(test)]
 mod create_purse;
 #[cfg(test)]
+mod crypto;
+#[cfg(test)]
+mod emit_event;
+#[cfg(test)]
+mod entry_points;
+#[cfg(test)]
 mod get_arg;
 #[cfg(test)]
 mod get_blocktime;
 #[cfg(test)]
 mod get_caller;
 #[cfg(test)]
+mod get_named_arg;
+#[cfg(test)]
 mod get_phase;
 #[cfg(test)]
 mod local_state;
@@ -15,6 +23,8 @@ mod main_purse;
 #[cfg(test)]
 mod mint_purse;
 #[cfg(test)]
+mod random_bytes;
+#[cfg(test)]
 mod revert;
 #[cfg(test)]
 mod transfer;
@@ -22,6 +32,8 @@ mod transfer;
 mod transfer_purse_to_account;
 #[cfg(test)]
 mod transfer_purse_to_purse;
+#[cfg(test)]
+mod vesting_purse;
 
 #[cfg(test)]
 pub mod account;
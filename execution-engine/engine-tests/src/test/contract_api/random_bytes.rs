@@ -0,0 +1,58 @@
+use std::collections::BTreeMap;
+
+use contract_ffi::key::Key;
+use contract_ffi::value::account::PublicKey;
+use engine_shared::transform::Transform;
+
+use crate::support::test_support::{
+    DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, STANDARD_PAYMENT_CONTRACT,
+};
+use crate::test::{DEFAULT_ACCOUNT_ADDR, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT};
+
+const CONTRACT_RANDOM_BYTES: &str = "random_bytes.wasm";
+
+fn do_pass(pass: &str) -> BTreeMap<String, Key> {
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+            .with_session_code(CONTRACT_RANDOM_BYTES, (pass.to_string(),))
+            .with_deploy_hash([1u8; 32])
+            .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+            .build();
+        ExecuteRequestBuilder::from_deploy_item(deploy).build()
+    };
+
+    let transforms = InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit()
+        .get_transforms();
+
+    match &transforms[0][&Key::Account(DEFAULT_ACCOUNT_ADDR)] {
+        Transform::AddKeys(keys) => keys.clone(),
+        other => panic!("expected AddKeys transform for account but got {:?}", other),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_not_perturb_uref_addresses() {
+    let baseline = do_pass("baseline");
+    let random = do_pass("random");
+
+    assert_eq!(baseline["uref1"], random["uref1"]);
+    assert_eq!(baseline["uref2"], random["uref2"]);
+}
+
+#[ignore]
+#[test]
+fn should_return_deterministic_bytes_per_call_depth() {
+    let first = do_pass("random");
+    let second = do_pass("random");
+
+    assert_eq!(first["random0"], second["random0"]);
+    assert_eq!(first["random1"], second["random1"]);
+    assert_ne!(first["random0"], first["random1"]);
+}
@@ -0,0 +1,47 @@
+use contract_ffi::value::account::PurseId;
+use contract_ffi::value::U512;
+
+use crate::support::test_support::{ExecuteRequestBuilder, InMemoryWasmTestBuilder};
+use crate::test::{DEFAULT_ACCOUNT_ADDR, DEFAULT_GENESIS_CONFIG};
+
+const CONTRACT_VESTING_PURSE: &str = "vesting_purse.wasm";
+const VESTING_PURSE_NAME: &str = "vesting_purse";
+
+#[ignore]
+#[test]
+fn should_only_transfer_unlocked_funds_from_vesting_purse() {
+    let exec_request_1 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_VESTING_PURSE,
+        (String::from("create"),),
+    )
+    .with_block_time(0)
+    .build();
+    let exec_request_2 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_VESTING_PURSE,
+        (String::from("withdraw"),),
+    )
+    .with_block_time(500)
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request_1)
+        .expect_success()
+        .commit();
+
+    let vesting_purse = {
+        let account = builder
+            .get_account(DEFAULT_ACCOUNT_ADDR)
+            .expect("should have account");
+        let key = account.named_keys()[VESTING_PURSE_NAME];
+        PurseId::new(*key.as_uref().expect("should be uref"))
+    };
+    assert_eq!(builder.get_purse_balance(vesting_purse), U512::from(1000));
+
+    builder.exec(exec_request_2).expect_success().commit();
+
+    assert_eq!(builder.get_purse_balance(vesting_purse), U512::from(500));
+}
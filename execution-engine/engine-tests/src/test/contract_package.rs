@@ -0,0 +1,134 @@
+use contract_ffi::contract_api::Error;
+use contract_ffi::named_args::NamedArgs;
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::{ContractPackageError, ContractVersion};
+use engine_core::execution;
+
+use crate::support::test_support::{
+    DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+};
+use crate::test::{
+    CONTRACT_STANDARD_PAYMENT, DEFAULT_ACCOUNT_ADDR, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT,
+};
+
+const CONTRACT_PACKAGE_V1: &str = "contract_package_v1.wasm";
+const CONTRACT_PACKAGE_V2: &str = "contract_package_v2.wasm";
+const CONTRACT_PACKAGE_CALLER: &str = "contract_package_caller.wasm";
+
+fn install_both_versions() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    for contract in &[CONTRACT_PACKAGE_V1, CONTRACT_PACKAGE_V2] {
+        let exec_request =
+            ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, contract, ()).build();
+        builder.exec(exec_request).expect_success().commit();
+    }
+
+    builder
+}
+
+/// Runs the caller contract and returns the error message if the deploy failed.
+fn exec_caller(
+    builder: &mut InMemoryWasmTestBuilder,
+    action: &str,
+    version: Option<ContractVersion>,
+    expected: Option<ContractVersion>,
+) -> Result<(), String> {
+    let mut named_args = NamedArgs::new();
+    named_args
+        .insert("action", action.to_string())
+        .expect("should insert action");
+    if let Some(version) = version {
+        named_args
+            .insert("version", version)
+            .expect("should insert version");
+    }
+    if let Some(expected) = expected {
+        named_args
+            .insert("expected", expected)
+            .expect("should insert expected");
+    }
+
+    // Each deploy gets its own fixed hash: the index of its response.
+    let index = builder.get_exec_responses_count();
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code_named_args(CONTRACT_PACKAGE_CALLER, &named_args)
+        .with_payment_code(CONTRACT_STANDARD_PAYMENT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+        .with_deploy_hash([index as u8; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    builder.exec(exec_request).commit();
+
+    if builder.is_error() {
+        Err(builder
+            .exec_error_message(index)
+            .expect("should have error message"))
+    } else {
+        Ok(())
+    }
+}
+
+fn exit_code(error: Error) -> String {
+    format!("Exit code: {}", u32::from(error))
+}
+
+#[ignore]
+#[test]
+fn should_call_pinned_and_latest_versions() {
+    let mut builder = install_both_versions();
+
+    exec_caller(&mut builder, "call", Some(1), Some(1)).expect("should call version 1");
+    exec_caller(&mut builder, "call", Some(2), Some(2)).expect("should call version 2");
+    exec_caller(&mut builder, "call_latest", None, Some(2)).expect("should call version 2");
+}
+
+#[ignore]
+#[test]
+fn should_roll_back_by_designating_previous_version() {
+    let mut builder = install_both_versions();
+
+    exec_caller(&mut builder, "set_latest", Some(1), None).expect("should designate version 1");
+    exec_caller(&mut builder, "call_latest", None, Some(1)).expect("should call version 1");
+
+    exec_caller(&mut builder, "disable", Some(2), None).expect("should disable version 2");
+    assert_eq!(
+        exec_caller(&mut builder, "call", Some(2), Some(2)),
+        Err(execution::Error::ContractPackage(ContractPackageError::DisabledVersion).to_string())
+    );
+    assert_eq!(
+        exec_caller(&mut builder, "set_latest", Some(2), None),
+        Err(exit_code(Error::DisabledContractVersion))
+    );
+
+    exec_caller(&mut builder, "enable", Some(2), None).expect("should enable version 2");
+    exec_caller(&mut builder, "call_latest", None, Some(1)).expect("should call version 1");
+    exec_caller(&mut builder, "set_latest", Some(2), None).expect("should designate version 2");
+    exec_caller(&mut builder, "call_latest", None, Some(2)).expect("should call version 2");
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_disable_latest_version() {
+    let mut builder = install_both_versions();
+
+    assert_eq!(
+        exec_caller(&mut builder, "disable", Some(2), None),
+        Err(exit_code(Error::LatestContractVersion))
+    );
+    exec_caller(&mut builder, "call_latest", None, Some(2)).expect("should call version 2");
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_disable_missing_version() {
+    let mut builder = install_both_versions();
+
+    assert_eq!(
+        exec_caller(&mut builder, "disable", Some(3), None),
+        Err(exit_code(Error::InvalidContractVersion))
+    );
+}
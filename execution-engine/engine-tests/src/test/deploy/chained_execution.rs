@@ -0,0 +1,107 @@
+use std::convert::TryInto;
+
+use grpc::RequestOptions;
+
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+use engine_grpc_server::engine_server::ipc::{ExecResult, ExecuteRequest};
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+use engine_grpc_server::engine_server::mappings::CommitTransforms;
+
+use crate::support::test_support::{
+    DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, STANDARD_PAYMENT_CONTRACT,
+};
+use crate::test::{DEFAULT_ACCOUNT_ADDR, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT};
+
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+
+fn create_account_then_use_it(chained: bool) -> ExecuteRequest {
+    let transfer = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_deploy_hash([1; 32])
+        .with_session_code(
+            CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+            (PublicKey::new(ACCOUNT_1_ADDR), *DEFAULT_PAYMENT * 10),
+        )
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+        .build();
+
+    let do_nothing = DeployItemBuilder::new()
+        .with_address(ACCOUNT_1_ADDR)
+        .with_deploy_hash([2; 32])
+        .with_session_code(CONTRACT_DO_NOTHING, ())
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[PublicKey::new(ACCOUNT_1_ADDR)])
+        .build();
+
+    let mut exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(transfer)
+        .push_deploy(do_nothing)
+        .build();
+    exec_request.set_chained(chained);
+    exec_request
+}
+
+fn execute(builder: &InMemoryWasmTestBuilder, mut exec_request: ExecuteRequest) -> ExecResult {
+    exec_request.set_parent_state_hash(builder.get_post_state_hash());
+    let exec_response = builder
+        .get_engine_state()
+        .execute(RequestOptions::new(), exec_request)
+        .wait_drop_metadata()
+        .expect("should exec");
+    assert!(exec_response.has_success());
+    exec_response.get_success().to_owned()
+}
+
+#[ignore]
+#[test]
+fn should_not_observe_previous_deploys_when_not_chained() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let exec_result = execute(&builder, create_account_then_use_it(false));
+    let deploy_results = exec_result.get_deploy_results();
+
+    assert!(!deploy_results[0].get_execution_result().has_error());
+    assert!(
+        deploy_results[1].has_precondition_failure(),
+        "account 1 should not exist in the prestate"
+    );
+    assert!(!exec_result.has_cumulative_effects());
+}
+
+#[ignore]
+#[test]
+fn should_observe_previous_deploys_when_chained() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let exec_result = execute(&builder, create_account_then_use_it(true));
+    let deploy_results = exec_result.get_deploy_results();
+
+    assert!(!deploy_results[0].get_execution_result().has_error());
+    assert!(
+        deploy_results[1].has_execution_result(),
+        "account 1 should have been created by the first deploy"
+    );
+    assert!(!deploy_results[1].get_execution_result().has_error());
+
+    let cumulative_effects: CommitTransforms = exec_result
+        .get_cumulative_effects()
+        .get_transform_map()
+        .try_into()
+        .expect("should convert");
+
+    let prestate_hash = builder.get_post_state_hash();
+    builder.commit_effects(prestate_hash, cumulative_effects.value());
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should get account 1");
+    let account_1_balance = builder.get_purse_balance(account_1.purse_id());
+    assert!(account_1_balance < *DEFAULT_PAYMENT * 10);
+    assert!(account_1_balance > U512::zero());
+}
@@ -1,6 +1,12 @@
 #[cfg(test)]
+mod chained_execution;
+#[cfg(test)]
+mod parallel_execution;
+#[cfg(test)]
 mod payment_code;
 #[cfg(test)]
 mod preconditions;
 #[cfg(test)]
+mod simulation;
+#[cfg(test)]
 mod stored_contracts;
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::convert::TryInto;
+
+use grpc::RequestOptions;
+use tempfile::TempDir;
+
+use contract_ffi::key::Key;
+use contract_ffi::value::account::PublicKey;
+use contract_ffi::value::U512;
+use engine_core::engine_state::EngineConfig;
+use engine_grpc_server::engine_server::ipc::{DeployResult, ExecuteRequest};
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+use engine_grpc_server::engine_server::mappings::CommitTransforms;
+use engine_shared::transform::Transform;
+
+use crate::support::test_support::{
+    DeployItemBuilder, ExecuteRequestBuilder, LmdbWasmTestBuilder, STANDARD_PAYMENT_CONTRACT,
+};
+use crate::test::{DEFAULT_ACCOUNT_ADDR, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT};
+
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const CONTRACT_REVERT: &str = "revert.wasm";
+const DEPLOY_WORKERS: usize = 4;
+const TRANSFER_COUNT: u8 = 7;
+
+fn transforms_of(deploy_result: &DeployResult) -> HashMap<Key, Transform> {
+    let commit_transforms: CommitTransforms = deploy_result
+        .get_execution_result()
+        .get_effects()
+        .get_transform_map()
+        .try_into()
+        .expect("should convert");
+    commit_transforms.value()
+}
+
+fn execute(engine_config: EngineConfig, exec_request: ExecuteRequest) -> Vec<DeployResult> {
+    let data_dir = TempDir::new().expect("should create temp dir");
+    let mut builder = LmdbWasmTestBuilder::new_with_config(&data_dir.path(), engine_config);
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let mut exec_request = exec_request;
+    exec_request.set_parent_state_hash(builder.get_post_state_hash());
+
+    let exec_response = builder
+        .get_engine_state()
+        .execute(RequestOptions::new(), exec_request)
+        .wait_drop_metadata()
+        .expect("should exec");
+    assert!(exec_response.has_success());
+
+    exec_response.get_success().get_deploy_results().to_vec()
+}
+
+#[ignore]
+#[test]
+fn should_execute_deploys_in_parallel_with_results_in_input_order() {
+    let exec_request = {
+        let mut builder = ExecuteRequestBuilder::new();
+        for i in 1..=TRANSFER_COUNT {
+            let deploy = DeployItemBuilder::new()
+                .with_address(DEFAULT_ACCOUNT_ADDR)
+                .with_deploy_hash([i; 32])
+                .with_session_code(
+                    CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+                    (PublicKey::new([i; 32]), U512::from(u64::from(i) * 1000)),
+                )
+                .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+                .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+                .build();
+            builder = builder.push_deploy(deploy);
+        }
+        let revert_deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_deploy_hash([0; 32])
+            .with_session_code(CONTRACT_REVERT, ())
+            .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+            .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+            .build();
+        builder.push_deploy(revert_deploy).build()
+    };
+
+    let sequential_config = EngineConfig::new().set_use_payment_code(true);
+    let parallel_config = EngineConfig::new()
+        .set_use_payment_code(true)
+        .set_deploy_workers(DEPLOY_WORKERS);
+
+    let sequential_results = execute(sequential_config, exec_request.clone());
+    let parallel_results = execute(parallel_config, exec_request);
+
+    assert_eq!(sequential_results.len(), usize::from(TRANSFER_COUNT) + 1);
+    assert_eq!(parallel_results.len(), sequential_results.len());
+
+    for (sequential, parallel) in sequential_results.iter().zip(parallel_results.iter()) {
+        assert_eq!(
+            sequential.get_execution_result().get_cost(),
+            parallel.get_execution_result().get_cost()
+        );
+        assert_eq!(
+            sequential.get_execution_result().has_error(),
+            parallel.get_execution_result().has_error()
+        );
+        assert_eq!(transforms_of(sequential), transforms_of(parallel));
+    }
+
+    let revert_result = parallel_results.last().unwrap().get_execution_result();
+    assert!(revert_result.has_error(), "revert deploy should be last");
+}
@@ -0,0 +1,126 @@
+use std::convert::TryFrom;
+
+use grpc::RequestOptions;
+
+use contract_ffi::value::account::{PublicKey, Weight};
+use contract_ffi::value::U512;
+use engine_core::engine_state::CONV_RATE;
+use engine_core::execution;
+use engine_grpc_server::engine_server::ipc::{DeployItem, SimulateRequest, SimulateResponse};
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+
+use crate::support::test_support::{
+    self, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+    STANDARD_PAYMENT_CONTRACT,
+};
+use crate::test::{DEFAULT_ACCOUNT_ADDR, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT};
+
+const CONTRACT_AUTHORIZED_KEYS: &str = "authorized_keys.wasm";
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+
+fn do_nothing_deploy() -> DeployItem {
+    DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_deploy_hash([1; 32])
+        .with_session_code(CONTRACT_DO_NOTHING, ())
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+        .build()
+}
+
+fn simulate(
+    builder: &InMemoryWasmTestBuilder,
+    deploy: DeployItem,
+    skip_authorization_thresholds: bool,
+) -> SimulateResponse {
+    let mut simulate_request = SimulateRequest::new();
+    simulate_request.set_parent_state_hash(builder.get_post_state_hash());
+    simulate_request.set_deploy(deploy);
+    simulate_request.set_protocol_version(test_support::get_protocol_version());
+    simulate_request.set_skip_authorization_thresholds(skip_authorization_thresholds);
+
+    ExecutionEngineService::simulate(
+        builder.get_engine_state(),
+        RequestOptions::new(),
+        simulate_request,
+    )
+    .wait_drop_metadata()
+    .expect("should simulate")
+}
+
+#[ignore]
+#[test]
+fn should_simulate_deploy_without_committing() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+    let prestate_hash = builder.get_post_state_hash();
+
+    let simulate_response = simulate(&builder, do_nothing_deploy(), false);
+    assert!(simulate_response.has_success());
+    let simulate_result = simulate_response.get_success();
+    assert!(!simulate_result
+        .get_deploy_result()
+        .get_execution_result()
+        .has_error());
+    assert_eq!(builder.get_post_state_hash(), prestate_hash);
+
+    let payment_cost =
+        U512::try_from(simulate_result.get_payment_cost()).expect("should parse payment cost");
+    let session_cost =
+        U512::try_from(simulate_result.get_session_cost()).expect("should parse session cost");
+    let suggested_payment = U512::try_from(simulate_result.get_suggested_payment())
+        .expect("should parse suggested payment");
+    assert!(payment_cost > U512::zero());
+    assert!(session_cost > U512::zero());
+    assert_eq!(
+        suggested_payment,
+        (payment_cost + session_cost) * U512::from(CONV_RATE)
+    );
+
+    // Executing the deploy for real costs exactly as much as the simulation reported.
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(do_nothing_deploy()).build();
+    builder.exec(exec_request).expect_success();
+    let exec_cost = U512::try_from(
+        builder
+            .get_exec_response(0)
+            .expect("should have exec response")
+            .get_success()
+            .get_deploy_results()[0]
+            .get_execution_result()
+            .get_cost(),
+    )
+    .expect("should parse cost");
+    assert_eq!(exec_cost, payment_cost + session_cost);
+}
+
+#[ignore]
+#[test]
+fn should_simulate_deploy_below_deploy_threshold_only_when_skipping_thresholds() {
+    // Raises the deploy threshold to 3, above the weight of the identity key.
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_AUTHORIZED_KEYS,
+        (Weight::new(4), Weight::new(3)),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let simulate_response = simulate(&builder, do_nothing_deploy(), false);
+    let deploy_result = simulate_response.get_success().get_deploy_result();
+    assert!(deploy_result.has_precondition_failure());
+    assert!(deploy_result
+        .get_precondition_failure()
+        .get_message()
+        .contains(&execution::Error::DeploymentAuthorizationFailure.to_string()));
+
+    let simulate_response = simulate(&builder, do_nothing_deploy(), true);
+    let deploy_result = simulate_response.get_success().get_deploy_result();
+    assert!(deploy_result.has_execution_result());
+    assert!(!deploy_result.get_execution_result().has_error());
+}
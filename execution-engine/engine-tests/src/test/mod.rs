@@ -1,4 +1,6 @@
 #[cfg(test)]
+mod contract_package;
+#[cfg(test)]
 mod metrics;
 #[cfg(test)]
 mod upgrade;
@@ -15,6 +17,7 @@ pub mod system_contracts;
 use lazy_static::lazy_static;
 use num_traits::identities::Zero;
 
+use contract_ffi::system_contracts::pos::RewardsConfig;
 use contract_ffi::value::account::PublicKey;
 use contract_ffi::value::{ProtocolVersion, U512};
 use engine_core::engine_state::genesis::{GenesisAccount, GenesisConfig};
@@ -59,6 +62,7 @@ lazy_static! {
             pos_installer_bytes,
             DEFAULT_ACCOUNTS.clone(),
             *DEFAULT_WASM_COSTS,
+            RewardsConfig::default(),
         )
     };
 }
@@ -1,4 +1,5 @@
 use contract_ffi::key::Key;
+use contract_ffi::system_contracts::pos::RewardsConfig;
 use contract_ffi::value::account::PublicKey;
 use contract_ffi::value::{ProtocolVersion, Value, U512};
 use engine_core::engine_state::genesis::{GenesisAccount, GenesisConfig};
@@ -62,6 +63,7 @@ fn should_run_genesis_with_chainspec() {
         pos_installer_bytes,
         accounts,
         wasm_costs,
+        RewardsConfig::default(),
     );
 
     let mut builder = InMemoryWasmTestBuilder::default();
@@ -144,6 +146,7 @@ fn should_fail_if_bad_mint_install_contract_is_provided() {
             pos_installer_bytes,
             accounts,
             wasm_costs,
+            RewardsConfig::default(),
         )
     };
 
@@ -192,6 +195,7 @@ fn should_fail_if_bad_pos_install_contract_is_provided() {
             pos_installer_bytes,
             accounts,
             wasm_costs,
+            RewardsConfig::default(),
         )
     };
 
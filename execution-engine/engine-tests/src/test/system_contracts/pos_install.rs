@@ -16,12 +16,14 @@ const SYSTEM_ADDR: [u8; 32] = [0u8; 32];
 const DEPLOY_HASH_2: [u8; 32] = [2u8; 32];
 const N_VALIDATORS: u8 = 5;
 
-// one named_key for each validator and three for the purses
-const EXPECTED_KNOWN_KEYS_LEN: usize = (N_VALIDATORS as usize) + 3;
+// one named_key for each validator and five for the purses
+const EXPECTED_KNOWN_KEYS_LEN: usize = (N_VALIDATORS as usize) + 5;
 
 const POS_BONDING_PURSE: &str = "pos_bonding_purse";
 const POS_PAYMENT_PURSE: &str = "pos_payment_purse";
 const POS_REWARDS_PURSE: &str = "pos_rewards_purse";
+const POS_BURN_PURSE: &str = "pos_burn_purse";
+const POS_TREASURY_PURSE: &str = "pos_treasury_purse";
 
 #[ignore]
 #[test]
@@ -96,6 +98,12 @@ fn should_run_pos_install_contract() {
 
     let rewards_purse_balance = builder.get_purse_balance(rewards_purse);
     assert_eq!(rewards_purse_balance, U512::zero());
+
+    // burn and treasury purses are empty
+    for purse_name in &[POS_BURN_PURSE, POS_TREASURY_PURSE] {
+        let purse = get_purse(named_keys, purse_name).expect("should find purse in named_keys");
+        assert_eq!(builder.get_purse_balance(purse), U512::zero());
+    }
 }
 
 fn get_purse(named_keys: &BTreeMap<String, Key>, name: &str) -> Option<PurseId> {
@@ -0,0 +1,145 @@
+use contract_ffi::key::Key;
+use contract_ffi::system_contracts::pos::DEFAULT_DISTRIBUTION_INTERVAL;
+use contract_ffi::value::account::{PublicKey, PurseId};
+use contract_ffi::value::U512;
+
+use engine_core::engine_state::genesis::{GenesisAccount, POS_BONDING_PURSE, POS_REWARDS_PURSE};
+use engine_core::engine_state::CONV_RATE;
+use engine_shared::motes::Motes;
+
+use crate::support::test_support::{
+    self, DistributeRewardsRequestBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+};
+use crate::test::{DEFAULT_ACCOUNTS, DEFAULT_ACCOUNT_ADDR};
+
+const CONTRACT_POS_DELEGATION: &str = "pos_delegation.wasm";
+const VALIDATOR_ADDR: [u8; 32] = [42u8; 32];
+const VALIDATOR_STAKE: u64 = 50_000;
+const DELEGATED_AMOUNT: u64 = 50_000;
+
+const TEST_DELEGATE: &str = "delegate";
+const TEST_UNDELEGATE: &str = "undelegate";
+const TEST_DISTRIBUTE_REWARDS: &str = "distribute_rewards";
+
+fn get_pos_purse_balance(builder: &InMemoryWasmTestBuilder, purse_name: &str) -> U512 {
+    let purse_id = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(purse_name)
+        .and_then(Key::as_uref)
+        .map(|u| PurseId::new(*u))
+        .expect("should find PoS purse");
+    builder.get_purse_balance(purse_id)
+}
+
+fn get_account_balance(builder: &InMemoryWasmTestBuilder, addr: [u8; 32]) -> U512 {
+    let account = builder.get_account(addr).expect("should have account");
+    builder.get_purse_balance(account.purse_id())
+}
+
+#[ignore]
+#[test]
+fn should_delegate_share_rewards_and_undelegate() {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        let account = GenesisAccount::new(
+            PublicKey::new(VALIDATOR_ADDR),
+            Motes::new(VALIDATOR_STAKE.into()) * Motes::new(2.into()),
+            Motes::new(VALIDATOR_STAKE.into()),
+        );
+        tmp.push(account);
+        tmp
+    };
+    let genesis_config = test_support::create_genesis_config(accounts);
+
+    let delegate_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_DELEGATION,
+        (
+            String::from(TEST_DELEGATE),
+            PublicKey::new(VALIDATOR_ADDR),
+            U512::from(DELEGATED_AMOUNT),
+        ),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&genesis_config)
+        .exec(delegate_request)
+        .expect_success()
+        .commit();
+
+    // The delegated motes are held in the bonding purse next to the validator's own bond
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_BONDING_PURSE),
+        U512::from(VALIDATOR_STAKE + DELEGATED_AMOUNT)
+    );
+
+    // Rewards are only distributed by the system, once per block
+    let user_distribute_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_DELEGATION,
+        (String::from(TEST_DISTRIBUTE_REWARDS),),
+    )
+    .build();
+    assert!(builder.exec(user_distribute_request).commit().is_error());
+
+    let rewards = get_pos_purse_balance(&builder, POS_REWARDS_PURSE);
+    assert!(!rewards.is_zero());
+    let delegator_balance_before = get_account_balance(&builder, DEFAULT_ACCOUNT_ADDR);
+    let validator_balance_before = get_account_balance(&builder, VALIDATOR_ADDR);
+
+    let mut distribute_rewards_request = DistributeRewardsRequestBuilder::new()
+        .with_block_time(DEFAULT_DISTRIBUTION_INTERVAL.0)
+        .build();
+    builder.distribute_rewards_with_request(&mut distribute_rewards_request);
+    assert!(builder
+        .get_distribute_rewards_response(0)
+        .expect("should have distribute rewards response")
+        .has_success());
+
+    // The validator's own bond and the delegation are equal, so the rewards are split in half
+    let share = rewards * VALIDATOR_STAKE / (VALIDATOR_STAKE + DELEGATED_AMOUNT);
+    assert_eq!(
+        get_account_balance(&builder, DEFAULT_ACCOUNT_ADDR),
+        delegator_balance_before + share
+    );
+    assert_eq!(
+        get_account_balance(&builder, VALIDATOR_ADDR),
+        validator_balance_before + share
+    );
+
+    let undelegate_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_DELEGATION,
+        (
+            String::from(TEST_UNDELEGATE),
+            PublicKey::new(VALIDATOR_ADDR),
+            None::<U512>,
+        ),
+    )
+    .build();
+    let delegator_balance_before = get_account_balance(&builder, DEFAULT_ACCOUNT_ADDR);
+    builder
+        .exec(undelegate_request.clone())
+        .expect_success()
+        .commit();
+
+    let exec_response = builder
+        .get_exec_response(2)
+        .expect("should have exec response");
+    let gas_cost = Motes::from_gas(test_support::get_exec_costs(&exec_response)[0], CONV_RATE)
+        .expect("should convert");
+    assert_eq!(
+        get_account_balance(&builder, DEFAULT_ACCOUNT_ADDR),
+        delegator_balance_before - gas_cost.value() + DELEGATED_AMOUNT
+    );
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_BONDING_PURSE),
+        U512::from(VALIDATOR_STAKE)
+    );
+
+    // Nothing is left to undelegate
+    assert!(builder.exec(undelegate_request).commit().is_error());
+}
@@ -3,8 +3,14 @@ mod bonding;
 #[cfg(test)]
 mod commit_validators;
 #[cfg(test)]
+mod delegation;
+#[cfg(test)]
 mod finalize_payment;
 #[cfg(test)]
 mod get_payment_purse;
 #[cfg(test)]
 mod refund_purse;
+#[cfg(test)]
+mod rewards_distribution;
+#[cfg(test)]
+mod slashing;
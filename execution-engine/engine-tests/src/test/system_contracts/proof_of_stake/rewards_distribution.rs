@@ -0,0 +1,189 @@
+use contract_ffi::key::Key;
+use contract_ffi::system_contracts::pos::RewardsConfig;
+use contract_ffi::value::account::{BlockTime, PublicKey, PurseId};
+use contract_ffi::value::U512;
+
+use engine_core::engine_state::genesis::{
+    GenesisAccount, GenesisConfig, POS_BURN_PURSE, POS_REWARDS_PURSE, POS_TREASURY_PURSE,
+};
+use engine_core::engine_state::CONV_RATE;
+use engine_shared::motes::Motes;
+
+use crate::support::test_support::{
+    self, DistributeRewardsRequestBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+};
+use crate::test::{
+    CONTRACT_MINT_INSTALL, CONTRACT_POS_INSTALL, DEFAULT_ACCOUNTS, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_CHAIN_NAME, DEFAULT_GENESIS_TIMESTAMP, DEFAULT_PROTOCOL_VERSION, DEFAULT_WASM_COSTS,
+};
+
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const VALIDATOR_ADDR: [u8; 32] = [42u8; 32];
+const VALIDATOR_STAKE: u64 = 50_000;
+const DISTRIBUTION_INTERVAL: u64 = 1000;
+
+fn genesis_config(rewards_config: RewardsConfig) -> GenesisConfig {
+    let mut accounts: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+    accounts.push(GenesisAccount::new(
+        PublicKey::new(VALIDATOR_ADDR),
+        Motes::new(VALIDATOR_STAKE.into()) * Motes::new(2.into()),
+        Motes::new(VALIDATOR_STAKE.into()),
+    ));
+    GenesisConfig::new(
+        DEFAULT_CHAIN_NAME.to_string(),
+        DEFAULT_GENESIS_TIMESTAMP,
+        *DEFAULT_PROTOCOL_VERSION,
+        test_support::read_wasm_file_bytes(CONTRACT_MINT_INSTALL),
+        test_support::read_wasm_file_bytes(CONTRACT_POS_INSTALL),
+        accounts,
+        *DEFAULT_WASM_COSTS,
+        rewards_config,
+    )
+}
+
+fn get_pos_purse_balance(builder: &InMemoryWasmTestBuilder, purse_name: &str) -> U512 {
+    let purse_id = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(purse_name)
+        .and_then(Key::as_uref)
+        .map(|u| PurseId::new(*u))
+        .expect("should find PoS purse");
+    builder.get_purse_balance(purse_id)
+}
+
+fn get_validator_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let account = builder
+        .get_account(VALIDATOR_ADDR)
+        .expect("should have validator account");
+    builder.get_purse_balance(account.purse_id())
+}
+
+/// Runs a deploy at `block_time` and returns how much it cost.
+fn exec_transfer(builder: &mut InMemoryWasmTestBuilder, block_time: u64) -> U512 {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, U512::from(1)),
+    )
+    .with_block_time(block_time)
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let exec_response = builder
+        .get_exec_response(builder.get_exec_responses_count() - 1)
+        .expect("should have exec response");
+    Motes::from_gas(test_support::get_exec_costs(&exec_response)[0], CONV_RATE)
+        .expect("should convert")
+        .value()
+}
+
+/// Runs the per-block rewards distribution at `block_time`.
+fn distribute_rewards(builder: &mut InMemoryWasmTestBuilder, block_time: u64) {
+    let mut distribute_rewards_request = DistributeRewardsRequestBuilder::new()
+        .with_block_time(block_time)
+        .build();
+    builder.distribute_rewards_with_request(&mut distribute_rewards_request);
+
+    let distribute_rewards_response = builder
+        .get_distribute_rewards_response(builder.get_distribute_rewards_responses_count() - 1)
+        .expect("should have distribute rewards response");
+    assert!(distribute_rewards_response.has_success());
+}
+
+#[ignore]
+#[test]
+fn should_split_payment_and_distribute_rewards_on_interval() {
+    let rewards_config =
+        RewardsConfig::new(BlockTime(DISTRIBUTION_INTERVAL), 500_000, 300_000, 200_000)
+            .expect("should create rewards config");
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&genesis_config(rewards_config));
+    let validator_balance = get_validator_balance(&builder);
+
+    // The payment is split, but the interval since genesis hasn't passed yet
+    let (commission_1, burn_1, treasury_1) = rewards_config.split(exec_transfer(&mut builder, 0));
+    distribute_rewards(&mut builder, 0);
+    assert!(!burn_1.is_zero() && !treasury_1.is_zero());
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_REWARDS_PURSE),
+        commission_1
+    );
+    assert_eq!(get_pos_purse_balance(&builder, POS_BURN_PURSE), burn_1);
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_TREASURY_PURSE),
+        treasury_1
+    );
+    assert_eq!(get_validator_balance(&builder), validator_balance);
+
+    // Deploys don't pay out rewards, even once the interval has passed
+    let (commission_2, burn_2, treasury_2) =
+        rewards_config.split(exec_transfer(&mut builder, DISTRIBUTION_INTERVAL));
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_REWARDS_PURSE),
+        commission_1 + commission_2
+    );
+    assert_eq!(get_validator_balance(&builder), validator_balance);
+
+    // The only bonded validator gets all of the collected rewards
+    distribute_rewards(&mut builder, DISTRIBUTION_INTERVAL);
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_REWARDS_PURSE),
+        U512::zero()
+    );
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_BURN_PURSE),
+        burn_1 + burn_2
+    );
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_TREASURY_PURSE),
+        treasury_1 + treasury_2
+    );
+    assert_eq!(
+        get_validator_balance(&builder),
+        validator_balance + commission_1 + commission_2
+    );
+
+    // The next distribution is only due once the interval has passed again
+    let (commission_3, _, _) =
+        rewards_config.split(exec_transfer(&mut builder, DISTRIBUTION_INTERVAL * 3 / 2));
+    distribute_rewards(&mut builder, DISTRIBUTION_INTERVAL * 3 / 2);
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_REWARDS_PURSE),
+        commission_3
+    );
+    assert_eq!(
+        get_validator_balance(&builder),
+        validator_balance + commission_1 + commission_2
+    );
+
+    distribute_rewards(&mut builder, DISTRIBUTION_INTERVAL * 2);
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_REWARDS_PURSE),
+        U512::zero()
+    );
+    assert_eq!(
+        get_validator_balance(&builder),
+        validator_balance + commission_1 + commission_2 + commission_3
+    );
+}
+
+#[ignore]
+#[test]
+fn should_pay_everything_to_validators_by_default() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&genesis_config(RewardsConfig::default()));
+
+    let cost = exec_transfer(&mut builder, 0);
+    assert_eq!(get_pos_purse_balance(&builder, POS_REWARDS_PURSE), cost);
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_BURN_PURSE),
+        U512::zero()
+    );
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_TREASURY_PURSE),
+        U512::zero()
+    );
+}
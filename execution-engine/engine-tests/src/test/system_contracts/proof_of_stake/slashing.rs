@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use contract_ffi::key::Key;
+use contract_ffi::system_contracts::pos::SlashDestination;
+use contract_ffi::value::account::{PublicKey, PurseId};
+use contract_ffi::value::U512;
+
+use engine_core::engine_state::genesis::{
+    GenesisAccount, GenesisConfig, POS_BONDING_PURSE, POS_BURN_PURSE, POS_TREASURY_PURSE,
+};
+use engine_shared::motes::Motes;
+
+use crate::support::test_support::{
+    self, ExecuteRequestBuilder, InMemoryWasmTestBuilder, SlashRequestBuilder,
+};
+use crate::test::{DEFAULT_ACCOUNTS, DEFAULT_ACCOUNT_ADDR};
+
+const VALIDATOR_1_ADDR: [u8; 32] = [42u8; 32];
+const VALIDATOR_1_STAKE: u64 = 50_000;
+const VALIDATOR_2_ADDR: [u8; 32] = [43u8; 32];
+const VALIDATOR_2_STAKE: u64 = 100_000;
+const DELEGATED_AMOUNT: u64 = 20_000;
+
+const CONTRACT_POS_DELEGATION: &str = "pos_delegation.wasm";
+const TEST_DELEGATE: &str = "delegate";
+
+fn get_pos_purse_balance(builder: &InMemoryWasmTestBuilder, purse_name: &str) -> U512 {
+    let purse_id = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(purse_name)
+        .and_then(Key::as_uref)
+        .map(|u| PurseId::new(*u))
+        .expect("should find PoS purse");
+    builder.get_purse_balance(purse_id)
+}
+
+fn genesis_config() -> GenesisConfig {
+    let mut accounts: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+    for &(addr, stake) in &[
+        (VALIDATOR_1_ADDR, VALIDATOR_1_STAKE),
+        (VALIDATOR_2_ADDR, VALIDATOR_2_STAKE),
+    ] {
+        accounts.push(GenesisAccount::new(
+            PublicKey::new(addr),
+            Motes::new(stake.into()),
+            Motes::new(stake.into()),
+        ));
+    }
+    test_support::create_genesis_config(accounts)
+}
+
+fn get_latest_bonded_validators(builder: &InMemoryWasmTestBuilder) -> HashMap<PublicKey, U512> {
+    builder
+        .get_bonded_validators()
+        .last()
+        .cloned()
+        .expect("should have bonded validators")
+}
+
+#[ignore]
+#[test]
+fn should_slash_validators() {
+    let genesis_config = genesis_config();
+    let validator_1 = PublicKey::new(VALIDATOR_1_ADDR);
+    let validator_2 = PublicKey::new(VALIDATOR_2_ADDR);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&genesis_config);
+
+    // A quarter of the first validator's stake is moved to the treasury
+    let mut slash_request = SlashRequestBuilder::new()
+        .with_validators(vec![validator_1])
+        .with_fraction(250_000)
+        .with_destination(SlashDestination::Treasury)
+        .build();
+    builder.slash_with_slash_request(&mut slash_request);
+    assert!(builder
+        .get_slash_response(0)
+        .expect("should have slash response")
+        .has_success());
+
+    let bonded_validators = get_latest_bonded_validators(&builder);
+    assert_eq!(
+        bonded_validators.get(&validator_1),
+        Some(&U512::from(VALIDATOR_1_STAKE * 3 / 4))
+    );
+    assert_eq!(
+        bonded_validators.get(&validator_2),
+        Some(&U512::from(VALIDATOR_2_STAKE))
+    );
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_TREASURY_PURSE),
+        U512::from(VALIDATOR_1_STAKE / 4)
+    );
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_BONDING_PURSE),
+        U512::from(VALIDATOR_1_STAKE * 3 / 4 + VALIDATOR_2_STAKE)
+    );
+
+    // Slashing every validator completely is rejected
+    let mut slash_request = SlashRequestBuilder::new()
+        .with_validators(vec![validator_1, validator_2])
+        .with_fraction(1_000_000)
+        .build();
+    builder.slash_with_slash_request(&mut slash_request);
+    assert!(builder
+        .get_slash_response(1)
+        .expect("should have slash response")
+        .has_failed_slash());
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_BURN_PURSE),
+        U512::zero()
+    );
+
+    // Slashing the rest of the first validator's stake unbonds them
+    let mut slash_request = SlashRequestBuilder::new()
+        .with_validators(vec![validator_1])
+        .with_fraction(1_000_000)
+        .with_destination(SlashDestination::Burn)
+        .build();
+    builder.slash_with_slash_request(&mut slash_request);
+    assert!(builder
+        .get_slash_response(2)
+        .expect("should have slash response")
+        .has_success());
+
+    let bonded_validators = get_latest_bonded_validators(&builder);
+    assert_eq!(bonded_validators.get(&validator_1), None);
+    assert_eq!(
+        bonded_validators.get(&validator_2),
+        Some(&U512::from(VALIDATOR_2_STAKE))
+    );
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_BURN_PURSE),
+        U512::from(VALIDATOR_1_STAKE * 3 / 4)
+    );
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_BONDING_PURSE),
+        U512::from(VALIDATOR_2_STAKE)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_slash_delegations() {
+    let validator_1 = PublicKey::new(VALIDATOR_1_ADDR);
+
+    let delegate_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_DELEGATION,
+        (
+            String::from(TEST_DELEGATE),
+            validator_1,
+            U512::from(DELEGATED_AMOUNT),
+        ),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&genesis_config())
+        .exec(delegate_request)
+        .expect_success()
+        .commit();
+
+    // The motes delegated to the first validator are slashed along with their stake
+    let mut slash_request = SlashRequestBuilder::new()
+        .with_validators(vec![validator_1])
+        .with_fraction(250_000)
+        .with_destination(SlashDestination::Treasury)
+        .build();
+    builder.slash_with_slash_request(&mut slash_request);
+    assert!(builder
+        .get_slash_response(0)
+        .expect("should have slash response")
+        .has_success());
+
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_TREASURY_PURSE),
+        U512::from((VALIDATOR_1_STAKE + DELEGATED_AMOUNT) / 4)
+    );
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_BONDING_PURSE),
+        U512::from((VALIDATOR_1_STAKE + DELEGATED_AMOUNT) * 3 / 4 + VALIDATOR_2_STAKE)
+    );
+}
@@ -28,6 +28,10 @@ fn get_upgraded_wasm_costs() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 3,
         opcodes_div: 8,
+        hash: 100,
+        hash_byte: 1,
+        verify_signature: 10_000,
+        verify_signature_byte: 1,
     }
 }
 
@@ -100,6 +104,15 @@ fn should_upgrade_system_contract() {
         "upgrade_response expected success"
     );
 
+    let mint_package = builder
+        .get_contract_package(builder.get_mint_contract_uref())
+        .expect("mint should be a contract package");
+    assert_eq!(
+        mint_package.latest_version(),
+        Some(2),
+        "modified mint should be the latest version"
+    );
+
     let exec_request = {
         ExecuteRequestBuilder::standard(
             DEFAULT_ACCOUNT_ADDR,
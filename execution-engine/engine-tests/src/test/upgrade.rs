@@ -84,15 +84,21 @@ fn should_upgrade_do_nothing_to_do_something() {
         builder.exec(exec_request).expect_success().commit();
     }
 
-    let contract = builder
-        .get_contract(*do_nothing_stored_uref)
-        .expect("should have contract");
+    let package = builder
+        .get_contract_package(*do_nothing_stored_uref)
+        .expect("should have contract package");
+
+    assert_eq!(
+        package.latest_version(),
+        Some(2),
+        "upgrade should add and designate version 2"
+    );
 
     // currently as the system is designed the new uref for the purse is added to the
-    // caller contract instead of the account...ideally the account would get the uref
+    // called package instead of the account...ideally the account would get the uref
     // but that's beyond the scope of this upgrade specific test
     assert!(
-        contract.named_keys().contains_key(PURSE_1),
+        package.named_keys().contains_key(PURSE_1),
         "should have new purse uref"
     );
 }
@@ -226,12 +232,12 @@ fn should_support_extending_functionality() {
     }
 
     // verify known uref actually exists prior to upgrade
-    let contract = builder
-        .get_contract(*stored_uref)
-        .expect("should have contract");
+    let package = builder
+        .get_contract_package(*stored_uref)
+        .expect("should have contract package");
     assert!(
-        contract.named_keys().contains_key(PURSE_1),
-        "purse uref should exist in contract's named_keys before upgrade"
+        package.named_keys().contains_key(PURSE_1),
+        "purse uref should exist in package's named_keys before upgrade"
     );
 
     // upgrade contract
@@ -246,13 +252,13 @@ fn should_support_extending_functionality() {
     }
 
     // verify uref still exists in named_keys after upgrade:
-    let contract = builder
-        .get_contract(*stored_uref)
-        .expect("should have contract");
+    let package = builder
+        .get_contract_package(*stored_uref)
+        .expect("should have contract package");
 
     assert!(
-        contract.named_keys().contains_key(PURSE_1),
-        "PURSE_1 uref should still exist in contract's named_keys after upgrade"
+        package.named_keys().contains_key(PURSE_1),
+        "PURSE_1 uref should still exist in package's named_keys after upgrade"
     );
 
     // call new remove function
@@ -271,13 +277,13 @@ fn should_support_extending_functionality() {
     }
 
     // verify known urefs no longer include removed purse
-    let contract = builder
-        .get_contract(*stored_uref)
-        .expect("should have contract");
+    let package = builder
+        .get_contract_package(*stored_uref)
+        .expect("should have contract package");
 
     assert!(
-        !contract.named_keys().contains_key(PURSE_1),
-        "PURSE_1 uref should no longer exist in contract's named_keys after remove"
+        !package.named_keys().contains_key(PURSE_1),
+        "PURSE_1 uref should no longer exist in package's named_keys after remove"
     );
 }
 
@@ -326,12 +332,12 @@ fn should_maintain_named_keys_across_upgrade() {
         builder.exec(exec_request).expect_success().commit();
 
         // verify known uref actually exists prior to upgrade
-        let contract = builder
-            .get_contract(*stored_uref)
-            .expect("should have contract");
+        let package = builder
+            .get_contract_package(*stored_uref)
+            .expect("should have contract package");
         assert!(
-            contract.named_keys().contains_key(purse_name),
-            "purse uref should exist in contract's named_keys before upgrade"
+            package.named_keys().contains_key(purse_name),
+            "purse uref should exist in package's named_keys before upgrade"
         );
     }
 
@@ -347,16 +353,16 @@ fn should_maintain_named_keys_across_upgrade() {
     }
 
     // verify all urefs still exist in named_keys after upgrade
-    let contract = builder
-        .get_contract(*stored_uref)
-        .expect("should have contract");
+    let package = builder
+        .get_contract_package(*stored_uref)
+        .expect("should have contract package");
 
     for index in 0..TOTAL_PURSES {
         let purse_name: &str = &format!("purse_{}", index);
         assert!(
-            contract.named_keys().contains_key(purse_name),
+            package.named_keys().contains_key(purse_name),
             format!(
-                "{} uref should still exist in contract's named_keys after upgrade",
+                "{} uref should still exist in package's named_keys after upgrade",
                 index
             )
         );
@@ -1,8 +1,27 @@
-use contract_ffi::bytesrepr;
-use contract_ffi::bytesrepr::{FromBytes, ToBytes, U32_SIZE};
+use contract_ffi::bytesrepr::{self, FromBytes, ToBytes, Write, U32_SIZE, U8_SIZE};
 
-const NUM_FIELDS: usize = 10;
-pub const WASM_COSTS_SIZE_SERIALIZED: usize = NUM_FIELDS * U32_SIZE;
+const NUM_FIELDS: usize = 14;
+pub const WASM_COSTS_SIZE_SERIALIZED: usize = U32_SIZE + U8_SIZE + NUM_FIELDS * U32_SIZE;
+
+/// Written in place of the `regular` cost of the legacy layout to mark a versioned layout.
+///
+/// Costs are always written in a versioned layout, in which any `regular` cost can be stored.  A
+/// `regular` cost of `u32::max_value()` can't have been stored in the legacy layout, though, as it
+/// would be read back as this marker.
+const VERSIONED_LAYOUT_MARKER: u32 = u32::max_value();
+
+/// The layout with the costs of the hash and signature verification host functions.
+const CRYPTO_COSTS_LAYOUT_VERSION: u8 = 1;
+
+/// Cost of a call to the hash host function, used when no costs are configured for it.
+pub const DEFAULT_HASH_COST: u32 = 10_000;
+/// Hashing cost per input byte, used when no costs are configured for it.
+pub const DEFAULT_HASH_BYTE_COST: u32 = 10;
+/// Cost of a call to the signature verification host function, used when no costs are configured
+/// for it.
+pub const DEFAULT_VERIFY_SIGNATURE_COST: u32 = 100_000;
+/// Signature verification cost per message byte, used when no costs are configured for it.
+pub const DEFAULT_VERIFY_SIGNATURE_BYTE_COST: u32 = 10;
 
 // Taken (partially) from parity-ethereum
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
@@ -30,28 +49,75 @@ pub struct WasmCosts {
     /// Cost of wasm opcode is calculated as TABLE_ENTRY_COST * `opcodes_mul` /
     /// `opcodes_div`
     pub opcodes_div: u32,
+    /// Fixed cost of a call to the hash host function
+    pub hash: u32,
+    /// Hashing cost, per input byte
+    pub hash_byte: u32,
+    /// Fixed cost of each signature verification, whatever the size of the message
+    pub verify_signature: u32,
+    /// Signature verification cost, per byte of public key, message and signature
+    pub verify_signature_byte: u32,
+}
+
+impl WasmCosts {
+    /// Sets the costs of the hash and signature verification host functions to their defaults.
+    ///
+    /// This is for cost tables which predate these host functions, so that they don't make them
+    /// free.
+    pub fn with_default_crypto_costs(self) -> Self {
+        WasmCosts {
+            hash: DEFAULT_HASH_COST,
+            hash_byte: DEFAULT_HASH_BYTE_COST,
+            verify_signature: DEFAULT_VERIFY_SIGNATURE_COST,
+            verify_signature_byte: DEFAULT_VERIFY_SIGNATURE_BYTE_COST,
+            ..self
+        }
+    }
 }
 
 impl ToBytes for WasmCosts {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
-        let mut ret: Vec<u8> = Vec::with_capacity(WASM_COSTS_SIZE_SERIALIZED);
-        ret.append(&mut self.regular.to_bytes()?);
-        ret.append(&mut self.div.to_bytes()?);
-        ret.append(&mut self.mul.to_bytes()?);
-        ret.append(&mut self.mem.to_bytes()?);
-        ret.append(&mut self.initial_mem.to_bytes()?);
-        ret.append(&mut self.grow_mem.to_bytes()?);
-        ret.append(&mut self.memcpy.to_bytes()?);
-        ret.append(&mut self.max_stack_height.to_bytes()?);
-        ret.append(&mut self.opcodes_mul.to_bytes()?);
-        ret.append(&mut self.opcodes_div.to_bytes()?);
-        Ok(ret)
+        bytesrepr::to_vec(self)
+    }
+
+    fn serialized_length(&self) -> usize {
+        WASM_COSTS_SIZE_SERIALIZED
+    }
+
+    fn write_bytes<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), bytesrepr::Error> {
+        VERSIONED_LAYOUT_MARKER.write_bytes(writer)?;
+        CRYPTO_COSTS_LAYOUT_VERSION.write_bytes(writer)?;
+        self.regular.write_bytes(writer)?;
+        self.div.write_bytes(writer)?;
+        self.mul.write_bytes(writer)?;
+        self.mem.write_bytes(writer)?;
+        self.initial_mem.write_bytes(writer)?;
+        self.grow_mem.write_bytes(writer)?;
+        self.memcpy.write_bytes(writer)?;
+        self.max_stack_height.write_bytes(writer)?;
+        self.opcodes_mul.write_bytes(writer)?;
+        self.opcodes_div.write_bytes(writer)?;
+        self.hash.write_bytes(writer)?;
+        self.hash_byte.write_bytes(writer)?;
+        self.verify_signature.write_bytes(writer)?;
+        self.verify_signature_byte.write_bytes(writer)
     }
 }
 
 impl FromBytes for WasmCosts {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
-        let (regular, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (marker, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let versioned = marker == VERSIONED_LAYOUT_MARKER;
+        let (regular, rem) = if versioned {
+            let (version, rem): (u8, &[u8]) = FromBytes::from_bytes(rem)?;
+            if version != CRYPTO_COSTS_LAYOUT_VERSION {
+                return Err(bytesrepr::Error::FormattingError);
+            }
+            FromBytes::from_bytes(rem)?
+        } else {
+            // The legacy layout, which starts with the `regular` cost
+            (marker, rem)
+        };
         let (div, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let (mul, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let (mem, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
@@ -61,7 +127,7 @@ impl FromBytes for WasmCosts {
         let (max_stack_height, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let (opcodes_mul, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let (opcodes_div, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
-        let wasm_costs = WasmCosts {
+        let legacy = WasmCosts {
             regular,
             div,
             mul,
@@ -72,8 +138,25 @@ impl FromBytes for WasmCosts {
             max_stack_height,
             opcodes_mul,
             opcodes_div,
+            ..WasmCosts::default()
         };
-        Ok((wasm_costs, rem))
+        if !versioned {
+            return Ok((legacy.with_default_crypto_costs(), rem));
+        }
+        let (hash, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (hash_byte, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (verify_signature, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (verify_signature_byte, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        Ok((
+            WasmCosts {
+                hash,
+                hash_byte,
+                verify_signature,
+                verify_signature_byte,
+                ..legacy
+            },
+            rem,
+        ))
     }
 }
 
@@ -95,6 +178,10 @@ pub mod gens {
             max_stack_height in num::u32::ANY,
             opcodes_mul in num::u32::ANY,
             opcodes_div in num::u32::ANY,
+            hash in num::u32::ANY,
+            hash_byte in num::u32::ANY,
+            verify_signature in num::u32::ANY,
+            verify_signature_byte in num::u32::ANY,
         ) -> WasmCosts {
             WasmCosts {
                 regular,
@@ -107,6 +194,10 @@ pub mod gens {
                 max_stack_height,
                 opcodes_mul,
                 opcodes_div,
+                hash,
+                hash_byte,
+                verify_signature,
+                verify_signature_byte,
             }
         }
     }
@@ -116,10 +207,13 @@ pub mod gens {
 mod tests {
     use proptest::proptest;
 
-    use contract_ffi::bytesrepr;
+    use contract_ffi::bytesrepr::{self, ToBytes};
     use engine_shared::test_utils;
 
-    use super::gens;
+    use super::{
+        gens, WasmCosts, DEFAULT_HASH_BYTE_COST, DEFAULT_HASH_COST, DEFAULT_VERIFY_SIGNATURE_COST,
+        WASM_COSTS_SIZE_SERIALIZED,
+    };
 
     #[test]
     fn should_serialize_and_deserialize() {
@@ -129,6 +223,54 @@ mod tests {
         bytesrepr::test_serialization_roundtrip(&free);
     }
 
+    #[test]
+    fn should_serialize_fields_in_declaration_order() {
+        let mock = test_utils::wasm_costs_mock();
+        let serialized = mock.to_bytes().expect("should serialize");
+        assert_eq!(serialized.len(), WASM_COSTS_SIZE_SERIALIZED);
+        assert_eq!(&serialized[..4], &u32::max_value().to_le_bytes());
+        assert_eq!(serialized[4], 1);
+        assert_eq!(&serialized[5..9], &mock.regular.to_le_bytes());
+        assert_eq!(&serialized[41..45], &mock.opcodes_div.to_le_bytes());
+        assert_eq!(&serialized[57..], &mock.verify_signature_byte.to_le_bytes());
+    }
+
+    #[test]
+    fn should_deserialize_legacy_layout_with_default_crypto_costs() {
+        let mock = test_utils::wasm_costs_mock();
+        let serialized = mock.to_bytes().expect("should serialize");
+        // The legacy layout is the first ten costs, without marker or version
+        let legacy = serialized[5..45].to_vec();
+
+        let (deserialized, rem): (WasmCosts, &[u8]) =
+            bytesrepr::FromBytes::from_bytes(&legacy).expect("should deserialize");
+        assert!(rem.is_empty());
+        assert_eq!(deserialized.regular, mock.regular);
+        assert_eq!(deserialized.opcodes_div, mock.opcodes_div);
+        assert_eq!(deserialized.hash, DEFAULT_HASH_COST);
+        assert_eq!(deserialized.hash_byte, DEFAULT_HASH_BYTE_COST);
+        assert_eq!(deserialized.verify_signature, DEFAULT_VERIFY_SIGNATURE_COST);
+    }
+
+    #[test]
+    fn should_keep_zero_crypto_costs() {
+        let free = test_utils::wasm_costs_free();
+        let serialized = free.to_bytes().expect("should serialize");
+        let deserialized: WasmCosts =
+            bytesrepr::deserialize(&serialized).expect("should deserialize");
+        assert_eq!(deserialized.hash, 0);
+        assert_eq!(deserialized.verify_signature, 0);
+    }
+
+    #[test]
+    fn should_serialize_max_regular_cost() {
+        let costs = WasmCosts {
+            regular: u32::max_value(),
+            ..test_utils::wasm_costs_mock()
+        };
+        bytesrepr::test_serialization_roundtrip(&costs);
+    }
+
     proptest! {
         #[test]
         fn should_serialize_and_deserialize_with_arbitrary_values(